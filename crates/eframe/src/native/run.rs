@@ -247,6 +247,73 @@ mod glow_integration {
         // Conceptually this will be split out eventually so that the rest of the state
         // can be persistent.
         gl_window: glutin::WindowedContext<glutin::PossiblyCurrent>,
+
+        damage_history: DamageHistory,
+    }
+
+    /// A rectangle in points to one in pixels, with the origin in the lower left corner.
+    fn to_glutin_rect(
+        rect: egui::Rect,
+        pixels_per_point: f32,
+        screen_size_in_pixels: [u32; 2],
+    ) -> glutin::Rect {
+        let min_x = (rect.min.x * pixels_per_point).round() as u32;
+        let min_y = (rect.min.y * pixels_per_point).round() as u32;
+        let max_x = ((rect.max.x * pixels_per_point).round() as u32).min(screen_size_in_pixels[0]);
+        let max_y = ((rect.max.y * pixels_per_point).round() as u32).min(screen_size_in_pixels[1]);
+        glutin::Rect {
+            x: min_x,
+            y: screen_size_in_pixels[1] - max_y,
+            width: max_x.saturating_sub(min_x),
+            height: max_y.saturating_sub(min_y),
+        }
+    }
+
+    /// The damage of the last few frames (see [`egui::FullOutput::damage`]),
+    /// so we know what to repaint in a back buffer that is a few frames old.
+    #[derive(Default)]
+    struct DamageHistory {
+        /// Newest last.
+        frames: std::collections::VecDeque<Vec<egui::Rect>>,
+    }
+
+    impl DamageHistory {
+        /// Most drivers use double or triple buffering.
+        const MAX_AGE: usize = 4;
+
+        /// Add the damage of a new frame.
+        ///
+        /// Returns what needs to be repainted in a back buffer of the given age
+        /// (see [`glutin::ContextWrapper::buffer_age`]),
+        /// or `None` if we don't know what it contains and need to repaint everything.
+        fn add(
+            &mut self,
+            damage: Vec<egui::Rect>,
+            buffer_age: u32,
+            screen_rect: egui::Rect,
+            pixels_per_point: f32,
+        ) -> Option<Vec<egui::Rect>> {
+            self.frames.push_back(damage);
+            while self.frames.len() > Self::MAX_AGE {
+                self.frames.pop_front();
+            }
+
+            let age = buffer_age as usize;
+            if age == 0 || self.frames.len() < age {
+                return None;
+            }
+
+            let mut damage: Vec<egui::Rect> = self
+                .frames
+                .iter()
+                .rev()
+                .take(age)
+                .flatten()
+                .copied()
+                .collect();
+            egui::merge_damage_rects(&mut damage, screen_rect, pixels_per_point);
+            Some(damage)
+        }
     }
 
     struct GlowWinitApp {
@@ -350,6 +417,7 @@ mod glow_integration {
             );
             let theme = system_theme.unwrap_or(self.native_options.default_theme);
             integration.egui_ctx.set_visuals(theme.egui_visuals());
+            integration.egui_ctx.tessellation_options().instancing = painter.supports_instancing();
 
             {
                 let event_loop_proxy = self.repaint_proxy.clone();
@@ -379,6 +447,7 @@ mod glow_integration {
                 painter,
                 integration,
                 app,
+                damage_history: Default::default(),
             });
         }
     }
@@ -418,23 +487,19 @@ mod glow_integration {
                     app,
                     integration,
                     painter,
+                    damage_history,
                 } = running;
 
                 let window = gl_window.window();
 
                 let screen_size_in_pixels: [u32; 2] = window.inner_size().into();
 
-                egui_glow::painter::clear(
-                    gl,
-                    screen_size_in_pixels,
-                    app.clear_color(&integration.egui_ctx.style().visuals),
-                );
-
                 let egui::FullOutput {
                     platform_output,
                     repaint_after,
                     textures_delta,
                     shapes,
+                    damage,
                 } = integration.update(app.as_mut(), window);
 
                 integration.handle_platform_output(window, platform_output);
@@ -444,18 +509,69 @@ mod glow_integration {
                     integration.egui_ctx.tessellate(shapes)
                 };
 
-                painter.paint_and_update_textures(
-                    screen_size_in_pixels,
-                    integration.egui_ctx.pixels_per_point(),
-                    &clipped_primitives,
-                    &textures_delta,
+                let pixels_per_point = integration.egui_ctx.pixels_per_point();
+                let clear_color = app.clear_color(&integration.egui_ctx.style().visuals);
+
+                // If the back buffer still holds a recent frame, only repaint what changed since:
+                let partial_damage = damage_history.add(
+                    damage,
+                    gl_window.buffer_age(),
+                    integration.egui_ctx.input().screen_rect(),
+                    pixels_per_point,
                 );
 
+                if let Some(partial_damage) = &partial_damage {
+                    egui_glow::painter::clear_damage(
+                        gl,
+                        screen_size_in_pixels,
+                        pixels_per_point,
+                        partial_damage,
+                        clear_color,
+                    );
+                    for (id, image_delta) in &textures_delta.set {
+                        painter.set_texture(*id, image_delta);
+                    }
+                    painter.paint_damaged_primitives(
+                        screen_size_in_pixels,
+                        pixels_per_point,
+                        &clipped_primitives,
+                        partial_damage,
+                    );
+                    for &id in &textures_delta.free {
+                        painter.free_texture(id);
+                    }
+                } else {
+                    egui_glow::painter::clear(gl, screen_size_in_pixels, clear_color);
+                    painter.paint_and_update_textures(
+                        screen_size_in_pixels,
+                        pixels_per_point,
+                        &clipped_primitives,
+                        &textures_delta,
+                    );
+                }
+
                 integration.post_rendering(app.as_mut(), window);
 
                 {
                     crate::profile_scope!("swap_buffers");
-                    gl_window.swap_buffers().unwrap();
+                    match partial_damage {
+                        // An empty list would mean "everything" to the driver.
+                        Some(partial_damage)
+                            if !partial_damage.is_empty()
+                                && gl_window.swap_buffers_with_damage_supported() =>
+                        {
+                            let rects: Vec<glutin::Rect> = partial_damage
+                                .iter()
+                                .map(|rect| {
+                                    to_glutin_rect(*rect, pixels_per_point, screen_size_in_pixels)
+                                })
+                                .collect();
+                            gl_window.swap_buffers_with_damage(&rects).unwrap();
+                        }
+                        _ => {
+                            gl_window.swap_buffers().unwrap();
+                        }
+                    }
                 }
 
                 let control_flow = if integration.should_close() {
@@ -709,6 +825,8 @@ mod wgpu_integration {
             );
             let theme = system_theme.unwrap_or(self.native_options.default_theme);
             integration.egui_ctx.set_visuals(theme.egui_visuals());
+            // Instancing is always supported by wgpu:
+            integration.egui_ctx.tessellation_options().instancing = true;
 
             {
                 let event_loop_proxy = self.repaint_proxy.clone();
@@ -791,6 +909,7 @@ mod wgpu_integration {
                     repaint_after,
                     textures_delta,
                     shapes,
+                    damage: _, // wgpu surfaces don't keep the previous frame, so we repaint everything
                 } = integration.update(app.as_mut(), window);
 
                 integration.handle_platform_output(window, platform_output);
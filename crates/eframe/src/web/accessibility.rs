@@ -0,0 +1,321 @@
+//! Mirrors [`egui::output::AccessibilityTree`] into hidden DOM elements,
+//! so that screen readers can see the widgets drawn on the canvas.
+//!
+//! Each widget becomes an invisible `<div>` with an ARIA role, placed over the widget.
+//! When a screen reader clicks or focuses one of them, the matching
+//! [`egui::Event::AccessibilityAction`] is sent to egui.
+//!
+//! Only active when [`egui::Options::accessibility_tree`] is set.
+//! DOM focus does not follow egui focus, and text and values are still entered
+//! with the keyboard as usual (through the text agent).
+
+use super::{AppRunner, AppRunnerContainer};
+use egui::{
+    mutex::MutexGuard,
+    output::{AccessibilityNode, AccessibilityTree},
+    AccessibilityAction, Id, IdMap,
+};
+use std::collections::HashMap;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+static CONTAINER_ID: &str = "egui_accessibility_tree";
+
+/// The class of the element of each node.
+static NODE_CLASS: &str = "egui_access_node";
+
+fn container() -> Option<web_sys::HtmlElement> {
+    web_sys::window()?
+        .document()?
+        .get_element_by_id(CONTAINER_ID)?
+        .dyn_into()
+        .ok()
+}
+
+/// Creates the element holding the tree, and forwards clicks and focus from it to egui.
+pub fn install_accessibility(runner_container: &mut AppRunnerContainer) -> Result<(), JsValue> {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let body = document.body().expect("document should have a body");
+    let container = document
+        .create_element("div")?
+        .dyn_into::<web_sys::HtmlElement>()?;
+    container.set_id(CONTAINER_ID);
+    {
+        // The node elements are placed relative to the viewport, like the canvas rect.
+        let style = container.style();
+        style.set_property("position", "fixed")?;
+        style.set_property("left", "0")?;
+        style.set_property("top", "0")?;
+        style.set_property("width", "0")?;
+        style.set_property("height", "0")?;
+        style.set_property("overflow", "visible")?;
+        style.set_property("opacity", "0")?;
+        // Let the mouse through to the canvas:
+        style.set_property("pointer-events", "none")?;
+    }
+    body.append_child(&container)?;
+
+    for (event_name, action) in [
+        ("click", AccessibilityAction::Click),
+        ("focusin", AccessibilityAction::Focus),
+    ] {
+        runner_container.add_event_listener(
+            &container,
+            event_name,
+            move |event: web_sys::Event, mut runner_lock: MutexGuard<'_, AppRunner>| {
+                let target = event
+                    .target()
+                    .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+                    .and_then(|element| element.closest(&format!(".{}", NODE_CLASS)).ok()?);
+                let target = target.and_then(|element| runner_lock.accessibility.id_of(&element));
+                if let Some(target) = target {
+                    runner_lock
+                        .input
+                        .raw
+                        .events
+                        .push(egui::Event::AccessibilityAction {
+                            target,
+                            action: action.clone(),
+                        });
+                    runner_lock.needs_repaint.repaint_asap();
+                    event.prevent_default();
+                }
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The DOM elements mirroring the last [`AccessibilityTree`].
+#[derive(Default)]
+pub struct AccessibilityMirror {
+    nodes: IdMap<web_sys::HtmlElement>,
+    /// The node of each element, by DOM id.
+    ids: HashMap<String, Id>,
+    last_tree: Option<AccessibilityTree>,
+    last_canvas_origin: egui::Pos2,
+}
+
+impl AccessibilityMirror {
+    /// Bring the DOM up to date with `tree`, whose root covers the canvas at `canvas_origin`.
+    ///
+    /// Does nothing if neither the tree nor the canvas moved since last time.
+    pub fn update(&mut self, tree: Option<AccessibilityTree>, canvas_origin: egui::Pos2) {
+        if tree == self.last_tree && canvas_origin == self.last_canvas_origin {
+            return;
+        }
+        let container = match container() {
+            Some(container) => container,
+            None => return,
+        };
+
+        // Remove the elements of nodes that are gone first, so they don't get in the way of
+        // the order check below:
+        let ids = &mut self.ids;
+        self.nodes.retain(|id, element| {
+            let keep = tree
+                .as_ref()
+                .map_or(false, |tree| tree.nodes.contains_key(id));
+            if !keep {
+                ids.remove(&element.id());
+                element.remove();
+            }
+            keep
+        });
+
+        if let Some(tree) = &tree {
+            // The container is at the top left of the viewport.
+            // Each element goes after the one before it: the previous sibling, or for the
+            // first child the name of its parent (and nothing for the root).
+            let mut stack = vec![(tree.root, container.clone().into(), None, egui::Pos2::ZERO)];
+            while let Some((id, parent_element, previous, parent_origin)) = stack.pop() {
+                let node = match tree.nodes.get(&id) {
+                    Some(node) => node,
+                    None => continue,
+                };
+                let element = match self.element(id) {
+                    Some(element) => element,
+                    None => continue,
+                };
+                let origin = if id == tree.root {
+                    canvas_origin
+                } else {
+                    node.rect.min + canvas_origin.to_vec2()
+                };
+                update_element(&element, node, origin - parent_origin);
+                place_element(&element, &parent_element, previous.as_ref());
+
+                // The children are placed in order, so each one after the one placed before it:
+                let mut previous = element.first_element_child();
+                let mut children = vec![];
+                for &child in &node.children {
+                    if let Some(child_element) = self.element(child) {
+                        children.push((child, previous.clone()));
+                        previous = Some(child_element.into());
+                    }
+                }
+                // Reversed, so that they are popped in order:
+                for (child, previous) in children.into_iter().rev() {
+                    stack.push((child, element.clone().into(), previous, origin));
+                }
+            }
+        }
+
+        self.last_tree = tree;
+        self.last_canvas_origin = canvas_origin;
+    }
+
+    fn element(&mut self, id: Id) -> Option<web_sys::HtmlElement> {
+        if let Some(element) = self.nodes.get(&id) {
+            return Some(element.clone());
+        }
+        let document = web_sys::window()?.document()?;
+        let element = document
+            .create_element("div")
+            .ok()?
+            .dyn_into::<web_sys::HtmlElement>()
+            .ok()?;
+        element.set_id(&dom_id(id));
+        element.set_class_name(NODE_CLASS);
+        let style = element.style();
+        style.set_property("position", "absolute").ok()?;
+        style.set_property("overflow", "visible").ok()?;
+        // The name of the node:
+        let text = document.create_element("span").ok()?;
+        element.append_child(&text).ok()?;
+        self.nodes.insert(id, element.clone());
+        self.ids.insert(element.id(), id);
+        Some(element)
+    }
+
+    /// The id of the node mirrored by `element`.
+    fn id_of(&self, element: &web_sys::Element) -> Option<Id> {
+        self.ids.get(&element.id()).copied()
+    }
+}
+
+/// Moves `element` into `parent`, right after `previous` (or first if `None`),
+/// unless it is already there.
+///
+/// Moving an element that has focus blurs it, so we only move what's out of place.
+fn place_element(
+    element: &web_sys::Element,
+    parent: &web_sys::Element,
+    previous: Option<&web_sys::Element>,
+) {
+    if element.parent_element().as_ref() == Some(parent)
+        && element.previous_element_sibling().as_ref() == previous
+    {
+        return;
+    }
+    let next = match previous {
+        Some(previous) => previous.next_sibling(),
+        None => parent.first_child(),
+    };
+    parent.insert_before(element, next.as_ref()).ok();
+}
+
+fn dom_id(id: Id) -> String {
+    format!("egui_access_{:?}", id)
+}
+
+fn update_element(element: &web_sys::HtmlElement, node: &AccessibilityNode, offset: egui::Vec2) {
+    let info = &node.info;
+    let style = element.style();
+    style.set_property("left", &format!("{}px", offset.x)).ok();
+    style.set_property("top", &format!("{}px", offset.y)).ok();
+    style
+        .set_property("width", &format!("{}px", node.rect.width()))
+        .ok();
+    style
+        .set_property("height", &format!("{}px", node.rect.height()))
+        .ok();
+
+    let mut name = info.label.clone().unwrap_or_default();
+    if info.typ == egui::WidgetType::TextEdit {
+        if let Some(text) = &info.current_text_value {
+            name = text.clone();
+        }
+    }
+    if let Some(text) = element.first_element_child() {
+        if text.text_content().as_deref() != Some(name.as_str()) {
+            text.set_text_content(Some(&name));
+        }
+    }
+
+    let set = |name: &str, value: Option<String>| match value {
+        Some(value) => element.set_attribute(name, &value).ok(),
+        None => element.remove_attribute(name).ok(),
+    };
+
+    set("role", aria_role(info.typ).map(str::to_owned));
+    if info.typ == egui::WidgetType::TextEdit {
+        set("aria-label", info.label.clone());
+    } else {
+        set("aria-label", None);
+    }
+    set("aria-disabled", (!info.enabled).then(|| "true".to_owned()));
+    set("tabindex", node.focusable.then(|| "-1".to_owned()));
+
+    let checked = match info.typ {
+        egui::WidgetType::Checkbox | egui::WidgetType::RadioButton => {
+            if info.mixed {
+                Some("mixed".to_owned())
+            } else {
+                info.selected.map(|selected| selected.to_string())
+            }
+        }
+        _ => None,
+    };
+    set("aria-checked", checked);
+    let pressed = if info.typ == egui::WidgetType::SelectableLabel {
+        info.selected.map(|selected| selected.to_string())
+    } else {
+        None
+    };
+    set("aria-pressed", pressed);
+    set(
+        "aria-expanded",
+        info.expanded.map(|expanded| expanded.to_string()),
+    );
+
+    set("aria-valuenow", info.value.map(|value| value.to_string()));
+    set("aria-valuemin", info.min.map(|min| min.to_string()));
+    set("aria-valuemax", info.max.map(|max| max.to_string()));
+    set("aria-description", info.tooltip.clone());
+}
+
+/// The [ARIA role](https://www.w3.org/TR/wai-aria-1.2/#role_definitions) of a widget.
+fn aria_role(typ: egui::WidgetType) -> Option<&'static str> {
+    use egui::WidgetType;
+    Some(match typ {
+        WidgetType::Label | WidgetType::Other => return None,
+        WidgetType::Link => "link",
+        WidgetType::TextEdit => "textbox",
+        WidgetType::Button
+        | WidgetType::ImageButton
+        | WidgetType::ColorButton
+        | WidgetType::SelectableLabel
+        | WidgetType::CollapsingHeader => "button",
+        WidgetType::Checkbox => "checkbox",
+        WidgetType::RadioButton => "radio",
+        WidgetType::ComboBox => "combobox",
+        WidgetType::Slider => "slider",
+        WidgetType::DragValue => "spinbutton",
+        WidgetType::ProgressBar => "progressbar",
+        WidgetType::Image => "img",
+        WidgetType::Window => "dialog",
+        WidgetType::Panel => "region",
+        WidgetType::Menu => "menu",
+        WidgetType::MenuItem => "menuitem",
+        WidgetType::Tab => "tab",
+        WidgetType::Tooltip => "tooltip",
+        WidgetType::ScrollArea => "group",
+        WidgetType::Grid => "grid",
+        WidgetType::Table => "table",
+        WidgetType::Cell => "cell",
+        WidgetType::TreeItem => "treeitem",
+        WidgetType::Plot => "figure",
+    })
+}
@@ -169,6 +169,7 @@ pub struct AppRunner {
     pub(crate) is_destroyed: std::sync::Arc<IsDestroyed>,
     last_save_time: f64,
     screen_reader: super::screen_reader::ScreenReader,
+    pub(crate) accessibility: super::accessibility::AccessibilityMirror,
     pub(crate) text_cursor_pos: Option<egui::Pos2>,
     pub(crate) mutable_text_under_cursor: bool,
     textures_delta: TexturesDelta,
@@ -208,6 +209,7 @@ impl AppRunner {
 
         let egui_ctx = egui::Context::default();
         load_memory(&egui_ctx);
+        egui_ctx.tessellation_options().instancing = painter.painter.supports_instancing();
 
         let theme = system_theme.unwrap_or(web_options.default_theme);
         egui_ctx.set_visuals(theme.egui_visuals());
@@ -250,6 +252,7 @@ impl AppRunner {
             is_destroyed: Default::default(),
             last_save_time: now_sec(),
             screen_reader: Default::default(),
+            accessibility: Default::default(),
             text_cursor_pos: None,
             mutable_text_under_cursor: false,
             textures_delta: Default::default(),
@@ -331,6 +334,7 @@ impl AppRunner {
             repaint_after,
             textures_delta,
             shapes,
+            damage: _, // the canvas is cleared each frame, so we always repaint everything
         } = full_output;
 
         self.handle_platform_output(platform_output);
@@ -375,10 +379,14 @@ impl AppRunner {
             open_url,
             copied_text,
             events: _, // already handled
+            accessibility_tree,
             mutable_text_under_cursor,
             text_cursor_pos,
         } = platform_output;
 
+        self.accessibility
+            .update(accessibility_tree, super::canvas_origin(self.canvas_id()));
+
         set_cursor_icon(cursor_icon);
         if let Some(open) = open_url {
             super::open_url(&open.url, open.new_tab);
@@ -523,6 +531,7 @@ fn start_runner(app_runner: AppRunner) -> Result<AppRunnerRef, JsValue> {
     super::events::install_canvas_events(&mut runner_container)?;
     super::events::install_document_events(&mut runner_container)?;
     text_agent::install_text_agent(&mut runner_container)?;
+    super::accessibility::install_accessibility(&mut runner_container)?;
 
     super::events::paint_and_schedule(&runner_container.runner, runner_container.panicked.clone())?;
 
@@ -288,6 +288,33 @@ pub fn install_canvas_events(runner_container: &mut AppRunnerContainer) -> Resul
         },
     )?;
 
+    // Pens also send mouse events, but only pointer events tell us about pressure, tilt etc:
+    for event_name in ["pointerdown", "pointermove", "pointerup"] {
+        runner_container.add_event_listener(
+            &canvas,
+            event_name,
+            |event: web_sys::PointerEvent, mut runner_lock| {
+                if let Some(pen) = pen_state_from_pointer_event(runner_lock.canvas_id(), &event) {
+                    runner_lock.input.raw.events.push(egui::Event::Pen(pen));
+                    runner_lock.needs_repaint.repaint_asap();
+                }
+            },
+        )?;
+    }
+
+    for event_name in ["pointerleave", "pointercancel"] {
+        runner_container.add_event_listener(
+            &canvas,
+            event_name,
+            |event: web_sys::PointerEvent, mut runner_lock| {
+                if event.pointer_type() == "pen" {
+                    runner_lock.input.raw.events.push(egui::Event::PenGone);
+                    runner_lock.needs_repaint.repaint_asap();
+                }
+            },
+        )?;
+    }
+
     runner_container.add_event_listener(
         &canvas,
         "touchstart",
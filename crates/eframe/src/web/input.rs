@@ -20,6 +20,29 @@ pub fn button_from_mouse_event(event: &web_sys::MouseEvent) -> Option<egui::Poin
     }
 }
 
+/// Returns `None` if the event is not from a pen.
+pub fn pen_state_from_pointer_event(
+    canvas_id: &str,
+    event: &web_sys::PointerEvent,
+) -> Option<egui::PenState> {
+    if event.pointer_type() != "pen" {
+        return None;
+    }
+
+    // See https://developer.mozilla.org/en-US/docs/Web/API/Pointer_events#determining_button_states
+    let buttons = event.buttons();
+    let (tip, barrel, eraser) = (1, 2, 32);
+    Some(egui::PenState {
+        pos: pos_from_mouse_event(canvas_id, event),
+        contact: buttons & (tip | eraser) != 0,
+        pressure: event.pressure(),
+        tilt: egui::vec2(event.tilt_x() as f32, event.tilt_y() as f32)
+            * (std::f32::consts::PI / 180.0),
+        barrel_button: buttons & barrel != 0,
+        eraser: buttons & eraser != 0,
+    })
+}
+
 /// A single touch is translated to a pointer movement. When a second touch is added, the pointer
 /// should not jump to a different position. Therefore, we do not calculate the average position
 /// of all touches, but we keep using the same touch as long as it is available.
@@ -2,6 +2,7 @@
 
 #![allow(clippy::missing_errors_doc)] // So many `-> Result<_, JsValue>`
 
+mod accessibility;
 pub mod backend;
 mod events;
 mod glow_wrapping;
@@ -69,6 +69,43 @@ fn vs_conv_main(
     return out;
 }
 
+// For `Primitive::Instanced`: each vertex is moved and tinted by the instance.
+
+@vertex
+fn vs_main_instanced(
+    @location(0) a_pos: vec2<f32>,
+    @location(1) a_tex_coord: vec2<f32>,
+    @location(2) a_color: u32,
+    @location(3) a_instance_offset: vec2<f32>,
+    @location(4) a_instance_color: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.tex_coord = a_tex_coord;
+    let color = unpack_color(a_color);
+    let tint = unpack_color(a_instance_color);
+    out.color = vec4<f32>(linear_from_srgb(color.rgb), color.a / 255.0)
+        * vec4<f32>(linear_from_srgb(tint.rgb), tint.a / 255.0);
+    out.position = position_from_screen(a_pos + a_instance_offset);
+    return out;
+}
+
+@vertex
+fn vs_conv_main_instanced(
+    @location(0) a_pos: vec2<f32>,
+    @location(1) a_tex_coord: vec2<f32>,
+    @location(2) a_color: u32,
+    @location(3) a_instance_offset: vec2<f32>,
+    @location(4) a_instance_color: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.tex_coord = a_tex_coord;
+    let color = unpack_color(a_color);
+    let tint = unpack_color(a_instance_color);
+    out.color = vec4<f32>(color.rgba / 255.0) * vec4<f32>(tint.rgba / 255.0);
+    out.position = position_from_screen(a_pos + a_instance_offset);
+    return out;
+}
+
 // Fragment shader bindings
 
 @group(1) @binding(0) var r_tex_color: texture_2d<f32>;
@@ -78,3 +115,13 @@ fn vs_conv_main(
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     return in.color * textureSample(r_tex_color, r_tex_sampler, in.tex_coord);
 }
+
+// For textures that are signed distance fields (stored in alpha), with the outline at 0.5.
+@fragment
+fn fs_main_sdf(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = textureSample(r_tex_color, r_tex_sampler, in.tex_coord).a;
+    // Smooth over roughly one physical pixel (but never zero, since smoothstep needs a range):
+    let width = max(0.7 * length(vec2<f32>(dpdx(distance), dpdy(distance))), 0.001);
+    let coverage = smoothstep(0.5 - width, 0.5 + width, distance);
+    return in.color * coverage;
+}
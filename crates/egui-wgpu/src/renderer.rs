@@ -1,6 +1,10 @@
 #![allow(unsafe_code)]
 
-use std::{borrow::Cow, collections::HashMap, num::NonZeroU32};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    num::NonZeroU32,
+};
 
 use egui::{epaint::Primitive, NumExt, PaintCallbackInfo};
 use type_map::concurrent::TypeMap;
@@ -79,6 +83,10 @@ enum BufferType {
     Uniform,
     Index,
     Vertex,
+    Instance,
+    OffscreenUniform,
+    OffscreenIndex,
+    OffscreenVertex,
 }
 
 /// Information about the screen used for rendering.
@@ -120,16 +128,33 @@ struct SizedBuffer {
 
 /// Render pass to render a egui based GUI.
 pub struct RenderPass {
-    render_pipeline: wgpu::RenderPipeline,
+    pipelines: Pipelines,
+    shader_module: wgpu::ShaderModule,
+    pipeline_layout: wgpu::PipelineLayout,
+    /// Pipelines for [`Self::paint_to_texture`], created on first use.
+    offscreen_pipelines: Option<Pipelines>,
     index_buffers: Vec<SizedBuffer>,
     vertex_buffers: Vec<SizedBuffer>,
+    /// One per [`Primitive::Instanced`].
+    instance_buffers: Vec<SizedBuffer>,
     uniform_buffer: SizedBuffer,
     uniform_bind_group: wgpu::BindGroup,
+    /// Buffers for [`Self::paint_to_texture`]. The main ones may already be in use for this frame.
+    offscreen_index_buffers: Vec<SizedBuffer>,
+    offscreen_vertex_buffers: Vec<SizedBuffer>,
+    offscreen_uniform_buffer: SizedBuffer,
+    offscreen_uniform_bind_group: wgpu::BindGroup,
     texture_bind_group_layout: wgpu::BindGroupLayout,
     /// Map of egui texture IDs to textures and their associated bindgroups (texture view +
     /// sampler). The texture may be None if the TextureId is just a handle to a user-provided
     /// sampler.
     textures: HashMap<egui::TextureId, (Option<wgpu::Texture>, wgpu::BindGroup)>,
+    /// Size in pixels of the textures painted with [`Self::paint_to_texture`].
+    offscreen_textures: HashMap<egui::TextureId, [u32; 2]>,
+    /// Size in pixels of the textures that have mipmaps, which we need to regenerate on updates.
+    mipmapped_textures: HashMap<egui::TextureId, [u32; 2]>,
+    /// Textures of [`egui::ImageData::Sdf`], painted with [`Pipelines::sdf`].
+    sdf_textures: HashSet<egui::TextureId>,
     next_user_texture_id: u64,
     /// Storage for use by [`egui::PaintCallback`]'s that need to store resources such as render
     /// pipelines that must have the lifetime of the renderpass.
@@ -151,18 +176,22 @@ impl RenderPass {
         };
         let module = device.create_shader_module(shader);
 
-        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("egui_uniform_buffer"),
-            contents: bytemuck::cast_slice(&[UniformBuffer {
-                screen_size_in_points: [0.0, 0.0],
-                _padding: Default::default(),
-            }]),
-            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
-        });
-        let uniform_buffer = SizedBuffer {
-            buffer: uniform_buffer,
-            size: std::mem::size_of::<UniformBuffer>(),
+        let create_uniform_buffer = |label| {
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(label),
+                contents: bytemuck::cast_slice(&[UniformBuffer {
+                    screen_size_in_points: [0.0, 0.0],
+                    _padding: Default::default(),
+                }]),
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            });
+            SizedBuffer {
+                buffer,
+                size: std::mem::size_of::<UniformBuffer>(),
+            }
         };
+        let uniform_buffer = create_uniform_buffer("egui_uniform_buffer");
+        let offscreen_uniform_buffer = create_uniform_buffer("egui_offscreen_uniform_buffer");
 
         let uniform_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -179,18 +208,26 @@ impl RenderPass {
                 }],
             });
 
-        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("egui_uniform_bind_group"),
-            layout: &uniform_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-                    buffer: &uniform_buffer.buffer,
-                    offset: 0,
-                    size: None,
-                }),
-            }],
-        });
+        let create_uniform_bind_group = |label, uniform_buffer: &SizedBuffer| {
+            device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(label),
+                layout: &uniform_bind_group_layout,
+                entries: &[wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &uniform_buffer.buffer,
+                        offset: 0,
+                        size: None,
+                    }),
+                }],
+            })
+        };
+        let uniform_bind_group =
+            create_uniform_bind_group("egui_uniform_bind_group", &uniform_buffer);
+        let offscreen_uniform_bind_group = create_uniform_bind_group(
+            "egui_offscreen_uniform_bind_group",
+            &offscreen_uniform_buffer,
+        );
 
         let texture_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
@@ -221,72 +258,33 @@ impl RenderPass {
             push_constant_ranges: &[],
         });
 
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("egui_pipeline"),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                entry_point: if output_format.describe().srgb {
-                    "vs_main"
-                } else {
-                    "vs_conv_main"
-                },
-                module: &module,
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: 5 * 4,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    // 0: vec2 position
-                    // 1: vec2 texture coordinates
-                    // 2: uint color
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
-                }],
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                unclipped_depth: false,
-                conservative: false,
-                cull_mode: None,
-                front_face: wgpu::FrontFace::default(),
-                polygon_mode: wgpu::PolygonMode::default(),
-                strip_index_format: None,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                alpha_to_coverage_enabled: false,
-                count: msaa_samples,
-                mask: !0,
-            },
-
-            fragment: Some(wgpu::FragmentState {
-                module: &module,
-                entry_point: "fs_main",
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: output_format,
-                    blend: Some(wgpu::BlendState {
-                        color: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::One,
-                            dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                        alpha: wgpu::BlendComponent {
-                            src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
-                            dst_factor: wgpu::BlendFactor::One,
-                            operation: wgpu::BlendOperation::Add,
-                        },
-                    }),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-            }),
-            multiview: None,
-        });
+        let pipelines = Pipelines::new(
+            device,
+            &module,
+            &pipeline_layout,
+            output_format,
+            msaa_samples,
+        );
 
         Self {
-            render_pipeline,
+            pipelines,
+            shader_module: module,
+            pipeline_layout,
+            offscreen_pipelines: None,
             vertex_buffers: Vec::with_capacity(64),
             index_buffers: Vec::with_capacity(64),
+            instance_buffers: Vec::new(),
             uniform_buffer,
             uniform_bind_group,
+            offscreen_index_buffers: Vec::new(),
+            offscreen_vertex_buffers: Vec::new(),
+            offscreen_uniform_buffer,
+            offscreen_uniform_bind_group,
             texture_bind_group_layout,
             textures: HashMap::new(),
+            offscreen_textures: HashMap::new(),
+            mipmapped_textures: HashMap::new(),
+            sdf_textures: HashSet::new(),
             next_user_texture_id: 0,
             paint_callback_resources: TypeMap::default(),
         }
@@ -332,6 +330,36 @@ impl RenderPass {
         rpass: &mut wgpu::RenderPass<'rpass>,
         paint_jobs: &[egui::epaint::ClippedPrimitive],
         screen_descriptor: &ScreenDescriptor,
+    ) {
+        self.execute_within(rpass, paint_jobs, screen_descriptor, None);
+    }
+
+    /// Like [`Self::execute_with_renderpass`], but only paints within the given rectangles (in points),
+    /// e.g. [`egui::FullOutput::damage`].
+    ///
+    /// This only makes sense if the render target still contains the previous frame,
+    /// i.e. it is your own texture and the render pass uses [`wgpu::LoadOp::Load`].
+    /// The damaged areas should be cleared or covered by opaque shapes (like a panel background).
+    ///
+    /// The rectangles must not overlap, or translucent shapes will be painted twice.
+    pub fn execute_damaged_with_renderpass<'rpass>(
+        &'rpass self,
+        rpass: &mut wgpu::RenderPass<'rpass>,
+        paint_jobs: &[egui::epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+        damage: &[egui::Rect],
+    ) {
+        for &damage_rect in damage {
+            self.execute_within(rpass, paint_jobs, screen_descriptor, Some(damage_rect));
+        }
+    }
+
+    fn execute_within<'rpass>(
+        &'rpass self,
+        rpass: &mut wgpu::RenderPass<'rpass>,
+        paint_jobs: &[egui::epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+        damage_rect: Option<egui::Rect>,
     ) {
         let pixels_per_point = screen_descriptor.pixels_per_point;
         let size_in_pixels = screen_descriptor.size_in_pixels;
@@ -340,8 +368,12 @@ impl RenderPass {
         // run.
         let mut needs_reset = true;
 
+        // The pipeline we last set. `None` after a reset.
+        let mut current_pipeline = None;
+
         let mut index_buffers = self.index_buffers.iter();
         let mut vertex_buffers = self.vertex_buffers.iter();
+        let mut instance_buffers = self.instance_buffers.iter();
 
         for egui::ClippedPrimitive {
             clip_rect,
@@ -357,20 +389,30 @@ impl RenderPass {
                     0.0,
                     1.0,
                 );
-                rpass.set_pipeline(&self.render_pipeline);
                 rpass.set_bind_group(0, &self.uniform_bind_group, &[]);
+                current_pipeline = None;
                 needs_reset = false;
             }
 
+            let clip_rect = &damage_rect.map_or(*clip_rect, |damage| clip_rect.intersect(damage));
             {
                 let rect = ScissorRect::new(clip_rect, pixels_per_point, size_in_pixels);
+                let outside_damage = damage_rect.is_some() && !clip_rect.is_positive();
 
-                if rect.width == 0 || rect.height == 0 {
+                if rect.width == 0 || rect.height == 0 || outside_damage {
                     // Skip rendering with zero-sized clip areas.
-                    if let Primitive::Mesh(_) = primitive {
-                        // If this is a mesh, we need to advance the index and vertex buffer iterators
-                        index_buffers.next().unwrap();
-                        vertex_buffers.next().unwrap();
+                    // If this is a mesh, we need to advance the buffer iterators:
+                    match primitive {
+                        Primitive::Mesh(_) => {
+                            index_buffers.next().unwrap();
+                            vertex_buffers.next().unwrap();
+                        }
+                        Primitive::Instanced(_) => {
+                            index_buffers.next().unwrap();
+                            vertex_buffers.next().unwrap();
+                            instance_buffers.next().unwrap();
+                        }
+                        Primitive::Callback(_) => {}
                     }
                     continue;
                 }
@@ -384,6 +426,8 @@ impl RenderPass {
                     let vertex_buffer = vertex_buffers.next().unwrap();
 
                     if let Some((_texture, bind_group)) = self.textures.get(&mesh.texture_id) {
+                        let sdf = self.sdf_textures.contains(&mesh.texture_id);
+                        set_pipeline(rpass, &mut current_pipeline, self.pipelines.get(sdf));
                         rpass.set_bind_group(1, bind_group, &[]);
                         rpass.set_index_buffer(
                             index_buffer.buffer.slice(..),
@@ -395,6 +439,35 @@ impl RenderPass {
                         tracing::warn!("Missing texture: {:?}", mesh.texture_id);
                     }
                 }
+                Primitive::Instanced(instanced) => {
+                    let index_buffer = index_buffers.next().unwrap();
+                    let vertex_buffer = vertex_buffers.next().unwrap();
+                    let instance_buffer = instance_buffers.next().unwrap();
+                    let mesh = &instanced.mesh;
+
+                    if let Some((_texture, bind_group)) = self.textures.get(&mesh.texture_id) {
+                        let sdf = self.sdf_textures.contains(&mesh.texture_id);
+                        set_pipeline(
+                            rpass,
+                            &mut current_pipeline,
+                            self.pipelines.get_instanced(sdf),
+                        );
+                        rpass.set_bind_group(1, bind_group, &[]);
+                        rpass.set_index_buffer(
+                            index_buffer.buffer.slice(..),
+                            wgpu::IndexFormat::Uint32,
+                        );
+                        rpass.set_vertex_buffer(0, vertex_buffer.buffer.slice(..));
+                        rpass.set_vertex_buffer(1, instance_buffer.buffer.slice(..));
+                        rpass.draw_indexed(
+                            0..mesh.indices.len() as u32,
+                            0,
+                            0..instanced.instances.len() as u32,
+                        );
+                    } else {
+                        tracing::warn!("Missing texture: {:?}", mesh.texture_id);
+                    }
+                }
                 Primitive::Callback(callback) => {
                     let cbfn = if let Some(c) = callback.callback.downcast_ref::<CallbackFn>() {
                         c
@@ -458,12 +531,6 @@ impl RenderPass {
         let width = image_delta.image.width() as u32;
         let height = image_delta.image.height() as u32;
 
-        let size = wgpu::Extent3d {
-            width,
-            height,
-            depth_or_array_layers: 1,
-        };
-
         let data_color32 = match &image_delta.image {
             egui::ImageData::Color(image) => {
                 assert_eq!(
@@ -481,26 +548,21 @@ impl RenderPass {
                 );
                 Cow::Owned(image.srgba_pixels(1.0).collect::<Vec<_>>())
             }
+            egui::ImageData::Sdf(image) => {
+                assert_eq!(
+                    width as usize * height as usize,
+                    image.pixels.len(),
+                    "Mismatch between texture size and texel count"
+                );
+                Cow::Owned(image.sdf_srgba_pixels().collect::<Vec<_>>())
+            }
         };
-        let data_bytes: &[u8] = bytemuck::cast_slice(data_color32.as_slice());
 
-        let queue_write_data_to_texture = |texture, origin| {
-            queue.write_texture(
-                wgpu::ImageCopyTexture {
-                    texture,
-                    mip_level: 0,
-                    origin,
-                    aspect: wgpu::TextureAspect::All,
-                },
-                data_bytes,
-                wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: NonZeroU32::new(4 * width),
-                    rows_per_image: NonZeroU32::new(height),
-                },
-                size,
-            );
-        };
+        if matches!(image_delta.image, egui::ImageData::Sdf(_)) {
+            self.sdf_textures.insert(id);
+        } else {
+            self.sdf_textures.remove(&id);
+        }
 
         if let Some(pos) = image_delta.pos {
             // update the existing texture
@@ -508,34 +570,58 @@ impl RenderPass {
                 .textures
                 .get(&id)
                 .expect("Tried to update a texture that has not been allocated yet.");
-            let origin = wgpu::Origin3d {
-                x: pos[0] as u32,
-                y: pos[1] as u32,
-                z: 0,
-            };
-            queue_write_data_to_texture(
-                texture.as_ref().expect("Tried to update user texture."),
-                origin,
-            );
+            let texture = texture.as_ref().expect("Tried to update user texture.");
+            if let Some(&texture_size) = self.mipmapped_textures.get(&id) {
+                write_mip_levels(
+                    queue,
+                    texture,
+                    texture_size,
+                    [pos[0] as u32, pos[1] as u32],
+                    [width, height],
+                    &data_color32,
+                );
+            } else {
+                write_texture_level(
+                    queue,
+                    texture,
+                    0,
+                    [pos[0] as u32, pos[1] as u32],
+                    [width, height],
+                    width,
+                    &data_color32,
+                );
+            }
         } else {
             // allocate a new texture
+            let options = image_delta.options;
+            let mip_level_count = options.mip_level_count(image_delta.image.size());
             let texture = device.create_texture(&wgpu::TextureDescriptor {
                 label: None,
-                size,
-                mip_level_count: 1,
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
                 format: wgpu::TextureFormat::Rgba8UnormSrgb,
                 usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             });
-            let filter = match image_delta.filter {
-                egui::TextureFilter::Nearest => wgpu::FilterMode::Nearest,
-                egui::TextureFilter::Linear => wgpu::FilterMode::Linear,
+            let address_mode = match options.wrap_mode {
+                egui::TextureWrapMode::ClampToEdge => wgpu::AddressMode::ClampToEdge,
+                egui::TextureWrapMode::Repeat => wgpu::AddressMode::Repeat,
+                egui::TextureWrapMode::MirroredRepeat => wgpu::AddressMode::MirrorRepeat,
             };
             let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
                 label: None,
-                mag_filter: filter,
-                min_filter: filter,
+                mag_filter: filter_mode(options.magnification),
+                min_filter: filter_mode(options.minification),
+                mipmap_filter: options
+                    .mipmap_mode
+                    .map_or(wgpu::FilterMode::Nearest, filter_mode),
+                address_mode_u: address_mode,
+                address_mode_v: address_mode,
                 ..Default::default()
             });
             let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -554,14 +640,37 @@ impl RenderPass {
                     },
                 ],
             });
-            let origin = wgpu::Origin3d::ZERO;
-            queue_write_data_to_texture(&texture, origin);
+            if mip_level_count > 1 {
+                write_mip_levels(
+                    queue,
+                    &texture,
+                    [width, height],
+                    [0, 0],
+                    [width, height],
+                    &data_color32,
+                );
+                self.mipmapped_textures.insert(id, [width, height]);
+            } else {
+                write_texture_level(
+                    queue,
+                    &texture,
+                    0,
+                    [0, 0],
+                    [width, height],
+                    width,
+                    &data_color32,
+                );
+                self.mipmapped_textures.remove(&id);
+            }
             self.textures.insert(id, (Some(texture), bind_group));
         };
     }
 
     pub fn free_texture(&mut self, id: &egui::TextureId) {
         self.textures.remove(id);
+        self.offscreen_textures.remove(id);
+        self.mipmapped_textures.remove(id);
+        self.sdf_textures.remove(id);
     }
 
     /// Get the WGPU texture and bind group associated to a texture that has been allocated by egui.
@@ -655,6 +764,247 @@ impl RenderPass {
         id
     }
 
+    /// Paint the given primitives into a new texture instead of to the screen.
+    ///
+    /// This is useful for caching expensive, mostly static UI, or for showing egui
+    /// on surfaces in a 3D scene.
+    /// The returned [`egui::TextureId`] can be shown with [`egui::Image`] like any other texture,
+    /// and freed with [`Self::free_texture`].
+    ///
+    /// The texture has the size given by `screen_descriptor`, and is cleared to `clear_color`
+    /// before painting. The work is submitted to `queue` right away, so call this before
+    /// painting the frame that shows the texture.
+    ///
+    /// [`egui::PaintCallback`]s are not supported when painting to a texture, and will be ignored.
+    pub fn paint_to_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        paint_jobs: &[egui::epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+        clear_color: wgpu::Color,
+    ) -> egui::TextureId {
+        let id = egui::TextureId::User(self.next_user_texture_id);
+        self.next_user_texture_id += 1;
+        self.paint_offscreen(
+            device,
+            queue,
+            id,
+            paint_jobs,
+            screen_descriptor,
+            clear_color,
+        );
+        id
+    }
+
+    /// Paint new content into a texture previously created with [`Self::paint_to_texture`].
+    ///
+    /// The texture is resized if the size in `screen_descriptor` has changed, but keeps its [`egui::TextureId`].
+    ///
+    /// Panics if the size is zero, or larger than the `max_texture_dimension_2d` limit of `device`,
+    /// or if `id` wasn't returned by [`Self::paint_to_texture`], or was freed since.
+    pub fn repaint_texture(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        id: egui::TextureId,
+        paint_jobs: &[egui::epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+        clear_color: wgpu::Color,
+    ) {
+        assert!(
+            self.offscreen_textures.contains_key(&id),
+            "Tried to repaint {:?}, which is not a texture created by paint_to_texture",
+            id
+        );
+
+        self.paint_offscreen(
+            device,
+            queue,
+            id,
+            paint_jobs,
+            screen_descriptor,
+            clear_color,
+        );
+    }
+
+    fn paint_offscreen(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        id: egui::TextureId,
+        paint_jobs: &[egui::epaint::ClippedPrimitive],
+        screen_descriptor: &ScreenDescriptor,
+        clear_color: wgpu::Color,
+    ) {
+        let size_in_pixels = screen_descriptor.size_in_pixels;
+        let pixels_per_point = screen_descriptor.pixels_per_point;
+
+        let [w, h] = size_in_pixels;
+        assert!(
+            w >= 1 && h >= 1,
+            "Tried to paint to a texture of size {}x{}. A texture must at least be one texel wide.",
+            w,
+            h
+        );
+        let max_texture_side = device.limits().max_texture_dimension_2d;
+        assert!(
+            w <= max_texture_side && h <= max_texture_side,
+            "Tried to paint to a texture of size {}x{}, but the maximum supported texture side is only {}",
+            w,
+            h,
+            max_texture_side
+        );
+
+        if self.offscreen_textures.get(&id) != Some(&size_in_pixels) {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("egui_offscreen_texture"),
+                size: wgpu::Extent3d {
+                    width: size_in_pixels[0],
+                    height: size_in_pixels[1],
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::RENDER_ATTACHMENT,
+            });
+            let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+                label: Some("egui_offscreen_texture_sampler"),
+                mag_filter: wgpu::FilterMode::Linear,
+                min_filter: wgpu::FilterMode::Linear,
+                ..Default::default()
+            });
+            let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some("egui_offscreen_texture_bind_group"),
+                layout: &self.texture_bind_group_layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: wgpu::BindingResource::TextureView(
+                            &texture.create_view(&wgpu::TextureViewDescriptor::default()),
+                        ),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: wgpu::BindingResource::Sampler(&sampler),
+                    },
+                ],
+            });
+            self.textures.insert(id, (Some(texture), bind_group));
+            self.offscreen_textures.insert(id, size_in_pixels);
+        }
+
+        // The main vertex, index and uniform buffers may already be in use for this frame,
+        // so we use separate ones:
+        self.update_buffer(
+            device,
+            queue,
+            &BufferType::OffscreenUniform,
+            0,
+            bytemuck::cast_slice(&[UniformBuffer {
+                screen_size_in_points: screen_descriptor.screen_size_in_points(),
+                _padding: Default::default(),
+            }]),
+        );
+        let mut meshes = Vec::with_capacity(paint_jobs.len());
+        for egui::ClippedPrimitive {
+            clip_rect,
+            primitive,
+        } in paint_jobs
+        {
+            let mesh = match primitive {
+                Primitive::Mesh(mesh) => Cow::Borrowed(&**mesh),
+                Primitive::Instanced(instanced) => Cow::Owned(instanced.to_mesh()),
+                Primitive::Callback(_) => {
+                    tracing::warn!("Paint callbacks are not supported when painting to a texture");
+                    continue;
+                }
+            };
+            let mesh_idx = meshes.len();
+            self.update_buffer(
+                device,
+                queue,
+                &BufferType::OffscreenIndex,
+                mesh_idx,
+                bytemuck::cast_slice(&mesh.indices),
+            );
+            self.update_buffer(
+                device,
+                queue,
+                &BufferType::OffscreenVertex,
+                mesh_idx,
+                bytemuck::cast_slice(&mesh.vertices),
+            );
+            meshes.push((clip_rect, mesh.texture_id, mesh.indices.len() as u32));
+        }
+
+        let offscreen_pipelines = self.offscreen_pipelines.get_or_insert_with(|| {
+            Pipelines::new(
+                device,
+                &self.shader_module,
+                &self.pipeline_layout,
+                wgpu::TextureFormat::Rgba8UnormSrgb,
+                1,
+            )
+        });
+
+        let (texture, _bind_group) = &self.textures[&id];
+        let view = texture
+            .as_ref()
+            .expect("offscreen textures are owned by the render pass")
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("egui_offscreen_encoder"),
+        });
+        {
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear_color),
+                        store: true,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                label: Some("egui offscreen render pass"),
+            });
+
+            rpass.set_bind_group(0, &self.offscreen_uniform_bind_group, &[]);
+
+            let buffers = self
+                .offscreen_index_buffers
+                .iter()
+                .zip(&self.offscreen_vertex_buffers);
+            for ((clip_rect, texture_id, num_indices), (index_buffer, vertex_buffer)) in
+                meshes.iter().zip(buffers)
+            {
+                let rect = ScissorRect::new(clip_rect, pixels_per_point, size_in_pixels);
+                if rect.width == 0 || rect.height == 0 {
+                    continue;
+                }
+                rpass.set_scissor_rect(rect.x, rect.y, rect.width, rect.height);
+
+                if let Some((_texture, bind_group)) = self.textures.get(texture_id) {
+                    let sdf = self.sdf_textures.contains(texture_id);
+                    rpass.set_pipeline(offscreen_pipelines.get(sdf));
+                    rpass.set_bind_group(1, bind_group, &[]);
+                    rpass
+                        .set_index_buffer(index_buffer.buffer.slice(..), wgpu::IndexFormat::Uint32);
+                    rpass.set_vertex_buffer(0, vertex_buffer.buffer.slice(..));
+                    rpass.draw_indexed(0..*num_indices, 0, 0..1);
+                } else {
+                    tracing::warn!("Missing texture: {:?}", texture_id);
+                }
+            }
+        }
+        queue.submit(Some(encoder.finish()));
+    }
+
     /// Uploads the uniform, vertex and index data used by the render pass.
     /// Should be called before `execute()`.
     pub fn update_buffers(
@@ -678,42 +1028,21 @@ impl RenderPass {
         );
 
         let mut mesh_idx = 0;
+        let mut instance_idx = 0;
         for egui::ClippedPrimitive { primitive, .. } in paint_jobs.iter() {
             match primitive {
                 Primitive::Mesh(mesh) => {
-                    let data: &[u8] = bytemuck::cast_slice(&mesh.indices);
-                    if mesh_idx < self.index_buffers.len() {
-                        self.update_buffer(device, queue, &BufferType::Index, mesh_idx, data);
-                    } else {
-                        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                            label: Some("egui_index_buffer"),
-                            contents: data,
-                            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
-                        });
-                        self.index_buffers.push(SizedBuffer {
-                            buffer,
-                            size: data.len(),
-                        });
-                    }
-
-                    let data: &[u8] = bytemuck::cast_slice(&mesh.vertices);
-                    if mesh_idx < self.vertex_buffers.len() {
-                        self.update_buffer(device, queue, &BufferType::Vertex, mesh_idx, data);
-                    } else {
-                        let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                            label: Some("egui_vertex_buffer"),
-                            contents: data,
-                            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
-                        });
-
-                        self.vertex_buffers.push(SizedBuffer {
-                            buffer,
-                            size: data.len(),
-                        });
-                    }
-
+                    self.update_mesh_buffers(device, queue, mesh_idx, mesh);
                     mesh_idx += 1;
                 }
+                Primitive::Instanced(instanced) => {
+                    self.update_mesh_buffers(device, queue, mesh_idx, &instanced.mesh);
+                    mesh_idx += 1;
+
+                    let data: &[u8] = bytemuck::cast_slice(&instanced.instances[..]);
+                    self.update_buffer(device, queue, &BufferType::Instance, instance_idx, data);
+                    instance_idx += 1;
+                }
                 Primitive::Callback(callback) => {
                     let cbfn = if let Some(c) = callback.callback.downcast_ref::<CallbackFn>() {
                         c
@@ -728,7 +1057,22 @@ impl RenderPass {
         }
     }
 
-    /// Updates the buffers used by egui. Will properly re-size the buffers if needed.
+    /// Upload the indices and vertices of the mesh with the given index.
+    fn update_mesh_buffers(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        mesh_idx: usize,
+        mesh: &egui::epaint::Mesh,
+    ) {
+        let data: &[u8] = bytemuck::cast_slice(&mesh.indices);
+        self.update_buffer(device, queue, &BufferType::Index, mesh_idx, data);
+        let data: &[u8] = bytemuck::cast_slice(&mesh.vertices);
+        self.update_buffer(device, queue, &BufferType::Vertex, mesh_idx, data);
+    }
+
+    /// Updates the buffers used by egui. Will properly re-size the buffers if needed,
+    /// and create the buffer if `index` is one past the last one of its type.
     fn update_buffer(
         &mut self,
         device: &wgpu::Device,
@@ -737,37 +1081,312 @@ impl RenderPass {
         index: usize,
         data: &[u8],
     ) {
-        let (buffer, storage, label) = match buffer_type {
+        let (buffers, storage, label) = match buffer_type {
             BufferType::Index => (
-                &mut self.index_buffers[index],
+                &mut self.index_buffers,
                 wgpu::BufferUsages::INDEX,
                 "egui_index_buffer",
             ),
             BufferType::Vertex => (
-                &mut self.vertex_buffers[index],
+                &mut self.vertex_buffers,
                 wgpu::BufferUsages::VERTEX,
                 "egui_vertex_buffer",
             ),
-            BufferType::Uniform => (
-                &mut self.uniform_buffer,
-                wgpu::BufferUsages::UNIFORM,
-                "egui_uniform_buffer",
+            BufferType::Instance => (
+                &mut self.instance_buffers,
+                wgpu::BufferUsages::VERTEX,
+                "egui_instance_buffer",
+            ),
+            BufferType::OffscreenIndex => (
+                &mut self.offscreen_index_buffers,
+                wgpu::BufferUsages::INDEX,
+                "egui_offscreen_index_buffer",
             ),
+            BufferType::OffscreenVertex => (
+                &mut self.offscreen_vertex_buffers,
+                wgpu::BufferUsages::VERTEX,
+                "egui_offscreen_vertex_buffer",
+            ),
+            BufferType::Uniform | BufferType::OffscreenUniform => {
+                // Never re-sized, since the bind group refers to it:
+                let buffer = if matches!(buffer_type, BufferType::Uniform) {
+                    &self.uniform_buffer
+                } else {
+                    &self.offscreen_uniform_buffer
+                };
+                debug_assert_eq!(data.len(), buffer.size);
+                queue.write_buffer(&buffer.buffer, 0, data);
+                return;
+            }
         };
 
-        if data.len() > buffer.size {
-            buffer.size = data.len();
-            buffer.buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        let create_buffer = || SizedBuffer {
+            buffer: device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some(label),
-                contents: bytemuck::cast_slice(data),
+                contents: data,
                 usage: storage | wgpu::BufferUsages::COPY_DST,
-            });
+            }),
+            size: data.len(),
+        };
+        if index == buffers.len() {
+            buffers.push(create_buffer());
+        } else if data.len() > buffers[index].size {
+            buffers[index] = create_buffer();
         } else {
-            queue.write_buffer(&buffer.buffer, 0, data);
+            queue.write_buffer(&buffers[index].buffer, 0, data);
+        }
+    }
+}
+
+fn filter_mode(filter: egui::TextureFilter) -> wgpu::FilterMode {
+    match filter {
+        egui::TextureFilter::Nearest => wgpu::FilterMode::Nearest,
+        egui::TextureFilter::Linear => wgpu::FilterMode::Linear,
+    }
+}
+
+/// Write a region of `pixels`, which is `row_width` texels wide, into one mip level of a texture.
+fn write_texture_level(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    mip_level: u32,
+    [x, y]: [u32; 2],
+    [width, height]: [u32; 2],
+    row_width: u32,
+    pixels: &[egui::Color32],
+) {
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level,
+            origin: wgpu::Origin3d { x, y, z: 0 },
+            aspect: wgpu::TextureAspect::All,
+        },
+        bytemuck::cast_slice(pixels),
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: NonZeroU32::new(4 * row_width),
+            rows_per_image: NonZeroU32::new(height),
+        },
+        wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
+/// Write a patch of a texture to all of its mip levels, downsampling it on the CPU.
+///
+/// wgpu has no built-in mipmap generation, so we do it ourselves.
+/// Patches that are not aligned to the mip level will be slightly off at their edges.
+fn write_mip_levels(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    texture_size: [u32; 2],
+    [mut x, mut y]: [u32; 2],
+    [mut width, mut height]: [u32; 2],
+    pixels: &[egui::Color32],
+) {
+    let mip_level_count = u32::BITS - texture_size[0].max(texture_size[1]).leading_zeros();
+    let mut pixels = Cow::Borrowed(pixels);
+
+    for mip_level in 0..mip_level_count {
+        if mip_level > 0 {
+            pixels = Cow::Owned(downsample(&pixels, [width, height]));
+            x /= 2;
+            y /= 2;
+            width = (width + 1) / 2;
+            height = (height + 1) / 2;
         }
+
+        let level_width = (texture_size[0] >> mip_level).at_least(1);
+        let level_height = (texture_size[1] >> mip_level).at_least(1);
+        write_texture_level(
+            queue,
+            texture,
+            mip_level,
+            [x, y],
+            [width.min(level_width - x), height.min(level_height - y)],
+            width,
+            &pixels,
+        );
     }
 }
 
+/// Halve the size of an image, rounding up, by averaging 2x2 blocks in linear space.
+fn downsample(pixels: &[egui::Color32], [width, height]: [u32; 2]) -> Vec<egui::Color32> {
+    let (width, height) = (width as usize, height as usize);
+    let (new_width, new_height) = ((width + 1) / 2, (height + 1) / 2);
+
+    let mut result = Vec::with_capacity(new_width * new_height);
+    for y in 0..new_height {
+        for x in 0..new_width {
+            let mut sum = egui::Rgba::TRANSPARENT;
+            let mut count = 0.0;
+            for sy in (2 * y)..(2 * y + 2).min(height) {
+                for sx in (2 * x)..(2 * x + 2).min(width) {
+                    sum = sum + egui::Rgba::from(pixels[sy * width + sx]);
+                    count += 1.0;
+                }
+            }
+            result.push((sum * (1.0 / count)).into());
+        }
+    }
+    result
+}
+
+/// The render pipelines for one output format.
+struct Pipelines {
+    /// For all textures but signed distance fields.
+    normal: wgpu::RenderPipeline,
+
+    /// For textures of [`egui::ImageData::Sdf`].
+    sdf: wgpu::RenderPipeline,
+
+    /// For [`Primitive::Instanced`].
+    instanced: wgpu::RenderPipeline,
+
+    /// For [`Primitive::Instanced`] with textures of [`egui::ImageData::Sdf`].
+    instanced_sdf: wgpu::RenderPipeline,
+}
+
+impl Pipelines {
+    fn new(
+        device: &wgpu::Device,
+        module: &wgpu::ShaderModule,
+        pipeline_layout: &wgpu::PipelineLayout,
+        output_format: wgpu::TextureFormat,
+        msaa_samples: u32,
+    ) -> Self {
+        let create = |instanced, fragment_entry_point| {
+            create_render_pipeline(
+                device,
+                module,
+                pipeline_layout,
+                output_format,
+                msaa_samples,
+                instanced,
+                fragment_entry_point,
+            )
+        };
+        Self {
+            normal: create(false, "fs_main"),
+            sdf: create(false, "fs_main_sdf"),
+            instanced: create(true, "fs_main"),
+            instanced_sdf: create(true, "fs_main_sdf"),
+        }
+    }
+
+    fn get(&self, sdf: bool) -> &wgpu::RenderPipeline {
+        if sdf {
+            &self.sdf
+        } else {
+            &self.normal
+        }
+    }
+
+    fn get_instanced(&self, sdf: bool) -> &wgpu::RenderPipeline {
+        if sdf {
+            &self.instanced_sdf
+        } else {
+            &self.instanced
+        }
+    }
+}
+
+/// Set the pipeline, unless it is the `current` one already.
+fn set_pipeline<'rpass>(
+    rpass: &mut wgpu::RenderPass<'rpass>,
+    current: &mut Option<&'rpass wgpu::RenderPipeline>,
+    pipeline: &'rpass wgpu::RenderPipeline,
+) {
+    if !current.map_or(false, |current| std::ptr::eq(current, pipeline)) {
+        rpass.set_pipeline(pipeline);
+        *current = Some(pipeline);
+    }
+}
+
+fn create_render_pipeline(
+    device: &wgpu::Device,
+    module: &wgpu::ShaderModule,
+    pipeline_layout: &wgpu::PipelineLayout,
+    output_format: wgpu::TextureFormat,
+    msaa_samples: u32,
+    instanced: bool,
+    fragment_entry_point: &str,
+) -> wgpu::RenderPipeline {
+    let buffers = [
+        wgpu::VertexBufferLayout {
+            array_stride: 5 * 4,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            // 0: vec2 position
+            // 1: vec2 texture coordinates
+            // 2: uint color
+            attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2, 2 => Uint32],
+        },
+        wgpu::VertexBufferLayout {
+            array_stride: 3 * 4,
+            step_mode: wgpu::VertexStepMode::Instance,
+            // 3: vec2 offset
+            // 4: uint color
+            attributes: &wgpu::vertex_attr_array![3 => Float32x2, 4 => Uint32],
+        },
+    ];
+
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("egui_pipeline"),
+        layout: Some(pipeline_layout),
+        vertex: wgpu::VertexState {
+            entry_point: match (output_format.describe().srgb, instanced) {
+                (true, false) => "vs_main",
+                (false, false) => "vs_conv_main",
+                (true, true) => "vs_main_instanced",
+                (false, true) => "vs_conv_main_instanced",
+            },
+            module,
+            buffers: if instanced { &buffers } else { &buffers[..1] },
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            unclipped_depth: false,
+            conservative: false,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::default(),
+            polygon_mode: wgpu::PolygonMode::default(),
+            strip_index_format: None,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+            alpha_to_coverage_enabled: false,
+            count: msaa_samples,
+            mask: !0,
+        },
+
+        fragment: Some(wgpu::FragmentState {
+            module,
+            entry_point: fragment_entry_point,
+            targets: &[Some(wgpu::ColorTargetState {
+                format: output_format,
+                blend: Some(wgpu::BlendState {
+                    color: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::One,
+                        dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                    alpha: wgpu::BlendComponent {
+                        src_factor: wgpu::BlendFactor::OneMinusDstAlpha,
+                        dst_factor: wgpu::BlendFactor::One,
+                        operation: wgpu::BlendOperation::Add,
+                    },
+                }),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+        }),
+        multiview: None,
+    })
+}
+
 /// A Rect in physical pixel space, used for setting cliipping rectangles.
 struct ScissorRect {
     x: u32,
@@ -813,3 +1432,11 @@ fn render_pass_impl_send_sync() {
     fn assert_send_sync<T: Send + Sync>() {}
     assert_send_sync::<RenderPass>();
 }
+
+#[test]
+fn downsample_odd_sized_image() {
+    let pixels = vec![egui::Color32::WHITE; 3 * 3];
+    let downsampled = downsample(&pixels, [3, 3]);
+    assert_eq!(downsampled.len(), 2 * 2);
+    assert!(downsampled.iter().all(|&c| c == egui::Color32::WHITE));
+}
@@ -159,6 +159,13 @@ impl State {
     /// and only when this returns `false` pass on the events to your game.
     ///
     /// Note that egui uses `tab` to move focus between elements, so this will always return `true` for tabs.
+    ///
+    /// ## Pens
+    /// [`egui::Event::Pen`] is only reported on iOS (the Apple Pencil), which is the only platform
+    /// where winit tells pens and fingers apart (pens have an altitude angle).
+    /// On Windows pens are reported as plain touches, and on other platforms as the mouse,
+    /// so there they only produce the usual touch and pointer events.
+    /// Tilt direction, hovering, the barrel button and the eraser are not reported by winit at all.
     pub fn on_event(
         &mut self,
         egui_ctx: &egui::Context,
@@ -362,6 +369,7 @@ impl State {
                 None => 0_f32,
             },
         });
+        self.on_pen_touch(touch);
         // If we're not yet tanslating a touch or we're translating this very
         // touch …
         if self.pointer_touch_id.is_none() || self.pointer_touch_id.unwrap() == touch.id {
@@ -399,6 +407,38 @@ impl State {
         }
     }
 
+    /// winit reports pens as touches, but only pens (the Apple Pencil) have an altitude angle.
+    /// See [`Self::on_event`] for the other platforms.
+    fn on_pen_touch(&mut self, touch: &winit::event::Touch) {
+        if let Some(winit::event::Force::Calibrated {
+            force,
+            max_possible_force,
+            altitude_angle: Some(_),
+        }) = touch.force
+        {
+            let event = match touch.phase {
+                winit::event::TouchPhase::Started | winit::event::TouchPhase::Moved => {
+                    egui::Event::Pen(egui::PenState {
+                        pos: egui::pos2(
+                            touch.location.x as f32 / self.pixels_per_point(),
+                            touch.location.y as f32 / self.pixels_per_point(),
+                        ),
+                        contact: true,
+                        pressure: (force / max_possible_force) as f32,
+                        // winit tells us how much the pen is tilted, but not in which direction:
+                        tilt: egui::Vec2::ZERO,
+                        barrel_button: false,
+                        eraser: false,
+                    })
+                }
+                winit::event::TouchPhase::Ended | winit::event::TouchPhase::Cancelled => {
+                    egui::Event::PenGone
+                }
+            };
+            self.egui_input.events.push(event);
+        }
+    }
+
     fn on_mouse_wheel(&mut self, delta: winit::event::MouseScrollDelta) {
         let delta = match delta {
             winit::event::MouseScrollDelta::LineDelta(x, y) => {
@@ -463,7 +503,10 @@ impl State {
     /// * copy text to the clipboard
     /// * open any clicked urls
     /// * update the IME
-    /// *
+    ///
+    /// [`egui::PlatformOutput::accessibility_tree`] is not exported to the platform accessibility APIs yet,
+    /// so native screen readers only get [`egui::PlatformOutput::events`] read out loud
+    /// (with [`egui::Options::screen_reader`]).
     pub fn handle_platform_output(
         &mut self,
         window: &winit::window::Window,
@@ -481,6 +524,7 @@ impl State {
             copied_text,
             events: _,                    // handled above
             mutable_text_under_cursor: _, // only used in eframe web
+            accessibility_tree: _,        // not exported yet, see above
             text_cursor_pos,
         } = platform_output;
         self.current_pixels_per_point = egui_ctx.pixels_per_point(); // someone can have changed it to scale the UI
@@ -754,3 +798,65 @@ macro_rules! profile_scope {
 
 #[allow(unused_imports)]
 pub(crate) use profile_scope;
+
+#[test]
+fn test_pen_touch() {
+    use winit::event::{Force, Touch, TouchPhase, WindowEvent};
+
+    fn touch(phase: TouchPhase, force: Option<Force>) -> WindowEvent<'static> {
+        WindowEvent::Touch(Touch {
+            device_id: unsafe { winit::event::DeviceId::dummy() },
+            phase,
+            location: winit::dpi::PhysicalPosition::new(10.0, 20.0),
+            force,
+            id: 0,
+        })
+    }
+
+    fn pen_events(state: &mut State) -> Vec<egui::Event> {
+        state
+            .egui_input
+            .events
+            .drain(..)
+            .filter(|event| matches!(event, egui::Event::Pen(_) | egui::Event::PenGone))
+            .collect()
+    }
+
+    let egui_ctx = egui::Context::default();
+    let mut state = State::new_with_wayland_display(None);
+
+    // The Apple Pencil:
+    let pencil = Force::Calibrated {
+        force: 2.0,
+        max_possible_force: 4.0,
+        altitude_angle: Some(1.0),
+    };
+    state.on_event(&egui_ctx, &touch(TouchPhase::Started, Some(pencil)));
+    state.on_event(&egui_ctx, &touch(TouchPhase::Ended, Some(pencil)));
+    assert_eq!(
+        pen_events(&mut state),
+        vec![
+            egui::Event::Pen(egui::PenState {
+                pos: egui::pos2(10.0, 20.0),
+                contact: true,
+                pressure: 0.5,
+                tilt: egui::Vec2::ZERO,
+                barrel_button: false,
+                eraser: false,
+            }),
+            egui::Event::PenGone,
+        ]
+    );
+
+    // A finger on iOS, and a finger or a pen on Windows:
+    let finger = Force::Calibrated {
+        force: 2.0,
+        max_possible_force: 4.0,
+        altitude_angle: None,
+    };
+    for force in [Some(finger), Some(Force::Normalized(0.5)), None] {
+        state.on_event(&egui_ctx, &touch(TouchPhase::Started, force));
+        state.on_event(&egui_ctx, &touch(TouchPhase::Ended, force));
+        assert_eq!(pen_events(&mut state), vec![]);
+    }
+}
@@ -53,6 +53,7 @@ pub struct Area {
     anchor: Option<(Align2, Vec2)>,
     new_pos: Option<Pos2>,
     drag_bounds: Option<Rect>,
+    role: WidgetType,
 }
 
 impl Area {
@@ -67,6 +68,7 @@ impl Area {
             new_pos: None,
             anchor: None,
             drag_bounds: None,
+            role: WidgetType::Other,
         }
     }
 
@@ -157,6 +159,14 @@ impl Area {
         self
     }
 
+    /// What kind of container this is, e.g. [`WidgetType::Menu`], for accessibility.
+    ///
+    /// Default: [`WidgetType::Other`].
+    pub fn role(mut self, role: WidgetType) -> Self {
+        self.role = role;
+        self
+    }
+
     pub(crate) fn get_pivot(&self) -> Align2 {
         if let Some((pivot, _)) = self.anchor {
             pivot
@@ -204,6 +214,7 @@ impl Area {
             new_pos,
             anchor,
             drag_bounds,
+            role,
         } = self;
 
         let layer_id = LayerId::new(order, id);
@@ -233,6 +244,9 @@ impl Area {
 
         state.pos = ctx.round_pos_to_pixels(state.pos);
 
+        ctx.accessibility_container(id, state.rect(), || WidgetInfo::new(role));
+        ctx.push_accessibility_parent(id); // popped in `Prepared::end`
+
         Prepared {
             layer_id,
             state,
@@ -374,6 +388,7 @@ impl Prepared {
             ctx.request_repaint();
         }
         ctx.memory().areas.set_state(layer_id, state);
+        ctx.pop_accessibility_parent();
 
         move_response
     }
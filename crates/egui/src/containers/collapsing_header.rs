@@ -82,6 +82,8 @@ impl CollapsingState {
         if response.clicked() {
             self.toggle(ui);
         }
+        let is_open = self.is_open();
+        response.widget_info(|| WidgetInfo::expandable(WidgetType::Button, is_open, ""));
         let openness = self.openness(ui.ctx());
         paint_default_icon(ui, openness, &response);
         response
@@ -104,6 +106,8 @@ impl CollapsingState {
         if response.clicked() {
             self.toggle(ui);
         }
+        let is_open = self.is_open();
+        response.widget_info(|| WidgetInfo::expandable(WidgetType::TreeItem, is_open, ""));
 
         let (mut icon_rect, _) = ui.spacing().icon_rectangles(response.rect);
         icon_rect.set_center(pos2(
@@ -278,9 +282,11 @@ impl<'ui, HeaderRet> HeaderResponse<'ui, HeaderRet> {
         InnerResponse<HeaderRet>,
         Option<InnerResponse<BodyRet>>,
     ) {
-        let body_response =
+        let ctx = self.ui.ctx().clone();
+        let body_response = ctx.with_accessibility_parent(self.toggle_button_response.id, || {
             self.state
-                .show_body_indented(&self.header_response.response, self.ui, add_body);
+                .show_body_indented(&self.header_response.response, self.ui, add_body)
+        });
         (
             self.toggle_button_response,
             self.header_response,
@@ -297,7 +303,10 @@ impl<'ui, HeaderRet> HeaderResponse<'ui, HeaderRet> {
         InnerResponse<HeaderRet>,
         Option<InnerResponse<BodyRet>>,
     ) {
-        let body_response = self.state.show_body_unindented(self.ui, add_body);
+        let ctx = self.ui.ctx().clone();
+        let body_response = ctx.with_accessibility_parent(self.toggle_button_response.id, || {
+            self.state.show_body_unindented(self.ui, add_body)
+        });
         (
             self.toggle_button_response,
             self.header_response,
@@ -540,8 +549,10 @@ impl CollapsingHeader {
             header_response.mark_changed();
         }
 
-        header_response
-            .widget_info(|| WidgetInfo::labeled(WidgetType::CollapsingHeader, text.text()));
+        let is_open = state.is_open();
+        header_response.widget_info(|| {
+            WidgetInfo::expandable(WidgetType::CollapsingHeader, is_open, text.text())
+        });
 
         let openness = state.openness(ui.ctx());
 
@@ -618,7 +629,10 @@ impl CollapsingHeader {
                 openness,
             } = self.begin(ui); // show the header
 
-            let ret_response = state.show_body_indented(&header_response, ui, add_body);
+            let ctx = ui.ctx().clone();
+            let ret_response = ctx.with_accessibility_parent(header_response.id, || {
+                state.show_body_indented(&header_response, ui, add_body)
+            });
 
             if let Some(ret_response) = ret_response {
                 CollapsingResponse {
@@ -143,13 +143,15 @@ impl ComboBox {
                 ui.spacing_mut().slider_width = width; // yes, this is ugly. Will remove later.
             }
             let mut ir = combo_box_dyn(ui, button_id, selected_text, menu_contents, icon);
+            let is_open = ui.memory().is_popup_open(button_id.with("popup"));
             if let Some(label) = label {
-                ir.response
-                    .widget_info(|| WidgetInfo::labeled(WidgetType::ComboBox, label.text()));
+                ir.response.widget_info(|| {
+                    WidgetInfo::expandable(WidgetType::ComboBox, is_open, label.text())
+                });
                 ir.response |= ui.label(label);
             } else {
                 ir.response
-                    .widget_info(|| WidgetInfo::labeled(WidgetType::ComboBox, ""));
+                    .widget_info(|| WidgetInfo::expandable(WidgetType::ComboBox, is_open, ""));
             }
             ir
         })
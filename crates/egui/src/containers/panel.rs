@@ -242,10 +242,14 @@ impl SidePanel {
         let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
         panel_ui.expand_to_include_rect(panel_rect);
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
-        let inner_response = frame.show(&mut panel_ui, |ui| {
-            ui.set_min_height(ui.max_rect().height()); // Make sure the frame fills the full height
-            ui.set_min_width(*width_range.start());
-            add_contents(ui)
+        let ctx = ui.ctx().clone();
+        ctx.accessibility_container(id, panel_rect, || WidgetInfo::new(WidgetType::Panel));
+        let inner_response = ctx.with_accessibility_parent(id, || {
+            frame.show(&mut panel_ui, |ui| {
+                ui.set_min_height(ui.max_rect().height()); // Make sure the frame fills the full height
+                ui.set_min_width(*width_range.start());
+                add_contents(ui)
+            })
         });
 
         let rect = inner_response.response.rect;
@@ -529,10 +533,14 @@ impl TopBottomPanel {
         let mut panel_ui = ui.child_ui_with_id_source(panel_rect, Layout::top_down(Align::Min), id);
         panel_ui.expand_to_include_rect(panel_rect);
         let frame = frame.unwrap_or_else(|| Frame::side_top_panel(ui.style()));
-        let inner_response = frame.show(&mut panel_ui, |ui| {
-            ui.set_min_width(ui.max_rect().width()); // Make the frame fill full width
-            ui.set_min_height(*height_range.start());
-            add_contents(ui)
+        let ctx = ui.ctx().clone();
+        ctx.accessibility_container(id, panel_rect, || WidgetInfo::new(WidgetType::Panel));
+        let inner_response = ctx.with_accessibility_parent(id, || {
+            frame.show(&mut panel_ui, |ui| {
+                ui.set_min_width(ui.max_rect().width()); // Make the frame fill full width
+                ui.set_min_height(*height_range.start());
+                add_contents(ui)
+            })
         });
 
         let rect = inner_response.response.rect;
@@ -670,9 +678,14 @@ impl CentralPanel {
         let mut panel_ui = ui.child_ui(panel_rect, Layout::top_down(Align::Min));
 
         let frame = frame.unwrap_or_else(|| Frame::central_panel(ui.style()));
-        frame.show(&mut panel_ui, |ui| {
-            ui.expand_to_include_rect(ui.max_rect()); // Expand frame to include it all
-            add_contents(ui)
+        let id = panel_ui.id();
+        let ctx = ui.ctx().clone();
+        ctx.accessibility_container(id, panel_rect, || WidgetInfo::new(WidgetType::Panel));
+        ctx.with_accessibility_parent(id, || {
+            frame.show(&mut panel_ui, |ui| {
+                ui.expand_to_include_rect(ui.max_rect()); // Expand frame to include it all
+                add_contents(ui)
+            })
         })
     }
 
@@ -252,6 +252,7 @@ fn show_tooltip_area_dyn<'c, R>(
 ) -> InnerResponse<R> {
     use containers::*;
     Area::new(id)
+        .role(WidgetType::Tooltip)
         .order(Order::Tooltip)
         .fixed_pos(window_pos)
         .interactable(false)
@@ -296,6 +297,7 @@ pub fn popup_below_widget<R>(
 ) -> Option<R> {
     if ui.memory().is_popup_open(popup_id) {
         let inner = Area::new(popup_id)
+            .role(WidgetType::Menu)
             .order(Order::Foreground)
             .fixed_pos(widget_response.rect.left_bottom())
             .show(ui.ctx(), |ui| {
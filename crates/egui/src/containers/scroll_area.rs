@@ -20,6 +20,14 @@ pub struct State {
     #[cfg_attr(feature = "serde", serde(skip))]
     pub vel: Vec2,
 
+    /// Where we are smoothly scrolling to, if anywhere.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    smooth_scroll_target: [Option<f32>; 2],
+
+    /// Was the mouse wheel (or trackpad) scrolling us last frame?
+    #[cfg_attr(feature = "serde", serde(skip))]
+    wheel_scrolling: bool,
+
     /// Mouse offset relative to the top of the handle when started moving the handle.
     scroll_start_offset_from_top_left: [Option<f32>; 2],
 
@@ -35,6 +43,8 @@ impl Default for State {
             offset: Vec2::ZERO,
             show_scroll: [false; 2],
             vel: Vec2::ZERO,
+            smooth_scroll_target: [None; 2],
+            wheel_scrolling: false,
             scroll_start_offset_from_top_left: [None; 2],
             scroll_stuck_to_end: [true; 2],
         }
@@ -334,8 +344,18 @@ impl ScrollArea {
         );
         let mut state = State::load(&ctx, id).unwrap_or_default();
 
-        state.offset.x = offset_x.unwrap_or(state.offset.x);
-        state.offset.y = offset_y.unwrap_or(state.offset.y);
+        for (d, offset) in [offset_x, offset_y].into_iter().enumerate() {
+            if let Some(offset) = offset {
+                state.offset[d] = offset;
+                state.smooth_scroll_target[d] = None;
+            }
+        }
+
+        let accessibility_offset = ctx.input().accessibility_set_value(id);
+        if let Some(offset) = accessibility_offset {
+            let d = if has_bar[1] { 1 } else { 0 };
+            state.offset[d] = offset as f32; // clamped in `Prepared::end`
+        }
 
         let max_scroll_bar_width = max_scroll_bar_width_with_margin(ui);
 
@@ -497,7 +517,11 @@ impl ScrollArea {
         let mut prepared = self.begin(ui);
         let id = prepared.id;
         let inner_rect = prepared.inner_rect;
-        let inner = add_contents(&mut prepared.content_ui, prepared.viewport);
+        let ctx = ui.ctx().clone();
+        ctx.accessibility_container(id, inner_rect, || WidgetInfo::new(WidgetType::ScrollArea));
+        let inner = ctx.with_accessibility_parent(id, || {
+            add_contents(&mut prepared.content_ui, prepared.viewport)
+        });
         let state = prepared.end(ui);
         ScrollAreaOutput {
             inner,
@@ -525,6 +549,8 @@ impl Prepared {
         } = self;
 
         let content_size = content_ui.min_size();
+        let scroll_style = ui.style().scroll.clone();
+        let dt = ui.input().stable_dt.min(0.1);
 
         for d in 0..2 {
             if has_bar[d] {
@@ -560,7 +586,11 @@ impl Prepared {
                     };
 
                     if delta != 0.0 {
-                        state.offset[d] += delta;
+                        if scroll_style.smooth_scroll_time > 0.0 {
+                            state.smooth_scroll_target[d] = Some(state.offset[d] + delta);
+                        } else {
+                            state.offset[d] += delta;
+                        }
                         ui.ctx().request_repaint();
                     }
                 }
@@ -606,6 +636,9 @@ impl Prepared {
             content_size.y > inner_rect.height(),
         ];
 
+        let max_offset = content_size - inner_rect.size();
+        let mut content_dragged = false;
+
         if content_is_too_large[0] || content_is_too_large[1] {
             // Drag contents to scroll (for touch screens mostly):
             let sense = if self.scrolling_enabled {
@@ -616,45 +649,62 @@ impl Prepared {
             let content_response = ui.interact(inner_rect, id.with("area"), sense);
 
             if content_response.dragged() {
+                content_dragged = true;
+
+                // When panning with two fingers, follow both of them:
+                let delta = {
+                    let input = ui.input();
+                    input
+                        .multi_touch()
+                        .map_or(input.pointer.delta(), |touch| touch.translation_delta)
+                };
                 for d in 0..2 {
                     if has_bar[d] {
-                        state.offset[d] -= ui.input().pointer.delta()[d];
+                        let mut delta = delta[d];
+                        let overscroll = overscroll(state.offset[d], max_offset[d]);
+                        if scroll_style.overscroll && overscroll * delta < 0.0 {
+                            // The further the content is pulled past its edge, the harder it gets:
+                            delta *= (1.0 - overscroll.abs() / scroll_style.max_overscroll)
+                                .at_least(0.0);
+                        }
+                        state.offset[d] -= delta;
                         state.vel[d] = ui.input().pointer.velocity()[d];
+                        state.smooth_scroll_target[d] = None;
                         state.scroll_stuck_to_end[d] = false;
                     } else {
                         state.vel[d] = 0.0;
                     }
                 }
-            } else {
-                let stop_speed = 20.0; // Pixels per second.
-                let friction_coeff = 1000.0; // Pixels per second squared.
-                let dt = ui.input().unstable_dt;
-
-                let friction = friction_coeff * dt;
-                if friction > state.vel.length() || state.vel.length() < stop_speed {
-                    state.vel = Vec2::ZERO;
-                } else {
-                    state.vel -= friction * state.vel.normalized();
-                    // Offset has an inverted coordinate system compared to
-                    // the velocity, so we subtract it instead of adding it
-                    state.offset -= state.vel * dt;
-                    ui.ctx().request_repaint();
+            } else if let Some(fling_velocity) = content_response.fling_velocity() {
+                for d in 0..2 {
+                    if has_bar[d] {
+                        state.vel[d] = fling_velocity[d];
+                    }
                 }
             }
         }
 
-        let max_offset = content_size - inner_rect.size();
+        let mut wheel_scrolling = false;
         if scrolling_enabled && ui.rect_contains_pointer(outer_rect) {
             for d in 0..2 {
                 if has_bar[d] {
                     let mut frame_state = ui.ctx().frame_state();
                     let scroll_delta = frame_state.scroll_delta;
 
-                    let scrolling_up = state.offset[d] > 0.0 && scroll_delta[d] > 0.0;
-                    let scrolling_down = state.offset[d] < max_offset[d] && scroll_delta[d] < 0.0;
+                    // If we are already smooth scrolling, continue from where we are heading:
+                    let offset = state.smooth_scroll_target[d].unwrap_or(state.offset[d]);
+                    let scrolling_up = offset > 0.0 && scroll_delta[d] > 0.0;
+                    let scrolling_down = offset < max_offset[d] && scroll_delta[d] < 0.0;
 
                     if scrolling_up || scrolling_down {
-                        state.offset[d] -= scroll_delta[d];
+                        let target = offset - scroll_delta[d];
+                        if scroll_style.smooth_scroll_time > 0.0 {
+                            state.smooth_scroll_target[d] = Some(target);
+                        } else {
+                            state.offset[d] = target;
+                        }
+                        state.vel[d] = 0.0;
+                        wheel_scrolling = true;
                         // Clear scroll delta so no parent scroll will use it.
                         frame_state.scroll_delta[d] = 0.0;
                         state.scroll_stuck_to_end[d] = false;
@@ -663,6 +713,73 @@ impl Prepared {
             }
         }
 
+        if state.wheel_scrolling
+            && !wheel_scrolling
+            && scroll_style.kinetic
+            && scroll_style.kinetic_wheel
+        {
+            // The scrolling stopped. If it was a flick (e.g. on a trackpad), keep going:
+            let velocity = ui.input().scroll_velocity();
+            for d in 0..2 {
+                if has_bar[d] && velocity[d] != 0.0 {
+                    state.vel[d] = velocity[d];
+                    state.smooth_scroll_target[d] = None;
+                }
+            }
+        }
+        state.wheel_scrolling = wheel_scrolling;
+
+        if !content_dragged {
+            if scroll_style.kinetic {
+                let friction = scroll_style.friction * dt;
+                if friction > state.vel.length() || state.vel.length() < scroll_style.stop_speed {
+                    state.vel = Vec2::ZERO;
+                } else {
+                    state.vel -= friction * state.vel.normalized();
+                    // Offset has an inverted coordinate system compared to
+                    // the velocity, so we subtract it instead of adding it
+                    state.offset -= state.vel * dt;
+                    ui.ctx().request_repaint();
+                }
+            } else {
+                state.vel = Vec2::ZERO;
+            }
+        }
+
+        for d in 0..2 {
+            if let Some(target) = state.smooth_scroll_target[d] {
+                let target = target.clamp(0.0, max_offset[d].at_least(0.0));
+                let t = ease_towards(dt, scroll_style.smooth_scroll_time);
+                state.offset[d] = lerp(state.offset[d]..=target, t);
+                if (target - state.offset[d]).abs() < 0.5 {
+                    state.offset[d] = target;
+                    state.smooth_scroll_target[d] = None;
+                } else {
+                    ui.ctx().request_repaint();
+                }
+            }
+        }
+
+        if scroll_style.overscroll && !content_dragged {
+            for d in 0..2 {
+                let overscroll = overscroll(state.offset[d], max_offset[d]);
+                if has_bar[d] && overscroll != 0.0 {
+                    // Brake hard, and spring back:
+                    state.vel[d] *= 1.0 - ease_towards(dt, 0.3 * scroll_style.bounce_time);
+                    let t = ease_towards(dt, scroll_style.bounce_time);
+                    state.offset[d] -= t * overscroll;
+                    if ((1.0 - t) * overscroll).abs() < 0.5
+                        && state.vel[d].abs() < scroll_style.stop_speed
+                    {
+                        state.offset[d] -= (1.0 - t) * overscroll;
+                        state.vel[d] = 0.0;
+                    } else {
+                        ui.ctx().request_repaint();
+                    }
+                }
+            }
+        }
+
         let show_scroll_this_frame = [
             content_is_too_large[0] || always_show_scroll,
             content_is_too_large[1] || always_show_scroll,
@@ -751,6 +868,7 @@ impl Prepared {
 
                 let new_handle_top = pointer_pos[d] - *scroll_start_offset_from_top_left;
                 state.offset[d] = remap(new_handle_top, min_main..=max_main, 0.0..=content_size[d]);
+                state.smooth_scroll_target[d] = None;
 
                 // some manual action taken, scroll not stuck
                 state.scroll_stuck_to_end[d] = false;
@@ -758,9 +876,14 @@ impl Prepared {
                 state.scroll_start_offset_from_top_left[d] = None;
             }
 
+            let overscroll_margin = if scroll_style.overscroll && !response.dragged() {
+                scroll_style.max_overscroll
+            } else {
+                0.0
+            };
             let unbounded_offset = state.offset[d];
-            state.offset[d] = state.offset[d].max(0.0);
-            state.offset[d] = state.offset[d].min(max_offset[d]);
+            state.offset[d] = state.offset[d].max(-overscroll_margin);
+            state.offset[d] = state.offset[d].min(max_offset[d] + overscroll_margin);
 
             if state.offset[d] != unbounded_offset {
                 state.vel[d] = 0.0;
@@ -821,8 +944,27 @@ impl Prepared {
         }
 
         let available_offset = content_size - inner_rect.size();
-        state.offset = state.offset.min(available_offset);
-        state.offset = state.offset.max(Vec2::ZERO);
+        for d in 0..2 {
+            let overscroll_margin = if scroll_style.overscroll && has_bar[d] {
+                scroll_style.max_overscroll
+            } else {
+                0.0
+            };
+            state.offset[d] = state.offset[d].min(available_offset[d] + overscroll_margin);
+            state.offset[d] = state.offset[d].max(-overscroll_margin);
+        }
+
+        // The value is the offset along the scrolling direction (vertical, if both):
+        let d = if has_bar[1] { 1 } else { 0 };
+        let max_offset = available_offset[d].at_least(0.0);
+        let offset = state.offset[d].clamp(0.0, max_offset);
+        ui.ctx()
+            .accessibility_container(id, inner_rect, || WidgetInfo {
+                value: Some(offset as f64),
+                min: Some(0.0),
+                max: Some(max_offset as f64),
+                ..WidgetInfo::new(WidgetType::ScrollArea)
+            });
 
         // Is scroll handle at end of content, or is there no scrollbar
         // yet (not enough content), but sticking is requested? If so, enter sticky mode.
@@ -844,7 +986,91 @@ impl Prepared {
     }
 }
 
+/// How far `offset` is past the start (negative) or end (positive) of the content.
+fn overscroll(offset: f32, max_offset: f32) -> f32 {
+    if offset < 0.0 {
+        offset
+    } else {
+        (offset - max_offset.at_least(0.0)).at_least(0.0)
+    }
+}
+
+/// How big a step to take towards a goal this frame,
+/// to get most of the way there (95%) after `duration` seconds.
+fn ease_towards(dt: f32, duration: f32) -> f32 {
+    if duration > 0.0 {
+        1.0 - (-3.0 * dt / duration).exp()
+    } else {
+        1.0
+    }
+}
+
 /// Width of a vertical scrollbar, or height of a horizontal scroll bar
 fn max_scroll_bar_width_with_margin(ui: &Ui) -> f32 {
     ui.spacing().item_spacing.x + ui.spacing().scroll_bar_width
 }
+
+#[test]
+fn test_smooth_scrolling_and_overscroll() {
+    let ctx = Context::default();
+
+    let mut time = 0.0;
+    let mut run_frame = |events: Vec<Event>| {
+        time += 1.0 / 60.0;
+        let raw_input = RawInput {
+            time: Some(time),
+            events,
+            ..Default::default()
+        };
+        let mut offset = Vec2::ZERO;
+        let _ = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let output = ScrollArea::vertical().max_height(100.0).show(ui, |ui| {
+                    for i in 0..100 {
+                        ui.label(format!("Row {}", i));
+                    }
+                });
+                offset = output.state.offset;
+            });
+        });
+        offset.y
+    };
+
+    let pos = pos2(20.0, 50.0);
+    run_frame(vec![Event::PointerMoved(pos)]);
+
+    // By default, a mouse wheel notch scrolls right away:
+    assert_eq!(run_frame(vec![Event::Scroll(vec2(0.0, -50.0))]), 50.0);
+    assert_eq!(run_frame(vec![Event::Scroll(vec2(0.0, 50.0))]), 0.0);
+
+    let mut style = (*ctx.style()).clone();
+    style.scroll.smooth_scroll_time = 0.1;
+    style.scroll.overscroll = true;
+    ctx.set_style(style);
+
+    // …but can be made to scroll smoothly:
+    let offset = run_frame(vec![Event::Scroll(vec2(0.0, -50.0))]);
+    assert!(0.0 < offset && offset < 50.0, "offset: {}", offset);
+    let offset = (0..30).map(|_| run_frame(vec![])).last().unwrap();
+    assert_eq!(offset, 50.0);
+
+    // Pull the content down past its top…
+    let button = |pos, pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers: Default::default(),
+    };
+    run_frame(vec![button(pos, true)]);
+    let mut offset = 0.0;
+    for i in 1..=20 {
+        offset = run_frame(vec![Event::PointerMoved(pos + vec2(0.0, 10.0 * i as f32))]);
+    }
+    assert!(offset < 0.0, "offset: {}", offset);
+    assert!(offset > -100.0, "it gets harder to pull: {}", offset);
+
+    // …and let go, to see it bounce back:
+    run_frame(vec![button(pos + vec2(0.0, 200.0), false)]);
+    let offset = (0..60).map(|_| run_frame(vec![])).last().unwrap();
+    assert_eq!(offset, 0.0);
+}
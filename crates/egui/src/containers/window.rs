@@ -39,7 +39,7 @@ impl<'open> Window<'open> {
     /// If you need a changing title, you must call `window.id(…)` with a fixed id.
     pub fn new(title: impl Into<WidgetText>) -> Self {
         let title = title.into().fallback_text_style(TextStyle::Heading);
-        let area = Area::new(title.text());
+        let area = Area::new(title.text()).role(WidgetType::Window);
         Self {
             title,
             open: None,
@@ -284,6 +284,11 @@ impl<'open> Window<'open> {
 
         let mut area = area.begin(ctx);
 
+        let is_open = collapsing.is_open();
+        ctx.accessibility_container(area_id, area.state().rect(), || {
+            WidgetInfo::expandable(WidgetType::Window, is_open, title.text())
+        });
+
         let title_content_spacing = 2.0 * ctx.style().spacing.item_spacing.y;
 
         // First interact (move etc) to avoid frame delay:
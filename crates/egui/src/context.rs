@@ -2,10 +2,24 @@
 use std::sync::Arc;
 
 use crate::{
-    animation_manager::AnimationManager, data::output::PlatformOutput, frame_state::FrameState,
-    input_state::*, layers::GraphicLayers, memory::Options, output::FullOutput, TextureHandle, *,
+    animation_manager::AnimationManager,
+    damage::DamageTracker,
+    data::output::PlatformOutput,
+    frame_state::FrameState,
+    input_state::*,
+    layers::GraphicLayers,
+    memory::Options,
+    output::FullOutput,
+    tessellation_cache::{TessellationCache, TessellatorSettings},
+    TextureHandle, *,
+};
+use epaint::{
+    mutex::*,
+    stats::*,
+    text::{FontTexture, Fonts},
+    textures::TextureOptions,
+    TessellationOptions, *,
 };
-use epaint::{mutex::*, stats::*, text::Fonts, textures::TextureFilter, TessellationOptions, *};
 
 // ----------------------------------------------------------------------------
 
@@ -35,6 +49,11 @@ struct ContextImpl {
     memory: Memory,
     animation_manager: AnimationManager,
     tex_manager: WrappedTextureManager,
+    /// The font textures other than the main one ([`TextureId::default`]), allocated once first used.
+    ///
+    /// These are the extra pages of the font atlas, and the texture of
+    /// [`epaint::text::GlyphRendering::Sdf`] glyphs.
+    font_texture_ids: ahash::HashMap<FontTexture, TextureId>,
 
     input: InputState,
 
@@ -44,6 +63,12 @@ struct ContextImpl {
     // The output of a frame:
     graphics: GraphicLayers,
     output: PlatformOutput,
+    damage_tracker: DamageTracker,
+
+    /// The number of shapes in each layer of the last [`Context::end_frame`],
+    /// so that [`Context::tessellate`] can cache each layer separately.
+    paint_layer_sizes: Vec<(LayerId, usize)>,
+    tessellation_cache: TessellationCache,
 
     paint_stats: PaintStats,
     /// the duration backend will poll for new events, before forcing another egui update
@@ -59,8 +84,11 @@ impl ContextImpl {
     fn begin_frame_mut(&mut self, new_raw_input: RawInput) {
         self.memory.begin_frame(&self.input, &new_raw_input);
 
-        self.input = std::mem::take(&mut self.input)
-            .begin_frame(new_raw_input, self.requested_repaint_last_frame);
+        self.input = std::mem::take(&mut self.input).begin_frame(
+            new_raw_input,
+            self.requested_repaint_last_frame,
+            &self.memory.options.style.interaction,
+        );
 
         if let Some(new_pixels_per_point) = self.memory.new_pixels_per_point.take() {
             self.input.pixels_per_point = new_pixels_per_point;
@@ -68,6 +96,21 @@ impl ContextImpl {
 
         self.frame_state.begin_frame(&self.input);
 
+        if self.memory.options.accessibility_tree {
+            self.frame_state.accessibility_tree =
+                Some(output::AccessibilityTree::new(self.input.screen_rect()));
+
+            for event in &self.input.events {
+                if let Event::AccessibilityAction {
+                    target,
+                    action: AccessibilityAction::Focus,
+                } = event
+                {
+                    self.memory.request_focus(*target);
+                }
+            }
+        }
+
         self.update_fonts_mut();
 
         // Ensure we register the background area so panels and background ui can catch clicks:
@@ -336,6 +379,9 @@ impl Context {
             drag_released: false,
             is_pointer_button_down_on: false,
             interact_pointer_pos: None,
+            long_pressed: false,
+            swiped: None,
+            fling_velocity: None,
             changed: false, // must be set by the widget itself
         };
 
@@ -353,8 +399,9 @@ impl Context {
         let input = &mut ctx_impl.input;
 
         // We only want to focus labels if the screen reader is on.
-        let interested_in_focus =
-            sense.interactive() || sense.focusable && memory.options.screen_reader;
+        let interested_in_focus = sense.interactive()
+            || sense.focusable
+                && (memory.options.screen_reader || memory.options.accessibility_tree);
 
         if interested_in_focus {
             memory.interested_in_focus(id);
@@ -368,6 +415,15 @@ impl Context {
             response.clicked[PointerButton::Primary as usize] = true;
         }
 
+        if sense.click
+            && memory.options.accessibility_tree
+            && input
+                .accessibility_actions(id)
+                .any(|action| *action == AccessibilityAction::Click)
+        {
+            response.clicked[PointerButton::Primary as usize] = true;
+        }
+
         if sense.click || sense.drag {
             memory.interaction.click_interest |= hovered && sense.click;
             memory.interaction.drag_interest |= hovered && sense.drag;
@@ -428,6 +484,35 @@ impl Context {
             response.interact_pointer_pos = input.pointer.interact_pos();
         }
 
+        if sense.click || sense.drag {
+            let resize_grab_radius_side = memory.options.style.interaction.resize_grab_radius_side;
+            for gesture in input.gestures() {
+                match *gesture {
+                    Gesture::LongPress { .. } => {
+                        response.long_pressed |= sense.click && response.is_pointer_button_down_on;
+                    }
+                    Gesture::Swipe {
+                        start_pos,
+                        direction,
+                    } => {
+                        // The finger has usually left a button by the time the swipe is done,
+                        // so we check where it started instead:
+                        if rect.contains(start_pos)
+                            && memory.layer_id_at(start_pos, resize_grab_radius_side)
+                                == Some(layer_id)
+                        {
+                            response.swiped = Some(direction);
+                        }
+                    }
+                    Gesture::Fling { velocity, .. } => {
+                        if response.is_pointer_button_down_on {
+                            response.fling_velocity = Some(velocity);
+                        }
+                    }
+                }
+            }
+        }
+
         if input.pointer.any_down() {
             response.hovered &= response.is_pointer_button_down_on; // we don't hover widgets while interacting with *other* widgets
         }
@@ -733,7 +818,7 @@ impl Context {
     ///             ui.ctx().load_texture(
     ///                 "my-image",
     ///                 egui::ColorImage::example(),
-    ///                 egui::TextureFilter::Linear
+    ///                 egui::TextureOptions::LINEAR
     ///             )
     ///         });
     ///
@@ -748,7 +833,7 @@ impl Context {
         &self,
         name: impl Into<String>,
         image: impl Into<ImageData>,
-        filter: TextureFilter,
+        options: TextureOptions,
     ) -> TextureHandle {
         let name = name.into();
         let image = image.into();
@@ -762,7 +847,7 @@ impl Context {
             max_texture_side
         );
         let tex_mngr = self.tex_manager();
-        let tex_id = tex_mngr.write().alloc(name, image, filter);
+        let tex_id = tex_mngr.write().alloc(name, image, options);
         TextureHandle::new(tex_mngr, tex_id)
     }
 
@@ -819,6 +904,12 @@ impl Context {
         if self.input().wants_repaint() {
             self.request_repaint();
         }
+        let long_press_duration = self.style().interaction.long_press_duration;
+        let time_until_long_press = self.input().time_until_long_press(long_press_duration);
+        if let Some(time_until_long_press) = time_until_long_press {
+            // Wake up to recognize the long-press, even if no new input arrives:
+            self.request_repaint_after(std::time::Duration::from_secs_f64(time_until_long_press));
+        }
 
         let textures_delta;
         {
@@ -836,10 +927,49 @@ impl Context {
                     .set(TextureId::default(), font_image_delta);
             }
 
+            {
+                let fonts = ctx_impl.fonts.as_ref().unwrap();
+                let mut tex_manager = ctx_impl.tex_manager.0.write();
+
+                // Free the textures of font atlas pages that are gone, e.g. after repacking:
+                let font_textures = fonts.font_textures();
+                ctx_impl.font_texture_ids.retain(|texture, texture_id| {
+                    let in_use = font_textures.iter().any(|(t, _)| t == texture);
+                    if !in_use {
+                        tex_manager.free(*texture_id);
+                    }
+                    in_use
+                });
+
+                for (texture, delta) in fonts.font_texture_deltas() {
+                    if let Some(&texture_id) = ctx_impl.font_texture_ids.get(&texture) {
+                        tex_manager.set(texture_id, delta);
+                    } else {
+                        // The first delta of an atlas is always the whole image.
+                        let name = match texture {
+                            FontTexture::Coverage(page) => format!("egui_font_texture_{}", page),
+                            FontTexture::Sdf => "egui_sdf_font_texture".to_owned(),
+                            FontTexture::Color => "egui_color_font_texture".to_owned(),
+                        };
+                        let texture_id = tex_manager.alloc(name, delta.image, delta.options);
+                        ctx_impl.font_texture_ids.insert(texture, texture_id);
+                    }
+                }
+            }
+
             textures_delta = ctx_impl.tex_manager.0.write().take_delta();
         };
 
-        let platform_output: PlatformOutput = std::mem::take(&mut self.output());
+        let mut platform_output: PlatformOutput = std::mem::take(&mut self.output());
+
+        let accessibility_tree = self.frame_state().accessibility_tree.take();
+        if let Some(mut tree) = accessibility_tree {
+            tree.focus = self
+                .memory()
+                .focus()
+                .filter(|id| tree.nodes.contains_key(id));
+            platform_output.accessibility_tree = Some(tree);
+        }
 
         // if repaint_requests is greater than zero. just set the duration to zero for immediate
         // repaint. if there's no repaint requests, then we can use the actual repaint_after instead.
@@ -856,44 +986,95 @@ impl Context {
         // will fail to overwrite the previous lower value. and thus, repaints will never
         // go back to higher values.
         self.write().repaint_after = std::time::Duration::MAX;
-        let shapes = self.drain_paint_lists();
+        let (shapes, damage) = self.drain_paint_lists(&textures_delta);
 
         FullOutput {
             platform_output,
             repaint_after,
             textures_delta,
             shapes,
+            damage,
         }
     }
 
-    fn drain_paint_lists(&self) -> Vec<ClippedShape> {
+    /// Returns the shapes to paint, and what parts of the screen changed since last frame.
+    fn drain_paint_lists(&self, textures_delta: &TexturesDelta) -> (Vec<ClippedShape>, Vec<Rect>) {
         let ctx_impl = &mut *self.write();
-        ctx_impl
-            .graphics
-            .drain(ctx_impl.memory.areas.order())
-            .collect()
+        let layers = ctx_impl.graphics.drain(ctx_impl.memory.areas.order());
+
+        let screen_rect = ctx_impl.input.screen_rect();
+        let damage = if ctx_impl.memory.options.track_damage {
+            let font_texture_ids = &ctx_impl.font_texture_ids;
+            ctx_impl.damage_tracker.update(
+                &layers,
+                screen_rect,
+                ctx_impl.input.pixels_per_point(),
+                textures_delta,
+                |texture_id| {
+                    texture_id == TextureId::default()
+                        || font_texture_ids.values().any(|&id| id == texture_id)
+                },
+            )
+        } else {
+            ctx_impl.damage_tracker.clear();
+            vec![screen_rect]
+        };
+
+        ctx_impl.paint_layer_sizes = layers
+            .iter()
+            .map(|(layer_id, shapes)| (*layer_id, shapes.len()))
+            .collect();
+        let shapes = layers.into_iter().flat_map(|(_, shapes)| shapes).collect();
+        (shapes, damage)
     }
 
     /// Tessellate the given shapes into triangle meshes.
-    pub fn tessellate(&self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
-        // A tempting optimization is to reuse the tessellation from last frame if the
-        // shapes are the same, but just comparing the shapes takes about 50% of the time
-        // it takes to tessellate them, so it is not a worth optimization.
-
+    ///
+    /// The shapes of each layer are hashed, and if a layer is unchanged since the last call,
+    /// its triangles from last time are reused. This makes large static windows and panels cheap.
+    /// This only works if the shapes are those of the last [`Self::end_frame`].
+    pub fn tessellate(&self, mut shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        // Galleys kept from earlier frames may refer to glyphs that have moved since:
+        self.fonts().refresh_galleys(&mut shapes);
+
+        let font_textures = self.fonts().font_textures();
+        let font_textures = font_textures
+            .into_iter()
+            .filter_map(|(texture, size)| {
+                let texture_id = *self.read().font_texture_ids.get(&texture)?;
+                Some((texture, texture_id, size))
+            })
+            .collect();
         let pixels_per_point = self.pixels_per_point();
-        let tessellation_options = *self.tessellation_options();
-        let texture_atlas = self.fonts().texture_atlas();
-        let font_tex_size = texture_atlas.lock().size();
-        let prepared_discs = texture_atlas.lock().prepared_discs();
-
-        let paint_stats = PaintStats::from_shapes(&shapes);
-        let clipped_primitives = tessellator::tessellate_shapes(
+        let options = *self.tessellation_options();
+        let font_tex_size = self.fonts().font_image_size();
+        let prepared_discs = self.fonts().prepared_discs();
+        let settings = TessellatorSettings {
             pixels_per_point,
-            tessellation_options,
+            options,
             font_tex_size,
+            font_textures,
             prepared_discs,
-            shapes,
-        );
+        };
+
+        let mut paint_stats = PaintStats::from_shapes(&shapes);
+        let layer_sizes = std::mem::take(&mut self.write().paint_layer_sizes);
+        let clipped_primitives =
+            if layer_sizes.iter().map(|(_, len)| len).sum::<usize>() == shapes.len() {
+                let mut shapes = shapes.into_iter();
+                let layers = layer_sizes
+                    .into_iter()
+                    .map(|(layer_id, len)| (layer_id, shapes.by_ref().take(len).collect()));
+                let (clipped_primitives, cached_layers, tessellated_layers) =
+                    self.write().tessellation_cache.tessellate(settings, layers);
+                paint_stats.cached_layers = cached_layers;
+                paint_stats.tessellated_layers = tessellated_layers;
+                clipped_primitives
+            } else {
+                // Not the shapes from `end_frame`, so we don't know where the layers are.
+                settings.tessellator().tessellate_shapes(shapes)
+            };
+
         self.write().paint_stats = paint_stats.with_clipped_primitives(&clipped_primitives);
         clipped_primitives
     }
@@ -1032,6 +1213,80 @@ impl Context {
     }
 }
 
+/// ## Accessibility
+impl Context {
+    /// Nodes added to the [`output::AccessibilityTree`] by `add_contents`
+    /// become children of the node with the given id.
+    ///
+    /// Use this for custom containers, after adding a node for the container itself
+    /// (e.g. with [`Response::widget_info`]).
+    pub fn with_accessibility_parent<R>(&self, id: Id, add_contents: impl FnOnce() -> R) -> R {
+        self.push_accessibility_parent(id);
+        let ret = add_contents();
+        self.pop_accessibility_parent();
+        ret
+    }
+
+    pub(crate) fn push_accessibility_parent(&self, id: Id) {
+        self.frame_state().accessibility_parents.push(id);
+    }
+
+    pub(crate) fn pop_accessibility_parent(&self) {
+        self.frame_state().accessibility_parents.pop();
+    }
+
+    /// Add a container (e.g. a window or a scroll area) to the [`output::AccessibilityTree`],
+    /// if we are building one.
+    ///
+    /// Use [`Self::with_accessibility_parent`] to put the contents inside it.
+    pub(crate) fn accessibility_container(
+        &self,
+        id: Id,
+        rect: Rect,
+        make_info: impl FnOnce() -> WidgetInfo,
+    ) {
+        self.accessibility_node(id, || output::AccessibilityNode {
+            parent: None,
+            children: vec![],
+            info: make_info(),
+            rect,
+            focusable: false,
+            clickable: false,
+        });
+    }
+
+    /// Set the [`WidgetInfo::tooltip`] of a node already in the [`output::AccessibilityTree`].
+    pub(crate) fn accessibility_tooltip(&self, id: Id, tooltip: &str) {
+        if let Some(tree) = &mut self.frame_state().accessibility_tree {
+            if let Some(node) = tree.nodes.get_mut(&id) {
+                node.info.tooltip = Some(tooltip.to_owned());
+            }
+        }
+    }
+
+    /// Add a node to the [`output::AccessibilityTree`], if we are building one.
+    pub(crate) fn accessibility_node(
+        &self,
+        id: Id,
+        make_node: impl FnOnce() -> output::AccessibilityNode,
+    ) {
+        if self.frame_state().accessibility_tree.is_none() {
+            return;
+        }
+        let node = make_node(); // don't hold the lock while calling user code
+
+        let frame_state = &mut *self.frame_state();
+        if let Some(tree) = &mut frame_state.accessibility_tree {
+            let parent = frame_state
+                .accessibility_parents
+                .last()
+                .copied()
+                .unwrap_or(tree.root);
+            tree.insert(id, parent, node);
+        }
+    }
+}
+
 /// ## Animation
 impl Context {
     /// Returns a value in the range [0, 1], to indicate "how on" this thing is.
@@ -1200,6 +1455,17 @@ impl Context {
             textures.len(),
             bytes as f64 * 1e-6
         ));
+
+        let font_atlas = self.fonts().font_atlas_stats();
+        ui.label(format!(
+            "Font atlas: {} page(s), {} glyph(s) using {:.0}% of the pages. {} glyph(s) evicted, {} repack(s)",
+            font_atlas.num_pages,
+            font_atlas.num_glyphs,
+            100.0 * font_atlas.glyph_texels as f32 / font_atlas.page_texels.max(1) as f32,
+            font_atlas.num_evicted,
+            font_atlas.num_repacks,
+        ));
+
         let max_preview_size = vec2(48.0, 32.0);
 
         ui.group(|ui| {
@@ -0,0 +1,435 @@
+//! Figuring out which parts of the screen changed since last frame (see [`crate::FullOutput::damage`]).
+
+use std::hash::{BuildHasher as _, Hasher as _};
+use std::sync::Arc;
+
+use epaint::{pos2, textures::TexturesDelta, ClippedShape, Galley, Rect, Shape, TextureId};
+
+use crate::{
+    tessellation_cache::{collect_galleys, hash_rect, hash_shape},
+    LayerId,
+};
+
+/// More damage rectangles than this are merged together.
+///
+/// Each rectangle costs a draw pass in the backend, so a few slightly too large rectangles
+/// are cheaper than many tight ones.
+const MAX_DAMAGE_RECTS: usize = 8;
+
+/// If more shapes than this changed in one layer, we damage their union instead of each one.
+const MAX_SHAPE_RECTS_PER_LAYER: usize = 16;
+
+/// What we remember of a shape until next frame.
+#[derive(Clone, Copy)]
+struct ShapeSummary {
+    /// Hash of the shape and its clip rectangle.
+    hash: u64,
+
+    /// See [`damage_rect`].
+    rect: Rect,
+}
+
+/// Remembers the shapes of last frame, so we can find out what changed.
+#[derive(Default)]
+pub(crate) struct DamageTracker {
+    hasher: ahash::RandomState,
+
+    /// The shapes of each layer last frame, in paint order.
+    ///
+    /// `None` before the first frame, or after tracking was turned off.
+    prev_layers: Option<Vec<(LayerId, Vec<ShapeSummary>)>>,
+
+    /// We hash galleys by pointer, so we keep those of last frame alive to make sure
+    /// no other galley gets the same address before we compare against them.
+    prev_galleys: Vec<Arc<Galley>>,
+
+    prev_screen_rect: Option<Rect>,
+    prev_pixels_per_point: f32,
+}
+
+impl DamageTracker {
+    /// Forget the previous frame, so that the next frame is damaged in full.
+    pub fn clear(&mut self) {
+        self.prev_layers = None;
+        self.prev_galleys.clear();
+    }
+
+    /// Compare the shapes of this frame with those of last frame, returning the damaged rectangles.
+    ///
+    /// `is_font_texture` should return true for the font atlas textures.
+    /// These only get new glyphs added to unused areas, so partial updates to them
+    /// don't change what is on screen.
+    pub fn update(
+        &mut self,
+        layers: &[(LayerId, Vec<ClippedShape>)],
+        screen_rect: Rect,
+        pixels_per_point: f32,
+        textures_delta: &TexturesDelta,
+        is_font_texture: impl Fn(TextureId) -> bool,
+    ) -> Vec<Rect> {
+        let mut galleys = vec![];
+        let new_layers: Vec<(LayerId, Vec<ShapeSummary>)> = layers
+            .iter()
+            .map(|(layer_id, shapes)| {
+                let summaries = shapes
+                    .iter()
+                    .map(|clipped_shape| {
+                        collect_galleys(&clipped_shape.1, &mut galleys);
+                        self.summarize(clipped_shape)
+                    })
+                    .collect();
+                (*layer_id, summaries)
+            })
+            .collect();
+
+        // Kept alive until we're done comparing:
+        let _prev_galleys = std::mem::replace(&mut self.prev_galleys, galleys);
+        let prev_layers = self.prev_layers.take();
+        let prev_screen_rect = self.prev_screen_rect.replace(screen_rect);
+        let prev_pixels_per_point =
+            std::mem::replace(&mut self.prev_pixels_per_point, pixels_per_point);
+
+        let damage = match prev_layers {
+            Some(prev_layers)
+                if prev_screen_rect == Some(screen_rect)
+                    && prev_pixels_per_point == pixels_per_point =>
+            {
+                find_damage(
+                    &prev_layers,
+                    &new_layers,
+                    layers,
+                    screen_rect,
+                    pixels_per_point,
+                    textures_delta,
+                    is_font_texture,
+                )
+            }
+            _ => vec![screen_rect], // Everything is new
+        };
+
+        self.prev_layers = Some(new_layers);
+        damage
+    }
+
+    fn summarize(&self, clipped_shape: &ClippedShape) -> ShapeSummary {
+        let mut hasher = self.hasher.build_hasher();
+        hash_rect(clipped_shape.0, &mut hasher);
+        hash_shape(&clipped_shape.1, &mut hasher);
+        ShapeSummary {
+            hash: hasher.finish(),
+            rect: damage_rect(clipped_shape),
+        }
+    }
+}
+
+/// `layers` are the shapes summarized in `new`.
+fn find_damage(
+    old: &[(LayerId, Vec<ShapeSummary>)],
+    new: &[(LayerId, Vec<ShapeSummary>)],
+    layers: &[(LayerId, Vec<ClippedShape>)],
+    screen_rect: Rect,
+    pixels_per_point: f32,
+    textures_delta: &TexturesDelta,
+    is_font_texture: impl Fn(TextureId) -> bool,
+) -> Vec<Rect> {
+    let mut damage = vec![];
+    diff_layers(old, new, &mut damage);
+
+    // We can't know what a paint callback paints, so assume it changes every frame:
+    for (_, shapes) in layers {
+        for clipped_shape in shapes {
+            add_callback_rects(clipped_shape.0, &clipped_shape.1, &mut damage);
+        }
+    }
+
+    for &(texture_id, ref delta) in &textures_delta.set {
+        if is_font_texture(texture_id) {
+            if delta.pos.is_none() {
+                // The whole font atlas was replaced, so the uv:s of all text may have changed.
+                return vec![screen_rect];
+            }
+        } else {
+            // The image changed, so everything showing it did too.
+            // Shapes that showed it last frame but not anymore changed, so they are already damaged.
+            for (_, shapes) in layers {
+                for clipped_shape in shapes {
+                    if uses_texture(&clipped_shape.1, texture_id) {
+                        damage.push(damage_rect(clipped_shape));
+                    }
+                }
+            }
+        }
+    }
+
+    merge_damage_rects(&mut damage, screen_rect, pixels_per_point);
+    damage
+}
+
+fn diff_layers(
+    old: &[(LayerId, Vec<ShapeSummary>)],
+    new: &[(LayerId, Vec<ShapeSummary>)],
+    damage: &mut Vec<Rect>,
+) {
+    // Layers that changed their order relative to the others (e.g. a window brought to front)
+    // are not part of the longest common subsequence, and need to be repainted in full:
+    let old_ids: Vec<LayerId> = old.iter().map(|(id, _)| *id).collect();
+    let new_ids: Vec<LayerId> = new.iter().map(|(id, _)| *id).collect();
+    let stable = longest_common_subsequence(&old_ids, &new_ids);
+
+    for (layer_id, new_shapes) in new {
+        let old_shapes = old.iter().find(|(id, _)| id == layer_id).map(|(_, s)| s);
+        match old_shapes {
+            Some(old_shapes) if stable.contains(layer_id) => {
+                diff_shapes(old_shapes, new_shapes, damage);
+            }
+            Some(old_shapes) => {
+                damage.push(union_rect(old_shapes));
+                damage.push(union_rect(new_shapes));
+            }
+            None => damage.push(union_rect(new_shapes)),
+        }
+    }
+
+    for (layer_id, old_shapes) in old {
+        if !new_ids.contains(layer_id) {
+            damage.push(union_rect(old_shapes));
+        }
+    }
+}
+
+/// Damage the shapes that differ between the two lists.
+///
+/// Shapes are usually added, removed or changed in one place,
+/// so we skip the common start and end and damage what's left in the middle.
+fn diff_shapes(old: &[ShapeSummary], new: &[ShapeSummary], damage: &mut Vec<Rect>) {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(a, b)| a.hash == b.hash)
+        .count();
+    let old = &old[prefix..];
+    let new = &new[prefix..];
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take_while(|(a, b)| a.hash == b.hash)
+        .count();
+    let old = &old[..old.len() - suffix];
+    let new = &new[..new.len() - suffix];
+
+    for changed in [old, new] {
+        if changed.len() <= MAX_SHAPE_RECTS_PER_LAYER {
+            damage.extend(changed.iter().map(|shape| shape.rect));
+        } else {
+            damage.push(union_rect(changed));
+        }
+    }
+}
+
+fn longest_common_subsequence(a: &[LayerId], b: &[LayerId]) -> Vec<LayerId> {
+    // lengths[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lengths = vec![vec![0_usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lengths[i][j] = if a[i] == b[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lcs = Vec::with_capacity(lengths[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            lcs.push(a[i]);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    lcs
+}
+
+/// The screen area a shape may touch, including feathering.
+fn damage_rect(ClippedShape(clip_rect, shape): &ClippedShape) -> Rect {
+    clip_rect.intersect(shape.visual_bounding_rect().expand(1.0))
+}
+
+fn union_rect(shapes: &[ShapeSummary]) -> Rect {
+    shapes
+        .iter()
+        .map(|shape| shape.rect)
+        .filter(|rect| rect.is_positive())
+        .fold(Rect::NOTHING, |a, b| a.union(b))
+}
+
+fn add_callback_rects(clip_rect: Rect, shape: &Shape, damage: &mut Vec<Rect>) {
+    match shape {
+        Shape::Callback(callback) => damage.push(clip_rect.intersect(callback.rect)),
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                add_callback_rects(clip_rect, shape, damage);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn uses_texture(shape: &Shape, texture_id: TextureId) -> bool {
+    match shape {
+        Shape::Mesh(mesh) => mesh.texture_id == texture_id,
+        Shape::Vec(shapes) => shapes.iter().any(|shape| uses_texture(shape, texture_id)),
+        Shape::Instanced(instanced) => uses_texture(&instanced.shape, texture_id),
+        _ => false,
+    }
+}
+
+/// Tidy up a list of damaged rectangles (in points).
+///
+/// The rectangles are clipped to the screen and rounded outwards to whole physical pixels.
+/// Overlapping rectangles are merged, so that painting each rectangle separately
+/// never paints the same pixel twice.
+/// If there are many rectangles, the closest ones are merged.
+///
+/// Useful when combining the damage of several frames, e.g. to repaint a back buffer
+/// that is a few frames old.
+pub fn merge_damage_rects(rects: &mut Vec<Rect>, screen_rect: Rect, pixels_per_point: f32) {
+    for rect in rects.iter_mut() {
+        *rect = round_to_pixels(rect.intersect(screen_rect), pixels_per_point);
+    }
+    rects.retain(|rect| rect.is_positive());
+    merge_overlapping(rects);
+}
+
+/// Merge rectangles until none of them overlap, and there are at most [`MAX_DAMAGE_RECTS`].
+pub(crate) fn merge_overlapping(rects: &mut Vec<Rect>) {
+    loop {
+        // Merge everything that overlaps:
+        let mut merged_any = false;
+        let mut i = 0;
+        while i < rects.len() {
+            let mut j = i + 1;
+            while j < rects.len() {
+                if rects[i].intersects(rects[j]) && rects[i].intersect(rects[j]).is_positive() {
+                    let other = rects.swap_remove(j);
+                    rects[i] = rects[i].union(other);
+                    merged_any = true;
+                } else {
+                    j += 1;
+                }
+            }
+            i += 1;
+        }
+
+        if merged_any {
+            continue; // the merged rectangles may overlap others now
+        }
+
+        if rects.len() <= MAX_DAMAGE_RECTS {
+            return;
+        }
+
+        // Merge the pair that grows the damaged area the least:
+        let mut best = (0, 1, f32::INFINITY);
+        for i in 0..rects.len() {
+            for j in (i + 1)..rects.len() {
+                let growth = rects[i].union(rects[j]).area() - rects[i].area() - rects[j].area();
+                if growth < best.2 {
+                    best = (i, j, growth);
+                }
+            }
+        }
+        let other = rects.swap_remove(best.1);
+        rects[best.0] = rects[best.0].union(other);
+    }
+}
+
+fn round_to_pixels(rect: Rect, pixels_per_point: f32) -> Rect {
+    let floor = |x: f32| (x * pixels_per_point).floor() / pixels_per_point;
+    let ceil = |x: f32| (x * pixels_per_point).ceil() / pixels_per_point;
+    Rect::from_min_max(
+        pos2(floor(rect.min.x), floor(rect.min.y)),
+        pos2(ceil(rect.max.x), ceil(rect.max.y)),
+    )
+}
+
+#[test]
+fn test_merge_damage_rects() {
+    let screen_rect = Rect::from_min_size(pos2(0.0, 0.0), epaint::vec2(100.0, 100.0));
+    let mut rects = vec![
+        Rect::from_min_max(pos2(10.2, 10.0), pos2(20.0, 20.7)),
+        Rect::from_min_max(pos2(15.0, 15.0), pos2(30.0, 30.0)), // overlaps the first one
+        Rect::from_min_max(pos2(50.0, 50.0), pos2(60.0, 60.0)),
+        Rect::from_min_max(pos2(90.0, 90.0), pos2(200.0, 200.0)), // partially off-screen
+        Rect::from_min_max(pos2(300.0, 0.0), pos2(400.0, 10.0)),  // off-screen
+        Rect::NOTHING,
+    ];
+    merge_damage_rects(&mut rects, screen_rect, 1.0);
+    rects.sort_by(|a, b| a.min.x.partial_cmp(&b.min.x).unwrap());
+    assert_eq!(
+        rects,
+        vec![
+            Rect::from_min_max(pos2(10.0, 10.0), pos2(30.0, 30.0)),
+            Rect::from_min_max(pos2(50.0, 50.0), pos2(60.0, 60.0)),
+            Rect::from_min_max(pos2(90.0, 90.0), pos2(100.0, 100.0)),
+        ]
+    );
+
+    // Too many rectangles:
+    let mut rects: Vec<Rect> = (0..20)
+        .map(|i| Rect::from_min_size(pos2(5.0 * i as f32, 0.0), epaint::vec2(1.0, 1.0)))
+        .collect();
+    merge_damage_rects(&mut rects, screen_rect, 1.0);
+    assert!(rects.len() <= MAX_DAMAGE_RECTS);
+    for (i, a) in rects.iter().enumerate() {
+        for b in &rects[i + 1..] {
+            assert!(!a.intersect(*b).is_positive());
+        }
+    }
+}
+
+#[test]
+fn test_damage_tracking() {
+    use crate::{vec2, CentralPanel, Context, Pos2, RawInput};
+
+    let ctx = Context::default();
+    ctx.options().track_damage = true;
+    let screen_rect = Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0));
+
+    // Returns the damage and the rectangle of the label showing `text`.
+    let run_frame = |text: &str| {
+        let raw_input = RawInput {
+            screen_rect: Some(screen_rect),
+            ..Default::default()
+        };
+        let mut label_rect = Rect::NOTHING;
+        let full_output = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.label("This label never changes");
+                label_rect = ui.label(text).rect;
+                ui.label("Neither does this one");
+            });
+        });
+        (full_output.damage, label_rect)
+    };
+
+    assert_eq!(run_frame("Hello").0, vec![screen_rect]);
+    run_frame("Hello");
+    assert!(run_frame("Hello").0.is_empty());
+
+    let (damage, label_rect) = run_frame("World");
+    assert!(!damage.is_empty());
+    // Only the changed label (and its feathering) needs repainting:
+    let damage_bounds = damage.iter().fold(Rect::NOTHING, |a, b| a.union(*b));
+    assert!(label_rect.expand(2.0).contains_rect(damage_bounds));
+
+    // Without tracking, everything is damaged every frame:
+    ctx.options().track_damage = false;
+    assert_eq!(run_frame("World").0, vec![screen_rect]);
+}
@@ -268,6 +268,72 @@ pub enum Event {
         /// The value is in the range from 0.0 (no pressure) to 1.0 (maximum pressure).
         force: f32,
     },
+
+    /// A pen (stylus) moved, touched or left the surface, or a button on it changed.
+    ///
+    /// This is also sent while the pen hovers above the surface, if the platform supports it.
+    ///
+    /// Report this *in addition to* [`Self::PointerMoved`], [`Self::PointerButton`], [`Self::PointerGone`]
+    /// (or [`Self::Touch`]), so that code that only cares about the pointer keeps working.
+    Pen(PenState),
+
+    /// The pen (stylus) is no longer close enough to the surface to be detected.
+    PenGone,
+
+    /// Assistive technology (e.g. a screen reader) wants to act on a widget.
+    ///
+    /// The `target` is an [`Id`](crate::Id) from [`crate::output::AccessibilityTree`].
+    AccessibilityAction {
+        target: crate::Id,
+        action: AccessibilityAction,
+    },
+}
+
+/// Something assistive technology can ask a widget to do, see [`Event::AccessibilityAction`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum AccessibilityAction {
+    /// Give the widget keyboard focus.
+    Focus,
+
+    /// Click the widget, as with the primary mouse button.
+    Click,
+
+    /// Set the value of e.g. a [`crate::Slider`] or [`crate::DragValue`].
+    SetValue(f64),
+
+    /// Replace the text of a [`crate::TextEdit`].
+    SetText(String),
+}
+
+/// The state of a pen (stylus), see [`Event::Pen`].
+///
+/// Not all platforms can measure everything,
+/// in which case the values will be zero or `false`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct PenState {
+    /// Where the tip of the pen is, touching or hovering above the surface, in points.
+    pub pos: Pos2,
+
+    /// Is the tip touching the surface?
+    pub contact: bool,
+
+    /// How hard the pen is pressed against the surface,
+    /// from `0.0` (not at all) to `1.0` (as hard as the device can measure).
+    pub pressure: f32,
+
+    /// How much the pen is tilted, in radians, in the range `[-π/2, π/2]`.
+    ///
+    /// `x` is the tilt towards the right of the screen, and `y` towards the bottom.
+    /// Zero means the pen is perpendicular to the surface.
+    pub tilt: Vec2,
+
+    /// Is the button on the side of the pen held down?
+    pub barrel_button: bool,
+
+    /// Is it the eraser end of the pen that is being used?
+    pub eraser: bool,
 }
 
 /// Mouse button (or similar for touch input)
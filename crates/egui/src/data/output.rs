@@ -1,6 +1,6 @@
 //! All the data egui returns to the backend at the end of each frame.
 
-use crate::WidgetType;
+use crate::{Id, IdMap, Rect, WidgetType};
 
 /// What egui emits each frame from [`crate::Context::run`].
 ///
@@ -30,6 +30,16 @@ pub struct FullOutput {
     ///
     /// You can use [`crate::Context::tessellate`] to turn this into triangles.
     pub shapes: Vec<epaint::ClippedShape>,
+
+    /// The parts of the screen (in points) that changed since the previous frame.
+    ///
+    /// The rectangles never overlap, and are aligned to physical pixels.
+    /// If this is empty, the screen looks exactly like last frame, and there is no need to repaint it.
+    ///
+    /// Unless `Options::track_damage` is on (see [`crate::Context::options`]), this is always the whole screen.
+    /// A backend that keeps the previous frame around can use this to only clear and repaint
+    /// the damaged parts.
+    pub damage: Vec<crate::Rect>,
 }
 
 impl FullOutput {
@@ -40,12 +50,17 @@ impl FullOutput {
             repaint_after,
             textures_delta,
             shapes,
+            damage,
         } = newer;
 
         self.platform_output.append(platform_output);
         self.repaint_after = repaint_after; // if the last frame doesn't need a repaint, then we don't need to repaint
         self.textures_delta.append(textures_delta);
         self.shapes = shapes; // Only paint the latest
+
+        // …but everything that changed since the last painted frame:
+        self.damage.extend(damage);
+        crate::damage::merge_overlapping(&mut self.damage);
     }
 }
 
@@ -85,6 +100,11 @@ pub struct PlatformOutput {
 
     /// Screen-space position of text edit cursor (used for IME).
     pub text_cursor_pos: Option<crate::Pos2>,
+
+    /// All the widgets of this frame, for assistive technology such as screen readers.
+    ///
+    /// Only set when `accessibility_tree` is turned on in [`crate::Context::options`].
+    pub accessibility_tree: Option<AccessibilityTree>,
 }
 
 impl PlatformOutput {
@@ -121,6 +141,7 @@ impl PlatformOutput {
             mut events,
             mutable_text_under_cursor,
             text_cursor_pos,
+            accessibility_tree,
         } = newer;
 
         self.cursor_icon = cursor_icon;
@@ -133,6 +154,9 @@ impl PlatformOutput {
         self.events.append(&mut events);
         self.mutable_text_under_cursor = mutable_text_under_cursor;
         self.text_cursor_pos = text_cursor_pos.or(self.text_cursor_pos);
+        if accessibility_tree.is_some() {
+            self.accessibility_tree = accessibility_tree;
+        }
     }
 
     /// Take everything ephemeral (everything except `cursor_icon` currently)
@@ -143,6 +167,131 @@ impl PlatformOutput {
     }
 }
 
+/// The widgets of a frame, arranged as a tree, for assistive technology such as screen readers.
+///
+/// The root is the whole screen. The children of the root are the widgets in panels
+/// and the [`crate::Area`]s (e.g. windows), which in turn have the widgets in them as children.
+///
+/// The integration can act on the nodes by sending
+/// [`crate::Event::AccessibilityAction`] the next frame.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccessibilityTree {
+    /// The node representing the whole screen.
+    pub root: Id,
+
+    /// All nodes, including the root.
+    pub nodes: IdMap<AccessibilityNode>,
+
+    /// Which node has keyboard focus, if any.
+    pub focus: Option<Id>,
+}
+
+impl AccessibilityTree {
+    /// The id of the root node.
+    pub fn root_id() -> Id {
+        Id::new("egui_accessibility_root")
+    }
+
+    pub(crate) fn new(screen_rect: Rect) -> Self {
+        let root = Self::root_id();
+        let mut nodes = IdMap::default();
+        nodes.insert(
+            root,
+            AccessibilityNode {
+                parent: None,
+                children: vec![],
+                info: WidgetInfo::new(WidgetType::Other),
+                rect: screen_rect,
+                focusable: false,
+                clickable: false,
+            },
+        );
+        Self {
+            root,
+            nodes,
+            focus: None,
+        }
+    }
+
+    /// Add a node as the last child of `parent`,
+    /// or update it if it was already added this frame.
+    pub(crate) fn insert(&mut self, id: Id, parent: Id, node: AccessibilityNode) {
+        if let Some(existing) = self.nodes.get_mut(&id) {
+            let children = std::mem::take(&mut existing.children);
+            let parent = existing.parent;
+            *existing = AccessibilityNode {
+                parent,
+                children,
+                ..node
+            };
+            return;
+        }
+
+        let parent = if self.nodes.contains_key(&parent) {
+            parent
+        } else {
+            self.root
+        };
+        if let Some(parent_node) = self.nodes.get_mut(&parent) {
+            parent_node.children.push(id);
+        }
+        self.nodes.insert(
+            id,
+            AccessibilityNode {
+                parent: Some(parent),
+                ..node
+            },
+        );
+    }
+
+    /// The node with this id, if any.
+    pub fn get(&self, id: Id) -> Option<&AccessibilityNode> {
+        self.nodes.get(&id)
+    }
+
+    /// The root node.
+    pub fn root_node(&self) -> &AccessibilityNode {
+        &self.nodes[&self.root]
+    }
+
+    /// The first node (depth-first) with the given label.
+    pub fn find_by_label(&self, label: &str) -> Option<(Id, &AccessibilityNode)> {
+        let mut stack = vec![self.root];
+        while let Some(id) = stack.pop() {
+            let node = self.nodes.get(&id)?;
+            if node.info.label.as_deref() == Some(label) {
+                return Some((id, node));
+            }
+            stack.extend(node.children.iter().rev());
+        }
+        None
+    }
+}
+
+/// A widget or container in an [`AccessibilityTree`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct AccessibilityNode {
+    /// `None` for the root.
+    pub parent: Option<Id>,
+
+    /// In the order they were added.
+    pub children: Vec<Id>,
+
+    /// What kind of widget this is, its label, value, etc.
+    pub info: WidgetInfo,
+
+    /// Where the widget is on screen, in points.
+    pub rect: Rect,
+
+    /// Can the widget get keyboard focus?
+    pub focusable: bool,
+
+    /// Does the widget react to [`crate::AccessibilityAction::Click`]?
+    pub clickable: bool,
+}
+
 /// What URL to open, and how.
 #[derive(Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -412,6 +561,26 @@ pub struct WidgetInfo {
 
     /// Selected range of characters in [`Self::current_text_value`].
     pub text_selection: Option<std::ops::RangeInclusive<usize>>,
+
+    /// The smallest value of sliders, progress bars etc.
+    pub min: Option<f64>,
+
+    /// The largest value of sliders, progress bars etc.
+    pub max: Option<f64>,
+
+    /// How much the value of sliders etc changes with each step (e.g. with the arrow keys).
+    pub step: Option<f64>,
+
+    /// Whether collapsing headers, windows, menus etc are open (expanded) or closed (collapsed).
+    pub expanded: Option<bool>,
+
+    /// A checkbox that is neither checked nor unchecked.
+    ///
+    /// [`Self::selected`] is then `None`.
+    pub mixed: bool,
+
+    /// A longer description of the widget, e.g. its hover text.
+    pub tooltip: Option<String>,
 }
 
 impl std::fmt::Debug for WidgetInfo {
@@ -425,6 +594,12 @@ impl std::fmt::Debug for WidgetInfo {
             selected,
             value,
             text_selection,
+            min,
+            max,
+            step,
+            expanded,
+            mixed,
+            tooltip,
         } = self;
 
         let mut s = f.debug_struct("WidgetInfo");
@@ -450,6 +625,24 @@ impl std::fmt::Debug for WidgetInfo {
         if let Some(text_selection) = text_selection {
             s.field("text_selection", text_selection);
         }
+        if let Some(min) = min {
+            s.field("min", min);
+        }
+        if let Some(max) = max {
+            s.field("max", max);
+        }
+        if let Some(step) = step {
+            s.field("step", step);
+        }
+        if let Some(expanded) = expanded {
+            s.field("expanded", expanded);
+        }
+        if *mixed {
+            s.field("mixed", mixed);
+        }
+        if let Some(tooltip) = tooltip {
+            s.field("tooltip", tooltip);
+        }
 
         s.finish()
     }
@@ -466,6 +659,12 @@ impl WidgetInfo {
             selected: None,
             value: None,
             text_selection: None,
+            min: None,
+            max: None,
+            step: None,
+            expanded: None,
+            mixed: false,
+            tooltip: None,
         }
     }
 
@@ -487,6 +686,17 @@ impl WidgetInfo {
         }
     }
 
+    /// A container with a label (e.g. a window title) that can be open or closed.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn expandable(typ: WidgetType, expanded: bool, label: impl ToString) -> Self {
+        let label = label.to_string();
+        Self {
+            label: if label.is_empty() { None } else { Some(label) },
+            expanded: Some(expanded),
+            ..Self::new(typ)
+        }
+    }
+
     pub fn drag_value(value: f64) -> Self {
         Self {
             value: Some(value),
@@ -543,6 +753,12 @@ impl WidgetInfo {
             selected,
             value,
             text_selection: _,
+            min: _,
+            max: _,
+            step: _,
+            expanded,
+            mixed,
+            tooltip,
         } = self;
 
         // TODO(emilk): localization
@@ -559,11 +775,33 @@ impl WidgetInfo {
             WidgetType::ColorButton => "color button",
             WidgetType::ImageButton => "image button",
             WidgetType::CollapsingHeader => "collapsing header",
-            WidgetType::Label | WidgetType::Other => "",
+            WidgetType::ProgressBar => "progress bar",
+            WidgetType::Image => "image",
+            WidgetType::Window => "window",
+            WidgetType::Menu => "menu",
+            WidgetType::MenuItem => "menu item",
+            WidgetType::Tab => "tab",
+            WidgetType::Tooltip => "tooltip",
+            WidgetType::ScrollArea => "scroll area",
+            WidgetType::Grid => "grid",
+            WidgetType::Table => "table",
+            WidgetType::Cell => "cell",
+            WidgetType::TreeItem => "tree item",
+            WidgetType::Plot => "plot",
+            WidgetType::Label | WidgetType::Panel | WidgetType::Other => "",
         };
 
         let mut description = widget_type.to_owned();
 
+        if *mixed {
+            description = format!("mixed {}", description);
+        }
+
+        if let Some(expanded) = expanded {
+            let state = if *expanded { "expanded" } else { "collapsed" };
+            description = format!("{} {}", state, description);
+        }
+
         if let Some(selected) = selected {
             if *typ == WidgetType::Checkbox {
                 let state = if *selected { "checked" } else { "unchecked" };
@@ -599,6 +837,167 @@ impl WidgetInfo {
         if !enabled {
             description += ": disabled";
         }
+
+        if let Some(tooltip) = tooltip {
+            description += ". ";
+            description += tooltip;
+        }
         description.trim().to_owned()
     }
 }
+
+#[test]
+fn test_accessibility_tree() {
+    use crate::{AccessibilityAction, CentralPanel, Context, Event, RawInput, Slider, Window};
+
+    struct State {
+        clicked: bool,
+        volume: f64,
+        checked: bool,
+    }
+
+    fn run_frame(ctx: &Context, state: &mut State, events: Vec<Event>) -> AccessibilityTree {
+        let raw_input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let full_output = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                state.clicked |= ui.button("Click me").clicked();
+                ui.add(Slider::new(&mut state.volume, 0.0..=1.0).text("Volume"));
+            });
+            Window::new("My window").show(ctx, |ui| {
+                ui.checkbox(&mut state.checked, "Check me");
+            });
+        });
+        full_output.platform_output.accessibility_tree.unwrap()
+    }
+
+    let ctx = Context::default();
+    ctx.options().accessibility_tree = true;
+    let mut state = State {
+        clicked: false,
+        volume: 0.5,
+        checked: false,
+    };
+
+    let tree = run_frame(&ctx, &mut state, vec![]);
+    let (button_id, button) = tree.find_by_label("Click me").unwrap();
+    assert_eq!(button.info.typ, WidgetType::Button);
+    assert!(button.clickable);
+    let panel = tree.get(button.parent.unwrap()).unwrap();
+    assert_eq!(panel.info.typ, WidgetType::Panel);
+    assert_eq!(panel.parent, Some(tree.root));
+
+    let (&slider_id, slider) = tree
+        .nodes
+        .iter()
+        .find(|(_, node)| node.info.typ == WidgetType::Slider)
+        .unwrap();
+    assert_eq!(slider.info.label.as_deref(), Some("Volume"));
+    assert_eq!(slider.info.value, Some(0.5));
+    assert_eq!((slider.info.min, slider.info.max), (Some(0.0), Some(1.0)));
+
+    let window_id = Id::new("My window");
+    let (checkbox_id, checkbox) = tree.find_by_label("Check me").unwrap();
+    assert_eq!(checkbox.parent, Some(window_id));
+    assert!(tree.get(window_id).unwrap().children.contains(&checkbox_id));
+    assert_eq!(tree.root_node().children.last(), Some(&window_id));
+    let window = tree.get(window_id).unwrap();
+    assert_eq!(window.info.typ, WidgetType::Window);
+    assert_eq!(window.info.expanded, Some(true));
+    assert_eq!(window.info.label.as_deref(), Some("My window"));
+
+    // Act on the widgets, as assistive technology would:
+    let tree = run_frame(
+        &ctx,
+        &mut state,
+        vec![
+            Event::AccessibilityAction {
+                target: button_id,
+                action: AccessibilityAction::Click,
+            },
+            Event::AccessibilityAction {
+                target: slider_id,
+                action: AccessibilityAction::SetValue(0.25),
+            },
+            Event::AccessibilityAction {
+                target: checkbox_id,
+                action: AccessibilityAction::Focus,
+            },
+        ],
+    );
+    assert!(state.clicked);
+    assert_eq!(state.volume, 0.25);
+    assert_eq!(tree.get(slider_id).unwrap().info.value, Some(0.25));
+    assert_eq!(tree.focus, Some(checkbox_id));
+
+    // Nothing is built unless asked for:
+    ctx.options().accessibility_tree = false;
+    let full_output = ctx.run(Default::default(), |_| {});
+    assert!(full_output.platform_output.accessibility_tree.is_none());
+}
+
+#[test]
+fn test_widget_info_fields() {
+    use crate::{AccessibilityAction, CentralPanel, Context, Event, RawInput, Slider};
+
+    let ctx = Context::default();
+    ctx.options().accessibility_tree = true;
+
+    let mut value = 3.0;
+    let mut run_frame = |events: Vec<Event>| {
+        let raw_input = RawInput {
+            events,
+            ..Default::default()
+        };
+        let full_output = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(
+                    Slider::new(&mut value, 0.0..=10.0)
+                        .step_by(0.5)
+                        .text("Speed"),
+                )
+                .on_hover_text("How fast to go");
+                ui.collapsing("Details", |ui| {
+                    ui.label("Hidden until expanded");
+                });
+            });
+        });
+        full_output.platform_output.accessibility_tree.unwrap()
+    };
+
+    let tree = run_frame(vec![]);
+    let (_, slider) = tree
+        .nodes
+        .iter()
+        .find(|(_, node)| node.info.typ == WidgetType::Slider)
+        .unwrap();
+    assert_eq!(slider.info.label.as_deref(), Some("Speed"));
+    assert_eq!(slider.info.value, Some(3.0));
+    assert_eq!(slider.info.min, Some(0.0));
+    assert_eq!(slider.info.max, Some(10.0));
+    assert_eq!(slider.info.step, Some(0.5));
+    assert_eq!(slider.info.expanded, None);
+    assert!(!slider.info.mixed);
+    assert_eq!(slider.info.tooltip.as_deref(), Some("How fast to go"));
+    assert!(slider.focusable);
+
+    let (header_id, header) = tree.find_by_label("Details").unwrap();
+    assert_eq!(header.info.typ, WidgetType::CollapsingHeader);
+    assert_eq!(header.info.expanded, Some(false));
+    assert_eq!(header.info.value, None);
+    assert_eq!(header.info.tooltip, None);
+    assert!(header.clickable);
+    assert!(tree.find_by_label("Hidden until expanded").is_none());
+
+    // Expanding it the way a screen reader would:
+    run_frame(vec![Event::AccessibilityAction {
+        target: header_id,
+        action: AccessibilityAction::Click,
+    }]);
+    let tree = run_frame(vec![]);
+    let (_, header) = tree.find_by_label("Details").unwrap();
+    assert_eq!(header.info.expanded, Some(true));
+    assert!(tree.find_by_label("Hidden until expanded").is_some());
+}
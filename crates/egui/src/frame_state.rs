@@ -41,6 +41,12 @@ pub(crate) struct FrameState {
 
     /// horizontal, vertical
     pub(crate) scroll_target: [Option<(RangeInclusive<f32>, Option<Align>)>; 2],
+
+    /// Built when `Options::accessibility_tree` is on.
+    pub(crate) accessibility_tree: Option<output::AccessibilityTree>,
+
+    /// The containers new accessibility nodes go into, innermost last.
+    pub(crate) accessibility_parents: Vec<Id>,
 }
 
 impl Default for FrameState {
@@ -53,6 +59,8 @@ impl Default for FrameState {
             tooltip_rect: None,
             scroll_delta: Vec2::ZERO,
             scroll_target: [None, None],
+            accessibility_tree: None,
+            accessibility_parents: vec![],
         }
     }
 }
@@ -67,6 +75,8 @@ impl FrameState {
             tooltip_rect,
             scroll_delta,
             scroll_target,
+            accessibility_tree,
+            accessibility_parents,
         } = self;
 
         used_ids.clear();
@@ -76,6 +86,8 @@ impl FrameState {
         *tooltip_rect = None;
         *scroll_delta = input.scroll_delta;
         *scroll_target = [None, None];
+        *accessibility_tree = None;
+        accessibility_parents.clear();
     }
 
     /// How much space is still available after panels has been added.
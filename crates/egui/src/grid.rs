@@ -380,8 +380,15 @@ impl Grid {
                 };
 
                 ui.set_grid(grid);
-                let r = add_contents(ui);
+                let ctx = ui.ctx().clone();
+                ctx.accessibility_container(id, ui.max_rect(), || {
+                    WidgetInfo::new(WidgetType::Grid)
+                });
+                let r = ctx.with_accessibility_parent(id, || add_contents(ui));
                 ui.save_grid();
+                ctx.accessibility_container(id, ui.min_rect(), || {
+                    WidgetInfo::new(WidgetType::Grid)
+                });
                 r
             })
             .inner
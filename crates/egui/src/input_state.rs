@@ -1,18 +1,18 @@
 mod touch_state;
 
 use crate::data::input::*;
-use crate::{emath::*, util::History};
+use crate::{emath::*, util::History, Id};
 use std::collections::{BTreeMap, HashSet};
 
 pub use crate::data::input::Key;
-pub use touch_state::MultiTouchInfo;
 use touch_state::TouchState;
+pub use touch_state::{Gesture, MultiTouchInfo, SwipeDirection};
 
 /// If the pointer moves more than this, it won't become a click (but it is still a drag)
 const MAX_CLICK_DIST: f32 = 6.0; // TODO(emilk): move to settings
 
 /// If the pointer is down for longer than this, it won't become a click (but it is still a drag)
-const MAX_CLICK_DURATION: f64 = 0.6; // TODO(emilk): move to settings
+pub(crate) const MAX_CLICK_DURATION: f64 = 0.6; // TODO(emilk): move to settings
 
 /// The new pointer press must come within this many seconds from previous pointer release
 const MAX_DOUBLE_CLICK_DELAY: f64 = 0.3; // TODO(emilk): move to settings
@@ -33,6 +33,9 @@ pub struct InputState {
     /// (We keep a separate [`TouchState`] for each encountered touch device.)
     touch_states: BTreeMap<TouchDeviceId, TouchState>,
 
+    /// The pen (stylus), if one is close enough to the surface to be detected.
+    pen: Option<PenState>,
+
     /// How many points the user scrolled.
     ///
     /// The delta dictates how the _content_ should move.
@@ -44,6 +47,9 @@ pub struct InputState {
     /// as when swiping down on a touch-screen or track-pad with natural scrolling.
     pub scroll_delta: Vec2,
 
+    /// Recent [`Self::scroll_delta`]s, used to calculate [`Self::scroll_velocity`].
+    scroll_history: History<Vec2>,
+
     /// Zoom scale factor this frame (e.g. from ctrl-scroll or pinch gesture).
     ///
     /// * `zoom = 1`: no change.
@@ -120,7 +126,9 @@ impl Default for InputState {
             raw: Default::default(),
             pointer: Default::default(),
             touch_states: Default::default(),
+            pen: None,
             scroll_delta: Vec2::ZERO,
+            scroll_history: History::new(0..1000, 0.1),
             zoom_factor_delta: 1.0,
             screen_rect: Rect::from_min_size(Default::default(), vec2(10_000.0, 10_000.0)),
             pixels_per_point: 1.0,
@@ -138,7 +146,12 @@ impl Default for InputState {
 
 impl InputState {
     #[must_use]
-    pub fn begin_frame(mut self, new: RawInput, requested_repaint_last_frame: bool) -> InputState {
+    pub fn begin_frame(
+        mut self,
+        new: RawInput,
+        requested_repaint_last_frame: bool,
+        interaction: &crate::style::Interaction,
+    ) -> InputState {
         let time = new.time.unwrap_or(self.time + new.predicted_dt as f64);
         let unstable_dt = (time - self.time) as f32;
 
@@ -153,11 +166,12 @@ impl InputState {
         let screen_rect = new.screen_rect.unwrap_or(self.screen_rect);
         self.create_touch_states_for_new_devices(&new.events);
         for touch_state in self.touch_states.values_mut() {
-            touch_state.begin_frame(time, &new, self.pointer.interact_pos);
+            touch_state.begin_frame(time, &new, self.pointer.interact_pos, interaction);
         }
         let pointer = self.pointer.begin_frame(time, &new);
 
         let mut keys_down = self.keys_down;
+        let mut pen = self.pen;
         let mut scroll_delta = Vec2::ZERO;
         let mut zoom_factor_delta = 1.0;
         for event in &new.events {
@@ -175,13 +189,28 @@ impl InputState {
                 Event::Zoom(factor) => {
                     zoom_factor_delta *= *factor;
                 }
+                Event::Pen(state) => {
+                    pen = Some(*state);
+                }
+                Event::PenGone => {
+                    pen = None;
+                }
                 _ => {}
             }
         }
+
+        let mut scroll_history = self.scroll_history;
+        if scroll_delta != Vec2::ZERO {
+            scroll_history.add(time, scroll_delta);
+        }
+        scroll_history.flush(time);
+
         InputState {
             pointer,
             touch_states: self.touch_states,
+            pen,
             scroll_delta,
+            scroll_history,
             zoom_factor_delta,
             screen_rect,
             pixels_per_point: new.pixels_per_point.unwrap_or(self.pixels_per_point),
@@ -241,8 +270,25 @@ impl InputState {
         )
     }
 
+    /// How fast the user has been scrolling lately (e.g. flicking on a trackpad), in points per second.
+    ///
+    /// Zero if there has not been enough scrolling to tell.
+    pub fn scroll_velocity(&self) -> Vec2 {
+        if self.scroll_history.len() >= 3 && self.scroll_history.duration() > 0.01 {
+            // Each delta is the scrolling since the previous one, so skip the first one:
+            let distance =
+                (self.scroll_history.values().skip(1)).fold(Vec2::ZERO, |sum, delta| sum + delta);
+            distance / self.scroll_history.duration()
+        } else {
+            Vec2::ZERO
+        }
+    }
+
     pub fn wants_repaint(&self) -> bool {
-        self.pointer.wants_repaint() || self.scroll_delta != Vec2::ZERO || !self.events.is_empty()
+        self.pointer.wants_repaint()
+            || self.scroll_delta != Vec2::ZERO
+            || !self.events.is_empty()
+            || self.gestures().next().is_some()
     }
 
     /// Check for a key press. If found, `true` is returned and the key pressed is consumed, so that this will only return `true` once.
@@ -308,6 +354,26 @@ impl InputState {
         })
     }
 
+    /// The [`Event::AccessibilityAction`]s targeting the widget with this id this frame.
+    pub fn accessibility_actions(&self, id: Id) -> impl Iterator<Item = &AccessibilityAction> {
+        self.events.iter().filter_map(move |event| match event {
+            Event::AccessibilityAction { target, action } if *target == id => Some(action),
+            _ => None,
+        })
+    }
+
+    /// Did assistive technology ask the widget with this id to set its value this frame?
+    ///
+    /// Returns the last requested value, if any.
+    pub fn accessibility_set_value(&self, id: Id) -> Option<f64> {
+        self.accessibility_actions(id)
+            .filter_map(|action| match action {
+                AccessibilityAction::SetValue(value) => Some(*value),
+                _ => None,
+            })
+            .last()
+    }
+
     /// Also known as device pixel ratio, > 1 for high resolution screens.
     #[inline(always)]
     pub fn pixels_per_point(&self) -> f32 {
@@ -364,6 +430,45 @@ impl InputState {
         }
     }
 
+    /// Single-finger touch gestures (long-press, swipe, fling) recognized this frame.
+    ///
+    /// Usually you would use e.g. [`crate::Response::long_pressed`] instead,
+    /// which only reports the gestures that were made on a specific widget.
+    ///
+    /// For two-finger gestures (pan, pinch and rotate), see [`Self::multi_touch`].
+    pub fn gestures(&self) -> impl Iterator<Item = &Gesture> {
+        self.touch_states
+            .values()
+            .flat_map(|touch_state| touch_state.gestures())
+    }
+
+    /// If a finger is being held down, how long until it becomes a long-press (in seconds)?
+    pub(crate) fn time_until_long_press(&self, long_press_duration: f64) -> Option<f64> {
+        self.touch_states
+            .values()
+            .filter_map(|touch_state| {
+                touch_state.time_until_long_press(self.time, long_press_duration)
+            })
+            .reduce(f64::min)
+    }
+
+    /// The latest state of the pen (stylus), if one is touching or hovering above the surface.
+    ///
+    /// The pen also moves the [`Self::pointer`], so you only need this
+    /// if you care about e.g. pressure, tilt or the eraser.
+    /// For drawing, use [`Self::pen_samples`] to get every sample from this frame.
+    pub fn pen(&self) -> Option<PenState> {
+        self.pen
+    }
+
+    /// Every pen (stylus) state reported this frame, in order.
+    pub fn pen_samples(&self) -> impl Iterator<Item = &PenState> {
+        self.events.iter().filter_map(|event| match event {
+            Event::Pen(state) => Some(state),
+            _ => None,
+        })
+    }
+
     /// True if there currently are any fingers touching egui.
     pub fn any_touches(&self) -> bool {
         !self.touch_states.is_empty()
@@ -837,7 +942,9 @@ impl InputState {
             raw,
             pointer,
             touch_states,
+            pen,
             scroll_delta,
+            scroll_history: _,
             zoom_factor_delta,
             screen_rect,
             pixels_per_point,
@@ -871,7 +978,12 @@ impl InputState {
             });
         }
 
+        ui.label(format!("pen: {:?}", pen));
         ui.label(format!("scroll_delta: {:?} points", scroll_delta));
+        ui.label(format!(
+            "scroll_velocity: {:?} points/s",
+            self.scroll_velocity()
+        ));
         ui.label(format!("zoom_factor_delta: {:4.2}x", zoom_factor_delta));
         ui.label(format!("screen_rect: {:?} points", screen_rect));
         ui.label(format!(
@@ -936,3 +1048,38 @@ impl PointerState {
         ui.label(format!("pointer_events: {:?}", pointer_events));
     }
 }
+
+#[test]
+fn test_pen() {
+    let pen = |pressure| PenState {
+        pos: pos2(10.0, 20.0),
+        contact: true,
+        pressure,
+        ..Default::default()
+    };
+    let frame = |input: InputState, events| {
+        input.begin_frame(
+            RawInput {
+                events,
+                ..Default::default()
+            },
+            false,
+            &Default::default(),
+        )
+    };
+
+    let input = frame(
+        InputState::default(),
+        vec![Event::Pen(pen(0.25)), Event::Pen(pen(0.5))],
+    );
+    let pressures: Vec<f32> = input.pen_samples().map(|pen| pen.pressure).collect();
+    assert_eq!(pressures, vec![0.25, 0.5]);
+    assert_eq!(input.pen(), Some(pen(0.5)));
+
+    // The pen is remembered until it is gone:
+    let input = frame(input, vec![]);
+    assert_eq!(input.pen_samples().count(), 0);
+    assert_eq!(input.pen(), Some(pen(0.5)));
+    let input = frame(input, vec![Event::PenGone]);
+    assert_eq!(input.pen(), None);
+}
@@ -3,9 +3,70 @@ use std::{collections::BTreeMap, fmt::Debug};
 use crate::{
     data::input::TouchDeviceId,
     emath::{normalized_angle, Pos2, Vec2},
+    style::Interaction,
+    util::History,
     Event, RawInput, TouchId, TouchPhase,
 };
 
+/// A gesture made with a single finger, recognized by egui.
+///
+/// See [`crate::InputState::gestures`] and e.g. [`crate::Response::long_pressed`].
+///
+/// Two-finger gestures (pan, pinch and rotate) are described by [`MultiTouchInfo`] instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// A finger was held down without moving for a while.
+    ///
+    /// This is reported once, while the finger is still down.
+    /// Usually used to open a context menu.
+    LongPress {
+        /// Where the finger is.
+        pos: Pos2,
+    },
+
+    /// A finger moved quickly in one direction and was then lifted.
+    Swipe {
+        /// Where the finger was first put down.
+        start_pos: Pos2,
+        direction: SwipeDirection,
+    },
+
+    /// A finger was lifted while still moving, "throwing" whatever it was dragging.
+    Fling {
+        /// Where the finger was first put down.
+        start_pos: Pos2,
+
+        /// In points per second.
+        velocity: Vec2,
+    },
+}
+
+/// The main direction of a [`Gesture::Swipe`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum SwipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SwipeDirection {
+    fn from_delta(delta: Vec2) -> Self {
+        if delta.x.abs() > delta.y.abs() {
+            if delta.x < 0.0 {
+                Self::Left
+            } else {
+                Self::Right
+            }
+        } else if delta.y < 0.0 {
+            Self::Up
+        } else {
+            Self::Down
+        }
+    }
+}
+
 /// All you probably need to know about a multi-touch gesture.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct MultiTouchInfo {
@@ -80,6 +141,29 @@ pub(crate) struct TouchState {
     /// If a gesture has been recognized (i.e. when exactly two fingers touch the surface), this
     /// holds state information
     gesture_state: Option<GestureState>,
+
+    /// If exactly one finger has been touching the surface since it was put down,
+    /// this tracks it for [`Gesture`]s.
+    single_touch: Option<SingleTouch>,
+
+    /// Gestures recognized this frame.
+    gestures: Vec<Gesture>,
+}
+
+#[derive(Clone, Debug)]
+struct SingleTouch {
+    id: TouchId,
+    start_time: f64,
+    start_pos: Pos2,
+
+    /// Used to calculate the velocity when the finger is lifted.
+    pos_history: History<Pos2>,
+
+    /// Moved too far to be a long-press?
+    moved: bool,
+
+    /// Has this already been reported as a [`Gesture::LongPress`]?
+    long_pressed: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -123,10 +207,19 @@ impl TouchState {
             device_id,
             active_touches: Default::default(),
             gesture_state: None,
+            single_touch: None,
+            gestures: Vec::new(),
         }
     }
 
-    pub fn begin_frame(&mut self, time: f64, new: &RawInput, pointer_pos: Option<Pos2>) {
+    pub fn begin_frame(
+        &mut self,
+        time: f64,
+        new: &RawInput,
+        pointer_pos: Option<Pos2>,
+        interaction: &Interaction,
+    ) {
+        self.gestures.clear();
         let mut added_or_removed_touches = false;
         for event in &new.events {
             match *event {
@@ -138,6 +231,19 @@ impl TouchState {
                     force,
                 } if device_id == self.device_id => match phase {
                     TouchPhase::Start => {
+                        // A second finger turns this into a multi-touch gesture:
+                        self.single_touch = self.active_touches.is_empty().then(|| {
+                            let mut pos_history = History::new(0..1000, 0.1);
+                            pos_history.add(time, pos);
+                            SingleTouch {
+                                id,
+                                start_time: time,
+                                start_pos: pos,
+                                pos_history,
+                                moved: false,
+                                long_pressed: false,
+                            }
+                        });
                         self.active_touches.insert(id, ActiveTouch { pos, force });
                         added_or_removed_touches = true;
                     }
@@ -146,15 +252,39 @@ impl TouchState {
                             touch.pos = pos;
                             touch.force = force;
                         }
+                        if let Some(touch) = &mut self.single_touch {
+                            if touch.id == id {
+                                touch.pos_history.add(time, pos);
+                                touch.moved |=
+                                    touch.start_pos.distance(pos) > super::MAX_CLICK_DIST;
+                            }
+                        }
                     }
                     TouchPhase::End | TouchPhase::Cancel => {
                         self.active_touches.remove(&id);
                         added_or_removed_touches = true;
+                        if let Some(touch) = self.single_touch.take() {
+                            if touch.id == id && phase == TouchPhase::End {
+                                self.end_single_touch(time, pos, touch, interaction);
+                            }
+                        }
                     }
                 },
                 _ => (),
             }
         }
+        if let Some(touch) = &mut self.single_touch {
+            if !touch.moved
+                && !touch.long_pressed
+                && interaction.long_press_duration <= time - touch.start_time
+            {
+                touch.long_pressed = true;
+                self.gestures.push(Gesture::LongPress {
+                    pos: touch.start_pos,
+                });
+            }
+        }
+
         // This needs to be called each frame, even if there are no new touch events.
         // Otherwise, we would send the same old delta information multiple times:
         self.update_gesture(time, pointer_pos);
@@ -168,6 +298,52 @@ impl TouchState {
         }
     }
 
+    fn end_single_touch(
+        &mut self,
+        time: f64,
+        pos: Pos2,
+        mut touch: SingleTouch,
+        interaction: &Interaction,
+    ) {
+        if touch.long_pressed {
+            return;
+        }
+
+        let delta = pos - touch.start_pos;
+        if time - touch.start_time <= interaction.max_swipe_duration
+            && interaction.min_swipe_distance <= delta.length()
+        {
+            self.gestures.push(Gesture::Swipe {
+                start_pos: touch.start_pos,
+                direction: SwipeDirection::from_delta(delta),
+            });
+        }
+
+        // If the finger came to a rest before it was lifted, the history will be (almost) empty:
+        touch.pos_history.add(time, pos);
+        if touch.pos_history.len() >= 3 {
+            let velocity = touch.pos_history.velocity().unwrap_or_default();
+            if interaction.min_fling_speed <= velocity.length() {
+                self.gestures.push(Gesture::Fling {
+                    start_pos: touch.start_pos,
+                    velocity,
+                });
+            }
+        }
+    }
+
+    /// Gestures recognized this frame.
+    pub fn gestures(&self) -> &[Gesture] {
+        &self.gestures
+    }
+
+    /// How long until a finger that is being held down becomes a long-press, if it stays still.
+    pub fn time_until_long_press(&self, time: f64, long_press_duration: f64) -> Option<f64> {
+        let touch = self.single_touch.as_ref()?;
+        (!touch.moved && !touch.long_pressed)
+            .then(|| (touch.start_time + long_press_duration - time).max(0.0))
+    }
+
     pub fn is_active(&self) -> bool {
         self.gesture_state.is_some()
     }
@@ -293,6 +469,7 @@ impl Debug for TouchState {
             f.write_fmt(format_args!("#{:?}: {:#?}\n", id, touch))?;
         }
         f.write_fmt(format_args!("gesture: {:#?}\n", self.gesture_state))?;
+        f.write_fmt(format_args!("single touch: {:#?}\n", self.single_touch))?;
         Ok(())
     }
 }
@@ -333,3 +510,99 @@ impl PinchType {
         }
     }
 }
+
+#[test]
+fn test_touch_gestures() {
+    use crate::{
+        vec2, Button, CentralPanel, Context, Event, PointerButton, Pos2, RawInput, Rect, Sense,
+        SwipeDirection, TouchDeviceId, TouchId, TouchPhase, Vec2,
+    };
+
+    #[derive(Default)]
+    struct State {
+        button_rect: Option<Rect>,
+        clicked: bool,
+        long_pressed: bool,
+        swiped: Option<SwipeDirection>,
+        fling_velocity: Option<Vec2>,
+    }
+
+    /// The events an integration sends for a finger.
+    fn touch(phase: TouchPhase, pos: Pos2) -> Vec<Event> {
+        let touch = Event::Touch {
+            device_id: TouchDeviceId(0),
+            id: TouchId(0),
+            phase,
+            pos,
+            force: 0.0,
+        };
+        let button = |pressed| Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        match phase {
+            TouchPhase::Start => vec![touch, Event::PointerMoved(pos), button(true)],
+            TouchPhase::Move => vec![touch, Event::PointerMoved(pos)],
+            TouchPhase::End | TouchPhase::Cancel => vec![touch, button(false), Event::PointerGone],
+        }
+    }
+
+    let ctx = Context::default();
+    let mut state = State::default();
+    let mut run_frame = |time: f64, events: Vec<Event>| {
+        let raw_input = RawInput {
+            time: Some(time),
+            events,
+            ..Default::default()
+        };
+        let full_output = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let response = ui.add(Button::new("Touch me").sense(Sense::click_and_drag()));
+                state.button_rect = Some(response.rect);
+                state.clicked |= response.clicked();
+                state.long_pressed |= response.long_pressed();
+                state.swiped = state.swiped.or(response.swiped());
+                state.fling_velocity = state.fling_velocity.or(response.fling_velocity());
+            });
+        });
+        (std::mem::take(&mut state), full_output.repaint_after)
+    };
+
+    let (State { button_rect, .. }, _) = run_frame(0.0, vec![]);
+    let pos = button_rect.unwrap().center();
+
+    // Hold a finger still on the button:
+    run_frame(1.0, touch(TouchPhase::Start, pos));
+    run_frame(1.1, vec![]);
+    let (held, repaint_after) = run_frame(1.2, vec![]);
+    assert!(!held.long_pressed);
+    assert!(
+        !repaint_after.is_zero(),
+        "should sleep until the long-press"
+    );
+    assert!(repaint_after <= std::time::Duration::from_secs_f64(0.4));
+    let (held, _) = run_frame(1.7, vec![]);
+    assert!(held.long_pressed);
+    let (released, _) = run_frame(1.8, touch(TouchPhase::End, pos));
+    assert!(!released.long_pressed, "only reported once");
+    assert!(!released.clicked, "a long-press is not a click");
+
+    // Quickly swipe right across the button:
+    run_frame(3.0, touch(TouchPhase::Start, pos));
+    for i in 1..=4 {
+        let time = 3.0 + 0.025 * i as f64;
+        let moved = run_frame(
+            time,
+            touch(TouchPhase::Move, pos + vec2(30.0 * i as f32, 0.0)),
+        );
+        assert_eq!(moved.0.swiped, None);
+    }
+    let (released, _) = run_frame(3.125, touch(TouchPhase::End, pos + vec2(150.0, 0.0)));
+    assert_eq!(released.swiped, Some(SwipeDirection::Right));
+    let velocity = released.fling_velocity.unwrap();
+    assert!(velocity.x > 1000.0 && velocity.y == 0.0);
+    assert!(!released.long_pressed);
+    assert!(!released.clicked);
+}
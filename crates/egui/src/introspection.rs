@@ -97,6 +97,8 @@ impl Widget for &epaint::stats::PaintStats {
                 clipped_primitives,
                 vertices,
                 indices,
+                cached_layers,
+                tessellated_layers,
             } = self;
 
             ui.label("Intermediate:");
@@ -122,6 +124,13 @@ impl Widget for &epaint::stats::PaintStats {
                 .on_hover_text("Number of separate clip rectangles");
             label(ui, vertices, "vertices");
             label(ui, indices, "indices").on_hover_text("Three 32-bit indices per triangles");
+            ui.label(format!(
+                "{:6} layers reused, {} tessellated",
+                cached_layers, tessellated_layers
+            ))
+            .on_hover_text(
+                "Layers whose shapes didn't change since last frame are not tessellated again",
+            );
             ui.add_space(10.0);
 
             // ui.label("Total:");
@@ -149,6 +158,7 @@ impl Widget for &mut epaint::TessellationOptions {
                 debug_ignore_clip_rects,
                 bezier_tolerance,
                 epsilon: _,
+                instancing: _, // up to the backend
             } = self;
 
             ui.checkbox(feathering, "Feathering (antialias)")
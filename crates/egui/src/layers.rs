@@ -166,8 +166,9 @@ impl GraphicLayers {
             .or_default()
     }
 
-    pub fn drain(&mut self, area_order: &[LayerId]) -> impl ExactSizeIterator<Item = ClippedShape> {
-        let mut all_shapes: Vec<_> = Default::default();
+    /// The shapes of each layer, in paint order.
+    pub fn drain(&mut self, area_order: &[LayerId]) -> Vec<(LayerId, Vec<ClippedShape>)> {
+        let mut all_layers: Vec<_> = Default::default();
 
         for &order in &Order::ALL {
             let order_map = &mut self.0[order as usize];
@@ -181,17 +182,21 @@ impl GraphicLayers {
             for layer_id in area_order {
                 if layer_id.order == order {
                     if let Some(list) = order_map.get_mut(&layer_id.id) {
-                        all_shapes.append(&mut list.0);
+                        if !list.is_empty() {
+                            all_layers.push((*layer_id, std::mem::take(&mut list.0)));
+                        }
                     }
                 }
             }
 
             // Also draw areas that are missing in `area_order`:
-            for shapes in order_map.values_mut() {
-                all_shapes.append(&mut shapes.0);
+            for (&id, list) in order_map.iter_mut() {
+                if !list.is_empty() {
+                    all_layers.push((LayerId::new(order, id), std::mem::take(&mut list.0)));
+                }
             }
         }
 
-        all_shapes.into_iter()
+        all_layers
     }
 }
@@ -302,6 +302,7 @@
 mod animation_manager;
 pub mod containers;
 mod context;
+mod damage;
 mod data;
 mod frame_state;
 pub(crate) mod grid;
@@ -317,6 +318,7 @@ pub(crate) mod placer;
 mod response;
 mod sense;
 pub mod style;
+mod tessellation_cache;
 mod ui;
 pub mod util;
 pub mod widget_text;
@@ -329,10 +331,10 @@ pub use emath::{lerp, pos2, remap, remap_clamp, vec2, Align, Align2, NumExt, Pos
 #[cfg(feature = "color-hex")]
 pub use epaint::hex_color;
 pub use epaint::{
-    color, mutex,
+    color, colormap, mutex,
     text::{FontData, FontDefinitions, FontFamily, FontId, FontTweak},
-    textures::{TextureFilter, TexturesDelta},
-    ClippedPrimitive, Color32, ColorImage, FontImage, ImageData, Mesh, PaintCallback,
+    textures::{TextureFilter, TextureOptions, TextureWrapMode, TexturesDelta},
+    ClippedPrimitive, Color32, ColorImage, Colormap, FontImage, ImageData, Mesh, PaintCallback,
     PaintCallbackInfo, Rgba, Rounding, Shape, Stroke, TextureHandle, TextureId,
 };
 
@@ -347,13 +349,14 @@ pub mod text {
 pub use {
     containers::*,
     context::Context,
+    damage::merge_damage_rects,
     data::{
         input::*,
         output::{self, CursorIcon, FullOutput, PlatformOutput, WidgetInfo},
     },
     grid::Grid,
     id::{Id, IdMap},
-    input_state::{InputState, MultiTouchInfo, PointerState},
+    input_state::{Gesture, InputState, MultiTouchInfo, PointerState, SwipeDirection},
     layers::{LayerId, Order},
     layout::*,
     memory::Memory,
@@ -505,7 +508,7 @@ pub mod special_emojis {
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum WidgetType {
-    Label, // TODO(emilk): emit Label events
+    Label,
     /// e.g. a hyperlink
     Link,
     TextEdit,
@@ -519,6 +522,44 @@ pub enum WidgetType {
     ColorButton,
     ImageButton,
     CollapsingHeader,
+    ProgressBar,
+    Image,
+
+    /// A [`crate::Window`].
+    Window,
+
+    /// A [`crate::SidePanel`], [`crate::TopBottomPanel`] or [`crate::CentralPanel`].
+    Panel,
+
+    /// The popup of a menu or [`crate::ComboBox`].
+    Menu,
+
+    /// A button in a menu, e.g. one that opens a sub-menu.
+    MenuItem,
+
+    /// One of several selectable tabs.
+    Tab,
+
+    /// A tooltip shown when hovering a widget.
+    Tooltip,
+
+    /// A [`crate::ScrollArea`]. The value is the scroll offset.
+    ScrollArea,
+
+    /// A [`crate::Grid`].
+    Grid,
+
+    /// A table, e.g. from `egui_extras`.
+    Table,
+
+    /// A cell in a [`Self::Table`].
+    Cell,
+
+    /// An item in a tree view that can be expanded or collapsed.
+    TreeItem,
+
+    /// A [`crate::plot::Plot`].
+    Plot,
 
     /// If you cannot fit any of the above slots.
     ///
@@ -107,6 +107,15 @@ pub struct Options {
     /// Screen readers is an experimental feature of egui, and not supported on all platforms.
     pub screen_reader: bool,
 
+    /// If true, egui builds a tree of all widgets each frame and outputs it in
+    /// [`crate::PlatformOutput::accessibility_tree`],
+    /// and reacts to [`crate::Event::AccessibilityAction`].
+    ///
+    /// This is for integrating with platform accessibility APIs.
+    /// `eframe` mirrors the tree into the DOM on the web; `egui-winit` does not export it yet.
+    /// It also makes e.g. labels focusable, like [`Self::screen_reader`] does.
+    pub accessibility_tree: bool,
+
     /// If true, the most common glyphs (ASCII) are pre-rendered to the texture atlas.
     ///
     /// Only the fonts in [`Style::text_styles`] will be pre-cached.
@@ -114,6 +123,15 @@ pub struct Options {
     /// This can lead to fewer texture operations, but may use up the texture atlas quicker
     /// if you are changing [`Style::text_styles`], of have a lot of text styles.
     pub preload_font_glyphs: bool,
+
+    /// If true, egui compares the shapes of each frame with those of the previous frame
+    /// and reports what changed in [`crate::FullOutput::damage`].
+    ///
+    /// This lets backends repaint only the parts of the screen that changed,
+    /// at the cost of hashing all shapes each frame, so only backends that make use of
+    /// the damage should turn this on.
+    /// When false, the whole screen is always reported as damaged.
+    pub track_damage: bool,
 }
 
 impl Default for Options {
@@ -122,7 +140,9 @@ impl Default for Options {
             style: Default::default(),
             tessellation_options: Default::default(),
             screen_reader: false,
+            accessibility_tree: false,
             preload_font_glyphs: true,
+            track_damage: false,
         }
     }
 }
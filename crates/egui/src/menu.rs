@@ -128,6 +128,7 @@ pub(crate) fn menu_ui<'c, R>(
     };
 
     let area = Area::new(menu_id)
+        .role(WidgetType::Menu)
         .order(Order::Foreground)
         .fixed_pos(pos)
         .interactable(true)
@@ -160,6 +161,7 @@ fn stationary_menu_impl<'c, R>(
     let title = title.into();
     let bar_id = ui.id();
     let menu_id = bar_id.with(title.text());
+    let title_text = title.text().to_owned();
 
     let mut bar_state = BarState::load(ui.ctx(), bar_id);
 
@@ -171,7 +173,13 @@ fn stationary_menu_impl<'c, R>(
     }
 
     let button_response = ui.add(button);
-    let inner = bar_state.bar_menu(&button_response, add_contents);
+    let ctx = ui.ctx().clone();
+    let inner = ctx.with_accessibility_parent(button_response.id, || {
+        bar_state.bar_menu(&button_response, add_contents)
+    });
+    let is_open = bar_state.open_menu.is_menu_open(menu_id);
+    button_response
+        .accessibility_node(|| WidgetInfo::expandable(WidgetType::MenuItem, is_open, title_text));
 
     bar_state.store(ui.ctx(), bar_id);
     InnerResponse::new(inner.map(|r| r.inner), button_response)
@@ -315,6 +323,11 @@ impl MenuRoot {
     ) -> MenuResponse {
         let response = response.interact(Sense::click());
         let pointer = &response.ctx.input().pointer;
+        if response.long_pressed() {
+            if let Some(pos) = pointer.interact_pos() {
+                return MenuResponse::Create(pos, id);
+            }
+        }
         if pointer.any_pressed() {
             if let Some(pos) = pointer.interact_pos() {
                 let mut destroy = false;
@@ -427,8 +440,9 @@ impl SubMenuButton {
         let desired_size = text_and_icon_size + 2.0 * button_padding;
 
         let (rect, response) = ui.allocate_at_least(desired_size, sense);
+        let is_open = menu_state.is_open(sub_id);
         response.widget_info(|| {
-            crate::WidgetInfo::labeled(crate::WidgetType::Button, &text_galley.text())
+            crate::WidgetInfo::expandable(crate::WidgetType::MenuItem, is_open, text_galley.text())
         });
 
         if ui.is_rect_visible(rect) {
@@ -1,7 +1,7 @@
 use crate::{
     emath::{Align, Pos2, Rect, Vec2},
-    menu, Context, CursorIcon, Id, LayerId, PointerButton, Sense, Ui, WidgetText,
-    NUM_POINTER_BUTTONS,
+    menu, Context, CursorIcon, Id, LayerId, MultiTouchInfo, PointerButton, Sense, SwipeDirection,
+    Ui, WidgetText, NUM_POINTER_BUTTONS,
 };
 
 // ----------------------------------------------------------------------------
@@ -72,6 +72,18 @@ pub struct Response {
     #[doc(hidden)]
     pub interact_pointer_pos: Option<Pos2>,
 
+    /// A finger was held down on this widget without moving for a while.
+    #[doc(hidden)]
+    pub long_pressed: bool,
+
+    /// A finger swiped across this widget, starting on it.
+    #[doc(hidden)]
+    pub swiped: Option<SwipeDirection>,
+
+    /// A finger was lifted from this widget while moving, with this velocity.
+    #[doc(hidden)]
+    pub fling_velocity: Option<Vec2>,
+
     /// What the underlying data changed?
     ///
     /// e.g. the slider was dragged, text was entered in a [`TextEdit`](crate::TextEdit) etc.
@@ -97,6 +109,9 @@ impl std::fmt::Debug for Response {
             drag_released,
             is_pointer_button_down_on,
             interact_pointer_pos,
+            long_pressed,
+            swiped,
+            fling_velocity,
             changed,
         } = self;
         f.debug_struct("Response")
@@ -113,6 +128,9 @@ impl std::fmt::Debug for Response {
             .field("drag_released", drag_released)
             .field("is_pointer_button_down_on", is_pointer_button_down_on)
             .field("interact_pointer_pos", interact_pointer_pos)
+            .field("long_pressed", long_pressed)
+            .field("swiped", swiped)
+            .field("fling_velocity", fling_velocity)
             .field("changed", changed)
             .finish()
     }
@@ -298,6 +316,44 @@ impl Response {
         self.interact_pointer_pos
     }
 
+    /// Was a finger held down on this widget without moving for a while?
+    ///
+    /// This is only `true` for one frame, while the finger is still down,
+    /// and the finger being lifted afterwards will not count as a click.
+    /// Long-pressing opens the [`Self::context_menu`], just like a secondary click.
+    ///
+    /// Note that the widget must be sensing clicks with [`Sense::click`].
+    pub fn long_pressed(&self) -> bool {
+        self.long_pressed
+    }
+
+    /// Did a finger that was put down on this widget swipe in some direction, and lift, this frame?
+    ///
+    /// Note that the widget must be sensing clicks or drags.
+    pub fn swiped(&self) -> Option<SwipeDirection> {
+        self.swiped
+    }
+
+    /// Was a finger that was dragging this widget lifted while still moving this frame?
+    ///
+    /// Returns the velocity (in points per second) that whatever was being dragged should keep moving with.
+    ///
+    /// Note that the widget must be sensing drags.
+    pub fn fling_velocity(&self) -> Option<Vec2> {
+        self.fling_velocity
+    }
+
+    /// The ongoing two-finger gesture (pan, pinch and rotate), if it started on this widget.
+    ///
+    /// See [`crate::InputState::multi_touch`].
+    pub fn multi_touch(&self) -> Option<MultiTouchInfo> {
+        let info = self.ctx.multi_touch()?;
+        (self.enabled
+            && self.rect.contains(info.start_pos)
+            && self.ctx.layer_id_at(info.start_pos) == Some(self.layer_id))
+        .then(|| info)
+    }
+
     /// If it is a good idea to show a tooltip, where is pointer?
     /// None if the pointer is outside the response area.
     pub fn hover_pos(&self) -> Option<Pos2> {
@@ -430,6 +486,10 @@ impl Response {
     /// If you call this multiple times the tooltips will stack underneath the previous ones.
     #[doc(alias = "tooltip")]
     pub fn on_hover_text(self, text: impl Into<WidgetText>) -> Self {
+        let text = text.into();
+        if self.enabled {
+            self.ctx.accessibility_tooltip(self.id, text.text());
+        }
         self.on_hover_ui(|ui| {
             ui.add(crate::widgets::Label::new(text));
         })
@@ -437,6 +497,10 @@ impl Response {
 
     /// Show this text when hovering if the widget is disabled.
     pub fn on_disabled_hover_text(self, text: impl Into<WidgetText>) -> Self {
+        let text = text.into();
+        if !self.enabled {
+            self.ctx.accessibility_tooltip(self.id, text.text());
+        }
         self.on_disabled_hover_ui(|ui| {
             ui.add(crate::widgets::Label::new(text));
         })
@@ -501,6 +565,8 @@ impl Response {
     /// For accessibility.
     ///
     /// Call after interacting and potential calls to [`Self::mark_changed`].
+    ///
+    /// This also adds the widget to the [`crate::output::AccessibilityTree`], if one is being built.
     pub fn widget_info(&self, make_info: impl Fn() -> crate::WidgetInfo) {
         use crate::output::OutputEvent;
         let event = if self.clicked() {
@@ -519,6 +585,24 @@ impl Response {
         if let Some(event) = event {
             self.ctx.output().events.push(event);
         }
+
+        self.accessibility_node(make_info);
+    }
+
+    /// Add the widget to the [`crate::output::AccessibilityTree`] (if one is being built)
+    /// without emitting any [`crate::output::OutputEvent`].
+    ///
+    /// Use this to update the [`crate::WidgetInfo`] of a widget that already called [`Self::widget_info`].
+    pub(crate) fn accessibility_node(&self, make_info: impl FnOnce() -> crate::WidgetInfo) {
+        self.ctx
+            .accessibility_node(self.id, || crate::output::AccessibilityNode {
+                parent: None,
+                children: vec![],
+                info: make_info(),
+                rect: self.rect,
+                focusable: self.sense.focusable,
+                clickable: self.sense.click,
+            });
     }
 
     /// Response to secondary clicks (right-clicks) by showing the given menu.
@@ -587,6 +671,9 @@ impl Response {
             is_pointer_button_down_on: self.is_pointer_button_down_on
                 || other.is_pointer_button_down_on,
             interact_pointer_pos: self.interact_pointer_pos.or(other.interact_pointer_pos),
+            long_pressed: self.long_pressed || other.long_pressed,
+            swiped: self.swiped.or(other.swiped),
+            fling_velocity: self.fling_velocity.or(other.fling_velocity),
             changed: self.changed || other.changed,
         }
     }
@@ -188,6 +188,9 @@ pub struct Style {
     /// How and when interaction happens.
     pub interaction: Interaction,
 
+    /// How [`crate::ScrollArea`]s move.
+    pub scroll: ScrollStyle,
+
     /// Colors etc.
     pub visuals: Visuals,
 
@@ -393,6 +396,55 @@ pub struct Interaction {
 
     /// If `false`, tooltips will show up anytime you hover anything, even is mouse is still moving
     pub show_tooltips_only_when_still: bool,
+
+    /// If a single finger is held still for this many seconds, it becomes a long-press.
+    ///
+    /// By default this is the longest possible click, so a long-press is never also a click.
+    pub long_press_duration: f64,
+
+    /// A finger must move at least this many points…
+    pub min_swipe_distance: f32,
+
+    /// …within this many seconds for a swipe.
+    pub max_swipe_duration: f64,
+
+    /// A finger lifted while moving faster than this (in points per second) flings.
+    pub min_fling_speed: f32,
+}
+
+/// How [`crate::ScrollArea`]s react to dragging, flicking and the mouse wheel.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ScrollStyle {
+    /// Keep scrolling after the content is dragged and released.
+    pub kinetic: bool,
+
+    /// Also keep scrolling after a flick on a trackpad, when [`Self::kinetic`] is on.
+    ///
+    /// Off by default, since many platforms already send such momentum as scroll events.
+    pub kinetic_wheel: bool,
+
+    /// How quickly kinetic scrolling slows down, in points per second squared.
+    pub friction: f32,
+
+    /// Kinetic scrolling stops once it is slower than this, in points per second.
+    pub stop_speed: f32,
+
+    /// Let the content be pulled past its edges, springing back when let go.
+    pub overscroll: bool,
+
+    /// How far past its edges the content can be pulled, in points.
+    pub max_overscroll: f32,
+
+    /// Roughly how many seconds it takes to spring back after an overscroll.
+    pub bounce_time: f32,
+
+    /// Roughly how many seconds it takes to scroll to where the mouse wheel
+    /// or [`crate::Response::scroll_to_me`] wants to go.
+    ///
+    /// Zero (the default) means scrolling there instantly.
+    pub smooth_scroll_time: f32,
 }
 
 /// Controls the visual style (colors etc) of egui.
@@ -629,6 +681,7 @@ impl Default for Style {
             wrap: None,
             spacing: Spacing::default(),
             interaction: Interaction::default(),
+            scroll: ScrollStyle::default(),
             visuals: Visuals::default(),
             animation_time: 1.0 / 12.0,
             debug: Default::default(),
@@ -664,6 +717,25 @@ impl Default for Interaction {
             resize_grab_radius_side: 5.0,
             resize_grab_radius_corner: 10.0,
             show_tooltips_only_when_still: false,
+            long_press_duration: crate::input_state::MAX_CLICK_DURATION,
+            min_swipe_distance: 50.0,
+            max_swipe_duration: 0.5,
+            min_fling_speed: 200.0,
+        }
+    }
+}
+
+impl Default for ScrollStyle {
+    fn default() -> Self {
+        Self {
+            kinetic: true,
+            kinetic_wheel: false,
+            friction: 1000.0,
+            stop_speed: 20.0,
+            overscroll: false,
+            max_overscroll: 100.0,
+            bounce_time: 0.2,
+            smooth_scroll_time: 0.0,
         }
     }
 }
@@ -842,6 +914,7 @@ impl Style {
             wrap: _,
             spacing,
             interaction,
+            scroll,
             visuals,
             animation_time,
             debug,
@@ -892,6 +965,7 @@ impl Style {
         ui.collapsing("🔠 Text Styles", |ui| text_styles_ui(ui, text_styles));
         ui.collapsing("📏 Spacing", |ui| spacing.ui(ui));
         ui.collapsing("☝ Interaction", |ui| interaction.ui(ui));
+        ui.collapsing("↕ Scrolling", |ui| scroll.ui(ui));
         ui.collapsing("🎨 Visuals", |ui| visuals.ui(ui));
         ui.collapsing("🐛 Debug", |ui| debug.ui(ui));
 
@@ -1033,6 +1107,10 @@ impl Interaction {
             resize_grab_radius_side,
             resize_grab_radius_corner,
             show_tooltips_only_when_still,
+            long_press_duration,
+            min_swipe_distance,
+            max_swipe_duration,
+            min_fling_speed,
         } = self;
         ui.add(Slider::new(resize_grab_radius_side, 0.0..=20.0).text("resize_grab_radius_side"));
         ui.add(
@@ -1042,6 +1120,57 @@ impl Interaction {
             show_tooltips_only_when_still,
             "Only show tooltips if mouse is still",
         );
+        ui.add(
+            Slider::new(long_press_duration, 0.1..=2.0)
+                .suffix(" s")
+                .text("long_press_duration"),
+        );
+        ui.add(Slider::new(min_swipe_distance, 0.0..=200.0).text("min_swipe_distance"));
+        ui.add(
+            Slider::new(max_swipe_duration, 0.1..=2.0)
+                .suffix(" s")
+                .text("max_swipe_duration"),
+        );
+        ui.add(Slider::new(min_fling_speed, 0.0..=1000.0).text("min_fling_speed"));
+
+        ui.vertical_centered(|ui| reset_button(ui, self));
+    }
+}
+
+impl ScrollStyle {
+    pub fn ui(&mut self, ui: &mut crate::Ui) {
+        let Self {
+            kinetic,
+            kinetic_wheel,
+            friction,
+            stop_speed,
+            overscroll,
+            max_overscroll,
+            bounce_time,
+            smooth_scroll_time,
+        } = self;
+        ui.checkbox(kinetic, "Kinetic scrolling")
+            .on_hover_text("Keep scrolling after dragging");
+        ui.add_enabled_ui(*kinetic, |ui| {
+            ui.checkbox(kinetic_wheel, "After trackpad flicks too");
+            ui.add(Slider::new(friction, 0.0..=5000.0).text("friction"));
+            ui.add(Slider::new(stop_speed, 0.0..=200.0).text("stop_speed"));
+        });
+        ui.checkbox(overscroll, "Overscroll")
+            .on_hover_text("Let the content be pulled past its edges");
+        ui.add_enabled_ui(*overscroll, |ui| {
+            ui.add(Slider::new(max_overscroll, 0.0..=500.0).text("max_overscroll"));
+            ui.add(
+                Slider::new(bounce_time, 0.01..=1.0)
+                    .text("bounce_time")
+                    .suffix(" s"),
+            );
+        });
+        ui.add(
+            Slider::new(smooth_scroll_time, 0.0..=1.0)
+                .text("smooth_scroll_time")
+                .suffix(" s"),
+        );
 
         ui.vertical_centered(|ui| reset_button(ui, self));
     }
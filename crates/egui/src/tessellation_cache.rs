@@ -0,0 +1,312 @@
+//! Reusing the tessellation of layers that didn't change since last frame.
+
+use std::hash::{BuildHasher as _, Hash, Hasher};
+use std::sync::Arc;
+
+use epaint::{
+    text::FontTexture, ClippedPrimitive, ClippedShape, Color32, Galley, Pos2, PreparedDisc, Rect,
+    Shape, Stroke, TessellationOptions, Tessellator, TextureId,
+};
+
+use crate::LayerId;
+
+/// Everything besides the shapes that affects the output of the [`Tessellator`].
+#[derive(Clone, PartialEq)]
+pub(crate) struct TessellatorSettings {
+    pub pixels_per_point: f32,
+    pub options: TessellationOptions,
+    pub font_tex_size: [usize; 2],
+    pub font_textures: Vec<(FontTexture, TextureId, [usize; 2])>,
+    pub prepared_discs: Vec<PreparedDisc>,
+}
+
+impl TessellatorSettings {
+    pub fn tessellator(&self) -> Tessellator {
+        let mut tessellator = Tessellator::new(
+            self.pixels_per_point,
+            self.options,
+            self.font_tex_size,
+            self.prepared_discs.clone(),
+        );
+        for &(texture, texture_id, size) in &self.font_textures {
+            tessellator.set_font_texture(texture, texture_id, size);
+        }
+        tessellator
+    }
+}
+
+/// The tessellated primitives of each layer last frame.
+#[derive(Default)]
+pub(crate) struct TessellationCache {
+    hasher: ahash::RandomState,
+    settings: Option<TessellatorSettings>,
+    layers: ahash::HashMap<LayerId, CachedLayer>,
+}
+
+struct CachedLayer {
+    shapes_hash: u64,
+    primitives: Vec<ClippedPrimitive>,
+
+    /// We hash galleys by pointer, so we keep them alive to make sure
+    /// no other galley gets the same address while this is cached.
+    _galleys: Vec<Arc<Galley>>,
+}
+
+impl TessellationCache {
+    /// Tessellate the shapes of each layer, reusing the primitives from last frame
+    /// for layers whose shapes didn't change.
+    ///
+    /// Returns the primitives, and how many layers were reused and how many were tessellated.
+    pub fn tessellate(
+        &mut self,
+        settings: TessellatorSettings,
+        layers: impl Iterator<Item = (LayerId, Vec<ClippedShape>)>,
+    ) -> (Vec<ClippedPrimitive>, usize, usize) {
+        if self.settings.as_ref() != Some(&settings) {
+            self.layers.clear();
+        }
+        let mut tessellator = settings.tessellator();
+        self.settings = Some(settings);
+
+        let mut old_layers = std::mem::take(&mut self.layers);
+        let mut clipped_primitives = vec![];
+        let mut num_cached = 0;
+        let mut num_tessellated = 0;
+
+        for (layer_id, shapes) in layers {
+            let mut hasher = self.hasher.build_hasher();
+            for ClippedShape(clip_rect, shape) in &shapes {
+                hash_rect(*clip_rect, &mut hasher);
+                hash_shape(shape, &mut hasher);
+            }
+            let shapes_hash = hasher.finish();
+
+            let layer = match old_layers.remove(&layer_id) {
+                Some(layer) if layer.shapes_hash == shapes_hash => {
+                    num_cached += 1;
+                    layer
+                }
+                _ => {
+                    num_tessellated += 1;
+                    let mut galleys = vec![];
+                    for ClippedShape(_, shape) in &shapes {
+                        collect_galleys(shape, &mut galleys);
+                    }
+                    CachedLayer {
+                        shapes_hash,
+                        primitives: tessellator.tessellate_shapes(shapes),
+                        _galleys: galleys,
+                    }
+                }
+            };
+
+            // Cheap, since the meshes are shared:
+            clipped_primitives.extend(layer.primitives.iter().cloned());
+            self.layers.insert(layer_id, layer);
+        }
+
+        (clipped_primitives, num_cached, num_tessellated)
+    }
+}
+
+pub(crate) fn collect_galleys(shape: &Shape, galleys: &mut Vec<Arc<Galley>>) {
+    match shape {
+        Shape::Text(text_shape) => galleys.push(text_shape.galley.clone()),
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                collect_galleys(shape, galleys);
+            }
+        }
+        _ => {}
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+// Shapes contain floats, so they can't implement `Hash`.
+// Instead we hash the bits of the floats, which is fine since we only care about exact equality.
+
+pub(crate) fn hash_shape(shape: &Shape, state: &mut impl Hasher) {
+    std::mem::discriminant(shape).hash(state);
+    match shape {
+        Shape::Noop => {}
+        Shape::Vec(shapes) => {
+            shapes.len().hash(state);
+            for shape in shapes {
+                hash_shape(shape, state);
+            }
+        }
+        Shape::Circle(circle) => {
+            hash_pos(circle.center, state);
+            circle.radius.to_bits().hash(state);
+            hash_fill_and_stroke(circle.fill, circle.stroke, state);
+        }
+        Shape::LineSegment { points, stroke } => {
+            hash_points(points, state);
+            hash_stroke(*stroke, state);
+        }
+        Shape::Path(path) => {
+            hash_points(&path.points, state);
+            path.closed.hash(state);
+            hash_fill_and_stroke(path.fill, path.stroke, state);
+        }
+        Shape::Rect(rect) => {
+            hash_rect(rect.rect, state);
+            let r = rect.rounding;
+            for corner in [r.nw, r.ne, r.sw, r.se] {
+                corner.to_bits().hash(state);
+            }
+            hash_fill_and_stroke(rect.fill, rect.stroke, state);
+        }
+        Shape::Text(text) => {
+            hash_pos(text.pos, state);
+            Arc::as_ptr(&text.galley).hash(state);
+            hash_stroke(text.underline, state);
+            text.override_text_color.hash(state);
+            text.angle.to_bits().hash(state);
+        }
+        Shape::Mesh(mesh) => {
+            mesh.texture_id.hash(state);
+            mesh.indices.hash(state);
+            mesh.vertices.len().hash(state);
+            for vertex in &mesh.vertices {
+                hash_pos(vertex.pos, state);
+                hash_pos(vertex.uv, state);
+                vertex.color.hash(state);
+            }
+        }
+        Shape::QuadraticBezier(bezier) => {
+            hash_points(&bezier.points, state);
+            bezier.closed.hash(state);
+            hash_fill_and_stroke(bezier.fill, bezier.stroke, state);
+        }
+        Shape::CubicBezier(bezier) => {
+            hash_points(&bezier.points, state);
+            bezier.closed.hash(state);
+            hash_fill_and_stroke(bezier.fill, bezier.stroke, state);
+        }
+        Shape::Instanced(instanced) => {
+            hash_shape(&instanced.shape, state);
+            instanced.instances.len().hash(state);
+            for instance in instanced.instances.iter() {
+                hash_pos(instance.offset.to_pos2(), state);
+                instance.color.hash(state);
+            }
+        }
+        Shape::Callback(callback) => {
+            // The cached primitives keep the callback alive, so the pointer stays unique.
+            hash_rect(callback.rect, state);
+            Arc::as_ptr(&callback.callback).cast::<()>().hash(state);
+        }
+    }
+}
+
+fn hash_pos(pos: Pos2, state: &mut impl Hasher) {
+    pos.x.to_bits().hash(state);
+    pos.y.to_bits().hash(state);
+}
+
+fn hash_points(points: &[Pos2], state: &mut impl Hasher) {
+    points.len().hash(state);
+    for &point in points {
+        hash_pos(point, state);
+    }
+}
+
+pub(crate) fn hash_rect(rect: Rect, state: &mut impl Hasher) {
+    hash_pos(rect.min, state);
+    hash_pos(rect.max, state);
+}
+
+fn hash_stroke(stroke: Stroke, state: &mut impl Hasher) {
+    stroke.width.to_bits().hash(state);
+    stroke.color.hash(state);
+}
+
+fn hash_fill_and_stroke(fill: Color32, stroke: Stroke, state: &mut impl Hasher) {
+    fill.hash(state);
+    hash_stroke(stroke, state);
+}
+
+#[test]
+fn test_tessellation_cache() {
+    use crate::{Id, Order};
+    use epaint::{pos2, Primitive};
+
+    let settings = TessellatorSettings {
+        pixels_per_point: 1.0,
+        options: Default::default(),
+        font_tex_size: [1, 1],
+        font_textures: vec![],
+        prepared_discs: vec![],
+    };
+    let background = LayerId::new(Order::Background, Id::new("background"));
+    let window = LayerId::new(Order::Middle, Id::new("window"));
+    let layers = |radius: f32| {
+        let clip_rect = Rect::EVERYTHING;
+        let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 100.0));
+        vec![
+            (
+                background,
+                vec![ClippedShape(
+                    clip_rect,
+                    Shape::rect_filled(rect, 2.0, Color32::GRAY),
+                )],
+            ),
+            (
+                window,
+                vec![ClippedShape(
+                    clip_rect,
+                    Shape::circle_filled(pos2(50.0, 50.0), radius, Color32::RED),
+                )],
+            ),
+        ]
+    };
+    let mesh = |clipped_primitive: &ClippedPrimitive| match &clipped_primitive.primitive {
+        Primitive::Mesh(mesh) => mesh.clone(),
+        Primitive::Instanced(_) | Primitive::Callback(_) => panic!("Expected a mesh"),
+    };
+
+    let mut cache = TessellationCache::default();
+    let (first, num_cached, num_tessellated) =
+        cache.tessellate(settings.clone(), layers(10.0).into_iter());
+    assert_eq!((num_cached, num_tessellated), (0, 2));
+
+    let (second, num_cached, num_tessellated) =
+        cache.tessellate(settings.clone(), layers(10.0).into_iter());
+    assert_eq!((num_cached, num_tessellated), (2, 0));
+    // Reusing a layer doesn't copy its meshes:
+    assert_eq!(first.len(), second.len());
+    for (a, b) in first.iter().zip(&second) {
+        assert!(Arc::ptr_eq(&mesh(a), &mesh(b)));
+    }
+
+    let (third, num_cached, num_tessellated) =
+        cache.tessellate(settings.clone(), layers(20.0).into_iter());
+    assert_eq!(
+        (num_cached, num_tessellated),
+        (1, 1),
+        "Only the window changed"
+    );
+    assert!(Arc::ptr_eq(&mesh(&second[0]), &mesh(&third[0])));
+    assert!(!Arc::ptr_eq(&mesh(&second[1]), &mesh(&third[1])));
+
+    // Same triangles as tessellating everything at once (which puts them all in one mesh):
+    let shapes = layers(20.0)
+        .into_iter()
+        .flat_map(|(_, shapes)| shapes)
+        .collect();
+    let uncached = settings.tessellator().tessellate_shapes(shapes);
+    let num_vertices = |primitives: &[ClippedPrimitive]| -> usize {
+        primitives.iter().map(|p| mesh(p).vertices.len()).sum()
+    };
+    assert_eq!(num_vertices(&third), num_vertices(&uncached));
+
+    // Different settings give different meshes:
+    let settings = TessellatorSettings {
+        pixels_per_point: 2.0,
+        ..settings
+    };
+    let (_, num_cached, num_tessellated) = cache.tessellate(settings, layers(20.0).into_iter());
+    assert_eq!((num_cached, num_tessellated), (0, 2));
+}
@@ -586,7 +586,8 @@ impl Ui {
         self.id.with(&id_source)
     }
 
-    pub(crate) fn next_auto_id(&self) -> Id {
+    /// The [`Id`] that the next widget or child [`Ui`] with an automatic id will get.
+    pub fn next_auto_id(&self) -> Id {
         Id::new(self.next_auto_id_source)
     }
 
@@ -1536,7 +1537,7 @@ impl Ui {
     ///             ui.ctx().load_texture(
     ///                 "my-image",
     ///                 egui::ColorImage::example(),
-    ///                 egui::TextureFilter::Linear
+    ///                 egui::TextureOptions::LINEAR
     ///             )
     ///         });
     ///
@@ -159,7 +159,12 @@ impl Widget for Button {
         }
 
         let (rect, response) = ui.allocate_at_least(desired_size, sense);
-        response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, text.text()));
+        let typ = if ui.menu_state().is_some() {
+            WidgetType::MenuItem
+        } else {
+            WidgetType::Button
+        };
+        response.widget_info(|| WidgetInfo::labeled(typ, text.text()));
 
         if ui.is_rect_visible(rect) {
             let visuals = ui.style().interact(&response);
@@ -216,10 +221,25 @@ impl Widget for Button {
 /// ui.add(egui::Checkbox::new(&mut my_bool, "Checked"));
 /// # });
 /// ```
+///
+/// A "select all" checkbox can use [`Self::indeterminate`] to show that only some items are selected:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// # let mut items = [true, false, true];
+/// let num_selected = items.iter().filter(|&&selected| selected).count();
+/// let mut all_selected = num_selected == items.len();
+/// let mixed = 0 < num_selected && num_selected < items.len();
+/// if ui.add(egui::Checkbox::new(&mut all_selected, "Select all").indeterminate(mixed)).changed() {
+///     items.iter_mut().for_each(|selected| *selected = all_selected);
+/// }
+/// # });
+/// ```
 #[must_use = "You should put this widget in an ui with `ui.add(widget);`"]
 pub struct Checkbox<'a> {
     checked: &'a mut bool,
     text: WidgetText,
+    indeterminate: bool,
 }
 
 impl<'a> Checkbox<'a> {
@@ -227,13 +247,29 @@ impl<'a> Checkbox<'a> {
         Checkbox {
             checked,
             text: text.into(),
+            indeterminate: false,
         }
     }
+
+    /// Show the checkbox in a third, "mixed" state, neither checked nor unchecked.
+    ///
+    /// Clicking it will still toggle the `bool`,
+    /// and it is up to you to stop setting this once the state is no longer mixed.
+    ///
+    /// Default: `false`.
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
 }
 
 impl<'a> Widget for Checkbox<'a> {
     fn ui(self, ui: &mut Ui) -> Response {
-        let Checkbox { checked, text } = self;
+        let Checkbox {
+            checked,
+            text,
+            indeterminate,
+        } = self;
 
         let spacing = &ui.spacing();
         let icon_width = spacing.icon_width;
@@ -262,11 +298,15 @@ impl<'a> Widget for Checkbox<'a> {
             response.mark_changed();
         }
         response.widget_info(|| {
-            WidgetInfo::selected(
-                WidgetType::Checkbox,
-                *checked,
-                text.as_ref().map_or("", |x| x.text()),
-            )
+            let label = text.as_ref().map_or("", |x| x.text());
+            if indeterminate {
+                WidgetInfo {
+                    mixed: true,
+                    ..WidgetInfo::labeled(WidgetType::Checkbox, label)
+                }
+            } else {
+                WidgetInfo::selected(WidgetType::Checkbox, *checked, label)
+            }
         });
 
         if ui.is_rect_visible(rect) {
@@ -280,7 +320,14 @@ impl<'a> Widget for Checkbox<'a> {
                 stroke: visuals.bg_stroke,
             });
 
-            if *checked {
+            if indeterminate {
+                // Horizontal dash:
+                ui.painter().hline(
+                    small_icon_rect.x_range(),
+                    small_icon_rect.center().y,
+                    visuals.fg_stroke,
+                );
+            } else if *checked {
                 // Check mark:
                 ui.painter().add(Shape::line(
                     vec![
@@ -229,7 +229,7 @@ impl<'a> Widget for DragValue<'a> {
                     .font(TextStyle::Monospace),
             );
             if let Ok(parsed_value) = value_text.parse() {
-                let parsed_value = clamp_to_range(parsed_value, clamp_range);
+                let parsed_value = clamp_to_range(parsed_value, clamp_range.clone());
                 set(&mut get_set_value, parsed_value);
             }
             if ui.input().key_pressed(Key::Enter) {
@@ -289,7 +289,7 @@ impl<'a> Widget for DragValue<'a> {
                     );
                     let rounded_new_value =
                         emath::round_to_decimals(rounded_new_value, auto_decimals);
-                    let rounded_new_value = clamp_to_range(rounded_new_value, clamp_range);
+                    let rounded_new_value = clamp_to_range(rounded_new_value, clamp_range.clone());
                     set(&mut get_set_value, rounded_new_value);
 
                     drag_state.last_dragged_id = Some(response.id);
@@ -305,7 +305,7 @@ impl<'a> Widget for DragValue<'a> {
                 if change != 0.0 {
                     let new_value = value + speed * change;
                     let new_value = emath::round_to_decimals(new_value, auto_decimals);
-                    let new_value = clamp_to_range(new_value, clamp_range);
+                    let new_value = clamp_to_range(new_value, clamp_range.clone());
                     set(&mut get_set_value, new_value);
                 }
             }
@@ -313,9 +313,23 @@ impl<'a> Widget for DragValue<'a> {
             response
         };
 
+        let (min, max) = (
+            clamp_range.start().min(*clamp_range.end()),
+            clamp_range.start().max(*clamp_range.end()),
+        );
+        let accessibility_value = ui.input().accessibility_set_value(response.id);
+        if let Some(new_value) = accessibility_value {
+            set(&mut get_set_value, clamp_to_range(new_value, clamp_range));
+        }
+
         response.changed = get(&mut get_set_value) != old_value;
 
-        response.widget_info(|| WidgetInfo::drag_value(value));
+        response.widget_info(|| WidgetInfo {
+            min: Some(min).filter(|min| min.is_finite()),
+            max: Some(max).filter(|max| max.is_finite()),
+            step: Some(speed.abs()),
+            ..WidgetInfo::drag_value(value)
+        });
         response
     }
 }
@@ -18,7 +18,7 @@ use emath::Rot2;
 ///             ui.ctx().load_texture(
 ///                 "my-image",
 ///                 egui::ColorImage::example(),
-///                 egui::TextureFilter::Linear
+///                 egui::TextureOptions::LINEAR
 ///             )
 ///         });
 ///
@@ -136,6 +136,7 @@ impl Widget for Image {
     fn ui(self, ui: &mut Ui) -> Response {
         let (rect, response) = ui.allocate_exact_size(self.size, self.sense);
         self.paint_at(ui, rect);
+        response.widget_info(|| WidgetInfo::new(WidgetType::Image));
         response
     }
 }
@@ -0,0 +1,397 @@
+//! Axes with tick labels and titles, drawn in margins around the data area of a [`super::Plot`].
+
+use std::{f32::consts::FRAC_PI_2, ops::RangeInclusive};
+
+use epaint::TextShape;
+
+use super::{transform::ScreenTransform, AxisFormatter, AxisScale, GridMark, PlotPoint};
+use crate::*;
+
+/// Space between the data area and the tick labels, and between the tick labels and the title.
+const AXIS_GAP: f32 = 4.0;
+
+/// Horizontal placement of a Y axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HPlacement {
+    Left,
+    Right,
+}
+
+/// Vertical placement of an X axis.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VPlacement {
+    Top,
+    Bottom,
+}
+
+/// Which side of the data area an axis is on.
+///
+/// Usually created from a [`HPlacement`] or [`VPlacement`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// Left of the data area for a Y axis, below it for an X axis.
+    LeftBottom,
+
+    /// Right of the data area for a Y axis, above it for an X axis.
+    RightTop,
+}
+
+impl From<HPlacement> for Placement {
+    fn from(placement: HPlacement) -> Self {
+        match placement {
+            HPlacement::Left => Placement::LeftBottom,
+            HPlacement::Right => Placement::RightTop,
+        }
+    }
+}
+
+impl From<VPlacement> for Placement {
+    fn from(placement: VPlacement) -> Self {
+        match placement {
+            VPlacement::Top => Placement::RightTop,
+            VPlacement::Bottom => Placement::LeftBottom,
+        }
+    }
+}
+
+/// Configuration of one axis of a [`super::Plot`]: its title, unit, tick labels, placement and scale.
+///
+/// ```
+/// # use egui::plot::{AxisHints, HPlacement};
+/// let pressure_axis = AxisHints::default()
+///     .label("Pressure")
+///     .unit("hPa")
+///     .placement(HPlacement::Right);
+/// ```
+pub struct AxisHints {
+    pub(super) label: String,
+    pub(super) unit: String,
+    pub(super) formatter: AxisFormatter,
+    pub(super) digits: usize,
+    pub(super) placement: Placement,
+    pub(super) scale: AxisScale,
+}
+
+impl Default for AxisHints {
+    fn default() -> Self {
+        Self {
+            label: String::new(),
+            unit: String::new(),
+            formatter: None,
+            digits: 5,
+            placement: Placement::LeftBottom,
+            scale: AxisScale::Linear,
+        }
+    }
+}
+
+impl AxisHints {
+    /// The title of the axis, shown next to the tick labels.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// The unit of the values on the axis, shown in brackets after the [`Self::label`].
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.unit = unit.into();
+        self
+    }
+
+    /// Provide a function to customize the tick labels based on the current visible value range.
+    ///
+    /// See [`super::Plot::x_axis_formatter`].
+    pub fn formatter(
+        mut self,
+        func: impl Fn(f64, &RangeInclusive<f64>) -> String + 'static,
+    ) -> Self {
+        self.formatter = Some(Box::new(func));
+        self
+    }
+
+    /// How many characters wide the tick labels of a Y axis can get.
+    /// Determines how much space is reserved for the axis.
+    ///
+    /// Default: `5`.
+    pub fn max_digits(mut self, digits: usize) -> Self {
+        self.digits = digits;
+        self
+    }
+
+    /// Which side of the data area the axis is on.
+    ///
+    /// Default: left for Y axes, bottom for X axes.
+    pub fn placement(mut self, placement: impl Into<Placement>) -> Self {
+        self.placement = placement.into();
+        self
+    }
+
+    /// How the values are spaced along the axis, e.g. logarithmically.
+    ///
+    /// Default: [`AxisScale::Linear`].
+    pub fn scale(mut self, scale: AxisScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// The title, including the unit.
+    pub(super) fn title(&self) -> Option<String> {
+        match (self.label.is_empty(), self.unit.is_empty()) {
+            (true, true) => None,
+            (false, true) => Some(self.label.clone()),
+            (true, false) => Some(format!("[{}]", self.unit)),
+            (false, false) => Some(format!("{} [{}]", self.label, self.unit)),
+        }
+    }
+
+    pub(super) fn format_tick(&self, value: f64, range: &RangeInclusive<f64>) -> String {
+        if let Some(formatter) = self.formatter.as_deref() {
+            formatter(value, range)
+        } else {
+            self.scale.format_tick(value, range)
+        }
+    }
+
+    /// How much space the axis needs across its direction (height for X, width for Y).
+    pub(super) fn thickness(&self, ui: &Ui, axis: usize) -> f32 {
+        let tick_font = TextStyle::Body.resolve(ui.style());
+        let title_font = TextStyle::Body.resolve(ui.style());
+        let fonts = ui.fonts();
+        let ticks = if axis == 0 {
+            fonts.row_height(&tick_font)
+        } else {
+            self.digits as f32 * fonts.glyph_width(&tick_font, '0')
+        };
+        let title = if self.title().is_some() {
+            AXIS_GAP + fonts.row_height(&title_font)
+        } else {
+            0.0
+        };
+        AXIS_GAP + ticks + title
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Where the data area and the axes of a plot go.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct AxesLayout {
+    /// The data area, where the items and the grid are painted.
+    pub plot_rect: Rect,
+
+    /// `None` if the X axis is hidden.
+    pub x_axis: Option<Rect>,
+
+    /// One for each Y axis, or empty if the Y axes are hidden.
+    pub y_axes: Vec<Rect>,
+}
+
+impl AxesLayout {
+    /// Reserve margins inside `rect` for the axes with the given placements and thicknesses.
+    ///
+    /// Y axes on the same side are stacked outwards in order.
+    /// If `rect` is too small to fit the axes, no margins are reserved.
+    pub fn new(rect: Rect, x_axis: Option<(Placement, f32)>, y_axes: &[(Placement, f32)]) -> Self {
+        let side = |placement| {
+            y_axes
+                .iter()
+                .filter(|(p, _)| *p == placement)
+                .map(|(_, thickness)| thickness)
+                .sum::<f32>()
+        };
+        let left = side(Placement::LeftBottom);
+        let right = side(Placement::RightTop);
+        let (bottom, top) = match x_axis {
+            Some((Placement::LeftBottom, thickness)) => (thickness, 0.0),
+            Some((Placement::RightTop, thickness)) => (0.0, thickness),
+            None => (0.0, 0.0),
+        };
+
+        let plot_rect = Rect::from_min_max(
+            pos2(rect.left() + left, rect.top() + top),
+            pos2(rect.right() - right, rect.bottom() - bottom),
+        );
+        if !plot_rect.is_positive() {
+            return Self {
+                plot_rect: rect,
+                x_axis: None,
+                y_axes: vec![],
+            };
+        }
+
+        let x_axis = x_axis.map(|(placement, thickness)| {
+            let y_range = match placement {
+                Placement::LeftBottom => plot_rect.bottom()..=plot_rect.bottom() + thickness,
+                Placement::RightTop => plot_rect.top() - thickness..=plot_rect.top(),
+            };
+            Rect::from_x_y_ranges(plot_rect.x_range(), y_range)
+        });
+
+        let mut next_left = plot_rect.left();
+        let mut next_right = plot_rect.right();
+        let y_axes = y_axes
+            .iter()
+            .map(|&(placement, thickness)| {
+                let x_range = match placement {
+                    Placement::LeftBottom => {
+                        next_left -= thickness;
+                        next_left..=next_left + thickness
+                    }
+                    Placement::RightTop => {
+                        next_right += thickness;
+                        next_right - thickness..=next_right
+                    }
+                };
+                Rect::from_x_y_ranges(x_range, plot_rect.y_range())
+            })
+            .collect();
+
+        Self {
+            plot_rect,
+            x_axis,
+            y_axes,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Paints the tick labels and the title of one axis into its margin.
+pub(super) struct AxisWidget<'a> {
+    pub hints: &'a AxisHints,
+
+    /// 0 for the X axis, 1 for the Y axes.
+    pub axis: usize,
+
+    /// The margin reserved for the axis by [`AxesLayout`].
+    pub rect: Rect,
+
+    pub transform: &'a ScreenTransform,
+    pub steps: &'a [GridMark],
+}
+
+impl<'a> AxisWidget<'a> {
+    pub fn paint(&self, ui: &Ui, shapes: &mut Vec<Shape>) {
+        let Self {
+            hints,
+            axis,
+            rect,
+            transform,
+            steps,
+        } = *self;
+
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let bounds = transform.bounds();
+        let axis_range = if axis == 0 {
+            bounds.range_x()
+        } else {
+            bounds.range_y()
+        };
+
+        // Tick labels, next to the data area:
+        for step in steps {
+            let spacing_in_points = transform.mark_spacing(axis, step);
+            let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
+            if text_alpha <= 0.0 {
+                continue;
+            }
+
+            let text = hints.format_tick(step.value, &axis_range);
+            // Custom formatters can return empty string to signal "no label at this resolution"
+            if text.is_empty() {
+                continue;
+            }
+
+            let color = color_from_alpha(ui, text_alpha);
+            let galley = ui.painter().layout_no_wrap(text, font_id.clone(), color);
+            let point = if axis == 0 {
+                PlotPoint::new(step.value, bounds.min[1])
+            } else {
+                PlotPoint::new(bounds.min[0], step.value)
+            };
+            let pos_in_gui = transform.position_from_point(&point);
+
+            let anchor = match (axis, hints.placement) {
+                (0, Placement::LeftBottom) => pos2(pos_in_gui.x, rect.top() + AXIS_GAP),
+                (0, _) => pos2(pos_in_gui.x, rect.bottom() - AXIS_GAP),
+                (_, Placement::LeftBottom) => pos2(rect.right() - AXIS_GAP, pos_in_gui.y),
+                (_, _) => pos2(rect.left() + AXIS_GAP, pos_in_gui.y),
+            };
+            let align = match (axis, hints.placement) {
+                (0, Placement::LeftBottom) => Align2::CENTER_TOP,
+                (0, _) => Align2::CENTER_BOTTOM,
+                (_, Placement::LeftBottom) => Align2::RIGHT_CENTER,
+                (_, _) => Align2::LEFT_CENTER,
+            };
+            let text_rect = align.anchor_rect(Rect::from_min_size(anchor, galley.size()));
+            shapes.push(Shape::galley(text_rect.min, galley));
+        }
+
+        // Title, on the far side of the tick labels:
+        if let Some(title) = hints.title() {
+            let color = ui.visuals().text_color();
+            let galley = ui.painter().layout_no_wrap(title, font_id, color);
+            let size = galley.size();
+            let shape = if axis == 0 {
+                let y = match hints.placement {
+                    Placement::LeftBottom => rect.bottom() - size.y,
+                    Placement::RightTop => rect.top(),
+                };
+                Shape::galley(pos2(rect.center().x - 0.5 * size.x, y), galley)
+            } else {
+                // Rotated to read from bottom to top, so `pos` ends up at the bottom left.
+                let x = match hints.placement {
+                    Placement::LeftBottom => rect.left(),
+                    Placement::RightTop => rect.right() - size.y,
+                };
+                let pos = pos2(x, rect.center().y + 0.5 * size.x);
+                Shape::Text(TextShape {
+                    angle: -FRAC_PI_2,
+                    ..TextShape::new(pos, galley)
+                })
+            };
+            shapes.push(shape);
+        }
+    }
+}
+
+pub(super) fn color_from_alpha(ui: &Ui, alpha: f32) -> Color32 {
+    if ui.visuals().dark_mode {
+        Rgba::from_white_alpha(alpha).into()
+    } else {
+        Rgba::from_black_alpha((4.0 * alpha).at_most(1.0)).into()
+    }
+}
+
+#[test]
+fn test_axes_layout() {
+    let rect = Rect::from_min_size(Pos2::ZERO, vec2(200.0, 100.0));
+    let layout = AxesLayout::new(
+        rect,
+        Some((Placement::LeftBottom, 20.0)),
+        &[
+            (Placement::LeftBottom, 30.0),
+            (Placement::RightTop, 25.0),
+            (Placement::LeftBottom, 10.0),
+        ],
+    );
+    let plot_rect = Rect::from_min_max(pos2(40.0, 0.0), pos2(175.0, 80.0));
+    assert_eq!(layout.plot_rect, plot_rect);
+    assert_eq!(
+        layout.x_axis,
+        Some(Rect::from_min_max(pos2(40.0, 80.0), pos2(175.0, 100.0)))
+    );
+    assert_eq!(
+        layout.y_axes,
+        vec![
+            Rect::from_min_max(pos2(10.0, 0.0), pos2(40.0, 80.0)),
+            Rect::from_min_max(pos2(175.0, 0.0), pos2(200.0, 80.0)),
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 80.0)),
+        ]
+    );
+
+    // Too small for the axes:
+    let layout = AxesLayout::new(rect, None, &[(Placement::RightTop, 300.0)]);
+    assert_eq!(layout.plot_rect, rect);
+    assert!(layout.y_axes.is_empty());
+}
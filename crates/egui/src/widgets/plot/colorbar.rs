@@ -0,0 +1,203 @@
+//! Colorbars explaining the colors of heatmaps and similar plot items.
+
+use std::ops::RangeInclusive;
+
+use epaint::{Colormap, Mesh};
+
+use super::{
+    axis::AxisWidget, log_grid_spacer, transform::ScreenTransform, AxisHints, AxisScale, GridInput,
+    HPlacement, Orientation, PlotBounds, VPlacement, MIN_LINE_SPACING_IN_POINTS,
+};
+use crate::*;
+
+/// Width of the color gradient.
+const BAR_WIDTH: f32 = 12.0;
+
+/// Space between the data area (or the previous colorbar) and the color gradient in a [`Plot`](super::Plot).
+const BAR_GAP: f32 = 8.0;
+
+/// A color gradient with tick labels, explaining which values a [`Colormap`] maps to which colors.
+///
+/// A [`Plot`](super::Plot) shows one right of the data area for each heatmap, contour plot or colored set of points
+/// that asks for it, but it can also be added to any [`Ui`]:
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::{plot::Colorbar, Colormap};
+/// ui.add(Colorbar::new(Colormap::magma(), 0.0..=100.0).label("Temperature").unit("°C"));
+/// # });
+/// ```
+pub struct Colorbar {
+    colormap: Colormap,
+    range: RangeInclusive<f64>,
+    hints: AxisHints,
+    orientation: Orientation,
+    length: f32,
+}
+
+impl Colorbar {
+    /// Explain `colormap`, with the start of `range` at the start of the colormap.
+    pub fn new(colormap: Colormap, range: RangeInclusive<f64>) -> Self {
+        Self {
+            colormap,
+            range,
+            hints: AxisHints::default().placement(HPlacement::Right),
+            orientation: Orientation::Vertical,
+            length: 200.0,
+        }
+    }
+
+    /// Title shown next to the tick labels.
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.hints = self.hints.label(label);
+        self
+    }
+
+    /// Unit shown in brackets after the label.
+    pub fn unit(mut self, unit: impl Into<String>) -> Self {
+        self.hints = self.hints.unit(unit);
+        self
+    }
+
+    /// Custom tick label formatter, like [`AxisHints::formatter`].
+    pub fn formatter(mut self, fmt: fn(f64, &RangeInclusive<f64>) -> String) -> Self {
+        self.hints = self.hints.formatter(fmt);
+        self
+    }
+
+    /// Whether the gradient goes from bottom to top ([`Orientation::Vertical`], the default),
+    /// or from left to right with the tick labels below it ([`Orientation::Horizontal`]).
+    ///
+    /// Inside a [`Plot`](super::Plot) colorbars are always vertical.
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self.hints = match orientation {
+            Orientation::Horizontal => self.hints.placement(VPlacement::Bottom),
+            Orientation::Vertical => self.hints.placement(HPlacement::Right),
+        };
+        self
+    }
+
+    /// Length of the gradient when added to a [`Ui`]. Default: `200.0`.
+    pub fn length(mut self, length: f32) -> Self {
+        self.length = length;
+        self
+    }
+
+    fn axis(&self) -> usize {
+        match self.orientation {
+            Orientation::Horizontal => 0,
+            Orientation::Vertical => 1,
+        }
+    }
+
+    /// Size across the gradient, including the tick labels and title.
+    fn thickness(&self, ui: &Ui) -> f32 {
+        BAR_WIDTH + self.hints.thickness(ui, self.axis())
+    }
+
+    /// How much space the colorbar needs right of the data area of a plot.
+    pub(super) fn margin_thickness(&self, ui: &Ui) -> f32 {
+        BAR_GAP + self.thickness(ui)
+    }
+
+    /// Paint the colorbar into the margin reserved for it right of the data area of a plot.
+    pub(super) fn paint_in_margin(&self, ui: &Ui, rect: Rect, shapes: &mut Vec<Shape>) {
+        let rect = Rect::from_x_y_ranges(rect.left() + BAR_GAP..=rect.right(), rect.y_range());
+        self.paint(ui, rect, shapes);
+    }
+
+    fn paint(&self, ui: &Ui, rect: Rect, shapes: &mut Vec<Shape>) {
+        let axis = self.axis();
+        let (bar_rect, axis_rect) = if axis == 0 {
+            let y = rect.top() + BAR_WIDTH;
+            (
+                Rect::from_x_y_ranges(rect.x_range(), rect.top()..=y),
+                Rect::from_x_y_ranges(rect.x_range(), y..=rect.bottom()),
+            )
+        } else {
+            let x = rect.left() + BAR_WIDTH;
+            (
+                Rect::from_x_y_ranges(rect.left()..=x, rect.y_range()),
+                Rect::from_x_y_ranges(x..=rect.right(), rect.y_range()),
+            )
+        };
+
+        // The gradient, from the start of the range at the bottom or left to the end at the top or right:
+        let mut mesh = Mesh::default();
+        let num_steps = 64;
+        for i in 0..=num_steps {
+            let t = i as f32 / num_steps as f32;
+            let color = self.colormap.sample(t);
+            let (a, b) = if axis == 0 {
+                let x = lerp(bar_rect.x_range(), t);
+                (pos2(x, bar_rect.top()), pos2(x, bar_rect.bottom()))
+            } else {
+                let y = lerp(bar_rect.bottom()..=bar_rect.top(), t);
+                (pos2(bar_rect.left(), y), pos2(bar_rect.right(), y))
+            };
+            mesh.colored_vertex(a, color);
+            mesh.colored_vertex(b, color);
+            if i > 0 {
+                let i = 2 * i as u32;
+                mesh.add_triangle(i - 2, i - 1, i);
+                mesh.add_triangle(i - 1, i, i + 1);
+            }
+        }
+        shapes.push(Shape::mesh(mesh));
+        shapes.push(Shape::rect_stroke(
+            bar_rect,
+            0.0,
+            ui.visuals().widgets.noninteractive.bg_stroke,
+        ));
+
+        // The tick labels and title, like an axis next to the gradient:
+        let (min, max) = (*self.range.start(), *self.range.end());
+        let is_valid = min.is_finite() && max.is_finite() && min < max;
+        if !is_valid {
+            return;
+        }
+        let bounds = if axis == 0 {
+            PlotBounds {
+                min: [min, 0.0],
+                max: [max, 1.0],
+            }
+        } else {
+            PlotBounds {
+                min: [0.0, min],
+                max: [1.0, max],
+            }
+        };
+        let transform =
+            ScreenTransform::new(bar_rect, bounds, [AxisScale::Linear; 2], false, false);
+        let steps = log_grid_spacer(10)(GridInput {
+            bounds: (min, max),
+            base_step_size: transform.dvalue_dpos()[axis].abs() * MIN_LINE_SPACING_IN_POINTS,
+        });
+        let axis = AxisWidget {
+            hints: &self.hints,
+            axis,
+            rect: axis_rect,
+            transform: &transform,
+            steps: &steps,
+        };
+        axis.paint(ui, shapes);
+    }
+}
+
+impl Widget for Colorbar {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let thickness = self.thickness(ui);
+        let size = match self.orientation {
+            Orientation::Horizontal => vec2(self.length, thickness),
+            Orientation::Vertical => vec2(thickness, self.length),
+        };
+        let (rect, response) = ui.allocate_exact_size(size, Sense::hover());
+        if ui.is_rect_visible(rect) {
+            let mut shapes = Vec::new();
+            self.paint(ui, rect, &mut shapes);
+            ui.painter().extend(shapes);
+        }
+        response
+    }
+}
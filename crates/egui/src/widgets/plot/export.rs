@@ -0,0 +1,323 @@
+//! Rendering plots off-screen, to save them as SVG or PNG.
+
+use epaint::{ahash::HashMap, ClippedPrimitive, ClippedShape, ColorImage, ImageData, TextureId};
+
+use super::{Plot, PlotMemory, PlotUi};
+use crate::*;
+
+/// A file format a [`Plot`] can be exported to.
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Vector graphics, see [`ExportedPlot::to_svg`].
+    Svg,
+
+    /// A raster image, see [`ExportedPlot::to_image`].
+    Png,
+}
+
+/// The size and resolution of an exported plot, see [`Plot::export`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExportOptions {
+    /// Size of the whole plot, including axes and legend, in points.
+    pub size: Vec2,
+
+    /// Pixels per inch, where `96` means one pixel per point. Only affects raster images.
+    pub dpi: f32,
+
+    /// Color painted behind the plot. Default: the window fill of the current style.
+    pub background: Option<Color32>,
+}
+
+impl ExportOptions {
+    /// Export at `size` points, with one pixel per point.
+    pub fn new(size: Vec2) -> Self {
+        Self {
+            size,
+            dpi: 96.0,
+            background: None,
+        }
+    }
+
+    /// Pixels per inch, where `96` means one pixel per point.
+    pub fn dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Color painted behind the plot. Use [`Color32::TRANSPARENT`] for no background.
+    pub fn background(mut self, background: Color32) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub fn pixels_per_point(&self) -> f32 {
+        self.dpi / 96.0
+    }
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self::new(vec2(640.0, 480.0))
+    }
+}
+
+/// A [`Plot`] rendered off-screen by [`Plot::export`], ready to be saved as SVG or as an image.
+pub struct ExportedPlot {
+    ctx: Context,
+    shapes: Vec<ClippedShape>,
+    textures: HashMap<TextureId, ImageData>,
+    rect: Rect,
+    pixels_per_point: f32,
+}
+
+impl ExportedPlot {
+    /// Provide the contents of a texture used by the plot, e.g. by a [`super::PlotImage`].
+    ///
+    /// egui only keeps the textures it creates itself (for text),
+    /// so any other texture is missing from the export until given here.
+    pub fn texture(mut self, id: TextureId, image: impl Into<ImageData>) -> Self {
+        self.textures.insert(id, image.into());
+        self
+    }
+
+    /// The textures used by the plot which are neither egui's own nor given with [`Self::texture`].
+    ///
+    /// Meshes using them are exported with their vertex colors only.
+    pub fn missing_textures(&self) -> Vec<TextureId> {
+        fn visit(
+            shape: &Shape,
+            textures: &HashMap<TextureId, ImageData>,
+            out: &mut Vec<TextureId>,
+        ) {
+            if let Shape::Vec(shapes) = shape {
+                for shape in shapes {
+                    visit(shape, textures, out);
+                }
+            } else {
+                let id = shape.texture_id();
+                if !textures.contains_key(&id) && !out.contains(&id) {
+                    out.push(id);
+                }
+            }
+        }
+
+        let mut missing = vec![];
+        for ClippedShape(_, shape) in &self.shapes {
+            visit(shape, &self.textures, &mut missing);
+        }
+        missing
+    }
+
+    fn warn_about_missing_textures(&self) {
+        #[cfg(feature = "tracing")]
+        {
+            let missing = self.missing_textures();
+            if !missing.is_empty() {
+                tracing::warn!(
+                    "Exporting a plot without the textures {:?}; see ExportedPlot::texture",
+                    missing
+                );
+            }
+        }
+    }
+
+    /// The shapes making up the plot, in points, with the top left corner of the plot at the origin.
+    pub fn shapes(&self) -> &[ClippedShape] {
+        &self.shapes
+    }
+
+    /// Size of the exported image in pixels.
+    pub fn size_in_pixels(&self) -> [usize; 2] {
+        let size = self.rect.size() * self.pixels_per_point;
+        [size.x.round() as usize, size.y.round() as usize]
+    }
+
+    /// The plot as an SVG document, measured in points.
+    ///
+    /// Textured meshes, like images, are embedded as PNG images at the requested DPI.
+    pub fn to_svg(&self) -> String {
+        self.warn_about_missing_textures();
+        epaint::svg::shapes_to_svg(
+            &self.shapes,
+            self.rect,
+            self.pixels_per_point,
+            &self.textures,
+        )
+    }
+
+    /// The plot as an image, painted on the CPU at the requested DPI.
+    ///
+    /// To save it as a PNG, see `egui_extras::image::encode_png`.
+    pub fn to_image(&self) -> ColorImage {
+        self.warn_about_missing_textures();
+        let primitives: Vec<ClippedPrimitive> = self.ctx.tessellate(self.shapes.clone());
+        epaint::raster::rasterize(
+            &primitives,
+            &self.textures,
+            self.size_in_pixels(),
+            self.pixels_per_point,
+            Color32::TRANSPARENT,
+        )
+    }
+}
+
+impl Plot {
+    /// Render the plot off-screen, independent of how it is shown in `ui`, so it can be saved as SVG or PNG.
+    ///
+    /// The plot uses the style, fonts and current view (zoom, pan, hidden items) of the plot with the same id in `ui`.
+    /// Width, height and aspect settings are replaced by the size in `options`.
+    ///
+    /// Textures not created by egui, like those of [`super::PlotImage`]s, must be given to [`ExportedPlot::texture`].
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{ExportFormat, ExportOptions, Line, Plot, PlotPoints};
+    /// let plot = || Plot::new("my_plot").export_menu(true);
+    /// let build = |plot_ui: &mut egui::plot::PlotUi<'_>| {
+    ///     plot_ui.line(Line::new(PlotPoints::from_explicit_callback(f64::sin, .., 100)));
+    ///     plot_ui.export_request()
+    /// };
+    /// let request = plot().show(ui, build).inner;
+    ///
+    /// // Usually only done when `request` is `Some`:
+    /// let exported = plot().export(ui, &ExportOptions::default().dpi(192.0), build);
+    /// if request == Some(ExportFormat::Svg) {
+    ///     let svg: String = exported.to_svg();
+    /// }
+    /// let image: egui::ColorImage = exported.to_image();
+    /// assert_eq!(image.size, [1280, 960]);
+    /// # });
+    /// ```
+    pub fn export<'a, R>(
+        self,
+        ui: &Ui,
+        options: &ExportOptions,
+        build_fn: impl FnOnce(&mut PlotUi<'a>) -> R + 'a,
+    ) -> ExportedPlot {
+        let plot_id = ui.make_persistent_id(self.id_source);
+        let rect = Rect::from_min_size(Pos2::ZERO, options.size);
+        let pixels_per_point = options.pixels_per_point();
+        let background = options
+            .background
+            .unwrap_or_else(|| ui.visuals().window_fill());
+
+        let ctx = Context::default();
+        // The font textures of `ctx` must not get the id of a texture shown in the plot:
+        ctx.tex_manager()
+            .write()
+            .continue_ids_of(&ui.ctx().tex_manager().read());
+        ctx.set_style(ui.ctx().style());
+        ctx.set_fonts(ui.ctx().fonts().lock().fonts.definitions().clone());
+        if let Some(memory) = PlotMemory::load(ui.ctx(), plot_id) {
+            memory.store(&ctx, plot_id);
+        }
+
+        let plot = Self {
+            width: Some(options.size.x),
+            height: Some(options.size.y),
+            min_size: Vec2::ZERO,
+            view_aspect: None,
+            linked_axes: None,
+            export_menu: false,
+            ..self
+        };
+        let raw_input = RawInput {
+            screen_rect: Some(rect),
+            pixels_per_point: Some(pixels_per_point),
+            ..Default::default()
+        };
+        let output = ctx.run(raw_input, |ctx| {
+            let mut ui = Ui::new(ctx.clone(), LayerId::background(), plot_id, rect, rect);
+            ui.painter().rect_filled(rect, 0.0, background);
+            plot.show_dyn(&mut ui, plot_id, Box::new(build_fn));
+        });
+
+        let mut textures: HashMap<TextureId, ImageData> = HashMap::default();
+        for (id, delta) in output.textures_delta.set {
+            match delta.pos {
+                None => {
+                    textures.insert(id, delta.image);
+                }
+                Some(pos) => match (textures.get_mut(&id), delta.image) {
+                    (Some(ImageData::Color(texture)), ImageData::Color(image)) => {
+                        patch(
+                            &mut texture.pixels,
+                            texture.size,
+                            &image.pixels,
+                            image.size,
+                            pos,
+                        );
+                    }
+                    (Some(ImageData::Font(texture)), ImageData::Font(image))
+                    | (Some(ImageData::Sdf(texture)), ImageData::Sdf(image)) => {
+                        patch(
+                            &mut texture.pixels,
+                            texture.size,
+                            &image.pixels,
+                            image.size,
+                            pos,
+                        );
+                    }
+                    _ => {}
+                },
+            }
+        }
+
+        ExportedPlot {
+            ctx,
+            shapes: output.shapes,
+            textures,
+            rect,
+            pixels_per_point,
+        }
+    }
+}
+
+/// Copy the pixels of an image of `size` into a texture of `texture_size`, at `[x, y]`.
+fn patch<T: Copy>(
+    texture: &mut [T],
+    texture_size: [usize; 2],
+    image: &[T],
+    size: [usize; 2],
+    [x, y]: [usize; 2],
+) {
+    for row in 0..size[1] {
+        let dst = (y + row) * texture_size[0] + x;
+        let src = row * size[0];
+        texture[dst..dst + size[0]].copy_from_slice(&image[src..src + size[0]]);
+    }
+}
+
+#[test]
+fn test_export_texture() {
+    use super::{PlotImage, PlotPoint};
+
+    let ctx = Context::default();
+    let red = ColorImage::new([2, 2], Color32::RED);
+    let texture = ctx.load_texture("red", red.clone(), Default::default());
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let exported = Plot::new("plot").include_x(-2.0).include_x(2.0).export(
+                ui,
+                &ExportOptions::new(vec2(100.0, 100.0)),
+                |plot_ui| {
+                    plot_ui.image(PlotImage::new(
+                        texture.id(),
+                        PlotPoint::new(0.0, 0.0),
+                        vec2(1.0, 1.0),
+                    ));
+                },
+            );
+            // The textures of the export don't take the id of ours:
+            assert!(!exported.textures.contains_key(&texture.id()));
+            assert_eq!(exported.missing_textures(), vec![texture.id()]);
+            assert!(!exported.to_image().pixels.contains(&Color32::RED));
+
+            let exported = exported.texture(texture.id(), red);
+            assert!(exported.missing_textures().is_empty());
+            assert!(exported.to_image().pixels.contains(&Color32::RED));
+            assert!(exported.to_svg().contains("<image"));
+        });
+    });
+}
@@ -0,0 +1,385 @@
+use std::ops::RangeInclusive;
+
+use epaint::util::FloatOrd;
+use epaint::Mesh;
+
+use super::{bounds_hover, ClosestElem, PlotConfig, PlotGeometry, PlotItem, PlotPoint, PlotPoints};
+use crate::plot::{LabelFormatter, PlotBounds, ScreenTransform};
+use crate::*;
+
+/// The filled area between two series, e.g. a confidence interval around a [`Line`](super::Line).
+///
+/// The points of both series are paired up by index, so they should be sampled at the same X values.
+/// If one series is longer, its extra points are ignored.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Band, Line, Plot, PlotPoints};
+/// let mean = |x: f64| x.sin();
+/// let spread = |x: f64| 0.2 + 0.1 * x.cos();
+/// Plot::new("confidence").show(ui, |plot_ui| {
+///     plot_ui.band(
+///         Band::new(
+///             PlotPoints::from_explicit_callback(move |x| mean(x) - spread(x), 0.0..=6.0, 100),
+///             PlotPoints::from_explicit_callback(move |x| mean(x) + spread(x), 0.0..=6.0, 100),
+///         )
+///         .name("95% interval"),
+///     );
+///     plot_ui.line(Line::new(PlotPoints::from_explicit_callback(mean, 0.0..=6.0, 100)).name("Mean"));
+/// });
+/// # });
+/// ```
+pub struct Band {
+    pub(crate) lower: PlotPoints,
+    pub(crate) upper: PlotPoints,
+    pub(crate) stroke: Stroke,
+    pub(crate) fill_alpha: f32,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+    /// Whether this is a layer of a [`StackedArea`], which only has an upper edge
+    /// and shows its height when hovered.
+    pub(crate) stacked: bool,
+}
+
+impl Band {
+    /// The area between `lower` and `upper`.
+    pub fn new(lower: impl Into<PlotPoints>, upper: impl Into<PlotPoints>) -> Self {
+        Self {
+            lower: lower.into(),
+            upper: upper.into(),
+            stroke: Stroke::new(0.0, Color32::TRANSPARENT),
+            fill_alpha: 0.25,
+            name: Default::default(),
+            highlight: false,
+            stacked: false,
+        }
+    }
+
+    /// Highlight the band in the plot by filling it more strongly.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Stroke of the lower and upper edges. Default: no edges.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Width of the lower and upper edges. Default: `0.0`, i.e. no edges.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Color of the band. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Opacity of the fill, from `0.0` to `1.0`. Default: `0.25`.
+    pub fn fill_alpha(mut self, fill_alpha: f32) -> Self {
+        self.fill_alpha = fill_alpha;
+        self
+    }
+
+    /// Name of the band.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    fn edges(&self) -> (&[PlotPoint], &[PlotPoint]) {
+        let (lower, upper) = (self.lower.points(), self.upper.points());
+        let len = lower.len().min(upper.len());
+        (&lower[..len], &upper[..len])
+    }
+}
+
+impl PlotItem for Band {
+    fn shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let (lower, upper) = self.edges();
+        if lower.len() < 2 {
+            return;
+        }
+
+        let mut fill_alpha = self.fill_alpha;
+        if self.highlight {
+            fill_alpha = (2.0 * fill_alpha).at_most(1.0);
+        }
+        let fill_color = Rgba::from(self.stroke.color)
+            .to_opaque()
+            .multiply(fill_alpha)
+            .into();
+
+        let lower_tf: Vec<Pos2> = lower
+            .iter()
+            .map(|point| transform.position_from_point(point))
+            .collect();
+        let upper_tf: Vec<Pos2> = upper
+            .iter()
+            .map(|point| transform.position_from_point(point))
+            .collect();
+
+        // One quad between each pair of consecutive points, which also works for bands that aren't convex:
+        let mut mesh = Mesh::default();
+        mesh.reserve_vertices(2 * lower_tf.len());
+        mesh.reserve_triangles(2 * (lower_tf.len() - 1));
+        for (lower, upper) in lower_tf.iter().zip(&upper_tf) {
+            mesh.colored_vertex(*lower, fill_color);
+            mesh.colored_vertex(*upper, fill_color);
+        }
+        for i in 0..lower_tf.len() as u32 - 1 {
+            mesh.add_triangle(2 * i, 2 * i + 1, 2 * i + 2);
+            mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
+        }
+        shapes.push(Shape::Mesh(mesh));
+
+        if self.stroke.width > 0.0 {
+            let mut stroke = self.stroke;
+            if self.highlight {
+                stroke.width *= 2.0;
+            }
+            if !self.stacked {
+                shapes.push(Shape::line(lower_tf, stroke));
+            }
+            shapes.push(Shape::line(upper_tf, stroke));
+        }
+    }
+
+    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
+        self.lower.generate_points(x_range.clone());
+        self.upper.generate_points(x_range);
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = self.lower.bounds();
+        bounds.merge(&self.upper.bounds());
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        let (lower, upper) = self.edges();
+        let value = transform.value_from_position(point);
+
+        // Like bars, the pointer is as close as it gets when it is inside the band:
+        for (i, (lower, upper)) in lower.windows(2).zip(upper.windows(2)).enumerate() {
+            let (x0, x1) = (lower[0].x, lower[1].x);
+            let is_within_x = x0.min(x1) <= value.x && value.x <= x0.max(x1);
+            if !is_within_x || x0 == x1 {
+                continue;
+            }
+            let t = (value.x - x0) / (x1 - x0);
+            let y_lower = lower[0].y + t * (lower[1].y - lower[0].y);
+            let y_upper = upper[0].y + t * (upper[1].y - upper[0].y);
+            if y_lower.min(y_upper) <= value.y && value.y <= y_lower.max(y_upper) {
+                let index = if t < 0.5 { i } else { i + 1 };
+                return Some(ClosestElem {
+                    index,
+                    dist_sq: 0.0,
+                });
+            }
+        }
+
+        lower
+            .iter()
+            .zip(upper)
+            .enumerate()
+            .flat_map(|(index, (lower, upper))| {
+                [lower, upper].map(|value| {
+                    let pos = transform.position_from_point(value);
+                    ClosestElem {
+                        index,
+                        dist_sq: point.distance_sq(pos),
+                    }
+                })
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        let (lower, upper) = self.edges();
+        let (lower, upper) = (lower[elem.index], upper[elem.index]);
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.extend_with(&PlotPoint::new(lower.x, lower.y));
+        bounds.extend_with_y(upper.y);
+
+        let name = if self.stacked {
+            let height = emath::round_to_decimals(upper.y - lower.y, 6);
+            if self.name.is_empty() {
+                height.to_string()
+            } else {
+                format!("{}: {}", self.name, height)
+            }
+        } else {
+            self.name.clone()
+        };
+        let center = PlotPoint::new(lower.x, (lower.y + upper.y) / 2.0);
+        bounds_hover(center, bounds, false, &name, plot, shapes, label_formatter);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Series stacked on top of each other, each filling the area between itself and the one below.
+///
+/// Each layer is shown as a separate [`Band`] with its own legend entry.
+/// Hovering a layer shows its own value as well as the stacked bounds.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Legend, Plot, StackedArea};
+/// let years = vec![2019.0, 2020.0, 2021.0, 2022.0];
+/// Plot::new("energy").legend(Legend::default()).show(ui, |plot_ui| {
+///     plot_ui.stacked_area(
+///         StackedArea::new(years)
+///             .layer("Solar", vec![1.0, 1.5, 2.5, 3.5])
+///             .layer("Wind", vec![2.0, 2.5, 3.0, 3.2])
+///             .layer("Hydro", vec![4.0, 4.1, 3.9, 4.0]),
+///     );
+/// });
+/// # });
+/// ```
+pub struct StackedArea {
+    pub(crate) xs: Vec<f64>,
+    pub(crate) layers: Vec<(String, Vec<f64>, Color32)>,
+    pub(crate) baseline: f64,
+    pub(crate) fill_alpha: f32,
+    pub(crate) width: f32,
+}
+
+impl StackedArea {
+    /// Layers sampled at the given X values.
+    pub fn new(xs: impl Into<Vec<f64>>) -> Self {
+        Self {
+            xs: xs.into(),
+            layers: Vec::new(),
+            baseline: 0.0,
+            fill_alpha: 0.6,
+            width: 1.0,
+        }
+    }
+
+    /// Add a layer on top of the previous ones, with one value per X value.
+    ///
+    /// Missing values count as zero.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn layer(self, name: impl ToString, values: impl Into<Vec<f64>>) -> Self {
+        self.layer_colored(name, values, Color32::TRANSPARENT)
+    }
+
+    /// Like [`Self::layer`], with a color instead of an automatically assigned one.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn layer_colored(
+        mut self,
+        name: impl ToString,
+        values: impl Into<Vec<f64>>,
+        color: impl Into<Color32>,
+    ) -> Self {
+        self.layers
+            .push((name.to_string(), values.into(), color.into()));
+        self
+    }
+
+    /// The value the lowest layer is stacked on. Default: `0.0`.
+    pub fn baseline(mut self, baseline: f64) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Opacity of the fill of each layer, from `0.0` to `1.0`. Default: `0.6`.
+    pub fn fill_alpha(mut self, fill_alpha: f32) -> Self {
+        self.fill_alpha = fill_alpha;
+        self
+    }
+
+    /// Width of the line on top of each layer. Use `0.0` for none. Default: `1.0`.
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// One band per layer, from bottom to top.
+    pub(crate) fn into_bands(self) -> Vec<Band> {
+        let Self {
+            xs,
+            layers,
+            baseline,
+            fill_alpha,
+            width,
+        } = self;
+
+        let mut below: Vec<f64> = vec![baseline; xs.len()];
+        layers
+            .into_iter()
+            .map(|(name, values, color)| {
+                let above: Vec<f64> = below
+                    .iter()
+                    .enumerate()
+                    .map(|(i, below)| below + values.get(i).copied().unwrap_or(0.0))
+                    .collect();
+                let to_points = |ys: &[f64]| -> PlotPoints {
+                    xs.iter().zip(ys).map(|(&x, &y)| [x, y]).collect()
+                };
+                let mut band = Band::new(to_points(&below), to_points(&above))
+                    .name(name)
+                    .color(color)
+                    .width(width)
+                    .fill_alpha(fill_alpha);
+                band.stacked = true;
+                below = above;
+                band
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_stacked_area() {
+    let bands = StackedArea::new(vec![0.0, 1.0, 2.0])
+        .baseline(1.0)
+        .layer("a", vec![1.0, 2.0, 3.0])
+        .layer("b", vec![0.5, 0.5])
+        .into_bands();
+    assert_eq!(bands.len(), 2);
+
+    let tops = |band: &Band| -> Vec<f64> { band.upper.points().iter().map(|p| p.y).collect() };
+    assert_eq!(tops(&bands[0]), vec![2.0, 3.0, 4.0]);
+    assert_eq!(tops(&bands[1]), vec![2.5, 3.5, 4.0]);
+    assert_eq!(bands[1].lower.points()[2].y, 4.0);
+    assert_eq!(bands[1].name, "b");
+}
@@ -0,0 +1,404 @@
+use std::ops::RangeInclusive;
+
+use epaint::{util::FloatOrd, Colormap};
+
+use super::{scalar_hover, ClosestElem, PlotConfig, PlotGeometry, PlotItem, PlotPoint};
+use crate::plot::{Colorbar, LabelFormatter, PlotBounds, ScreenTransform};
+use crate::*;
+
+/// Contour lines through a grid of values, connecting the places where the values cross given levels.
+///
+/// The value at column `x` and row `y` is `values[y * cols + x]`, located at
+/// `origin + (x * spacing_x, y * spacing_y)`. Row 0 is at the bottom.
+///
+/// Each level gets its color from a [`Colormap`], unless a single [`Self::color`] is set.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Contour, Plot};
+/// let values: Vec<f64> = (0..50 * 50)
+///     .map(|i| {
+///         let (x, y) = ((i % 50) as f64 / 10.0 - 2.5, (i / 50) as f64 / 10.0 - 2.5);
+///         (-(x * x + y * y)).exp()
+///     })
+///     .collect();
+/// Plot::new("contour").show(ui, |plot_ui| plot_ui.contour(Contour::new(values, 50).levels(5)));
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contour {
+    pub(crate) values: Vec<f64>,
+    pub(crate) cols: usize,
+    pub(crate) rows: usize,
+    pub(crate) origin: PlotPoint,
+    pub(crate) spacing: [f64; 2],
+    pub(crate) levels: Vec<f64>,
+    pub(crate) num_levels: usize,
+    pub(crate) colormap: Colormap,
+    pub(crate) stroke: Stroke,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+    pub(crate) show_colorbar: bool,
+
+    /// The contour line segments and the index of their level, computed in `initialize`.
+    segments: Vec<(usize, [PlotPoint; 2])>,
+}
+
+impl Contour {
+    /// `values` is split into rows of `cols` values each.
+    /// If the last row is incomplete, it is ignored.
+    pub fn new(values: impl Into<Vec<f64>>, cols: usize) -> Self {
+        let mut values = values.into();
+        let rows = values.len().checked_div(cols).unwrap_or(0);
+        values.truncate(rows * cols);
+        Self {
+            values,
+            cols,
+            rows,
+            origin: PlotPoint::new(0.0, 0.0),
+            spacing: [1.0, 1.0],
+            levels: vec![],
+            num_levels: 10,
+            colormap: Colormap::default(),
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            highlight: false,
+            show_colorbar: true,
+            segments: vec![],
+        }
+    }
+
+    /// Where the first value is. Default: the origin.
+    pub fn origin(mut self, origin: PlotPoint) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// The distance between neighboring values in plot coordinates. Default: `1.0` by `1.0`.
+    pub fn spacing(mut self, x: f64, y: f64) -> Self {
+        self.spacing = [x, y];
+        self
+    }
+
+    /// Draw this many levels, evenly spaced between the smallest and the largest value. Default: 10.
+    pub fn levels(mut self, num_levels: usize) -> Self {
+        self.num_levels = num_levels;
+        self.levels.clear();
+        self
+    }
+
+    /// Draw lines at exactly these levels.
+    pub fn at_levels(mut self, levels: impl Into<Vec<f64>>) -> Self {
+        self.levels = levels.into();
+        self
+    }
+
+    /// The colors of the levels, from the lowest to the highest. Default: [`Colormap::viridis`].
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Draw all levels in this color instead of using the colormap.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Stroke width of the lines.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Show a colorbar right of the plot, explaining the colors of the levels. Default: `true`.
+    ///
+    /// Not shown if a single [`Self::color`] is used.
+    pub fn show_colorbar(mut self, show: bool) -> Self {
+        self.show_colorbar = show;
+        self
+    }
+
+    /// Highlight the contour lines in the plot by scaling them up.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Name of this contour plot.
+    ///
+    /// This name will show up in the plot legend and as the title of the colorbar.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    fn value_range(&self) -> Option<(f64, f64)> {
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, -f64::INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        (min < max).then(|| (min, max))
+    }
+
+    fn resolved_levels(&self) -> Vec<f64> {
+        if !self.levels.is_empty() {
+            return self.levels.clone();
+        }
+        match self.value_range() {
+            Some((min, max)) => {
+                let step = (max - min) / (self.num_levels + 1) as f64;
+                (1..=self.num_levels)
+                    .map(|i| min + i as f64 * step)
+                    .collect()
+            }
+            None => vec![],
+        }
+    }
+
+    /// The range of levels shown on the colorbar.
+    fn level_range(&self, levels: &[f64]) -> RangeInclusive<f64> {
+        let (min, max) = levels
+            .iter()
+            .fold((f64::INFINITY, -f64::INFINITY), |(min, max), &level| {
+                (min.min(level), max.max(level))
+            });
+        min..=max
+    }
+
+    fn level_color(&self, levels: &[f64], level: f64) -> Color32 {
+        if self.stroke.color != Color32::TRANSPARENT {
+            return self.stroke.color;
+        }
+        self.colormap.sample_range(level, self.level_range(levels))
+    }
+
+    fn point(&self, col: usize, row: usize) -> PlotPoint {
+        PlotPoint::new(
+            self.origin.x + col as f64 * self.spacing[0],
+            self.origin.y + row as f64 * self.spacing[1],
+        )
+    }
+}
+
+impl PlotItem for Contour {
+    fn shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let levels = self.resolved_levels();
+        let mut stroke = self.stroke;
+        if self.highlight {
+            stroke.width *= 2.0;
+        }
+        for (level, [a, b]) in &self.segments {
+            stroke.color = self.level_color(&levels, levels[*level]);
+            let points = [
+                transform.position_from_point(a),
+                transform.position_from_point(b),
+            ];
+            shapes.push(Shape::line_segment(points, stroke));
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {
+        let levels = self.resolved_levels();
+        self.segments.clear();
+        if self.cols < 2 || self.rows < 2 {
+            return;
+        }
+        for row in 0..self.rows - 1 {
+            for col in 0..self.cols - 1 {
+                let corners = [
+                    (col, row),
+                    (col + 1, row),
+                    (col + 1, row + 1),
+                    (col, row + 1),
+                ]
+                .map(|(col, row)| (self.point(col, row), self.values[row * self.cols + col]));
+                for (level_index, &level) in levels.iter().enumerate() {
+                    for segment in marching_square(corners, level) {
+                        self.segments.push((level_index, segment));
+                    }
+                }
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        if self.stroke.color == Color32::TRANSPARENT {
+            self.colormap.sample(0.5)
+        } else {
+            self.stroke.color
+        }
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        if !self.values.is_empty() {
+            bounds.extend_with(&self.point(0, 0));
+            bounds.extend_with(&self.point(self.cols - 1, self.rows - 1));
+        }
+        bounds
+    }
+
+    fn colorbar(&self) -> Option<Colorbar> {
+        let levels = self.resolved_levels();
+        let uses_colormap = self.stroke.color == Color32::TRANSPARENT;
+        (self.show_colorbar && uses_colormap && !levels.is_empty()).then(|| {
+            Colorbar::new(self.colormap.clone(), self.level_range(&levels)).label(&self.name)
+        })
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        self.segments
+            .iter()
+            .enumerate()
+            .map(|(index, (_, [a, b]))| {
+                let a = transform.position_from_point(a);
+                let b = transform.position_from_point(b);
+                let dist_sq = point.distance_sq(closest_on_segment(point, a, b));
+                ClosestElem { index, dist_sq }
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        let (level, [a, b]) = &self.segments[elem.index];
+        let levels = self.resolved_levels();
+
+        // Highlight the whole level:
+        let stroke = Stroke::new(
+            self.stroke.width * 2.0,
+            self.level_color(&levels, levels[*level]),
+        );
+        for (_, [a, b]) in self.segments.iter().filter(|(l, _)| l == level) {
+            let points = [
+                plot.transform.position_from_point(a),
+                plot.transform.position_from_point(b),
+            ];
+            shapes.push(Shape::line_segment(points, stroke));
+        }
+
+        let pointer = plot.ui.input().pointer.hover_pos().unwrap_or_default();
+        let closest = closest_on_segment(
+            pointer,
+            plot.transform.position_from_point(a),
+            plot.transform.position_from_point(b),
+        );
+        let value = plot.transform.value_from_position(closest);
+        scalar_hover(
+            value,
+            levels[*level],
+            &self.name,
+            plot,
+            shapes,
+            label_formatter,
+        );
+    }
+}
+
+fn closest_on_segment(point: Pos2, a: Pos2, b: Pos2) -> Pos2 {
+    let ab = b - a;
+    let length_sq = ab.length_sq();
+    if length_sq == 0.0 {
+        return a;
+    }
+    let t = ((point - a).dot(ab) / length_sq).clamp(0.0, 1.0);
+    a + t * ab
+}
+
+/// The contour line segments through one grid cell, given as its corners (position and value)
+/// in counter-clockwise order starting at the lower left.
+fn marching_square(corners: [(PlotPoint, f64); 4], level: f64) -> Vec<[PlotPoint; 2]> {
+    if corners.iter().any(|(_, value)| value.is_nan()) {
+        return vec![];
+    }
+    let case = corners.iter().enumerate().fold(0, |case, (i, (_, value))| {
+        case | (((*value > level) as usize) << i)
+    });
+
+    // Where the level crosses the edge from corner `i` to the next one:
+    let edge = |i: usize| {
+        let (a, va) = corners[i];
+        let (b, vb) = corners[(i + 1) % 4];
+        let t = (level - va) / (vb - va);
+        PlotPoint::new(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y))
+    };
+
+    const BOTTOM: usize = 0;
+    const RIGHT: usize = 1;
+    const TOP: usize = 2;
+    const LEFT: usize = 3;
+
+    let edge_pairs: &[(usize, usize)] = match case {
+        1 | 14 => &[(LEFT, BOTTOM)],
+        2 | 13 => &[(BOTTOM, RIGHT)],
+        3 | 12 => &[(LEFT, RIGHT)],
+        4 | 11 => &[(RIGHT, TOP)],
+        6 | 9 => &[(BOTTOM, TOP)],
+        7 | 8 => &[(LEFT, TOP)],
+        // Saddles, where opposite corners are above the level:
+        5 => &[(LEFT, BOTTOM), (RIGHT, TOP)],
+        10 => &[(BOTTOM, RIGHT), (TOP, LEFT)],
+        _ => &[],
+    };
+    edge_pairs
+        .iter()
+        .map(|&(from, to)| [edge(from), edge(to)])
+        .collect()
+}
+
+#[test]
+fn test_marching_square() {
+    let corners = |values: [f64; 4]| {
+        let positions = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+        [0, 1, 2, 3].map(|i| (PlotPoint::from(positions[i]), values[i]))
+    };
+
+    assert!(marching_square(corners([0.0; 4]), 0.5).is_empty());
+    assert!(marching_square(corners([1.0; 4]), 0.5).is_empty());
+
+    // Only the lower left corner is above the level:
+    let segments = marching_square(corners([1.0, 0.0, 0.0, 0.0]), 0.5);
+    assert_eq!(segments.len(), 1);
+    let [a, b] = segments[0];
+    assert_eq!((a.x, a.y), (0.0, 0.5));
+    assert_eq!((b.x, b.y), (0.5, 0.0));
+
+    // A vertical line through the middle:
+    let segments = marching_square(corners([0.0, 1.0, 1.0, 0.0]), 0.5);
+    assert_eq!(segments.len(), 1);
+    let [a, b] = segments[0];
+    assert_eq!((a.x, a.y), (0.5, 0.0));
+    assert_eq!((b.x, b.y), (0.5, 1.0));
+
+    // Saddle:
+    assert_eq!(marching_square(corners([1.0, 0.0, 1.0, 0.0]), 0.5).len(), 2);
+}
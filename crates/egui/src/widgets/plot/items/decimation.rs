@@ -0,0 +1,201 @@
+//! Reducing long series to about one sample per pixel column before painting them.
+
+use std::ops::{Range, RangeInclusive};
+
+/// How a [`super::Series`] is reduced to about the resolution of the screen before it is painted.
+///
+/// Only the samples in the visible X range are looked at, whichever method is used.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decimation {
+    /// Paint every visible sample.
+    None,
+
+    /// Keep the first, last, smallest and largest sample of each pixel column.
+    ///
+    /// Looks the same as painting every sample, including narrow spikes. This is the default.
+    MinMax,
+
+    /// Largest-Triangle-Three-Buckets: keep the one sample of each pixel column that best preserves
+    /// the shape of the line.
+    ///
+    /// Uses fewer samples than [`Self::MinMax`], but may smooth over narrow spikes.
+    Lttb,
+}
+
+impl Default for Decimation {
+    fn default() -> Self {
+        Self::MinMax
+    }
+}
+
+impl Decimation {
+    /// The indices of the samples to paint, in order.
+    ///
+    /// `xs` must be sorted, and `ys` must be as long as `xs`.
+    pub(super) fn indices(
+        self,
+        xs: &[f64],
+        ys: &[f64],
+        x_range: RangeInclusive<f64>,
+        num_columns: usize,
+    ) -> Vec<usize> {
+        let range = visible_range(xs, x_range);
+        match self {
+            Self::None => range.collect(),
+            Self::MinMax => min_max(xs, ys, range, num_columns),
+            Self::Lttb => lttb(xs, ys, range, num_columns),
+        }
+    }
+}
+
+/// The indices of the samples in `x_range`, plus one more on each side
+/// so that a line through them reaches the edges of the range.
+///
+/// `xs` must be sorted.
+pub(super) fn visible_range(xs: &[f64], x_range: RangeInclusive<f64>) -> Range<usize> {
+    let start = xs
+        .partition_point(|x| x < x_range.start())
+        .saturating_sub(1);
+    let end = (xs.partition_point(|x| x <= x_range.end()) + 1).min(xs.len());
+    start..end.max(start)
+}
+
+/// Split the X range into `num_buckets` buckets of the same width,
+/// and keep the first, last, smallest and largest sample of each.
+fn min_max(xs: &[f64], ys: &[f64], range: Range<usize>, num_buckets: usize) -> Vec<usize> {
+    if range.len() <= 4 * num_buckets {
+        return range.collect();
+    }
+
+    let x_min = xs[range.start];
+    let bucket_width = (xs[range.end - 1] - x_min) / num_buckets as f64;
+
+    let mut indices = Vec::with_capacity(4 * num_buckets);
+    let mut start = range.start;
+    while start < range.end {
+        let bucket_end_x = if bucket_width > 0.0 {
+            let bucket = ((xs[start] - x_min) / bucket_width).floor();
+            x_min + (bucket + 1.0) * bucket_width
+        } else {
+            f64::INFINITY
+        };
+        let end =
+            (start + xs[start..range.end].partition_point(|x| *x < bucket_end_x)).max(start + 1);
+
+        let (mut min, mut max) = (start, start);
+        for i in start..end {
+            if ys[i] < ys[min] {
+                min = i;
+            }
+            if ys[i] > ys[max] {
+                max = i;
+            }
+        }
+
+        let mut bucket_indices = [start, min, max, end - 1];
+        bucket_indices.sort_unstable();
+        for index in bucket_indices {
+            if indices.last() != Some(&index) {
+                indices.push(index);
+            }
+        }
+
+        start = end;
+    }
+    indices
+}
+
+/// Largest-Triangle-Three-Buckets, keeping `threshold` samples.
+///
+/// Keeps the first and last sample, and from each bucket in between the one sample
+/// which forms the largest triangle with the previously kept sample and the average of the next bucket.
+fn lttb(xs: &[f64], ys: &[f64], range: Range<usize>, threshold: usize) -> Vec<usize> {
+    if threshold < 3 || range.len() <= threshold {
+        return range.collect();
+    }
+
+    let first = range.start;
+    let last = range.end - 1;
+    let bucket_size = (range.len() - 2) as f64 / (threshold - 2) as f64;
+    let bucket_start =
+        |bucket: usize| (first + 1 + (bucket as f64 * bucket_size) as usize).min(last);
+
+    let mut indices = Vec::with_capacity(threshold);
+    indices.push(first);
+    let mut previous = first;
+    for bucket in 0..threshold - 2 {
+        let (start, end) = (bucket_start(bucket), bucket_start(bucket + 1));
+
+        // The average of the next bucket, which for the last bucket is the last sample:
+        let (next_start, next_end) = if bucket + 3 == threshold {
+            (last, last + 1)
+        } else {
+            (end, bucket_start(bucket + 2).max(end + 1))
+        };
+        let count = (next_end - next_start) as f64;
+        let avg_x = xs[next_start..next_end].iter().sum::<f64>() / count;
+        let avg_y = ys[next_start..next_end].iter().sum::<f64>() / count;
+
+        let (px, py) = (xs[previous], ys[previous]);
+        let mut best = start;
+        let mut best_area = -1.0;
+        for i in start..end.max(start + 1) {
+            let area = ((px - avg_x) * (ys[i] - py) - (px - xs[i]) * (avg_y - py)).abs();
+            if area > best_area {
+                best_area = area;
+                best = i;
+            }
+        }
+        indices.push(best);
+        previous = best;
+    }
+    indices.push(last);
+    indices
+}
+
+#[test]
+fn test_visible_range() {
+    let xs = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+    assert_eq!(visible_range(&xs, 1.5..=3.5), 1..5);
+    assert_eq!(visible_range(&xs, 2.0..=3.0), 1..5);
+    assert_eq!(visible_range(&xs, -10.0..=10.0), 0..6);
+    assert_eq!(visible_range(&xs, 10.0..=20.0), 5..6);
+    assert_eq!(visible_range(&[], 0.0..=1.0), 0..0);
+}
+
+#[test]
+fn test_min_max_keeps_spikes() {
+    let xs: Vec<f64> = (0..10_000).map(|i| i as f64).collect();
+    let mut ys = vec![0.0; xs.len()];
+    ys[1234] = 100.0;
+    ys[5678] = -100.0;
+
+    let indices = Decimation::MinMax.indices(&xs, &ys, 0.0..=10_000.0, 100);
+    assert!(indices.len() <= 4 * 100);
+    assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(indices.first(), Some(&0));
+    assert_eq!(indices.last(), Some(&9_999));
+    assert!(indices.contains(&1234));
+    assert!(indices.contains(&5678));
+
+    // Only the visible samples are looked at:
+    let indices = Decimation::MinMax.indices(&xs, &ys, 2000.0..=3000.0, 100);
+    assert_eq!(indices.first(), Some(&1999));
+    assert_eq!(indices.last(), Some(&3001));
+}
+
+#[test]
+fn test_lttb() {
+    let xs: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+    let ys: Vec<f64> = xs.iter().map(|x| (x * 0.1).sin()).collect();
+
+    let indices = Decimation::Lttb.indices(&xs, &ys, 0.0..=1000.0, 50);
+    assert_eq!(indices.len(), 50);
+    assert!(indices.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(indices.first(), Some(&0));
+    assert_eq!(indices.last(), Some(&999));
+
+    // Few samples are kept as they are:
+    let indices = Decimation::Lttb.indices(&xs[..10], &ys[..10], 0.0..=1000.0, 50);
+    assert_eq!(indices, (0..10).collect::<Vec<_>>());
+}
@@ -0,0 +1,239 @@
+use std::ops::RangeInclusive;
+
+use super::{bounds_hover, ClosestElem, PlotConfig, PlotGeometry, PlotItem, PlotPoint, PlotPoints};
+use crate::plot::{LabelFormatter, PlotBounds, ScreenTransform};
+use crate::*;
+
+/// Error bars showing the uncertainty of each point, along X, Y or both.
+///
+/// Errors can be symmetric, e.g. `1.0 ± 0.2`, or asymmetric, e.g. `1.0 (−0.1, +0.3)`.
+/// Points without an error, e.g. because fewer errors than points were given, get no bar along that axis.
+/// Give the error bars the same name as the [`Line`](super::Line) or [`Points`](super::Points)
+/// they belong to, to share their legend entry.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{ErrorBars, Plot, Points};
+/// let measurements = vec![[1.0, 2.1], [2.0, 3.9], [3.0, 6.2]];
+/// Plot::new("measurements").show(ui, |plot_ui| {
+///     plot_ui.points(Points::new(measurements.clone()).radius(3.0).name("Measured"));
+///     plot_ui.error_bars(
+///         ErrorBars::new(measurements)
+///             .y_errors([0.3, 0.2, 0.5])
+///             .x_errors_asymmetric([[0.1, 0.2]; 3])
+///             .name("Measured"),
+///     );
+/// });
+/// # });
+/// ```
+pub struct ErrorBars {
+    pub(crate) series: PlotPoints,
+    /// The error below and above each point, along each axis.
+    pub(crate) errors: [Vec<[f64; 2]>; 2],
+    pub(crate) stroke: Stroke,
+    pub(crate) cap_width: f32,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+}
+
+impl ErrorBars {
+    /// Error bars around the given points. Add the errors with [`Self::x_errors`], [`Self::y_errors`] and similar.
+    pub fn new(series: impl Into<PlotPoints>) -> Self {
+        Self {
+            series: series.into(),
+            errors: Default::default(),
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            cap_width: 6.0,
+            name: Default::default(),
+            highlight: false,
+        }
+    }
+
+    /// The same error on both sides of each point along X.
+    pub fn x_errors(self, errors: impl IntoIterator<Item = f64>) -> Self {
+        self.x_errors_asymmetric(errors.into_iter().map(|error| [error, error]))
+    }
+
+    /// The error to the left and to the right of each point along X.
+    pub fn x_errors_asymmetric(mut self, errors: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        self.errors[0] = errors.into_iter().collect();
+        self
+    }
+
+    /// The same error on both sides of each point along Y.
+    pub fn y_errors(self, errors: impl IntoIterator<Item = f64>) -> Self {
+        self.y_errors_asymmetric(errors.into_iter().map(|error| [error, error]))
+    }
+
+    /// The error below and above each point along Y.
+    pub fn y_errors_asymmetric(mut self, errors: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        self.errors[1] = errors.into_iter().collect();
+        self
+    }
+
+    /// Width of the caps at the ends of the bars, in screen points. Use `0.0` for no caps. Default: `6.0`.
+    pub fn cap_width(mut self, cap_width: f32) -> Self {
+        self.cap_width = cap_width;
+        self
+    }
+
+    /// Highlight the error bars in the plot by scaling up the lines.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Stroke width. A high value means the plot thickens.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Name of the error bars.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// The error below and above the point with the given index along `axis`, if it has any.
+    fn error(&self, axis: usize, index: usize) -> Option<[f64; 2]> {
+        self.errors[axis].get(index).copied()
+    }
+
+    /// The bounds of the error bars of the point with the given index.
+    fn point_bounds(&self, index: usize) -> PlotBounds {
+        let point = self.series.points()[index];
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.extend_with(&point);
+        if let Some([left, right]) = self.error(0, index) {
+            bounds.extend_with_x(point.x - left);
+            bounds.extend_with_x(point.x + right);
+        }
+        if let Some([below, above]) = self.error(1, index) {
+            bounds.extend_with_y(point.y - below);
+            bounds.extend_with_y(point.y + above);
+        }
+        bounds
+    }
+}
+
+impl PlotItem for ErrorBars {
+    fn shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let mut stroke = self.stroke;
+        let mut cap_width = self.cap_width;
+        if self.highlight {
+            stroke.width *= 2.0;
+            cap_width *= 1.5;
+        }
+
+        for index in 0..self.series.points().len() {
+            let bounds = self.point_bounds(index);
+            let point = self.series.points()[index];
+            let min = transform.position_from_point(&PlotPoint::new(bounds.min()[0], point.y));
+            let max = transform.position_from_point(&PlotPoint::new(bounds.max()[0], point.y));
+            if self.error(0, index).is_some() {
+                shapes.push(Shape::line_segment([min, max], stroke));
+                if cap_width > 0.0 {
+                    for end in [min, max] {
+                        let cap = vec2(0.0, cap_width / 2.0);
+                        shapes.push(Shape::line_segment([end - cap, end + cap], stroke));
+                    }
+                }
+            }
+
+            let min = transform.position_from_point(&PlotPoint::new(point.x, bounds.min()[1]));
+            let max = transform.position_from_point(&PlotPoint::new(point.x, bounds.max()[1]));
+            if self.error(1, index).is_some() {
+                shapes.push(Shape::line_segment([min, max], stroke));
+                if cap_width > 0.0 {
+                    for end in [min, max] {
+                        let cap = vec2(cap_width / 2.0, 0.0);
+                        shapes.push(Shape::line_segment([end - cap, end + cap], stroke));
+                    }
+                }
+            }
+        }
+    }
+
+    fn initialize(&mut self, x_range: RangeInclusive<f64>) {
+        self.series.generate_points(x_range);
+    }
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Points(self.series.points())
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        for index in 0..self.series.points().len() {
+            bounds.merge(&self.point_bounds(index));
+        }
+        bounds
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        bounds_hover(
+            self.series.points()[elem.index],
+            self.point_bounds(elem.index),
+            true,
+            &self.name,
+            plot,
+            shapes,
+            label_formatter,
+        );
+    }
+}
+
+#[test]
+fn test_error_bar_bounds() {
+    let error_bars = ErrorBars::new(vec![[1.0, 2.0], [2.0, 4.0], [3.0, 1.0]])
+        .y_errors([0.5, 1.0])
+        .x_errors_asymmetric([[0.0, 0.0], [0.0, 0.0], [0.25, 2.0]]);
+    assert_eq!(error_bars.error(1, 2), None);
+
+    let bounds = error_bars.point_bounds(1);
+    assert_eq!((bounds.min(), bounds.max()), ([2.0, 3.0], [2.0, 5.0]));
+
+    let bounds = error_bars.bounds();
+    assert_eq!((bounds.min(), bounds.max()), ([1.0, 1.0], [5.0, 5.0]));
+}
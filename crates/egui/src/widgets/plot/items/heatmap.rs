@@ -0,0 +1,456 @@
+use std::ops::RangeInclusive;
+
+use epaint::{Colormap, Mesh};
+
+use super::{scalar_hover, ClosestElem, PlotConfig, PlotGeometry, PlotItem, PlotPoint, PlotPoints};
+use crate::plot::{Colorbar, LabelFormatter, PlotBounds, ScreenTransform};
+use crate::*;
+
+/// A grid of values, each shown as a rectangular cell colored by a [`Colormap`].
+///
+/// The value of column `x` and row `y` is `values[y * cols + x]`.
+/// Row 0 is at the bottom. Cells with a `NaN` value are not shown.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Heatmap, Plot};
+/// let values: Vec<f64> = (0..100).map(|i| ((i % 10) * (i / 10)) as f64).collect();
+/// Plot::new("heatmap").show(ui, |plot_ui| plot_ui.heatmap(Heatmap::new(values, 10)));
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Heatmap {
+    pub(crate) values: Vec<f64>,
+    pub(crate) cols: usize,
+    pub(crate) rows: usize,
+    pub(crate) origin: PlotPoint,
+    pub(crate) cell_size: [f64; 2],
+    pub(crate) colormap: Colormap,
+    pub(crate) range: Option<RangeInclusive<f64>>,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+    pub(crate) show_colorbar: bool,
+}
+
+impl Heatmap {
+    /// `values` is split into rows of `cols` values each.
+    /// If the last row is incomplete, it is ignored.
+    pub fn new(values: impl Into<Vec<f64>>, cols: usize) -> Self {
+        let mut values = values.into();
+        let rows = values.len().checked_div(cols).unwrap_or(0);
+        values.truncate(rows * cols);
+        Self {
+            values,
+            cols,
+            rows,
+            origin: PlotPoint::new(0.0, 0.0),
+            cell_size: [1.0, 1.0],
+            colormap: Colormap::default(),
+            range: None,
+            name: Default::default(),
+            highlight: false,
+            show_colorbar: true,
+        }
+    }
+
+    /// The lower left corner of the first cell. Default: the origin.
+    pub fn origin(mut self, origin: PlotPoint) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// The size of each cell in plot coordinates. Default: `1.0` by `1.0`.
+    pub fn cell_size(mut self, width: f64, height: f64) -> Self {
+        self.cell_size = [width, height];
+        self
+    }
+
+    /// The colors to map the values to. Default: [`Colormap::viridis`].
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// The values mapped to the first and last color of the colormap.
+    /// Values outside are clamped.
+    ///
+    /// Default: from the smallest to the largest value.
+    pub fn range(mut self, range: RangeInclusive<f64>) -> Self {
+        self.range = Some(range);
+        self
+    }
+
+    /// Show a colorbar right of the plot, explaining the colors. Default: `true`.
+    pub fn show_colorbar(mut self, show: bool) -> Self {
+        self.show_colorbar = show;
+        self
+    }
+
+    /// Highlight the heatmap by outlining it.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Name of this heatmap.
+    ///
+    /// This name will show up in the plot legend and as the title of the colorbar.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub(crate) fn value_range(&self) -> RangeInclusive<f64> {
+        if let Some(range) = &self.range {
+            return range.clone();
+        }
+        let (min, max) = self
+            .values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, -f64::INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        if min < max {
+            min..=max
+        } else if min.is_finite() {
+            min - 0.5..=min + 0.5
+        } else {
+            0.0..=1.0
+        }
+    }
+
+    fn cell_min(&self, col: usize, row: usize) -> PlotPoint {
+        PlotPoint::new(
+            self.origin.x + col as f64 * self.cell_size[0],
+            self.origin.y + row as f64 * self.cell_size[1],
+        )
+    }
+
+    fn cell_rect(&self, transform: &ScreenTransform, col: usize, row: usize) -> Rect {
+        transform.rect_from_values(&self.cell_min(col, row), &self.cell_min(col + 1, row + 1))
+    }
+
+    /// The columns or rows overlapping `min..max` along the given axis.
+    fn visible(&self, axis: usize, min: f64, max: f64) -> std::ops::Range<usize> {
+        let count = if axis == 0 { self.cols } else { self.rows };
+        let origin = if axis == 0 {
+            self.origin.x
+        } else {
+            self.origin.y
+        };
+        let size = self.cell_size[axis];
+        let start = ((min - origin) / size).floor().max(0.0) as usize;
+        let end = (((max - origin) / size).ceil().max(0.0) as usize).min(count);
+        start.min(end)..end
+    }
+}
+
+impl PlotItem for Heatmap {
+    fn shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let bounds = transform.bounds();
+        let range = self.value_range();
+        let mut mesh = Mesh::default();
+        for row in self.visible(1, bounds.min[1], bounds.max[1]) {
+            for col in self.visible(0, bounds.min[0], bounds.max[0]) {
+                let value = self.values[row * self.cols + col];
+                let color = self.colormap.sample_range(value, range.clone());
+                if color != Color32::TRANSPARENT {
+                    mesh.add_colored_rect(self.cell_rect(transform, col, row), color);
+                }
+            }
+        }
+        shapes.push(Shape::mesh(mesh));
+
+        if self.highlight {
+            let rect = transform
+                .rect_from_values(&self.cell_min(0, 0), &self.cell_min(self.cols, self.rows));
+            let stroke = Stroke::new(2.0, self.colormap.sample(1.0));
+            shapes.push(Shape::rect_stroke(rect, 0.0, stroke));
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.colormap.sample(0.5)
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        if !self.values.is_empty() {
+            bounds.extend_with(&self.cell_min(0, 0));
+            bounds.extend_with(&self.cell_min(self.cols, self.rows));
+        }
+        bounds
+    }
+
+    fn colorbar(&self) -> Option<Colorbar> {
+        self.show_colorbar
+            .then(|| Colorbar::new(self.colormap.clone(), self.value_range()).label(&self.name))
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        let value = transform.value_from_position(point);
+        let col = (value.x - self.origin.x) / self.cell_size[0];
+        let row = (value.y - self.origin.y) / self.cell_size[1];
+        let is_inside =
+            0.0 <= col && col < self.cols as f64 && 0.0 <= row && row < self.rows as f64;
+        if !is_inside {
+            return None;
+        }
+        let index = row as usize * self.cols + col as usize;
+        // Like bars, the pointer is as close as it gets when it is inside a cell.
+        (!self.values[index].is_nan()).then(|| ClosestElem {
+            index,
+            dist_sq: 0.0,
+        })
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        let (col, row) = (elem.index % self.cols, elem.index / self.cols);
+        let rect = self.cell_rect(plot.transform, col, row);
+        let stroke = Stroke::new(2.0, plot.ui.visuals().text_color());
+        shapes.push(Shape::rect_stroke(rect, 0.0, stroke));
+
+        let center = plot.transform.value_from_position(rect.center());
+        let value = self.values[elem.index];
+        scalar_hover(center, value, &self.name, plot, shapes, label_formatter);
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// Scatter data counted in a grid of bins, shown as a [`Heatmap`] of the counts.
+///
+/// Empty bins are not shown.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Histogram2d, Plot};
+/// let points: Vec<[f64; 2]> = (0..1000)
+///     .map(|i| {
+///         let t = i as f64 * 0.1;
+///         [t.sin() * t.sqrt(), t.cos() * t.sqrt()]
+///     })
+///     .collect();
+/// Plot::new("histogram").show(ui, |plot_ui| {
+///     plot_ui.histogram_2d(Histogram2d::new(points).bins(20, 20));
+/// });
+/// # });
+/// ```
+pub struct Histogram2d {
+    pub(crate) series: PlotPoints,
+    pub(crate) bins: [usize; 2],
+    pub(crate) bounds: Option<PlotBounds>,
+    pub(crate) heatmap: Heatmap,
+}
+
+impl Histogram2d {
+    pub fn new(series: impl Into<PlotPoints>) -> Self {
+        Self {
+            series: series.into(),
+            bins: [10, 10],
+            bounds: None,
+            heatmap: Heatmap::new(vec![], 0),
+        }
+    }
+
+    /// The number of bins along X and Y. Default: 10 by 10.
+    pub fn bins(mut self, x: usize, y: usize) -> Self {
+        self.bins = [x.at_least(1), y.at_least(1)];
+        self
+    }
+
+    /// The area to divide into bins. Points outside are not counted.
+    ///
+    /// Default: the bounding box of the points.
+    pub fn bounds(mut self, bounds: PlotBounds) -> Self {
+        self.bounds = Some(bounds);
+        self
+    }
+
+    /// The colors to map the counts to. Default: [`Colormap::viridis`].
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.heatmap.colormap = colormap;
+        self
+    }
+
+    /// Show a colorbar right of the plot, explaining the colors. Default: `true`.
+    pub fn show_colorbar(mut self, show: bool) -> Self {
+        self.heatmap.show_colorbar = show;
+        self
+    }
+
+    /// Highlight the histogram by outlining it.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.heatmap.highlight = highlight;
+        self
+    }
+
+    /// Name of this histogram.
+    ///
+    /// This name will show up in the plot legend and as the title of the colorbar.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.heatmap.name = name.to_string();
+        self
+    }
+
+    /// Count the points in each bin.
+    pub(crate) fn into_heatmap(self) -> Heatmap {
+        let Self {
+            series,
+            bins: [cols, rows],
+            bounds,
+            heatmap,
+        } = self;
+
+        let points = series.points();
+        let mut bounds = bounds.unwrap_or_else(|| {
+            let mut bounds = PlotBounds::NOTHING;
+            for point in points
+                .iter()
+                .filter(|point| point.x.is_finite() && point.y.is_finite())
+            {
+                bounds.extend_with(point);
+            }
+            bounds
+        });
+        if !bounds.is_finite() {
+            return heatmap;
+        }
+        // Make sure points on the upper edges end up in the last bins:
+        if bounds.width() <= 0.0 {
+            bounds.expand_x(0.5);
+        }
+        if bounds.height() <= 0.0 {
+            bounds.expand_y(0.5);
+        }
+        let cell_size = [bounds.width() / cols as f64, bounds.height() / rows as f64];
+
+        let mut counts = vec![0.0; cols * rows];
+        for point in points {
+            let col = ((point.x - bounds.min[0]) / cell_size[0]).floor();
+            let row = ((point.y - bounds.min[1]) / cell_size[1]).floor();
+            let col = if point.x == bounds.max[0] {
+                col - 1.0
+            } else {
+                col
+            };
+            let row = if point.y == bounds.max[1] {
+                row - 1.0
+            } else {
+                row
+            };
+            if (0.0..cols as f64).contains(&col) && (0.0..rows as f64).contains(&row) {
+                counts[row as usize * cols + col as usize] += 1.0;
+            }
+        }
+        for count in &mut counts {
+            if *count == 0.0 {
+                *count = f64::NAN;
+            }
+        }
+
+        Heatmap {
+            values: counts,
+            cols,
+            rows,
+            origin: PlotPoint::new(bounds.min[0], bounds.min[1]),
+            cell_size,
+            ..heatmap
+        }
+    }
+}
+
+#[test]
+fn test_histogram_2d() {
+    let points = vec![[0.0, 0.0], [0.1, 0.1], [1.0, 1.0], [0.9, 0.2], [5.0, 5.0]];
+    let heatmap = Histogram2d::new(points)
+        .bins(2, 2)
+        .bounds(PlotBounds {
+            min: [0.0, 0.0],
+            max: [1.0, 1.0],
+        })
+        .into_heatmap();
+    assert_eq!((heatmap.cols, heatmap.rows), (2, 2));
+    assert_eq!(heatmap.values[0], 2.0);
+    assert_eq!(heatmap.values[1], 1.0);
+    assert!(heatmap.values[2].is_nan());
+    assert_eq!(heatmap.values[3], 1.0);
+    assert_eq!(heatmap.value_range(), 1.0..=2.0);
+
+    // Points that are off in either direction don't stretch the bounds:
+    let points = vec![
+        [0.0, 0.0],
+        [1.0, 1.0],
+        [2.0, f64::NAN],
+        [f64::INFINITY, 0.5],
+    ];
+    let heatmap = Histogram2d::new(points).bins(2, 2).into_heatmap();
+    assert_eq!(heatmap.origin, PlotPoint::new(0.0, 0.0));
+    assert_eq!(heatmap.cell_size, [0.5, 0.5]);
+    assert_eq!(heatmap.values[0], 1.0);
+    assert_eq!(heatmap.values[3], 1.0);
+}
+
+#[test]
+fn test_heatmap_single_value() {
+    // All cells have the same value, so the range is empty:
+    let heatmap = Heatmap::new(vec![3.0; 4], 2).range(3.0..=3.0);
+    let ctx = Context::default();
+    let _ = ctx.run(Default::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let transform = ScreenTransform::new(
+                Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0)),
+                PlotBounds {
+                    min: [0.0, 0.0],
+                    max: [2.0, 2.0],
+                },
+                [crate::plot::AxisScale::Linear; 2],
+                false,
+                false,
+            );
+            let mut shapes = vec![];
+            heatmap.shapes(ui, &transform, &mut shapes);
+            match &shapes[0] {
+                Shape::Mesh(mesh) => {
+                    assert_eq!(mesh.vertices.len(), 4 * 4, "every cell is painted");
+                    let color = heatmap.colormap.sample(0.5);
+                    assert!(mesh.vertices.iter().all(|vertex| vertex.color == color));
+                }
+                _ => panic!("Expected a mesh"),
+            }
+        });
+    });
+}
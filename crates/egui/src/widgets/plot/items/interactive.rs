@@ -0,0 +1,702 @@
+//! Plot elements the user can drag: cursors, points, spans and callouts.
+
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+
+use crate::plot::{PlotBounds, ScreenTransform};
+use crate::*;
+
+use super::{LineStyle, Orientation, PlotGeometry, PlotItem, PlotPoint};
+
+/// How close in points the pointer has to be to a cursor, point or span edge to grab it.
+pub(crate) const HANDLE_RADIUS: f32 = 6.0;
+
+/// How opaque the fill of a [`Span`] is, relative to its color.
+const SPAN_FILL_ALPHA: f32 = 0.15;
+
+/// How the user interacted with a [`Cursor`], [`DragPoint`], [`Span`] or [`Callout`] this frame.
+///
+/// The new value has already been written back when this is returned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DragResponse {
+    /// The pointer is over the element, or the element is being dragged.
+    pub hovered: bool,
+
+    /// The user is dragging the element.
+    pub dragged: bool,
+
+    /// The user changed the value this frame.
+    pub changed: bool,
+
+    /// The user stopped dragging the element this frame.
+    pub drag_released: bool,
+}
+
+impl DragResponse {
+    pub(crate) fn union(self, other: Self) -> Self {
+        Self {
+            hovered: self.hovered || other.hovered,
+            dragged: self.dragged || other.dragged,
+            changed: self.changed || other.changed,
+            drag_released: self.drag_released || other.drag_released,
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A vertical or horizontal line spanning the whole plot, which the user can drag.
+///
+/// Add it with [`crate::plot::PlotUi::cursor`]. Unlike [`super::VLine`] and [`super::HLine`],
+/// it does not affect the automatic bounds of the plot.
+pub struct Cursor {
+    pub(crate) id: Id,
+    pub(crate) orientation: Orientation,
+    pub(crate) value: f64,
+    pub(crate) stroke: Stroke,
+    pub(crate) style: LineStyle,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+}
+
+impl Cursor {
+    fn new(id_source: impl Hash, orientation: Orientation) -> Self {
+        Self {
+            id: Id::new(id_source),
+            orientation,
+            value: 0.0,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            style: LineStyle::Solid,
+            name: String::default(),
+            highlight: false,
+        }
+    }
+
+    /// A vertical line at an X value, which the user can drag left and right.
+    ///
+    /// The `id_source` must be unique within the plot.
+    pub fn vertical(id_source: impl Hash) -> Self {
+        Self::new(id_source, Orientation::Vertical)
+    }
+
+    /// A horizontal line at a Y value, which the user can drag up and down.
+    ///
+    /// The `id_source` must be unique within the plot.
+    pub fn horizontal(id_source: impl Hash) -> Self {
+        Self::new(id_source, Orientation::Horizontal)
+    }
+
+    /// Add a stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Stroke width.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Set the line's style. Default is `LineStyle::Solid`.
+    pub fn style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Name of this cursor.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// The index of the coordinate the cursor moves along.
+    pub(crate) fn axis(&self) -> usize {
+        match self.orientation {
+            Orientation::Vertical => 0,
+            Orientation::Horizontal => 1,
+        }
+    }
+}
+
+impl PlotItem for Cursor {
+    fn shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let bounds = transform.bounds();
+        let (a, b) = match self.orientation {
+            Orientation::Vertical => (
+                PlotPoint::new(self.value, bounds.min[1]),
+                PlotPoint::new(self.value, bounds.max[1]),
+            ),
+            Orientation::Horizontal => (
+                PlotPoint::new(bounds.min[0], self.value),
+                PlotPoint::new(bounds.max[0], self.value),
+            ),
+        };
+        let points = vec![
+            transform.position_from_point(&a),
+            transform.position_from_point(&b),
+        ];
+        self.style
+            .style_line(points, self.stroke, self.highlight, shapes);
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        PlotBounds::NOTHING
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A point the user can drag, e.g. to edit the control points of a curve.
+///
+/// Add it with [`crate::plot::PlotUi::drag_point`].
+pub struct DragPoint {
+    pub(crate) id: Id,
+    pub(crate) position: PlotPoint,
+    pub(crate) radius: f32,
+    pub(crate) color: Color32,
+    pub(crate) lock: [bool; 2],
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+}
+
+impl DragPoint {
+    /// The `id_source` must be unique within the plot.
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            position: PlotPoint::new(0.0, 0.0),
+            radius: 4.0,
+            color: Color32::TRANSPARENT,
+            lock: [false; 2],
+            name: String::default(),
+            highlight: false,
+        }
+    }
+
+    /// Radius of the marker. Default: `4.0`.
+    pub fn radius(mut self, radius: impl Into<f32>) -> Self {
+        self.radius = radius.into();
+        self
+    }
+
+    /// Marker color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Keep the X coordinate fixed, so the point can only be dragged up and down.
+    pub fn lock_x(mut self, lock: bool) -> Self {
+        self.lock[0] = lock;
+        self
+    }
+
+    /// Keep the Y coordinate fixed, so the point can only be dragged left and right.
+    pub fn lock_y(mut self, lock: bool) -> Self {
+        self.lock[1] = lock;
+        self
+    }
+
+    /// Name of this point.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+}
+
+impl PlotItem for DragPoint {
+    fn shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let center = transform.position_from_point(&self.position);
+        let mut radius = self.radius;
+        if self.highlight {
+            radius *= 2f32.sqrt();
+        }
+        let stroke = Stroke::new(1.0, ui.visuals().extreme_bg_color);
+        shapes.push(Shape::Circle(epaint::CircleShape {
+            center,
+            radius,
+            fill: self.color,
+            stroke,
+        }));
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn color(&self) -> Color32 {
+        self.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Points(std::slice::from_ref(&self.position))
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        bounds.extend_with(&self.position);
+        bounds
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A shaded range of X values, e.g. a selection, which the user can create, move and resize.
+///
+/// Add it with [`crate::plot::PlotUi::span`]. Dragging an edge resizes the span, and dragging inside it
+/// moves it. Dragging with shift held selects a new range.
+pub struct Span {
+    pub(crate) id: Id,
+    pub(crate) range: RangeInclusive<f64>,
+    pub(crate) creatable: bool,
+    pub(crate) stroke: Stroke,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+}
+
+impl Span {
+    /// The `id_source` must be unique within the plot.
+    pub fn new(id_source: impl Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            range: 0.0..=0.0,
+            creatable: true,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: String::default(),
+            highlight: false,
+        }
+    }
+
+    /// Whether dragging with shift held selects a new range. Default: `true`.
+    pub fn creatable(mut self, creatable: bool) -> Self {
+        self.creatable = creatable;
+        self
+    }
+
+    /// Stroke of the edges. The inside is filled with a faint version of its color.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Color of the edges and the fill. Default is `Color32::TRANSPARENT` which means a color will be
+    /// auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Name of this span.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+}
+
+impl PlotItem for Span {
+    fn shapes(&self, _ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let bounds = transform.bounds();
+        let rect = transform.rect_from_values(
+            &PlotPoint::new(*self.range.start(), bounds.min[1]),
+            &PlotPoint::new(*self.range.end(), bounds.max[1]),
+        );
+        let mut stroke = self.stroke;
+        if self.highlight {
+            stroke.width *= 2.0;
+        }
+        let fill = stroke.color.linear_multiply(SPAN_FILL_ALPHA);
+        shapes.push(Shape::rect_filled(rect, 0.0, fill));
+        shapes.push(Shape::line_segment(
+            [rect.left_top(), rect.left_bottom()],
+            stroke,
+        ));
+        shapes.push(Shape::line_segment(
+            [rect.right_top(), rect.right_bottom()],
+            stroke,
+        ));
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        PlotBounds::NOTHING
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A text label connected to a point in the plot by a leader line. The user can drag the label around.
+///
+/// Add it with [`crate::plot::PlotUi::callout`].
+pub struct Callout {
+    pub(crate) id: Id,
+    pub(crate) target: PlotPoint,
+    pub(crate) text: String,
+    pub(crate) offset: Vec2,
+    pub(crate) color: Color32,
+    pub(crate) name: String,
+    pub(crate) highlight: bool,
+}
+
+impl Callout {
+    /// A label with `text`, pointing at `target`.
+    ///
+    /// The `id_source` must be unique within the plot.
+    pub fn new(
+        id_source: impl Hash,
+        target: impl Into<PlotPoint>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self {
+            id: Id::new(id_source),
+            target: target.into(),
+            text: text.into(),
+            offset: Vec2::ZERO,
+            color: Color32::TRANSPARENT,
+            name: String::default(),
+            highlight: false,
+        }
+    }
+
+    /// Color of the leader line and the border of the label.
+    /// Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Name of this callout.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// The screen rectangle of the label, including its padding.
+    pub(crate) fn label_rect(&self, transform: &ScreenTransform, text_size: Vec2) -> Rect {
+        let center = transform.position_from_point(&self.target) + self.offset;
+        Rect::from_center_size(center, text_size).expand(4.0)
+    }
+}
+
+impl PlotItem for Callout {
+    fn shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let font_id = TextStyle::Body.resolve(ui.style());
+        let galley =
+            ui.painter()
+                .layout_no_wrap(self.text.clone(), font_id, ui.visuals().text_color());
+        let rect = self.label_rect(transform, galley.size());
+        let target = transform.position_from_point(&self.target);
+
+        let stroke = Stroke::new(if self.highlight { 2.0 } else { 1.0 }, self.color);
+        if !rect.contains(target) {
+            shapes.push(Shape::line_segment([target, rect.clamp(target)], stroke));
+        }
+        shapes.push(Shape::circle_filled(target, 2.5, self.color));
+        shapes.push(Shape::rect_filled(rect, 2.0, ui.visuals().extreme_bg_color));
+        shapes.push(Shape::rect_stroke(rect, 2.0, stroke));
+        shapes.push(Shape::galley(rect.center() - 0.5 * galley.size(), galley));
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn color(&self) -> Color32 {
+        self.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        PlotBounds::NOTHING
+    }
+}
+
+/// Shows a plot of `0..=10` on both axes with `add_items` for a few frames,
+/// dragging the pointer between the two `path` offsets from the screen position of `from`,
+/// with `modifiers` held.
+///
+/// Returns the response of `add_items` in each frame (hovering, pressing, dragging and releasing),
+/// and where `from` would end up if it was dragged along.
+#[cfg(test)]
+fn drag_in_plot(
+    from: PlotPoint,
+    path: [Vec2; 2],
+    modifiers: Modifiers,
+    mut add_items: impl FnMut(&mut crate::plot::PlotUi<'_>) -> DragResponse,
+) -> (Vec<DragResponse>, PlotPoint) {
+    use crate::plot::Plot;
+
+    let ctx = Context::default();
+    let mut run_frame = |events: Vec<Event>| {
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+            modifiers,
+            events,
+            ..Default::default()
+        };
+        let mut result = None;
+        let _ = ctx.run(raw_input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                let plot = Plot::new("plot")
+                    .width(400.0)
+                    .height(300.0)
+                    .include_x(0.0)
+                    .include_x(10.0)
+                    .include_y(0.0)
+                    .include_y(10.0);
+                result = Some(
+                    plot.show(ui, |plot_ui| {
+                        let from = plot_ui.screen_from_plot(from);
+                        let to = plot_ui.plot_from_screen(from + path[1] - path[0]);
+                        (from, to, add_items(plot_ui))
+                    })
+                    .inner,
+                );
+            });
+        });
+        result.unwrap()
+    };
+
+    // Let the plot settle on its bounds:
+    run_frame(vec![]);
+    let (from, to, _) = run_frame(vec![]);
+    let (start, end) = (from + path[0], from + path[1]);
+
+    let button = |pos, pressed| Event::PointerButton {
+        pos,
+        button: PointerButton::Primary,
+        pressed,
+        modifiers,
+    };
+    let responses = vec![
+        run_frame(vec![Event::PointerMoved(start)]).2,
+        run_frame(vec![button(start, true)]).2,
+        run_frame(vec![Event::PointerMoved(end)]).2,
+        run_frame(vec![button(end, false)]).2,
+    ];
+    (responses, to)
+}
+
+#[test]
+fn test_drag_cursor() {
+    let mut value = 5.0;
+    let (responses, to) = drag_in_plot(
+        PlotPoint::new(5.0, 5.0),
+        [Vec2::ZERO, vec2(40.0, 25.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.cursor(Cursor::vertical("cursor"), &mut value),
+    );
+    assert!(responses[0].hovered && !responses[0].dragged);
+    assert!(responses[1].hovered && !responses[1].changed);
+    assert!(responses[2].dragged && responses[2].changed);
+    assert!(responses[3].drag_released && !responses[3].changed);
+    // Only along the X axis:
+    assert!(value > 5.5);
+    assert!((value - to.x).abs() < 1e-9, "{} != {}", value, to.x);
+
+    // Missing the cursor drags the plot instead:
+    let mut value = 5.0;
+    let (responses, _) = drag_in_plot(
+        PlotPoint::new(2.0, 5.0),
+        [Vec2::ZERO, vec2(40.0, 0.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.cursor(Cursor::vertical("cursor"), &mut value),
+    );
+    assert!(responses.iter().all(|response| !response.hovered));
+    assert_eq!(value, 5.0);
+}
+
+#[test]
+fn test_drag_point() {
+    let mut position = PlotPoint::new(5.0, 5.0);
+    let (responses, to) = drag_in_plot(
+        position,
+        [Vec2::ZERO, vec2(40.0, 30.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.drag_point(DragPoint::new("point"), &mut position),
+    );
+    assert!(responses[2].changed);
+    // Y grows upwards:
+    assert!(position.x > 5.5 && position.y < 4.5);
+    assert!((position.x - to.x).abs() < 1e-9 && (position.y - to.y).abs() < 1e-9);
+
+    let mut position = PlotPoint::new(5.0, 5.0);
+    let (_, to) = drag_in_plot(
+        position,
+        [Vec2::ZERO, vec2(40.0, 30.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.drag_point(DragPoint::new("point").lock_y(true), &mut position),
+    );
+    assert!((position.x - to.x).abs() < 1e-9);
+    assert_eq!(position.y, 5.0);
+}
+
+#[test]
+fn test_drag_span() {
+    // Moving the whole span:
+    let mut range = Some(4.0..=6.0);
+    let (responses, to) = drag_in_plot(
+        PlotPoint::new(5.0, 5.0),
+        [Vec2::ZERO, vec2(40.0, 0.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.span(Span::new("span"), &mut range),
+    );
+    assert!(responses[0].hovered);
+    assert!(responses[2].changed);
+    let (start, end) = range.unwrap().into_inner();
+    let moved = to.x - 5.0;
+    assert!(moved > 0.5);
+    assert!((start - (4.0 + moved)).abs() < 1e-6, "{}", start);
+    assert!((end - (6.0 + moved)).abs() < 1e-6, "{}", end);
+
+    // Dragging the start edge past the end keeps the range ordered:
+    let mut range = Some(4.0..=6.0);
+    let (_, to) = drag_in_plot(
+        PlotPoint::new(4.0, 5.0),
+        [Vec2::ZERO, vec2(120.0, 0.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.span(Span::new("span"), &mut range),
+    );
+    assert!(to.x > 6.0);
+    let (start, end) = range.unwrap().into_inner();
+    assert_eq!(start, 6.0);
+    assert!((end - to.x).abs() < 1e-9, "{}", end);
+
+    // Selecting a new range with shift held:
+    let shift = Modifiers {
+        shift: true,
+        ..Default::default()
+    };
+    let mut range = None;
+    let (_, to) = drag_in_plot(
+        PlotPoint::new(2.0, 5.0),
+        [Vec2::ZERO, vec2(80.0, 0.0)],
+        shift,
+        |plot_ui| plot_ui.span(Span::new("span").creatable(true), &mut range),
+    );
+    let (start, end) = range.unwrap().into_inner();
+    assert!((start - 2.0).abs() < 1e-6, "{}", start);
+    assert!((end - to.x).abs() < 1e-6, "{}", end);
+
+    // Not without shift:
+    let mut range = None;
+    drag_in_plot(
+        PlotPoint::new(2.0, 5.0),
+        [Vec2::ZERO, vec2(80.0, 0.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.span(Span::new("span").creatable(true), &mut range),
+    );
+    assert_eq!(range, None);
+}
+
+#[test]
+fn test_drag_callout() {
+    let target = PlotPoint::new(5.0, 5.0);
+    let mut offset = vec2(40.0, -30.0);
+    // Grab the middle of the label, not the point it points at:
+    let grab = offset;
+    let (responses, _) = drag_in_plot(
+        target,
+        [grab, grab + vec2(10.0, 5.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.callout(Callout::new("callout", target, "Peak"), &mut offset),
+    );
+    assert!(responses[0].hovered);
+    assert!(responses[2].changed);
+    assert_eq!(offset, vec2(50.0, -25.0));
+
+    // The point itself isn't a handle:
+    let mut offset = vec2(40.0, -30.0);
+    let (responses, _) = drag_in_plot(
+        target,
+        [Vec2::ZERO, vec2(10.0, 5.0)],
+        Modifiers::NONE,
+        |plot_ui| plot_ui.callout(Callout::new("callout", target, "Peak"), &mut offset),
+    );
+    assert!(!responses[0].hovered);
+    assert_eq!(offset, vec2(40.0, -30.0));
+}
@@ -3,21 +3,38 @@
 use std::ops::RangeInclusive;
 
 use epaint::util::FloatOrd;
-use epaint::Mesh;
+use epaint::{Colormap, Instance, Mesh};
 
 use crate::*;
 
-use super::{LabelFormatter, PlotBounds, ScreenTransform};
+use super::{AxisScale, Colorbar, LabelFormatter, PlotBounds, ScreenTransform};
 use rect_elem::*;
 use values::{ClosestElem, PlotGeometry};
 
+pub use band::{Band, StackedArea};
 pub use bar::Bar;
 pub use box_elem::{BoxElem, BoxSpread};
+pub use contour::Contour;
+pub use decimation::Decimation;
+pub use error_bars::ErrorBars;
+pub use heatmap::{Heatmap, Histogram2d};
+pub(super) use interactive::HANDLE_RADIUS;
+pub use interactive::{Callout, Cursor, DragPoint, DragResponse, Span};
+pub use pie::{PieChart, PieSlice};
+pub use stream::StreamBuffer;
 pub use values::{LineStyle, MarkerShape, Orientation, PlotPoint, PlotPoints};
 
+mod band;
 mod bar;
 mod box_elem;
+mod contour;
+mod decimation;
+mod error_bars;
+mod heatmap;
+mod interactive;
+mod pie;
 mod rect_elem;
+mod stream;
 mod values;
 
 const DEFAULT_FILL_ALPHA: f32 = 0.05;
@@ -28,6 +45,7 @@ pub(super) struct PlotConfig<'a> {
     pub transform: &'a ScreenTransform,
     pub show_x: bool,
     pub show_y: bool,
+    pub polar: bool,
 }
 
 /// Trait shared by things that can be drawn in the plot.
@@ -48,6 +66,11 @@ pub(super) trait PlotItem {
 
     fn bounds(&self) -> PlotBounds;
 
+    /// The colorbar explaining the colors of the item, if it should get one.
+    fn colorbar(&self) -> Option<Colorbar> {
+        None
+    }
+
     fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
         match self.geometry() {
             PlotGeometry::None => None,
@@ -85,19 +108,134 @@ pub(super) trait PlotItem {
             }
         };
 
-        let line_color = if plot.ui.visuals().dark_mode {
-            Color32::from_gray(100).additive()
-        } else {
-            Color32::from_black_alpha(180)
-        };
-
         // this method is only called, if the value is in the result set of find_closest()
         let value = points[elem.index];
-        let pointer = plot.transform.position_from_point(&value);
+        hover_point(value, self.name(), plot, shapes, label_formatter);
+    }
+}
+
+/// Mark the hovered point and show rulers through it.
+fn hover_point(
+    value: PlotPoint,
+    name: &str,
+    plot: &PlotConfig<'_>,
+    shapes: &mut Vec<Shape>,
+    label_formatter: &LabelFormatter,
+) {
+    let line_color = if plot.ui.visuals().dark_mode {
+        Color32::from_gray(100).additive()
+    } else {
+        Color32::from_black_alpha(180)
+    };
+
+    let pointer = plot.transform.position_from_point(&value);
+    shapes.push(Shape::circle_filled(pointer, 3.0, line_color));
+
+    rulers_at_value(pointer, value, name, plot, shapes, label_formatter);
+}
+
+/// Hover an item which has a scalar value at each point, like a heatmap.
+///
+/// The value is passed to the label formatter as part of the name, e.g. `"temperature: 21.5"`.
+fn scalar_hover(
+    value: PlotPoint,
+    scalar: f64,
+    name: &str,
+    plot: &PlotConfig<'_>,
+    shapes: &mut Vec<Shape>,
+    label_formatter: &LabelFormatter,
+) {
+    let scalar = emath::round_to_decimals(scalar, 6);
+    let name = if name.is_empty() {
+        scalar.to_string()
+    } else {
+        format!("{}: {}", name, scalar)
+    };
+    hover_point(value, &name, plot, shapes, label_formatter);
+}
+
+/// Hover a point with a range of values around it, like an error bar, showing the bounds of the range.
+///
+/// `bounds` is the range along each axis, which is empty for axes without a range.
+/// If `show_value` is `false`, only the bounds are shown along axes with a range, e.g. for bands.
+fn bounds_hover(
+    value: PlotPoint,
+    bounds: PlotBounds,
+    show_value: bool,
+    name: &str,
+    plot: &PlotConfig<'_>,
+    shapes: &mut Vec<Shape>,
+    label_formatter: &LabelFormatter,
+) {
+    let line_color = rulers_color(plot.ui);
+    let pointer = plot.transform.position_from_point(&value);
+    if show_value {
         shapes.push(Shape::circle_filled(pointer, 3.0, line_color));
+    }
 
-        rulers_at_value(pointer, value, self.name(), plot, shapes, label_formatter);
+    let has_range = [0, 1].map(|axis| bounds.min()[axis] < bounds.max()[axis]);
+    let [min, max] = [bounds.min(), bounds.max()]
+        .map(|[x, y]| plot.transform.position_from_point(&PlotPoint::new(x, y)));
+    if plot.show_x {
+        if has_range[0] {
+            shapes.push(vertical_line(min, plot.transform, line_color));
+            shapes.push(vertical_line(max, plot.transform, line_color));
+        }
+        if show_value || !has_range[0] {
+            shapes.push(vertical_line(pointer, plot.transform, line_color));
+        }
+    }
+    if plot.show_y {
+        if has_range[1] {
+            shapes.push(horizontal_line(min, plot.transform, line_color));
+            shapes.push(horizontal_line(max, plot.transform, line_color));
+        }
+        if show_value || !has_range[1] {
+            shapes.push(horizontal_line(pointer, plot.transform, line_color));
+        }
     }
+
+    let text = if let Some(custom_label) = label_formatter {
+        custom_label(name, &value)
+    } else {
+        let scales = plot.transform.scales();
+        let format = |axis: usize, value: f64| {
+            scales[axis].format_hover(value, plot.transform.resolution(axis, value))
+        };
+        let mut text = name.to_owned();
+        for (axis, axis_name, is_shown) in [(0, "x", plot.show_x), (1, "y", plot.show_y)] {
+            if !is_shown {
+                continue;
+            }
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            let v = if axis == 0 { value.x } else { value.y };
+            let range = format!(
+                "{} … {}",
+                format(axis, bounds.min()[axis]),
+                format(axis, bounds.max()[axis])
+            );
+            text += &if !has_range[axis] {
+                format!("{} = {}", axis_name, format(axis, v))
+            } else if show_value {
+                format!("{} = {} ({})", axis_name, format(axis, v), range)
+            } else {
+                format!("{} = {}", axis_name, range)
+            };
+        }
+        text
+    };
+
+    let font_id = TextStyle::Body.resolve(plot.ui.style());
+    shapes.push(Shape::text(
+        &plot.ui.fonts(),
+        pointer + vec2(3.0, -2.0),
+        Align2::LEFT_BOTTOM,
+        text,
+        font_id,
+        plot.ui.visuals().text_color(),
+    ));
 }
 
 // ----------------------------------------------------------------------------
@@ -489,6 +627,194 @@ impl PlotItem for Line {
     }
 }
 
+/// A line through a long series of samples, borrowed from separate X and Y slices.
+///
+/// Unlike [`Line`], the samples are not copied. Only the visible ones are looked at,
+/// and they are reduced to about one per pixel column before painting (see [`Decimation`]),
+/// so this works for millions of samples.
+/// Hovering still finds the exact samples.
+///
+/// The X values must be sorted in ascending order.
+///
+/// The bounds of the samples (for fitting the plot to them) are computed once, in [`Self::new`].
+/// If the samples don't change between frames, you can keep the `Series` around
+/// and add a clone of it every frame, so that they aren't scanned again.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Plot, Series};
+/// let times: Vec<f64> = (0..1_000_000).map(|i| i as f64 * 0.001).collect();
+/// let values: Vec<f64> = times.iter().map(|t| t.sin()).collect();
+/// Plot::new("recording").show(ui, |plot_ui| plot_ui.series(Series::new(&times, &values)));
+/// # });
+/// ```
+#[derive(Clone)]
+pub struct Series<'a> {
+    pub(super) xs: &'a [f64],
+    pub(super) ys: &'a [f64],
+    bounds: PlotBounds,
+    pub(super) stroke: Stroke,
+    pub(super) name: String,
+    pub(super) highlight: bool,
+    pub(super) style: LineStyle,
+    pub(super) decimation: Decimation,
+}
+
+impl<'a> Series<'a> {
+    /// `xs` must be sorted in ascending order.
+    /// If the slices have different lengths, the extra samples of the longer one are ignored.
+    pub fn new(xs: &'a [f64], ys: &'a [f64]) -> Self {
+        let len = xs.len().min(ys.len());
+        let (xs, ys) = (&xs[..len], &ys[..len]);
+
+        let mut bounds = PlotBounds::NOTHING;
+        if let (Some(first), Some(last)) = (xs.first(), xs.last()) {
+            bounds.extend_with_x(*first);
+            bounds.extend_with_x(*last);
+        }
+        for y in ys {
+            bounds.extend_with_y(*y);
+        }
+
+        Self {
+            xs,
+            ys,
+            bounds,
+            stroke: Stroke::new(1.0, Color32::TRANSPARENT),
+            name: Default::default(),
+            highlight: false,
+            style: LineStyle::Solid,
+            decimation: Decimation::default(),
+        }
+    }
+
+    /// Highlight this line in the plot by scaling up the line.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    /// Add a stroke.
+    pub fn stroke(mut self, stroke: impl Into<Stroke>) -> Self {
+        self.stroke = stroke.into();
+        self
+    }
+
+    /// Stroke width. A high value means the plot thickens.
+    pub fn width(mut self, width: impl Into<f32>) -> Self {
+        self.stroke.width = width.into();
+        self
+    }
+
+    /// Stroke color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.stroke.color = color.into();
+        self
+    }
+
+    /// Set the line's style. Default is `LineStyle::Solid`.
+    pub fn style(mut self, style: LineStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// How to reduce the samples before painting. Default is [`Decimation::MinMax`].
+    pub fn decimation(mut self, decimation: Decimation) -> Self {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Name of this line.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on.
+    ///
+    /// Multiple plot items may share the same name, in which case they will also share an entry in
+    /// the legend.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    fn point(&self, index: usize) -> PlotPoint {
+        PlotPoint::new(self.xs[index], self.ys[index])
+    }
+}
+
+impl<'a> PlotItem for Series<'a> {
+    fn shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let num_columns = (transform.frame().width() * ui.ctx().pixels_per_point()).ceil() as usize;
+        let indices = self.decimation.indices(
+            self.xs,
+            self.ys,
+            transform.bounds().range_x(),
+            num_columns.at_least(1),
+        );
+        let values_tf = indices
+            .into_iter()
+            .map(|index| transform.position_from_point(&self.point(index)))
+            .collect();
+        self.style
+            .style_line(values_tf, self.stroke, self.highlight, shapes);
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.stroke.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        self.bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        // Only samples this close to the pointer along X can be close enough to be hovered:
+        let radius = vec2(16.0, 0.0);
+        let x_range = transform.value_from_position(point - radius).x
+            ..=transform.value_from_position(point + radius).x;
+        decimation::visible_range(self.xs, x_range)
+            .map(|index| {
+                let pos = transform.position_from_point(&self.point(index));
+                let dist_sq = point.distance_sq(pos);
+                ClosestElem { index, dist_sq }
+            })
+            .min_by_key(|e| e.dist_sq.ord())
+    }
+
+    fn on_hover(
+        &self,
+        elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        label_formatter: &LabelFormatter,
+    ) {
+        hover_point(
+            self.point(elem.index),
+            self.name(),
+            plot,
+            shapes,
+            label_formatter,
+        );
+    }
+}
+
 /// A convex polygon.
 pub struct Polygon {
     pub(super) series: PlotPoints,
@@ -748,6 +1074,11 @@ pub struct Points {
     pub(super) name: String,
     pub(super) highlight: bool,
     pub(super) stems: Option<f32>,
+    /// Values mapped to the colors of the markers, one per point, instead of a single color.
+    pub(super) color_values: Vec<f64>,
+    pub(super) colormap: Option<Colormap>,
+    pub(super) color_range: Option<RangeInclusive<f64>>,
+    pub(super) show_colorbar: bool,
 }
 
 impl Points {
@@ -761,6 +1092,10 @@ impl Points {
             name: Default::default(),
             highlight: false,
             stems: None,
+            color_values: Vec::new(),
+            colormap: None,
+            color_range: None,
+            show_colorbar: true,
         }
     }
 
@@ -782,6 +1117,50 @@ impl Points {
         self
     }
 
+    /// Color each marker by a value, one per point, mapped through `colormap`.
+    ///
+    /// Points without a value, or with a `NaN` value, are not shown.
+    /// A colorbar explaining the colors is shown right of the plot, see [`Self::show_colorbar`].
+    pub fn color_by(mut self, values: impl Into<Vec<f64>>, colormap: Colormap) -> Self {
+        self.color_values = values.into();
+        self.colormap = Some(colormap);
+        self
+    }
+
+    /// The values mapped to the start and the end of the colormap given to [`Self::color_by`].
+    /// Default: the range of the values.
+    pub fn color_range(mut self, range: RangeInclusive<f64>) -> Self {
+        self.color_range = Some(range);
+        self
+    }
+
+    /// Show a colorbar right of the plot when the points are colored with [`Self::color_by`].
+    /// Default: `true`.
+    ///
+    /// The name of the points is used as the title of the colorbar.
+    pub fn show_colorbar(mut self, show: bool) -> Self {
+        self.show_colorbar = show;
+        self
+    }
+
+    fn resolved_color_range(&self) -> RangeInclusive<f64> {
+        if let Some(range) = &self.color_range {
+            return range.clone();
+        }
+        let (min, max) = self
+            .color_values
+            .iter()
+            .filter(|value| value.is_finite())
+            .fold((f64::INFINITY, -f64::INFINITY), |(min, max), &value| {
+                (min.min(value), max.max(value))
+            });
+        if min <= max {
+            min..=max
+        } else {
+            0.0..=1.0
+        }
+    }
+
     /// Whether to fill the marker.
     pub fn filled(mut self, filled: bool) -> Self {
         self.filled = filled;
@@ -832,113 +1211,138 @@ impl PlotItem for Points {
 
         let stroke_size = radius / 5.0;
 
-        let default_stroke = Stroke::new(stroke_size, *color);
-        let mut stem_stroke = default_stroke;
+        // The marker is painted in white around the origin, and then copied to each point in
+        // the right color. This is a lot faster than a separate shape per point.
+        let default_stroke = Stroke::new(stroke_size, Color32::WHITE);
+        let mut stem_stroke = Stroke::new(stroke_size, *color);
         let stroke = (!filled)
             .then(|| default_stroke)
             .unwrap_or_else(Stroke::none);
-        let fill = filled.then(|| *color).unwrap_or_default();
+        let fill = filled.then(|| Color32::WHITE).unwrap_or_default();
 
         if *highlight {
             radius *= 2f32.sqrt();
             stem_stroke.width *= 2.0;
         }
 
-        let y_reference = stems.map(|y| transform.position_from_point(&PlotPoint::new(0.0, y)).y);
-
-        series
+        let centers: Vec<Pos2> = series
             .points()
             .iter()
             .map(|value| transform.position_from_point(value))
-            .for_each(|center| {
-                let tf = |dx: f32, dy: f32| -> Pos2 { center + radius * vec2(dx, dy) };
+            .collect();
 
-                if let Some(y) = y_reference {
-                    let stem = Shape::line_segment([center, pos2(center.x, y)], stem_stroke);
-                    shapes.push(stem);
-                }
+        if let Some(y) = stems {
+            let y = transform.position_from_point(&PlotPoint::new(0.0, *y)).y;
+            for &center in &centers {
+                shapes.push(Shape::line_segment(
+                    [center, pos2(center.x, y)],
+                    stem_stroke,
+                ));
+            }
+        }
 
-                match shape {
-                    MarkerShape::Circle => {
-                        shapes.push(Shape::Circle(epaint::CircleShape {
-                            center,
-                            radius,
-                            fill,
-                            stroke,
-                        }));
-                    }
-                    MarkerShape::Diamond => {
-                        let points = vec![
-                            tf(0.0, 1.0),  // bottom
-                            tf(-1.0, 0.0), // left
-                            tf(0.0, -1.0), // top
-                            tf(1.0, 0.0),  // right
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Square => {
-                        let points = vec![
-                            tf(-frac_1_sqrt_2, frac_1_sqrt_2),
-                            tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(frac_1_sqrt_2, frac_1_sqrt_2),
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Cross => {
-                        let diagonal1 = [
-                            tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(frac_1_sqrt_2, frac_1_sqrt_2),
-                        ];
-                        let diagonal2 = [
-                            tf(frac_1_sqrt_2, -frac_1_sqrt_2),
-                            tf(-frac_1_sqrt_2, frac_1_sqrt_2),
-                        ];
-                        shapes.push(Shape::line_segment(diagonal1, default_stroke));
-                        shapes.push(Shape::line_segment(diagonal2, default_stroke));
-                    }
-                    MarkerShape::Plus => {
-                        let horizontal = [tf(-1.0, 0.0), tf(1.0, 0.0)];
-                        let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
-                        shapes.push(Shape::line_segment(horizontal, default_stroke));
-                        shapes.push(Shape::line_segment(vertical, default_stroke));
-                    }
-                    MarkerShape::Up => {
-                        let points =
-                            vec![tf(0.0, -1.0), tf(0.5 * sqrt_3, 0.5), tf(-0.5 * sqrt_3, 0.5)];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Down => {
-                        let points = vec![
-                            tf(0.0, 1.0),
-                            tf(-0.5 * sqrt_3, -0.5),
-                            tf(0.5 * sqrt_3, -0.5),
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Left => {
-                        let points =
-                            vec![tf(-1.0, 0.0), tf(0.5, -0.5 * sqrt_3), tf(0.5, 0.5 * sqrt_3)];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Right => {
-                        let points = vec![
-                            tf(1.0, 0.0),
-                            tf(-0.5, 0.5 * sqrt_3),
-                            tf(-0.5, -0.5 * sqrt_3),
-                        ];
-                        shapes.push(Shape::convex_polygon(points, fill, stroke));
-                    }
-                    MarkerShape::Asterisk => {
-                        let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
-                        let diagonal1 = [tf(-frac_sqrt_3_2, 0.5), tf(frac_sqrt_3_2, -0.5)];
-                        let diagonal2 = [tf(-frac_sqrt_3_2, -0.5), tf(frac_sqrt_3_2, 0.5)];
-                        shapes.push(Shape::line_segment(vertical, default_stroke));
-                        shapes.push(Shape::line_segment(diagonal1, default_stroke));
-                        shapes.push(Shape::line_segment(diagonal2, default_stroke));
-                    }
-                }
-            });
+        let tf = |dx: f32, dy: f32| -> Pos2 { pos2(radius * dx, radius * dy) };
+        let marker = match shape {
+            MarkerShape::Circle => vec![Shape::Circle(epaint::CircleShape {
+                center: Pos2::ZERO,
+                radius,
+                fill,
+                stroke,
+            })],
+            MarkerShape::Diamond => {
+                let points = vec![
+                    tf(0.0, 1.0),  // bottom
+                    tf(-1.0, 0.0), // left
+                    tf(0.0, -1.0), // top
+                    tf(1.0, 0.0),  // right
+                ];
+                vec![Shape::convex_polygon(points, fill, stroke)]
+            }
+            MarkerShape::Square => {
+                let points = vec![
+                    tf(-frac_1_sqrt_2, frac_1_sqrt_2),
+                    tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
+                    tf(frac_1_sqrt_2, -frac_1_sqrt_2),
+                    tf(frac_1_sqrt_2, frac_1_sqrt_2),
+                ];
+                vec![Shape::convex_polygon(points, fill, stroke)]
+            }
+            MarkerShape::Cross => {
+                let diagonal1 = [
+                    tf(-frac_1_sqrt_2, -frac_1_sqrt_2),
+                    tf(frac_1_sqrt_2, frac_1_sqrt_2),
+                ];
+                let diagonal2 = [
+                    tf(frac_1_sqrt_2, -frac_1_sqrt_2),
+                    tf(-frac_1_sqrt_2, frac_1_sqrt_2),
+                ];
+                vec![
+                    Shape::line_segment(diagonal1, default_stroke),
+                    Shape::line_segment(diagonal2, default_stroke),
+                ]
+            }
+            MarkerShape::Plus => {
+                let horizontal = [tf(-1.0, 0.0), tf(1.0, 0.0)];
+                let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
+                vec![
+                    Shape::line_segment(horizontal, default_stroke),
+                    Shape::line_segment(vertical, default_stroke),
+                ]
+            }
+            MarkerShape::Up => {
+                let points = vec![tf(0.0, -1.0), tf(0.5 * sqrt_3, 0.5), tf(-0.5 * sqrt_3, 0.5)];
+                vec![Shape::convex_polygon(points, fill, stroke)]
+            }
+            MarkerShape::Down => {
+                let points = vec![
+                    tf(0.0, 1.0),
+                    tf(-0.5 * sqrt_3, -0.5),
+                    tf(0.5 * sqrt_3, -0.5),
+                ];
+                vec![Shape::convex_polygon(points, fill, stroke)]
+            }
+            MarkerShape::Left => {
+                let points = vec![tf(-1.0, 0.0), tf(0.5, -0.5 * sqrt_3), tf(0.5, 0.5 * sqrt_3)];
+                vec![Shape::convex_polygon(points, fill, stroke)]
+            }
+            MarkerShape::Right => {
+                let points = vec![
+                    tf(1.0, 0.0),
+                    tf(-0.5, 0.5 * sqrt_3),
+                    tf(-0.5, -0.5 * sqrt_3),
+                ];
+                vec![Shape::convex_polygon(points, fill, stroke)]
+            }
+            MarkerShape::Asterisk => {
+                let vertical = [tf(0.0, -1.0), tf(0.0, 1.0)];
+                let diagonal1 = [tf(-frac_sqrt_3_2, 0.5), tf(frac_sqrt_3_2, -0.5)];
+                let diagonal2 = [tf(-frac_sqrt_3_2, -0.5), tf(frac_sqrt_3_2, 0.5)];
+                vec![
+                    Shape::line_segment(vertical, default_stroke),
+                    Shape::line_segment(diagonal1, default_stroke),
+                    Shape::line_segment(diagonal2, default_stroke),
+                ]
+            }
+        };
+
+        let instances: Vec<Instance> = if let Some(colormap) = &self.colormap {
+            let range = self.resolved_color_range();
+            centers
+                .iter()
+                .enumerate()
+                .map(|(i, center)| {
+                    let value = self.color_values.get(i).copied().unwrap_or(f64::NAN);
+                    let color = colormap.sample_range(value, range.clone());
+                    Instance::new(center.to_vec2(), color)
+                })
+                .collect()
+        } else {
+            centers
+                .iter()
+                .map(|center| Instance::new(center.to_vec2(), *color))
+                .collect()
+        };
+        shapes.push(Shape::instanced(marker, instances));
     }
 
     fn initialize(&mut self, x_range: RangeInclusive<f64>) {
@@ -968,6 +1372,11 @@ impl PlotItem for Points {
     fn bounds(&self) -> PlotBounds {
         self.series.bounds()
     }
+
+    fn colorbar(&self) -> Option<Colorbar> {
+        let colormap = self.colormap.as_ref().filter(|_| self.show_colorbar)?;
+        Some(Colorbar::new(colormap.clone(), self.resolved_color_range()).label(&self.name))
+    }
 }
 
 /// A set of arrows.
@@ -1086,6 +1495,9 @@ impl PlotItem for Arrows {
 }
 
 /// An image in the plot.
+///
+/// egui keeps no copy of the texture, so to see the image in [`super::Plot::export`]ed plots,
+/// pass it to [`super::ExportedPlot::texture`].
 #[derive(Clone)]
 pub struct PlotImage {
     pub(super) position: PlotPoint,
@@ -1658,6 +2070,11 @@ pub(super) fn rulers_at_value(
     shapes: &mut Vec<Shape>,
     label_formatter: &LabelFormatter,
 ) {
+    if plot.polar {
+        polar_rulers_at_value(pointer, value, name, plot, shapes, label_formatter);
+        return;
+    }
+
     let line_color = rulers_color(plot.ui);
     if plot.show_x {
         shapes.push(vertical_line(pointer, plot.transform, line_color));
@@ -1673,20 +2090,17 @@ pub(super) fn rulers_at_value(
     }
 
     let text = {
-        let scale = plot.transform.dvalue_dpos();
-        let x_decimals = ((-scale[0].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
-        let y_decimals = ((-scale[1].abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+        let scales = plot.transform.scales();
+        let x = scales[0].format_hover(value.x, plot.transform.resolution(0, value.x));
+        let y = scales[1].format_hover(value.y, plot.transform.resolution(1, value.y));
         if let Some(custom_label) = label_formatter {
             custom_label(name, &value)
         } else if plot.show_x && plot.show_y {
-            format!(
-                "{}x = {:.*}\ny = {:.*}",
-                prefix, x_decimals, value.x, y_decimals, value.y
-            )
+            format!("{}x = {}\ny = {}", prefix, x, y)
         } else if plot.show_x {
-            format!("{}x = {:.*}", prefix, x_decimals, value.x)
+            format!("{}x = {}", prefix, x)
         } else if plot.show_y {
-            format!("{}y = {:.*}", prefix, y_decimals, value.y)
+            format!("{}y = {}", prefix, y)
         } else {
             unreachable!()
         }
@@ -1704,6 +2118,67 @@ pub(super) fn rulers_at_value(
     ));
 }
 
+/// Like [`rulers_at_value`] for polar plots: draws the circle and the spoke through the `pointer`,
+/// and shows the radius and angle of `value`.
+fn polar_rulers_at_value(
+    pointer: Pos2,
+    value: PlotPoint,
+    name: &str,
+    plot: &PlotConfig<'_>,
+    shapes: &mut Vec<Shape>,
+    label_formatter: &LabelFormatter,
+) {
+    let line_color = rulers_color(plot.ui);
+    let center = plot
+        .transform
+        .position_from_point(&PlotPoint::new(0.0, 0.0));
+    let r = value.x.hypot(value.y);
+    let theta = value.y.atan2(value.x).to_degrees().rem_euclid(360.0);
+    if plot.show_x {
+        shapes.push(Shape::circle_stroke(
+            center,
+            center.distance(pointer),
+            (1.0, line_color),
+        ));
+    }
+    if plot.show_y {
+        shapes.push(Shape::line_segment([center, pointer], (1.0, line_color)));
+    }
+
+    let text = if let Some(custom_label) = label_formatter {
+        custom_label(name, &value)
+    } else {
+        // The resolution of the angle follows from how far one pixel is along the circle:
+        let r_resolution = plot.transform.resolution(0, r);
+        let theta_resolution = (r_resolution / r.max(r_resolution)).to_degrees();
+        let r = AxisScale::Linear.format_hover(r, r_resolution);
+        let theta = AxisScale::Linear.format_hover(theta, theta_resolution);
+        let mut text = name.to_owned();
+        for (line, is_shown) in [
+            (format!("r = {}", r), plot.show_x),
+            (format!("θ = {}°", theta), plot.show_y),
+        ] {
+            if is_shown {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text += &line;
+            }
+        }
+        text
+    };
+
+    let font_id = TextStyle::Body.resolve(plot.ui.style());
+    shapes.push(Shape::text(
+        &plot.ui.fonts(),
+        pointer + vec2(3.0, -2.0),
+        Align2::LEFT_BOTTOM,
+        text,
+        font_id,
+        plot.ui.visuals().text_color(),
+    ));
+}
+
 fn find_closest_rect<'a, T>(
     rects: impl IntoIterator<Item = &'a T>,
     point: Pos2,
@@ -0,0 +1,425 @@
+use std::f64::consts::TAU;
+use std::ops::RangeInclusive;
+
+use epaint::Mesh;
+
+use super::{ClosestElem, PlotConfig, PlotGeometry, PlotItem, PlotPoint};
+use crate::plot::{LabelFormatter, PlotBounds, ScreenTransform};
+use crate::*;
+
+/// A slice of a [`PieChart`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct PieSlice {
+    pub(crate) value: f64,
+    pub(crate) name: String,
+    pub(crate) color: Color32,
+    pub(crate) explode: f64,
+    pub(crate) highlight: bool,
+
+    // Laid out by the chart:
+    pub(crate) center: PlotPoint,
+    pub(crate) radius: f64,
+    pub(crate) inner_radius: f64,
+    /// Angle where the slice starts, in radians, counter-clockwise from the positive X axis.
+    pub(crate) start_angle: f64,
+    /// The fraction of the whole pie covered by the slice, which goes clockwise from the start angle.
+    pub(crate) fraction: f64,
+    pub(crate) show_label: bool,
+    pub(crate) show_percentage: bool,
+}
+
+impl PieSlice {
+    /// A slice whose size is proportional to `value`. Negative values count as zero.
+    pub fn new(value: f64) -> Self {
+        Self {
+            value,
+            name: Default::default(),
+            color: Color32::TRANSPARENT,
+            explode: 0.0,
+            highlight: false,
+            center: PlotPoint::new(0.0, 0.0),
+            radius: 1.0,
+            inner_radius: 0.0,
+            start_angle: 0.0,
+            fraction: 0.0,
+            show_label: true,
+            show_percentage: true,
+        }
+    }
+
+    /// Name of this slice.
+    ///
+    /// This name will show up in the plot legend, if legends are turned on,
+    /// and next to the slice, unless [`PieChart::labels`] is turned off.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn name(mut self, name: impl ToString) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    /// Fill color. Default is `Color32::TRANSPARENT` which means a color will be auto-assigned.
+    pub fn color(mut self, color: impl Into<Color32>) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// Move the slice out of the pie by the given fraction of the radius, to make it stand out.
+    pub fn explode(mut self, explode: f64) -> Self {
+        self.explode = explode;
+        self
+    }
+
+    /// Highlight the slice in the plot by outlining it.
+    pub fn highlight(mut self, highlight: bool) -> Self {
+        self.highlight = highlight;
+        self
+    }
+
+    fn sweep(&self) -> f64 {
+        self.fraction * TAU
+    }
+
+    fn mid_angle(&self) -> f64 {
+        self.start_angle - self.sweep() / 2.0
+    }
+
+    /// The point at the given radius and angle around the center of the slice, which moves out if it is exploded.
+    fn point_at(&self, radius: f64, angle: f64) -> PlotPoint {
+        let offset = self.explode * self.radius;
+        let (sin, cos) = self.mid_angle().sin_cos();
+        let center = PlotPoint::new(self.center.x + offset * cos, self.center.y + offset * sin);
+        PlotPoint::new(
+            center.x + radius * angle.cos(),
+            center.y + radius * angle.sin(),
+        )
+    }
+
+    /// The outer arc, followed by the inner arc in reverse, in screen coordinates.
+    fn outline(&self, transform: &ScreenTransform) -> (Vec<Pos2>, Vec<Pos2>) {
+        // About one point every two degrees, so the arcs look round:
+        let num_points = ((self.sweep() / TAU * 180.0).ceil() as usize).at_least(1) + 1;
+        let angles = (0..num_points)
+            .map(|i| self.start_angle - self.sweep() * i as f64 / (num_points - 1) as f64);
+        let outer = angles
+            .clone()
+            .map(|angle| transform.position_from_point(&self.point_at(self.radius, angle)))
+            .collect();
+        let inner = angles
+            .map(|angle| transform.position_from_point(&self.point_at(self.inner_radius, angle)))
+            .collect();
+        (outer, inner)
+    }
+
+    fn percentage_text(&self) -> String {
+        format!("{:.1}%", 100.0 * self.fraction)
+    }
+}
+
+impl PlotItem for PieSlice {
+    fn shapes(&self, ui: &mut Ui, transform: &ScreenTransform, shapes: &mut Vec<Shape>) {
+        let (outer, inner) = self.outline(transform);
+
+        let mut mesh = Mesh::default();
+        mesh.reserve_vertices(2 * outer.len());
+        mesh.reserve_triangles(2 * (outer.len() - 1));
+        for (outer, inner) in outer.iter().zip(&inner) {
+            mesh.colored_vertex(*outer, self.color);
+            mesh.colored_vertex(*inner, self.color);
+        }
+        for i in 0..outer.len() as u32 - 1 {
+            mesh.add_triangle(2 * i, 2 * i + 1, 2 * i + 2);
+            mesh.add_triangle(2 * i + 1, 2 * i + 2, 2 * i + 3);
+        }
+        shapes.push(Shape::Mesh(mesh));
+
+        // Separate the slices with the background color:
+        let mut outline = outer;
+        outline.extend(inner.into_iter().rev());
+        let separator = Stroke::new(1.0, ui.visuals().extreme_bg_color);
+        shapes.push(Shape::closed_line(outline.clone(), separator));
+        if self.highlight {
+            let stroke = Stroke::new(2.0, ui.visuals().text_color());
+            shapes.push(Shape::closed_line(outline, stroke));
+        }
+
+        let (sin, cos) = self.mid_angle().sin_cos();
+        if self.show_label && !self.name.is_empty() {
+            let pos =
+                transform.position_from_point(&self.point_at(1.08 * self.radius, self.mid_angle()));
+            let anchor = if cos > 0.2 {
+                Align2::LEFT_CENTER
+            } else if cos < -0.2 {
+                Align2::RIGHT_CENTER
+            } else if sin > 0.0 {
+                Align2::CENTER_BOTTOM
+            } else {
+                Align2::CENTER_TOP
+            };
+            shapes.push(Shape::text(
+                &ui.fonts(),
+                pos,
+                anchor,
+                &self.name,
+                TextStyle::Body.resolve(ui.style()),
+                ui.visuals().text_color(),
+            ));
+        }
+
+        // Small slices have no room for their percentage:
+        if self.show_percentage && self.fraction >= 0.04 {
+            let radius = (self.radius + self.inner_radius) / 2.0;
+            let pos = transform.position_from_point(&self.point_at(radius, self.mid_angle()));
+            let text_color = if Rgba::from(self.color).intensity() > 0.5 {
+                Color32::BLACK
+            } else {
+                Color32::WHITE
+            };
+            shapes.push(Shape::text(
+                &ui.fonts(),
+                pos,
+                Align2::CENTER_CENTER,
+                self.percentage_text(),
+                TextStyle::Small.resolve(ui.style()),
+                text_color,
+            ));
+        }
+    }
+
+    fn initialize(&mut self, _x_range: RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn color(&self) -> Color32 {
+        self.color
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::Rects
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        let mut bounds = PlotBounds::NOTHING;
+        let center = self.point_at(0.0, 0.0);
+        bounds.extend_with(&PlotPoint::new(
+            center.x - self.radius,
+            center.y - self.radius,
+        ));
+        bounds.extend_with(&PlotPoint::new(
+            center.x + self.radius,
+            center.y + self.radius,
+        ));
+        bounds
+    }
+
+    fn find_closest(&self, point: Pos2, transform: &ScreenTransform) -> Option<ClosestElem> {
+        let value = transform.value_from_position(point);
+        let center = self.point_at(0.0, 0.0);
+        let (dx, dy) = (value.x - center.x, value.y - center.y);
+        let radius = dx.hypot(dy);
+        let angle = dy.atan2(dx);
+        // Like bars, the pointer is as close as it gets when it is inside the slice:
+        let is_inside = self.inner_radius <= radius
+            && radius <= self.radius
+            && (self.start_angle - angle).rem_euclid(TAU) <= self.sweep();
+        is_inside.then(|| ClosestElem {
+            index: 0,
+            dist_sq: 0.0,
+        })
+    }
+
+    fn on_hover(
+        &self,
+        _elem: ClosestElem,
+        shapes: &mut Vec<Shape>,
+        plot: &PlotConfig<'_>,
+        _label_formatter: &LabelFormatter,
+    ) {
+        let (mut outline, inner) = self.outline(plot.transform);
+        outline.extend(inner.into_iter().rev());
+        let stroke = Stroke::new(2.0, plot.ui.visuals().text_color());
+        shapes.push(Shape::closed_line(outline, stroke));
+
+        let value = emath::round_to_decimals(self.value, 6);
+        let text = if self.name.is_empty() {
+            format!("{} ({})", value, self.percentage_text())
+        } else {
+            format!("{}\n{} ({})", self.name, value, self.percentage_text())
+        };
+        let radius = (self.radius + self.inner_radius) / 2.0;
+        let pos = plot
+            .transform
+            .position_from_point(&self.point_at(radius, self.mid_angle()));
+        shapes.push(Shape::text(
+            &plot.ui.fonts(),
+            pos + vec2(3.0, -2.0),
+            Align2::LEFT_BOTTOM,
+            text,
+            TextStyle::Body.resolve(plot.ui.style()),
+            plot.ui.visuals().text_color(),
+        ));
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A pie chart, or a donut chart with a hole in the middle.
+///
+/// Each [`PieSlice`] gets its own legend entry. Hovering a slice shows its value and percentage.
+/// For round pies, keep the data aspect ratio at `1.0`, e.g. with [`Plot::data_aspect`](super::super::Plot::data_aspect).
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Legend, PieChart, PieSlice, Plot};
+/// let chart = PieChart::new(vec![
+///     PieSlice::new(45.0).name("Rent"),
+///     PieSlice::new(25.0).name("Food"),
+///     PieSlice::new(10.0).name("Travel").explode(0.1),
+///     PieSlice::new(20.0).name("Other"),
+/// ])
+/// .donut(0.5);
+/// Plot::new("budget")
+///     .data_aspect(1.0)
+///     .show_axes([false; 2])
+///     .legend(Legend::default())
+///     .show(ui, |plot_ui| plot_ui.pie_chart(chart));
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct PieChart {
+    pub(crate) slices: Vec<PieSlice>,
+    pub(crate) center: PlotPoint,
+    pub(crate) radius: f64,
+    pub(crate) hole: f64,
+    pub(crate) start_angle: f64,
+    pub(crate) show_labels: bool,
+    pub(crate) show_percentages: bool,
+}
+
+impl PieChart {
+    /// A pie of the given slices, going clockwise from the top.
+    pub fn new(slices: Vec<PieSlice>) -> Self {
+        Self {
+            slices,
+            center: PlotPoint::new(0.0, 0.0),
+            radius: 1.0,
+            hole: 0.0,
+            start_angle: TAU / 4.0,
+            show_labels: true,
+            show_percentages: true,
+        }
+    }
+
+    /// Center of the pie. Default: the origin.
+    pub fn center(mut self, center: impl Into<PlotPoint>) -> Self {
+        self.center = center.into();
+        self
+    }
+
+    /// Radius of the pie. Default: `1.0`.
+    pub fn radius(mut self, radius: f64) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Turn the pie into a donut, with a hole of the given fraction of the radius.
+    pub fn donut(mut self, hole: f64) -> Self {
+        self.hole = hole.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Angle where the first slice starts, in radians, counter-clockwise from the positive X axis.
+    /// Default: `TAU / 4.0`, i.e. the top.
+    pub fn start_angle(mut self, start_angle: f64) -> Self {
+        self.start_angle = start_angle;
+        self
+    }
+
+    /// Show the names of the slices next to them. Default: `true`.
+    pub fn labels(mut self, show: bool) -> Self {
+        self.show_labels = show;
+        self
+    }
+
+    /// Show the percentage of each slice on it. Default: `true`.
+    pub fn percentages(mut self, show: bool) -> Self {
+        self.show_percentages = show;
+        self
+    }
+
+    /// The slices, laid out one after the other.
+    pub(crate) fn into_slices(self) -> Vec<PieSlice> {
+        let value = |slice: &PieSlice| {
+            if slice.value > 0.0 {
+                slice.value
+            } else {
+                0.0
+            }
+        };
+        let total: f64 = self.slices.iter().map(value).sum();
+        if total <= 0.0 {
+            return vec![];
+        }
+
+        let mut start_angle = self.start_angle;
+        self.slices
+            .into_iter()
+            .map(|slice| {
+                let fraction = value(&slice) / total;
+                let slice = PieSlice {
+                    center: self.center,
+                    radius: self.radius,
+                    inner_radius: self.hole * self.radius,
+                    start_angle,
+                    fraction,
+                    show_label: self.show_labels,
+                    show_percentage: self.show_percentages,
+                    ..slice
+                };
+                start_angle -= fraction * TAU;
+                slice
+            })
+            .filter(|slice| slice.fraction > 0.0)
+            .collect()
+    }
+}
+
+#[test]
+fn test_pie_layout() {
+    let slices = PieChart::new(vec![
+        PieSlice::new(3.0),
+        PieSlice::new(-1.0),
+        PieSlice::new(1.0).explode(0.5),
+    ])
+    .donut(0.25)
+    .into_slices();
+    assert_eq!(slices.len(), 2);
+    assert_eq!(slices[0].fraction, 0.75);
+    assert_eq!(slices[1].start_angle, TAU / 4.0 - 0.75 * TAU);
+    assert_eq!(slices[1].inner_radius, 0.25);
+
+    // The exploded slice, going from the left to the top, moves up and to the left:
+    let center = slices[1].point_at(0.0, 0.0);
+    assert!(center.x < 0.0 && center.y > 0.0);
+
+    let transform = ScreenTransform::new(
+        Rect::from_min_size(Pos2::ZERO, vec2(100.0, 100.0)),
+        PlotBounds::new_symmetrical(2.0),
+        [crate::plot::AxisScale::Linear; 2],
+        false,
+        false,
+    );
+    let at = |x, y| transform.position_from_point(&PlotPoint::new(x, y));
+    assert!(slices[0].find_closest(at(0.5, -0.5), &transform).is_some());
+    assert!(slices[0].find_closest(at(0.1, 0.1), &transform).is_none());
+    assert!(slices[1].find_closest(at(-0.5, 0.9), &transform).is_some());
+}
@@ -0,0 +1,416 @@
+//! Ring buffers of live samples, which can be filled from other threads while a plot shows them.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use epaint::mutex::Mutex;
+
+use super::{PlotBounds, PlotPoint, PlotPoints};
+
+/// The latest samples of a live signal, e.g. telemetry, for a [`Line`](super::Line) or [`Points`](super::Points)
+/// that is updated every frame.
+///
+/// Holds at most a fixed number of samples, replacing the oldest one with each new sample once it is full.
+/// Clones share the same samples, so one clone can be filled from another thread while the other is shown.
+///
+/// The clone that is shown keeps its own copy of the samples, and only copies the samples added since
+/// it was last shown, so the buffer is locked only briefly each frame. The bounds are kept up to date as
+/// samples come and go. See [`Plot::follow_x`](super::super::Plot::follow_x) to scroll along with the latest samples.
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{Line, Plot, StreamBuffer};
+/// let stream = StreamBuffer::new(10_000);
+/// let producer = stream.clone();
+/// std::thread::spawn(move || {
+///     for i in 0..1000 {
+///         let t = i as f64 * 0.01;
+///         producer.push([t, t.sin()]);
+///     }
+/// })
+/// .join()
+/// .unwrap();
+///
+/// Plot::new("telemetry")
+///     .follow_x(5.0)
+///     .show(ui, |plot_ui| plot_ui.line(Line::new(&stream)));
+/// # });
+/// ```
+pub struct StreamBuffer {
+    ring: Arc<Mutex<Ring>>,
+
+    /// The samples this clone showed last.
+    view: Mutex<StreamView>,
+}
+
+impl Clone for StreamBuffer {
+    fn clone(&self) -> Self {
+        Self {
+            ring: self.ring.clone(),
+            view: Default::default(),
+        }
+    }
+}
+
+impl StreamBuffer {
+    /// A buffer keeping the latest `capacity` samples.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            ring: Arc::new(Mutex::new(Ring::new(capacity))),
+            view: Default::default(),
+        }
+    }
+
+    /// Add a sample, replacing the oldest one if the buffer is full.
+    pub fn push(&self, point: impl Into<PlotPoint>) {
+        self.ring.lock().push(point.into());
+    }
+
+    /// Add many samples at once, locking the buffer only once.
+    pub fn extend<P: Into<PlotPoint>>(&self, points: impl IntoIterator<Item = P>) {
+        let mut ring = self.ring.lock();
+        for point in points {
+            ring.push(point.into());
+        }
+    }
+
+    /// Remove all samples.
+    pub fn clear(&self) {
+        self.ring.lock().clear();
+    }
+
+    /// The number of samples in the buffer.
+    pub fn len(&self) -> usize {
+        self.ring.lock().points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The most samples the buffer keeps.
+    pub fn capacity(&self) -> usize {
+        self.ring.lock().capacity
+    }
+
+    /// The most recently added sample.
+    pub fn latest(&self) -> Option<PlotPoint> {
+        self.ring.lock().points.back().copied()
+    }
+
+    /// The bounds of all samples in the buffer, including those added while it is paused.
+    pub fn bounds(&self) -> PlotBounds {
+        self.ring.lock().bounds()
+    }
+
+    /// While paused, plots keep showing the samples from when the buffer was paused,
+    /// but new samples are still added.
+    pub fn set_paused(&self, paused: bool) {
+        let mut ring = self.ring.lock();
+        if paused {
+            let end = ring.end_index();
+            ring.paused_end.get_or_insert(end);
+        } else {
+            ring.paused_end = None;
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.ring.lock().paused_end.is_some()
+    }
+
+    /// The samples to show, copying only those added since the last call.
+    pub fn points(&self) -> PlotPoints {
+        let mut view = self.view.lock();
+        view.update(&self.ring.lock());
+        PlotPoints::Stream(view.snapshot())
+    }
+}
+
+impl From<&StreamBuffer> for PlotPoints {
+    fn from(stream: &StreamBuffer) -> Self {
+        stream.points()
+    }
+}
+
+/// The samples of a [`StreamBuffer`] at one point in time.
+#[derive(Clone)]
+pub struct StreamSnapshot {
+    /// The samples are `points[start..]`.
+    points: Arc<Vec<PlotPoint>>,
+    start: usize,
+    bounds: PlotBounds,
+}
+
+impl StreamSnapshot {
+    pub(super) fn points(&self) -> &[PlotPoint] {
+        &self.points[self.start..]
+    }
+
+    pub(super) fn bounds(&self) -> PlotBounds {
+        self.bounds
+    }
+}
+
+struct Ring {
+    points: VecDeque<PlotPoint>,
+    capacity: usize,
+
+    /// How many samples were removed from the front since the buffer was created,
+    /// i.e. the index of `points[0]` among all samples ever added.
+    first_index: u64,
+
+    min_x: SlidingExtremum,
+    max_x: SlidingExtremum,
+    min_y: SlidingExtremum,
+    max_y: SlidingExtremum,
+
+    /// While paused, the index after the last sample added before pausing.
+    paused_end: Option<u64>,
+}
+
+impl Ring {
+    fn new(capacity: usize) -> Self {
+        Self {
+            points: VecDeque::with_capacity(capacity),
+            capacity,
+            first_index: 0,
+            min_x: SlidingExtremum::new(false),
+            max_x: SlidingExtremum::new(true),
+            min_y: SlidingExtremum::new(false),
+            max_y: SlidingExtremum::new(true),
+            paused_end: None,
+        }
+    }
+
+    fn push(&mut self, point: PlotPoint) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.points.len() == self.capacity {
+            self.points.pop_front();
+            self.first_index += 1;
+            let first_index = self.first_index;
+            for extremum in self.extrema_mut() {
+                extremum.remove_before(first_index);
+            }
+        }
+
+        let index = self.first_index + self.points.len() as u64;
+        self.points.push_back(point);
+        self.min_x.push(index, point.x);
+        self.max_x.push(index, point.x);
+        self.min_y.push(index, point.y);
+        self.max_y.push(index, point.y);
+    }
+
+    fn clear(&mut self) {
+        self.first_index += self.points.len() as u64;
+        self.points.clear();
+        for extremum in self.extrema_mut() {
+            extremum.candidates.clear();
+        }
+    }
+
+    /// The index the next sample will get.
+    fn end_index(&self) -> u64 {
+        self.first_index + self.points.len() as u64
+    }
+
+    fn extrema_mut(&mut self) -> [&mut SlidingExtremum; 4] {
+        [
+            &mut self.min_x,
+            &mut self.max_x,
+            &mut self.min_y,
+            &mut self.max_y,
+        ]
+    }
+
+    fn bounds(&self) -> PlotBounds {
+        PlotBounds {
+            min: [
+                self.min_x.get().unwrap_or(f64::INFINITY),
+                self.min_y.get().unwrap_or(f64::INFINITY),
+            ],
+            max: [
+                self.max_x.get().unwrap_or(-f64::INFINITY),
+                self.max_y.get().unwrap_or(-f64::INFINITY),
+            ],
+        }
+    }
+}
+
+/// The copy of the samples of a [`Ring`] shown by one [`StreamBuffer`].
+struct StreamView {
+    /// Shared with the plots showing it, and reused once they are done with it.
+    snapshot: StreamSnapshot,
+
+    /// The index of the first sample of the snapshot in the [`Ring`].
+    first_index: u64,
+}
+
+impl Default for StreamView {
+    fn default() -> Self {
+        Self {
+            snapshot: StreamSnapshot {
+                points: Default::default(),
+                start: 0,
+                bounds: PlotBounds::NOTHING,
+            },
+            first_index: 0,
+        }
+    }
+}
+
+impl StreamView {
+    /// Catch up with `ring`, copying only the samples added since the last update.
+    fn update(&mut self, ring: &Ring) {
+        let paused = ring.paused_end.is_some();
+        let end = ring.paused_end.unwrap_or_else(|| ring.end_index());
+        let mut view_end = self.first_index + self.snapshot.points().len() as u64;
+        if view_end < ring.first_index.min(end) || end < view_end {
+            // Samples between ours and the new ones were removed, or ours were cleared:
+            view_end = ring.first_index;
+            self.first_index = ring.first_index;
+            self.snapshot.start = self.snapshot.points.len();
+        }
+
+        let num_removed = if paused {
+            0
+        } else {
+            ring.first_index.saturating_sub(self.first_index) as usize
+        };
+        let new_points = ring
+            .points
+            .range((view_end.max(ring.first_index) - ring.first_index) as usize..)
+            .take(end.saturating_sub(view_end) as usize);
+        if num_removed == 0 && new_points.len() == 0 {
+            return;
+        }
+
+        let snapshot = &mut self.snapshot;
+        snapshot.start += num_removed;
+        self.first_index += num_removed as u64;
+        let points = Arc::make_mut(&mut snapshot.points);
+        if snapshot.start >= points.len() - snapshot.start {
+            // Moving the remaining samples to the front costs no more than adding those removed did:
+            points.drain(..snapshot.start);
+            snapshot.start = 0;
+        }
+        points.extend(new_points);
+
+        snapshot.bounds = if paused {
+            let mut bounds = PlotBounds::NOTHING;
+            for point in &points[snapshot.start..] {
+                bounds.extend_with(point);
+            }
+            bounds
+        } else {
+            ring.bounds()
+        };
+    }
+
+    fn snapshot(&self) -> StreamSnapshot {
+        self.snapshot.clone()
+    }
+}
+
+/// The smallest or largest value among the latest samples, updated in amortized constant time.
+struct SlidingExtremum {
+    /// Indices and values of the samples that can still become the extremum
+    /// as older samples are removed, the extremum first.
+    candidates: VecDeque<(u64, f64)>,
+    largest: bool,
+}
+
+impl SlidingExtremum {
+    fn new(largest: bool) -> Self {
+        Self {
+            candidates: VecDeque::new(),
+            largest,
+        }
+    }
+
+    fn push(&mut self, index: u64, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        // Older samples that are not more extreme than the new one can never be the extremum again:
+        while let Some(&(_, last)) = self.candidates.back() {
+            let is_dominated = if self.largest {
+                last <= value
+            } else {
+                last >= value
+            };
+            if !is_dominated {
+                break;
+            }
+            self.candidates.pop_back();
+        }
+        self.candidates.push_back((index, value));
+    }
+
+    fn remove_before(&mut self, index: u64) {
+        while self
+            .candidates
+            .front()
+            .map_or(false, |&(first, _)| first < index)
+        {
+            self.candidates.pop_front();
+        }
+    }
+
+    fn get(&self) -> Option<f64> {
+        self.candidates.front().map(|&(_, value)| value)
+    }
+}
+
+#[test]
+fn test_stream_buffer() {
+    let stream = StreamBuffer::new(3);
+    stream.extend([[0.0, 5.0], [1.0, 1.0], [2.0, 4.0], [3.0, 2.0], [4.0, 3.0]]);
+    assert_eq!(stream.len(), 3);
+    assert_eq!(stream.latest(), Some(PlotPoint::new(4.0, 3.0)));
+    let bounds = stream.bounds();
+    assert_eq!((bounds.min(), bounds.max()), ([2.0, 2.0], [4.0, 4.0]));
+
+    let points = stream.points();
+    assert_eq!(points.points().len(), 3);
+    drop(points);
+
+    // Only the new sample is copied, and the removed one is skipped rather than moving the others:
+    stream.push([5.0, 0.0]);
+    let points = stream.points();
+    assert_eq!(stream.view.lock().snapshot.start, 1);
+    assert_eq!(
+        points.points(),
+        [[3.0, 2.0], [4.0, 3.0], [5.0, 0.0]].map(PlotPoint::from)
+    );
+    drop(points);
+
+    // Another clone has its own copy, which stays up to date:
+    let other = stream.clone();
+    for i in 0..20 {
+        let x = 6.0 + i as f64;
+        other.push([x, 0.0]);
+        if i % 3 == 0 {
+            let xs: Vec<f64> = other.points().points().iter().map(|p| p.x).collect();
+            assert_eq!(xs, [x - 2.0, x - 1.0, x]);
+        }
+    }
+    assert_eq!(stream.points().points()[0], PlotPoint::new(23.0, 0.0));
+    assert!(stream.view.lock().snapshot.points.len() <= 6);
+
+    stream.set_paused(true);
+    stream.extend([[26.0, 9.0], [27.0, 0.0], [28.0, 0.0]]);
+    assert_eq!(stream.points().points()[2], PlotPoint::new(25.0, 0.0));
+    assert_eq!(stream.bounds().max(), [28.0, 9.0]);
+    // A new clone shows the samples from before the pause that are still there:
+    assert!(stream.clone().points().points().is_empty());
+    stream.set_paused(false);
+    assert_eq!(stream.points().points()[0], PlotPoint::new(26.0, 9.0));
+
+    stream.clear();
+    assert!(stream.is_empty());
+    assert!(!stream.bounds().is_valid());
+}
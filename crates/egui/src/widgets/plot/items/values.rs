@@ -1,6 +1,7 @@
 use epaint::{Pos2, Shape, Stroke, Vec2};
 use std::ops::{Bound, RangeBounds, RangeInclusive};
 
+use super::stream::StreamSnapshot;
 use crate::plot::transform::PlotBounds;
 
 /// A point coordinate in the plot.
@@ -150,10 +151,11 @@ impl Default for Orientation {
 
 /// Represents many [`PlotPoint`]s.
 ///
-/// These can be an owned `Vec` or generated with a function.
+/// These can be an owned `Vec`, generated with a function, or the latest samples of a [`StreamBuffer`](super::StreamBuffer).
 pub enum PlotPoints {
     Owned(Vec<PlotPoint>),
     Generator(ExplicitGenerator),
+    Stream(StreamSnapshot),
     // Borrowed(&[PlotPoint]), // TODO: Lifetimes are tricky in this case.
 }
 
@@ -190,6 +192,7 @@ impl PlotPoints {
         match self {
             PlotPoints::Owned(points) => points.as_slice(),
             PlotPoints::Generator(_) => &[],
+            PlotPoints::Stream(snapshot) => snapshot.points(),
         }
     }
 
@@ -249,6 +252,31 @@ impl PlotPoints {
             .collect()
     }
 
+    /// From points in polar coordinates, each given as `[r, θ]` with the angle `θ` in radians,
+    /// counter-clockwise from the positive X axis. See [`Plot::polar`](crate::plot::Plot::polar).
+    pub fn from_polar(points: impl IntoIterator<Item = [f64; 2]>) -> Self {
+        points
+            .into_iter()
+            .map(|[r, theta]| [r * theta.cos(), r * theta.sin()])
+            .collect()
+    }
+
+    /// Draw a curve in polar coordinates, where `function` gives the radius for each angle in radians.
+    pub fn from_polar_callback(
+        function: impl Fn(f64) -> f64,
+        theta_range: impl RangeBounds<f64>,
+        points: usize,
+    ) -> Self {
+        Self::from_parametric_callback(
+            |theta| {
+                let r = function(theta);
+                (r * theta.cos(), r * theta.sin())
+            },
+            theta_range,
+            points,
+        )
+    }
+
     /// From a series of y-values.
     /// The x-values will be the indices of these values
     pub fn from_ys_f32(ys: &[f32]) -> Self {
@@ -269,6 +297,7 @@ impl PlotPoints {
         match self {
             PlotPoints::Owned(points) => points.is_empty(),
             PlotPoints::Generator(_) => false,
+            PlotPoints::Stream(snapshot) => snapshot.points().is_empty(),
         }
     }
 
@@ -312,6 +341,7 @@ impl PlotPoints {
                 bounds
             }
             PlotPoints::Generator(generator) => generator.estimate_bounds(),
+            PlotPoints::Stream(snapshot) => snapshot.bounds(),
         }
     }
 }
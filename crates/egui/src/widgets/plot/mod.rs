@@ -6,19 +6,32 @@ use crate::*;
 use epaint::color::Hsva;
 use epaint::util::FloatOrd;
 
-use items::PlotItem;
+use axis::{AxesLayout, AxisWidget};
+use items::{PlotItem, HANDLE_RADIUS};
 use legend::LegendWidget;
 use transform::ScreenTransform;
 
+pub use axis::{AxisHints, HPlacement, Placement, VPlacement};
+pub use colorbar::Colorbar;
+pub use export::{ExportFormat, ExportOptions, ExportedPlot};
+
 pub use items::{
-    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, HLine, Line, LineStyle, MarkerShape,
-    Orientation, PlotImage, PlotPoint, PlotPoints, Points, Polygon, Text, VLine,
+    Arrows, Band, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, Callout, Contour, Cursor, Decimation,
+    DragPoint, DragResponse, ErrorBars, HLine, Heatmap, Histogram2d, Line, LineStyle, MarkerShape,
+    Orientation, PieChart, PieSlice, PlotImage, PlotPoint, PlotPoints, Points, Polygon, Series,
+    Span, StackedArea, StreamBuffer, Text, VLine,
 };
 pub use legend::{Corner, Legend};
+pub use scale::AxisScale;
 pub use transform::PlotBounds;
 
+mod axis;
+mod colorbar;
+mod export;
 mod items;
 mod legend;
+mod polar;
+mod scale;
 mod transform;
 
 type LabelFormatterFn = dyn Fn(&str, &PlotPoint) -> String;
@@ -98,8 +111,14 @@ struct PlotMemory {
     hidden_items: ahash::HashSet<String>,
     min_auto_bounds: PlotBounds,
     last_screen_transform: ScreenTransform,
+    /// The bounds of the secondary Y axes, which share the X range with `last_screen_transform`.
+    y_axis_bounds: Vec<PlotBounds>,
     /// Allows to remember the first click position when performing a boxed zoom
     last_click_pos_for_zoom: Option<Pos2>,
+    /// The interactive element the user is dragging, if any.
+    dragged_handle: Option<Id>,
+    /// The format picked in the export context menu, reported by [`PlotUi::export_request`] in the next frame.
+    export_request: Option<ExportFormat>,
 }
 
 impl PlotMemory {
@@ -196,6 +215,7 @@ pub struct Plot {
     allow_scroll: bool,
     min_auto_bounds: PlotBounds,
     margin_fraction: Vec2,
+    follow_x: Option<f64>,
     allow_boxed_zoom: bool,
     boxed_zoom_pointer_button: PointerButton,
     linked_axes: Option<LinkedAxisGroup>,
@@ -210,11 +230,14 @@ pub struct Plot {
     show_y: bool,
     label_formatter: LabelFormatter,
     coordinates_formatter: Option<(Corner, CoordinatesFormatter)>,
-    axis_formatters: [AxisFormatter; 2],
+    x_axis: AxisHints,
+    y_axes: Vec<AxisHints>,
     legend_config: Option<Legend>,
     show_background: bool,
     show_axes: [bool; 2],
+    polar: bool,
     grid_spacers: [GridSpacer; 2],
+    export_menu: bool,
 }
 
 impl Plot {
@@ -230,6 +253,7 @@ impl Plot {
             allow_scroll: true,
             min_auto_bounds: PlotBounds::NOTHING,
             margin_fraction: Vec2::splat(0.05),
+            follow_x: None,
             allow_boxed_zoom: true,
             boxed_zoom_pointer_button: PointerButton::Secondary,
             linked_axes: None,
@@ -244,11 +268,14 @@ impl Plot {
             show_y: true,
             label_formatter: None,
             coordinates_formatter: None,
-            axis_formatters: [None, None], // [None; 2] requires Copy
+            x_axis: AxisHints::default(),
+            y_axes: vec![AxisHints::default()],
             legend_config: None,
             show_background: true,
             show_axes: [true; 2],
+            polar: false,
             grid_spacers: [log_grid_spacer(10), log_grid_spacer(10)],
+            export_menu: false,
         }
     }
 
@@ -334,6 +361,16 @@ impl Plot {
         self
     }
 
+    /// While the X axis is automatically fitted, only show the last `window` of it, so the plot
+    /// scrolls along with the latest samples of a [`StreamBuffer`] or any other growing series.
+    ///
+    /// Panning the plot stops following, double-clicking it resumes.
+    /// The Y axis still fits all samples, including those scrolled out of view.
+    pub fn follow_x(mut self, window: f64) -> Self {
+        self.follow_x = Some(window);
+        self
+    }
+
     /// Whether to allow zooming in the plot by dragging out a box with the secondary mouse button.
     ///
     /// Default: `true`.
@@ -404,7 +441,7 @@ impl Plot {
         mut self,
         func: impl Fn(f64, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
-        self.axis_formatters[0] = Some(Box::new(func));
+        self.x_axis.formatter = Some(Box::new(func));
         self
     }
 
@@ -419,13 +456,14 @@ impl Plot {
         mut self,
         func: impl Fn(f64, &RangeInclusive<f64>) -> String + 'static,
     ) -> Self {
-        self.axis_formatters[1] = Some(Box::new(func));
+        self.y_axes[0].formatter = Some(Box::new(func));
         self
     }
 
     /// Configure how the grid in the background is spaced apart along the X axis.
     ///
     /// Default is a log-10 grid, i.e. every plot unit is divided into 10 other units.
+    /// Only used if the X axis has an [`AxisScale::Linear`] scale.
     ///
     /// The function has this signature:
     /// ```ignore
@@ -459,6 +497,7 @@ impl Plot {
     }
 
     /// Default is a log-10 grid, i.e. every plot unit is divided into 10 other units.
+    /// Only used if the (primary) Y axis has an [`AxisScale::Linear`] scale.
     ///
     /// See [`Self::x_grid_spacer`] for explanation.
     pub fn y_grid_spacer(mut self, spacer: impl Fn(GridInput) -> Vec<GridMark> + 'static) -> Self {
@@ -496,12 +535,133 @@ impl Plot {
 
     /// Show the axes.
     /// Can be useful to disable if the plot is overlaid over an existing grid or content.
+    /// Hidden axes don't take up any space around the data area.
     /// Default: `[true; 2]`.
     pub fn show_axes(mut self, show: [bool; 2]) -> Self {
         self.show_axes = show;
         self
     }
 
+    /// Show the plot in polar coordinates, with circles around the origin and spokes at every 30°
+    /// instead of the X and Y axes. Hovering shows the radius and angle.
+    ///
+    /// Items are still given in X and Y, see [`PlotPoints::from_polar`] to convert from radius and angle.
+    /// The data aspect ratio is kept at `1.0`, and the plot is automatically fitted around the origin.
+    /// Default: `false`.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{Line, Plot, PlotPoints};
+    /// let rose = PlotPoints::from_polar_callback(|theta| (3.0 * theta).cos(), 0.0..=std::f64::consts::PI, 200);
+    /// Plot::new("rose").polar(true).show(ui, |plot_ui| plot_ui.line(Line::new(rose)));
+    /// # });
+    /// ```
+    pub fn polar(mut self, polar: bool) -> Self {
+        self.polar = polar;
+        self
+    }
+
+    /// Offer to export the plot as SVG or PNG in a context menu when it is right-clicked.
+    /// Default: `false`.
+    ///
+    /// The picked format is reported by [`PlotUi::export_request`] in the next frame,
+    /// so the app can render the same contents with [`Self::export`] and save the result where it wants.
+    pub fn export_menu(mut self, export_menu: bool) -> Self {
+        self.export_menu = export_menu;
+        self
+    }
+
+    /// Space the values on the X axis logarithmically, or as dates and times. Default: [`AxisScale::Linear`].
+    pub fn x_axis_scale(mut self, scale: AxisScale) -> Self {
+        self.x_axis.scale = scale;
+        self
+    }
+
+    /// Space the values on the (primary) Y axis logarithmically, or as dates and times.
+    /// Default: [`AxisScale::Linear`].
+    pub fn y_axis_scale(mut self, scale: AxisScale) -> Self {
+        self.y_axes[0].scale = scale;
+        self
+    }
+
+    /// Set the title of the X axis. Default: none.
+    pub fn x_axis_label(mut self, label: impl Into<String>) -> Self {
+        self.x_axis.label = label.into();
+        self
+    }
+
+    /// Set the title of the (primary) Y axis. Default: none.
+    pub fn y_axis_label(mut self, label: impl Into<String>) -> Self {
+        self.y_axes[0].label = label.into();
+        self
+    }
+
+    /// Whether the X axis is drawn above or below the data area. Default: below.
+    pub fn x_axis_position(mut self, placement: VPlacement) -> Self {
+        self.x_axis.placement = placement.into();
+        self
+    }
+
+    /// Whether the (primary) Y axis is drawn left or right of the data area. Default: left.
+    pub fn y_axis_position(mut self, placement: HPlacement) -> Self {
+        self.y_axes[0].placement = placement.into();
+        self
+    }
+
+    /// How many characters wide the tick labels of the (primary) Y axis can get.
+    /// Determines how much space is reserved for the axis. Default: `5`.
+    ///
+    /// See also [`AxisHints::max_digits`].
+    pub fn y_axis_max_digits(mut self, digits: usize) -> Self {
+        self.y_axes[0].digits = digits;
+        self
+    }
+
+    /// Configure the X axis, replacing anything set with e.g. [`Self::x_axis_label`] or
+    /// [`Self::x_axis_formatter`].
+    pub fn x_axis(mut self, hints: AxisHints) -> Self {
+        self.x_axis = hints;
+        self
+    }
+
+    /// Configure the (primary) Y axis, replacing anything set with e.g. [`Self::y_axis_label`] or
+    /// [`Self::y_axis_formatter`].
+    pub fn y_axis(mut self, hints: AxisHints) -> Self {
+        self.y_axes[0] = hints;
+        self
+    }
+
+    /// Add a secondary Y axis with its own bounds, e.g. to show two quantities with different
+    /// units in the same plot.
+    ///
+    /// The first secondary axis has index 1, the next one 2, and so on.
+    /// Add items to it with [`PlotUi::with_y_axis`].
+    /// Its bounds fit its own items, and follow the primary Y axis when the plot is dragged or zoomed.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// use egui::plot::{AxisHints, HPlacement, Line, Plot, PlotPoints};
+    /// let temperature = Line::new(PlotPoints::from_ys_f32(&[12.0, 14.5, 17.0, 15.5]));
+    /// let pressure = Line::new(PlotPoints::from_ys_f32(&[1012.0, 1009.0, 1003.0, 1007.0]));
+    /// Plot::new("weather")
+    ///     .y_axis(AxisHints::default().label("Temperature").unit("°C"))
+    ///     .secondary_y_axis(
+    ///         AxisHints::default()
+    ///             .label("Pressure")
+    ///             .unit("hPa")
+    ///             .placement(HPlacement::Right),
+    ///     )
+    ///     .show(ui, |plot_ui| {
+    ///         plot_ui.line(temperature);
+    ///         plot_ui.with_y_axis(1, |plot_ui| plot_ui.line(pressure));
+    ///     });
+    /// # });
+    /// ```
+    pub fn secondary_y_axis(mut self, hints: AxisHints) -> Self {
+        self.y_axes.push(hints);
+        self
+    }
+
     /// Add a [`LinkedAxisGroup`] so that this plot will share the bounds with other plots that have this
     /// group assigned. A plot cannot belong to more than one group.
     pub fn link_axis(mut self, group: LinkedAxisGroup) -> Self {
@@ -510,17 +670,25 @@ impl Plot {
     }
 
     /// Interact with and add items to the plot and finally draw it.
-    pub fn show<R>(self, ui: &mut Ui, build_fn: impl FnOnce(&mut PlotUi) -> R) -> InnerResponse<R> {
-        self.show_dyn(ui, Box::new(build_fn))
+    ///
+    /// The items added to the [`PlotUi`] may borrow data from outside the closure, see [`Series`].
+    pub fn show<'a, R>(
+        self,
+        ui: &mut Ui,
+        build_fn: impl FnOnce(&mut PlotUi<'a>) -> R + 'a,
+    ) -> InnerResponse<R> {
+        let plot_id = ui.make_persistent_id(self.id_source);
+        self.show_dyn(ui, plot_id, Box::new(build_fn))
     }
 
     fn show_dyn<'a, R>(
         self,
         ui: &mut Ui,
-        build_fn: Box<dyn FnOnce(&mut PlotUi) -> R + 'a>,
+        plot_id: Id,
+        build_fn: Box<dyn FnOnce(&mut PlotUi<'a>) -> R + 'a>,
     ) -> InnerResponse<R> {
         let Self {
-            id_source,
+            id_source: _,
             center_x_axis,
             center_y_axis,
             allow_zoom,
@@ -530,6 +698,7 @@ impl Plot {
             boxed_zoom_pointer_button: boxed_zoom_pointer,
             min_auto_bounds,
             margin_fraction,
+            follow_x,
             width,
             height,
             min_size,
@@ -539,14 +708,25 @@ impl Plot {
             mut show_y,
             label_formatter,
             coordinates_formatter,
-            axis_formatters,
+            x_axis,
+            y_axes,
             legend_config,
             show_background,
-            show_axes,
+            mut show_axes,
+            polar,
             linked_axes,
             grid_spacers,
+            export_menu,
         } = self;
 
+        // Polar plots have their own grid instead of the axes, which only works with a square aspect.
+        let data_aspect = if polar {
+            show_axes = [false; 2];
+            Some(1.0)
+        } else {
+            data_aspect
+        };
+
         // Determine the size of the plot in the UI
         let size = {
             let width = width
@@ -572,23 +752,28 @@ impl Plot {
         };
 
         // Allocate the space.
-        let (rect, response) = ui.allocate_exact_size(size, Sense::drag());
+        let (complete_rect, response) = ui.allocate_exact_size(size, Sense::drag());
+        response.widget_info(|| WidgetInfo::new(WidgetType::Plot));
 
         // Load or initialize the memory.
-        let plot_id = ui.make_persistent_id(id_source);
-        ui.ctx().check_for_id_clash(plot_id, rect, "Plot");
+        let scales = [x_axis.scale, y_axes[0].scale];
+        ui.ctx().check_for_id_clash(plot_id, complete_rect, "Plot");
         let mut memory = PlotMemory::load(ui.ctx(), plot_id).unwrap_or_else(|| PlotMemory {
             auto_bounds: (!min_auto_bounds.is_valid()).into(),
             hovered_entry: None,
             hidden_items: Default::default(),
             min_auto_bounds,
             last_screen_transform: ScreenTransform::new(
-                rect,
+                complete_rect,
                 min_auto_bounds,
+                scales,
                 center_x_axis,
                 center_y_axis,
             ),
+            y_axis_bounds: vec![],
             last_click_pos_for_zoom: None,
+            dragged_handle: None,
+            export_request: None,
         });
 
         // If the min bounds changed, recalculate everything.
@@ -607,26 +792,69 @@ impl Plot {
             mut hovered_entry,
             mut hidden_items,
             last_screen_transform,
+            y_axis_bounds: last_y_axis_bounds,
             mut last_click_pos_for_zoom,
+            dragged_handle,
+            export_request,
             ..
         } = memory;
 
         // Call the plot build function.
         let mut plot_ui = PlotUi {
             items: Vec::new(),
+            y_axis: 0,
+            num_y_axes: y_axes.len(),
             next_auto_color_idx: 0,
             last_screen_transform,
             response,
             ctx: ui.ctx().clone(),
+            plot_id,
+            dragged_handle,
+            handle_cursor_icon: None,
+            export_request,
         };
         let inner = build_fn(&mut plot_ui);
         let PlotUi {
             mut items,
             mut response,
             last_screen_transform,
+            dragged_handle,
+            handle_cursor_icon,
             ..
         } = plot_ui;
 
+        // Reserve margins for the axes and colorbars, around the data area.
+        let colorbars: Vec<_> = items
+            .iter()
+            .filter(|(_, item)| !hidden_items.contains(item.name()))
+            .filter_map(|(_, item)| item.colorbar())
+            .collect();
+        let (axes_layout, colorbar_rects) = {
+            let x_axis = show_axes[0].then(|| (x_axis.placement, x_axis.thickness(ui, 0)));
+            let mut side_axes: Vec<_> = if show_axes[1] {
+                y_axes
+                    .iter()
+                    .map(|hints| (hints.placement, hints.thickness(ui, 1)))
+                    .collect()
+            } else {
+                vec![]
+            };
+            let num_y_axes = side_axes.len();
+            side_axes.extend(
+                colorbars
+                    .iter()
+                    .map(|colorbar| (Placement::RightTop, colorbar.margin_thickness(ui))),
+            );
+            let mut axes_layout = AxesLayout::new(complete_rect, x_axis, &side_axes);
+            let colorbar_rects = if axes_layout.y_axes.is_empty() {
+                vec![]
+            } else {
+                axes_layout.y_axes.split_off(num_y_axes)
+            };
+            (axes_layout, colorbar_rects)
+        };
+        let rect = axes_layout.plot_rect;
+
         // Background
         if show_background {
             ui.painter().with_clip_rect(rect).add(epaint::RectShape {
@@ -638,24 +866,26 @@ impl Plot {
         }
 
         // --- Legend ---
-        let legend = legend_config
-            .and_then(|config| LegendWidget::try_new(rect, config, &items, &hidden_items));
+        let legend = legend_config.and_then(|config| {
+            let items = items.iter().map(|(_, item)| &**item);
+            LegendWidget::try_new(rect, config, items, &hidden_items)
+        });
         // Don't show hover cursor when hovering over legend.
         if hovered_entry.is_some() {
             show_x = false;
             show_y = false;
         }
         // Remove the deselected items.
-        items.retain(|item| !hidden_items.contains(item.name()));
+        items.retain(|(_, item)| !hidden_items.contains(item.name()));
         // Highlight the hovered items.
         if let Some(hovered_name) = &hovered_entry {
             items
                 .iter_mut()
-                .filter(|entry| entry.name() == hovered_name)
-                .for_each(|entry| entry.highlight());
+                .filter(|(_, entry)| entry.name() == hovered_name)
+                .for_each(|(_, entry)| entry.highlight());
         }
         // Move highlighted items to front.
-        items.sort_by_key(|item| item.highlighted());
+        items.sort_by_key(|(_, item)| item.highlighted());
 
         // --- Bound computation ---
         let mut bounds = *last_screen_transform.bounds();
@@ -695,27 +925,42 @@ impl Plot {
                 bounds.set_y(&min_auto_bounds);
             }
 
-            for item in &items {
+            for (y_axis, item) in &items {
                 let item_bounds = item.bounds();
 
                 if auto_bounds.x {
                     bounds.merge_x(&item_bounds);
                 }
-                if auto_bounds.y {
+                // Items on secondary Y axes get their own bounds below.
+                if auto_bounds.y && *y_axis == 0 {
                     bounds.merge_y(&item_bounds);
                 }
             }
 
+            if polar {
+                if auto_bounds.x {
+                    bounds.make_x_symmetrical();
+                }
+                if auto_bounds.y {
+                    bounds.make_y_symmetrical();
+                }
+            }
+
             if auto_bounds.x {
-                bounds.add_relative_margin_x(margin_fraction);
+                if let Some(window) = follow_x {
+                    bounds.min[0] = bounds.max[0] - window;
+                } else {
+                    bounds.add_relative_margin_x(margin_fraction, scales);
+                }
             }
 
             if auto_bounds.y {
-                bounds.add_relative_margin_y(margin_fraction);
+                bounds.add_relative_margin_y(margin_fraction, scales);
             }
         }
 
-        let mut transform = ScreenTransform::new(rect, bounds, center_x_axis, center_y_axis);
+        let mut transform =
+            ScreenTransform::new(rect, bounds, scales, center_x_axis, center_y_axis);
 
         // Enforce aspect ratio
         if let Some(data_aspect) = data_aspect {
@@ -727,8 +972,10 @@ impl Plot {
             }
         }
 
-        // Dragging
-        if allow_drag && response.dragged_by(PointerButton::Primary) {
+        // Dragging, unless the user drags an interactive element, which keeps the view still.
+        if dragged_handle.is_some() {
+            auto_bounds = false.into();
+        } else if allow_drag && response.dragged_by(PointerButton::Primary) {
             response = response.on_hover_cursor(CursorIcon::Grabbing);
             transform.translate_bounds(-response.drag_delta());
             auto_bounds = false.into();
@@ -803,8 +1050,46 @@ impl Plot {
             }
         }
 
+        // The secondary Y axes fit their own items when the bounds are automatic,
+        // and otherwise keep their relation to the primary Y axis from last frame.
+        let y_axis_transforms: Vec<ScreenTransform> = (1..y_axes.len())
+            .map(|y_axis| {
+                let axis_scales = [x_axis.scale, y_axes[y_axis].scale];
+                let mut axis_bounds = *transform.bounds();
+                let last_axis_bounds = last_y_axis_bounds
+                    .get(y_axis - 1)
+                    .filter(|last_axis_bounds| last_axis_bounds.is_valid());
+                match last_axis_bounds {
+                    Some(last_axis_bounds) if !auto_bounds.y => {
+                        // Keep the relation between the transformed values of both axes.
+                        let from = last_screen_transform.bounds().scaled(scales).range_y();
+                        let to = last_axis_bounds.scaled(axis_scales).range_y();
+                        let mut scaled = axis_bounds.scaled(scales);
+                        scaled.min[1] = remap(scaled.min[1], from.clone(), to.clone());
+                        scaled.max[1] = remap(scaled.max[1], from, to);
+                        axis_bounds.set_y(&scaled.unscaled(axis_scales));
+                    }
+                    _ => {
+                        axis_bounds.set_y(&PlotBounds::NOTHING);
+                        for (_, item) in items.iter().filter(|(axis, _)| *axis == y_axis) {
+                            axis_bounds.merge_y(&item.bounds());
+                        }
+                        axis_bounds.add_relative_margin_y(margin_fraction, axis_scales);
+                        if !axis_bounds.is_valid() {
+                            axis_bounds.set_y(transform.bounds());
+                        }
+                    }
+                }
+                ScreenTransform::new(rect, axis_bounds, axis_scales, center_x_axis, false)
+            })
+            .collect();
+        let y_axis_bounds = y_axis_transforms
+            .iter()
+            .map(|transform| *transform.bounds())
+            .collect();
+
         // Initialize values from functions.
-        for item in &mut items {
+        for (_, item) in &mut items {
             item.initialize(transform.bounds().range_x());
         }
 
@@ -814,9 +1099,14 @@ impl Plot {
             show_y,
             label_formatter,
             coordinates_formatter,
-            axis_formatters,
+            x_axis,
+            y_axes,
+            axes_layout,
+            colorbars: colorbars.into_iter().zip(colorbar_rects).collect(),
             show_axes,
+            polar,
             transform: transform.clone(),
+            y_axis_transforms,
             grid_spacers,
         };
         prepared.ui(ui, &response);
@@ -827,11 +1117,29 @@ impl Plot {
         }
 
         if let Some(mut legend) = legend {
-            ui.add(&mut legend);
+            let ctx = ui.ctx().clone();
+            ctx.with_accessibility_parent(response.id, || ui.add(&mut legend));
             hidden_items = legend.hidden_items();
             hovered_entry = legend.hovered_entry_name();
         }
 
+        let mut export_request = None;
+        let response = if export_menu {
+            response.context_menu(|ui| {
+                for (format, text) in [
+                    (ExportFormat::Svg, "Export as SVG"),
+                    (ExportFormat::Png, "Export as PNG"),
+                ] {
+                    if ui.button(text).clicked() {
+                        export_request = Some(format);
+                        ui.close_menu();
+                    }
+                }
+            })
+        } else {
+            response
+        };
+
         if let Some(group) = linked_axes.as_ref() {
             group.set(*transform.bounds());
         }
@@ -842,11 +1150,17 @@ impl Plot {
             hidden_items,
             min_auto_bounds,
             last_screen_transform: transform,
+            y_axis_bounds,
             last_click_pos_for_zoom,
+            dragged_handle: dragged_handle.filter(|_| response.dragged_by(PointerButton::Primary)),
+            export_request,
         };
         memory.store(ui.ctx(), plot_id);
 
-        let response = if show_x || show_y {
+        let response = if let Some(cursor_icon) = handle_cursor_icon {
+            ui.output().cursor_icon = cursor_icon;
+            response
+        } else if show_x || show_y {
             response.on_hover_cursor(CursorIcon::Crosshair)
         } else {
             response
@@ -858,15 +1172,24 @@ impl Plot {
 
 /// Provides methods to interact with a plot while building it. It is the single argument of the closure
 /// provided to [`Plot::show`]. See [`Plot`] for an example of how to use it.
-pub struct PlotUi {
-    items: Vec<Box<dyn PlotItem>>,
+pub struct PlotUi<'a> {
+    /// Each item with the index of its Y axis.
+    items: Vec<(usize, Box<dyn PlotItem + 'a>)>,
+    y_axis: usize,
+    num_y_axes: usize,
     next_auto_color_idx: usize,
     last_screen_transform: ScreenTransform,
     response: Response,
     ctx: Context,
+    plot_id: Id,
+    /// The interactive element the user is dragging, if any.
+    dragged_handle: Option<Id>,
+    /// The cursor icon for the hovered or dragged interactive element.
+    handle_cursor_icon: Option<CursorIcon>,
+    export_request: Option<ExportFormat>,
 }
 
-impl PlotUi {
+impl<'a> PlotUi<'a> {
     fn auto_color(&mut self) -> Color32 {
         let i = self.next_auto_color_idx;
         self.next_auto_color_idx += 1;
@@ -875,10 +1198,30 @@ impl PlotUi {
         Hsva::new(h, 0.85, 0.5, 1.0).into() // TODO(emilk): OkLab or some other perspective color space
     }
 
+    fn push_item(&mut self, item: Box<dyn PlotItem + 'a>) {
+        self.items.push((self.y_axis, item));
+    }
+
     pub fn ctx(&self) -> &Context {
         &self.ctx
     }
 
+    /// Plot the items added in `add_contents` against the Y axis with the given index,
+    /// where 0 is the primary Y axis and 1 is the first one added with [`Plot::secondary_y_axis`].
+    ///
+    /// Indices of axes that don't exist are treated as the primary Y axis.
+    pub fn with_y_axis<R>(
+        &mut self,
+        y_axis: usize,
+        add_contents: impl FnOnce(&mut Self) -> R,
+    ) -> R {
+        let y_axis = if y_axis < self.num_y_axes { y_axis } else { 0 };
+        let previous = std::mem::replace(&mut self.y_axis, y_axis);
+        let inner = add_contents(self);
+        self.y_axis = previous;
+        inner
+    }
+
     /// The plot bounds as they were in the last frame. If called on the first frame and the bounds were not
     /// further specified in the plot builder, this will return bounds centered on the origin. The bounds do
     /// not change until the plot is drawn.
@@ -886,6 +1229,12 @@ impl PlotUi {
         *self.last_screen_transform.bounds()
     }
 
+    /// The format the user picked in the export context menu in the last frame, if any.
+    /// See [`Plot::export_menu`].
+    pub fn export_request(&self) -> Option<ExportFormat> {
+        self.export_request
+    }
+
     /// Returns `true` if the plot area is currently hovered.
     pub fn plot_hovered(&self) -> bool {
         self.response.hovered()
@@ -931,7 +1280,88 @@ impl PlotUi {
         if line.stroke.color == Color32::TRANSPARENT {
             line.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(line));
+        self.push_item(Box::new(line));
+    }
+
+    /// Add a line through a long series of samples, reduced to the resolution of the screen.
+    pub fn series(&mut self, mut series: Series<'a>) {
+        if series.xs.is_empty() {
+            return;
+        };
+
+        // Give the stroke an automatic color if no color has been assigned.
+        if series.stroke.color == Color32::TRANSPARENT {
+            series.stroke.color = self.auto_color();
+        }
+        self.push_item(Box::new(series));
+    }
+
+    /// Add error bars.
+    pub fn error_bars(&mut self, mut error_bars: ErrorBars) {
+        if error_bars.series.is_empty() {
+            return;
+        }
+
+        // Give the stroke an automatic color if no color has been assigned.
+        if error_bars.stroke.color == Color32::TRANSPARENT {
+            error_bars.stroke.color = self.auto_color();
+        }
+        self.push_item(Box::new(error_bars));
+    }
+
+    /// Add a filled band between two series.
+    pub fn band(&mut self, mut band: Band) {
+        if band.lower.is_empty() || band.upper.is_empty() {
+            return;
+        }
+
+        // Give the band an automatic color if no color has been assigned.
+        if band.stroke.color == Color32::TRANSPARENT {
+            band.stroke.color = self.auto_color();
+        }
+        self.push_item(Box::new(band));
+    }
+
+    /// Add a stacked area chart, with one band and legend entry per layer.
+    pub fn stacked_area(&mut self, stacked_area: StackedArea) {
+        if stacked_area.xs.is_empty() {
+            return;
+        }
+        for band in stacked_area.into_bands() {
+            self.band(band);
+        }
+    }
+
+    /// Add a pie or donut chart, with one legend entry per slice.
+    pub fn pie_chart(&mut self, chart: PieChart) {
+        for mut slice in chart.into_slices() {
+            // Give the slice an automatic color if no color has been assigned.
+            if slice.color == Color32::TRANSPARENT {
+                slice.color = self.auto_color();
+            }
+            self.push_item(Box::new(slice));
+        }
+    }
+
+    /// Add a heatmap, with a colorbar explaining its colors.
+    pub fn heatmap(&mut self, heatmap: Heatmap) {
+        if heatmap.values.is_empty() {
+            return;
+        }
+        self.push_item(Box::new(heatmap));
+    }
+
+    /// Add contour lines through a grid of values.
+    pub fn contour(&mut self, contour: Contour) {
+        if contour.values.is_empty() {
+            return;
+        }
+        self.push_item(Box::new(contour));
+    }
+
+    /// Add a 2D histogram, counting scatter data in a grid of bins.
+    pub fn histogram_2d(&mut self, histogram: Histogram2d) {
+        self.heatmap(histogram.into_heatmap());
     }
 
     /// Add a polygon. The polygon has to be convex.
@@ -944,7 +1374,7 @@ impl PlotUi {
         if polygon.stroke.color == Color32::TRANSPARENT {
             polygon.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(polygon));
+        self.push_item(Box::new(polygon));
     }
 
     /// Add a text.
@@ -953,7 +1383,7 @@ impl PlotUi {
             return;
         };
 
-        self.items.push(Box::new(text));
+        self.push_item(Box::new(text));
     }
 
     /// Add data points.
@@ -966,7 +1396,7 @@ impl PlotUi {
         if points.color == Color32::TRANSPARENT {
             points.color = self.auto_color();
         }
-        self.items.push(Box::new(points));
+        self.push_item(Box::new(points));
     }
 
     /// Add arrows.
@@ -979,12 +1409,12 @@ impl PlotUi {
         if arrows.color == Color32::TRANSPARENT {
             arrows.color = self.auto_color();
         }
-        self.items.push(Box::new(arrows));
+        self.push_item(Box::new(arrows));
     }
 
     /// Add an image.
     pub fn image(&mut self, image: PlotImage) {
-        self.items.push(Box::new(image));
+        self.push_item(Box::new(image));
     }
 
     /// Add a horizontal line.
@@ -994,7 +1424,7 @@ impl PlotUi {
         if hline.stroke.color == Color32::TRANSPARENT {
             hline.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(hline));
+        self.push_item(Box::new(hline));
     }
 
     /// Add a vertical line.
@@ -1004,7 +1434,7 @@ impl PlotUi {
         if vline.stroke.color == Color32::TRANSPARENT {
             vline.stroke.color = self.auto_color();
         }
-        self.items.push(Box::new(vline));
+        self.push_item(Box::new(vline));
     }
 
     /// Add a box plot diagram.
@@ -1017,7 +1447,7 @@ impl PlotUi {
         if box_plot.default_color == Color32::TRANSPARENT {
             box_plot = box_plot.color(self.auto_color());
         }
-        self.items.push(Box::new(box_plot));
+        self.push_item(Box::new(box_plot));
     }
 
     /// Add a bar chart.
@@ -1030,7 +1460,275 @@ impl PlotUi {
         if chart.default_color == Color32::TRANSPARENT {
             chart = chart.color(self.auto_color());
         }
-        self.items.push(Box::new(chart));
+        self.push_item(Box::new(chart));
+    }
+
+    // ------------------------------------------------------------------------
+    // Interactive elements
+
+    /// Whether the user hovers or drags the element with the given id,
+    /// which the pointer is over at screen positions where `hit` is `true`.
+    ///
+    /// Only one element can be dragged at a time, and the first one added wins.
+    fn interact(
+        &mut self,
+        id: Id,
+        hit: impl Fn(Pos2) -> bool,
+        cursor_icon: CursorIcon,
+    ) -> DragResponse {
+        let id = self.plot_id.with(id);
+        if self.dragged_handle.is_none()
+            && self.response.drag_started()
+            && self.response.dragged_by(PointerButton::Primary)
+        {
+            let press_origin = self.ctx.input().pointer.press_origin();
+            if press_origin.map_or(false, &hit) {
+                self.dragged_handle = Some(id);
+            }
+        }
+
+        let is_dragged_handle = self.dragged_handle == Some(id);
+        let hovered = is_dragged_handle
+            || (self.dragged_handle.is_none() && self.response.hover_pos().map_or(false, &hit));
+        if hovered {
+            self.handle_cursor_icon = Some(cursor_icon);
+        }
+        DragResponse {
+            hovered,
+            dragged: is_dragged_handle && self.response.dragged(),
+            changed: false,
+            drag_released: is_dragged_handle && self.response.drag_released(),
+        }
+    }
+
+    /// Where `value` ends up when it moves along with the pointer dragged this frame.
+    fn dragged_value(&self, value: PlotPoint) -> PlotPoint {
+        let delta = self.response.drag_delta();
+        let transform = &self.last_screen_transform;
+        let dragged = transform.value_from_position(transform.position_from_point(&value) + delta);
+        // Keep the exact value along the axes the pointer didn't move along:
+        PlotPoint::new(
+            if delta.x == 0.0 { value.x } else { dragged.x },
+            if delta.y == 0.0 { value.y } else { dragged.y },
+        )
+    }
+
+    /// Add a line the user can drag, at `value` on the X axis for [`Cursor::vertical`],
+    /// or on the Y axis for [`Cursor::horizontal`].
+    ///
+    /// `value` is updated while the user drags the line.
+    /// Interactive elements always use the primary Y axis.
+    ///
+    /// ```
+    /// # egui::__run_test_ui(|ui| {
+    /// # let mut time = 1.0;
+    /// use egui::plot::{Cursor, Plot};
+    /// Plot::new("my_plot").show(ui, |plot_ui| {
+    ///     if plot_ui.cursor(Cursor::vertical("time").name("Time"), &mut time).changed {
+    ///         // React to the new time.
+    ///     }
+    /// });
+    /// # });
+    /// ```
+    pub fn cursor(&mut self, mut cursor: Cursor, value: &mut f64) -> DragResponse {
+        let axis = cursor.axis();
+        let frame = self.last_screen_transform.frame().expand(HANDLE_RADIUS);
+        let screen_value = self
+            .last_screen_transform
+            .position_from_point(&PlotPoint::new(*value, *value))[axis];
+        let cursor_icon = match cursor.orientation {
+            Orientation::Vertical => CursorIcon::ResizeHorizontal,
+            Orientation::Horizontal => CursorIcon::ResizeVertical,
+        };
+        let hit = move |pos: Pos2| {
+            frame.contains(pos) && (pos[axis] - screen_value).abs() <= HANDLE_RADIUS
+        };
+        let mut response = self.interact(cursor.id, hit, cursor_icon);
+
+        let dragged = self.dragged_value(PlotPoint::new(*value, *value));
+        let dragged = if axis == 0 { dragged.x } else { dragged.y };
+        if response.dragged && dragged != *value {
+            *value = dragged;
+            response.changed = true;
+        }
+
+        cursor.value = *value;
+        cursor.highlight |= response.hovered;
+        if cursor.stroke.color == Color32::TRANSPARENT {
+            cursor.stroke.color = self.auto_color();
+        }
+        self.items.push((0, Box::new(cursor)));
+        response
+    }
+
+    /// Add a point the user can drag.
+    ///
+    /// `position` is updated while the user drags the point.
+    /// Interactive elements always use the primary Y axis.
+    pub fn drag_point(&mut self, mut point: DragPoint, position: &mut PlotPoint) -> DragResponse {
+        let center = self.last_screen_transform.position_from_point(position);
+        let radius = point.radius.max(HANDLE_RADIUS);
+        let hit = move |pos: Pos2| pos.distance(center) <= radius;
+        let mut response = self.interact(point.id, hit, CursorIcon::Grab);
+
+        let dragged = self.dragged_value(*position);
+        if response.dragged {
+            if !point.lock[0] && dragged.x != position.x {
+                position.x = dragged.x;
+                response.changed = true;
+            }
+            if !point.lock[1] && dragged.y != position.y {
+                position.y = dragged.y;
+                response.changed = true;
+            }
+        }
+
+        point.position = *position;
+        point.highlight |= response.hovered;
+        if point.color == Color32::TRANSPARENT {
+            point.color = self.auto_color();
+        }
+        self.items.push((0, Box::new(point)));
+        response
+    }
+
+    /// Add a shaded range of X values the user can move, resize and, with shift held, select anew.
+    ///
+    /// `range` is updated while the user drags the span, and is `None` until a range is selected.
+    /// The start of the range is always at most its end.
+    pub fn span(
+        &mut self,
+        mut span: Span,
+        range: &mut Option<RangeInclusive<f64>>,
+    ) -> DragResponse {
+        let frame = *self.last_screen_transform.frame();
+        let shift = self.ctx.input().modifiers.shift;
+        let (start_id, end_id) = (span.id.with("start"), span.id.with("end"));
+        let mut response = DragResponse::default();
+
+        if let Some((mut start, mut end)) = range.clone().map(RangeInclusive::into_inner) {
+            let to_screen_x = |x: f64| {
+                self.last_screen_transform
+                    .position_from_point(&PlotPoint::new(x, 0.0))
+                    .x
+            };
+            let (left, right) = (to_screen_x(start), to_screen_x(end));
+            let near_edge = move |pos: Pos2, x: f32| {
+                frame.expand(HANDLE_RADIUS).contains(pos) && (pos.x - x).abs() <= HANDLE_RADIUS
+            };
+
+            let start_response = self.interact(
+                start_id,
+                move |pos| near_edge(pos, left),
+                CursorIcon::ResizeHorizontal,
+            );
+            let end_response = self.interact(
+                end_id,
+                move |pos| near_edge(pos, right),
+                CursorIcon::ResizeHorizontal,
+            );
+            let creates = span.creatable && shift;
+            let body_response = self.interact(
+                span.id.with("body"),
+                move |pos| !creates && frame.contains(pos) && left < pos.x && pos.x < right,
+                CursorIcon::Grab,
+            );
+
+            let previous = (start, end);
+            if start_response.dragged || body_response.dragged {
+                start = self.dragged_value(PlotPoint::new(start, 0.0)).x;
+            }
+            if end_response.dragged || body_response.dragged {
+                end = self.dragged_value(PlotPoint::new(end, 0.0)).x;
+            }
+            response = start_response.union(end_response).union(body_response);
+            response.changed = response.dragged && (start, end) != previous;
+
+            // Keep the range ordered, and the user holding the same edge, when an edge is dragged past the other.
+            if start > end {
+                std::mem::swap(&mut start, &mut end);
+                let (start_id, end_id) = (self.plot_id.with(start_id), self.plot_id.with(end_id));
+                if self.dragged_handle == Some(start_id) {
+                    self.dragged_handle = Some(end_id);
+                } else if self.dragged_handle == Some(end_id) {
+                    self.dragged_handle = Some(start_id);
+                }
+            }
+            *range = Some(start..=end);
+        }
+
+        // Select a new range by dragging with shift held:
+        if span.creatable
+            && shift
+            && self.dragged_handle.is_none()
+            && self.response.drag_started()
+            && self.response.dragged_by(PointerButton::Primary)
+        {
+            let input = self.ctx.input();
+            let press_origin = input
+                .pointer
+                .press_origin()
+                .filter(|pos| frame.contains(*pos));
+            if let (Some(press_origin), Some(pos)) = (press_origin, input.pointer.interact_pos()) {
+                let from = self
+                    .last_screen_transform
+                    .value_from_position(press_origin)
+                    .x;
+                let to = self.last_screen_transform.value_from_position(pos).x;
+                drop(input);
+                self.dragged_handle =
+                    Some(
+                        self.plot_id
+                            .with(if from <= to { end_id } else { start_id }),
+                    );
+                *range = Some(from.min(to)..=from.max(to));
+                response.dragged = true;
+                response.changed = true;
+            }
+        }
+
+        let range = match range {
+            Some(range) => range.clone(),
+            None => return response,
+        };
+        span.range = range;
+        span.highlight |= response.hovered;
+        if span.stroke.color == Color32::TRANSPARENT {
+            span.stroke.color = self.auto_color();
+        }
+        self.items.push((0, Box::new(span)));
+        response
+    }
+
+    /// Add a text label pointing at a point in the plot, which the user can drag around.
+    ///
+    /// `offset` is the position of the center of the label relative to the point, in screen points,
+    /// and is updated while the user drags the label.
+    pub fn callout(&mut self, mut callout: Callout, offset: &mut Vec2) -> DragResponse {
+        let font_id = TextStyle::Body.resolve(&self.ctx.style());
+        let text_size = self
+            .ctx
+            .fonts()
+            .layout_no_wrap(callout.text.clone(), font_id, Color32::WHITE)
+            .size();
+        callout.offset = *offset;
+        let rect = callout.label_rect(&self.last_screen_transform, text_size);
+        let mut response =
+            self.interact(callout.id, move |pos| rect.contains(pos), CursorIcon::Grab);
+
+        let delta = self.response.drag_delta();
+        if response.dragged && delta != Vec2::ZERO {
+            *offset += delta;
+            response.changed = true;
+        }
+
+        callout.offset = *offset;
+        callout.highlight |= response.hovered;
+        if callout.color == Color32::TRANSPARENT {
+            callout.color = self.auto_color();
+        }
+        self.items.push((0, Box::new(callout)));
+        response
     }
 }
 
@@ -1108,45 +1806,94 @@ pub fn uniform_grid_spacer(spacer: impl Fn(GridInput) -> [f64; 3] + 'static) ->
 
 // ----------------------------------------------------------------------------
 
-struct PreparedPlot {
-    items: Vec<Box<dyn PlotItem>>,
+struct PreparedPlot<'a> {
+    /// Each item with the index of its Y axis.
+    items: Vec<(usize, Box<dyn PlotItem + 'a>)>,
     show_x: bool,
     show_y: bool,
     label_formatter: LabelFormatter,
     coordinates_formatter: Option<(Corner, CoordinatesFormatter)>,
-    axis_formatters: [AxisFormatter; 2],
+    x_axis: AxisHints,
+    y_axes: Vec<AxisHints>,
+    axes_layout: AxesLayout,
+    /// Each colorbar with the margin reserved for it.
+    colorbars: Vec<(Colorbar, Rect)>,
     show_axes: [bool; 2],
+    polar: bool,
     transform: ScreenTransform,
+    /// One for each secondary Y axis.
+    y_axis_transforms: Vec<ScreenTransform>,
     grid_spacers: [GridSpacer; 2],
 }
 
-impl PreparedPlot {
+impl<'a> PreparedPlot<'a> {
     fn ui(self, ui: &mut Ui, response: &Response) {
         let mut shapes = Vec::new();
+        let mut axis_shapes = Vec::new();
+
+        if self.show_axes[0] {
+            let steps = self.grid_steps(0, &self.transform);
+            self.paint_grid(ui, 0, &steps, &mut shapes);
+            if let Some(rect) = self.axes_layout.x_axis {
+                let axis = AxisWidget {
+                    hints: &self.x_axis,
+                    axis: 0,
+                    rect,
+                    transform: &self.transform,
+                    steps: &steps,
+                };
+                axis.paint(ui, &mut axis_shapes);
+            }
+        }
 
-        for d in 0..2 {
-            if self.show_axes[d] {
-                self.paint_axis(ui, d, &mut shapes);
+        if self.show_axes[1] {
+            // Only the primary Y axis gets grid lines, so they don't clutter the plot.
+            let steps = self.grid_steps(1, &self.transform);
+            self.paint_grid(ui, 1, &steps, &mut shapes);
+            let axis_rects = &self.axes_layout.y_axes;
+            for (y_axis, (hints, &rect)) in self.y_axes.iter().zip(axis_rects).enumerate() {
+                let transform = self.y_axis_transform(y_axis);
+                let axis = AxisWidget {
+                    hints,
+                    axis: 1,
+                    rect,
+                    transform,
+                    steps: &self.grid_steps(1, transform),
+                };
+                axis.paint(ui, &mut axis_shapes);
             }
         }
 
+        if self.polar {
+            polar::paint_grid(ui, &self.transform, &self.grid_spacers[0], &mut shapes);
+        }
+
+        for (colorbar, rect) in &self.colorbars {
+            colorbar.paint_in_margin(ui, *rect, &mut axis_shapes);
+        }
+
         let transform = &self.transform;
 
         let mut plot_ui = ui.child_ui(*transform.frame(), Layout::default());
         plot_ui.set_clip_rect(*transform.frame());
-        for item in &self.items {
-            item.shapes(&mut plot_ui, transform, &mut shapes);
+        for (y_axis, item) in &self.items {
+            item.shapes(&mut plot_ui, self.y_axis_transform(*y_axis), &mut shapes);
         }
 
-        if let Some(pointer) = response.hover_pos() {
+        let pointer = response
+            .hover_pos()
+            .filter(|pointer| transform.frame().contains(*pointer));
+
+        if let Some(pointer) = pointer {
             self.hover(ui, pointer, &mut shapes);
         }
 
+        ui.painter().extend(axis_shapes);
         let painter = ui.painter().with_clip_rect(*transform.frame());
         painter.extend(shapes);
 
         if let Some((corner, formatter)) = self.coordinates_formatter.as_ref() {
-            if let Some(pointer) = response.hover_pos() {
+            if let Some(pointer) = pointer {
                 let font_id = TextStyle::Monospace.resolve(ui.style());
                 let coordinate = transform.value_from_position(pointer);
                 let text = formatter.format(&coordinate, transform.bounds());
@@ -1162,44 +1909,43 @@ impl PreparedPlot {
         }
     }
 
-    fn paint_axis(&self, ui: &Ui, axis: usize, shapes: &mut Vec<Shape>) {
-        let Self {
-            transform,
-            axis_formatters,
-            grid_spacers,
-            ..
-        } = self;
+    /// The transform of the Y axis with the given index, 0 being the primary one.
+    fn y_axis_transform(&self, y_axis: usize) -> &ScreenTransform {
+        if y_axis == 0 {
+            &self.transform
+        } else {
+            &self.y_axis_transforms[y_axis - 1]
+        }
+    }
 
+    fn grid_steps(&self, axis: usize, transform: &ScreenTransform) -> Vec<GridMark> {
         let bounds = transform.bounds();
-        let axis_range = match axis {
-            0 => bounds.range_x(),
-            1 => bounds.range_y(),
-            _ => panic!("Axis {} does not exist.", axis),
+        let input = GridInput {
+            bounds: (bounds.min[axis], bounds.max[axis]),
+            base_step_size: transform.dvalue_dpos()[axis] * MIN_LINE_SPACING_IN_POINTS,
         };
+        match transform.scales()[axis] {
+            AxisScale::Linear => (self.grid_spacers[axis])(input),
+            scale => scale.grid_marks(input),
+        }
+    }
 
-        let font_id = TextStyle::Body.resolve(ui.style());
+    fn paint_grid(&self, ui: &Ui, axis: usize, steps: &[GridMark], shapes: &mut Vec<Shape>) {
+        let transform = &self.transform;
 
-        // Where on the cross-dimension to show the label values
+        // Where on the cross-dimension to put the grid lines
         let bounds = transform.bounds();
         let value_cross = 0.0_f64.clamp(bounds.min[1 - axis], bounds.max[1 - axis]);
 
-        let input = GridInput {
-            bounds: (bounds.min[axis], bounds.max[axis]),
-            base_step_size: transform.dvalue_dpos()[axis] * MIN_LINE_SPACING_IN_POINTS,
-        };
-        let steps = (grid_spacers[axis])(input);
-
         for step in steps {
-            let value_main = step.value;
-
             let value = if axis == 0 {
-                PlotPoint::new(value_main, value_cross)
+                PlotPoint::new(step.value, value_cross)
             } else {
-                PlotPoint::new(value_cross, value_main)
+                PlotPoint::new(value_cross, step.value)
             };
 
             let pos_in_gui = transform.position_from_point(&value);
-            let spacing_in_points = (transform.dpos_dvalue()[axis] * step.step_size).abs() as f32;
+            let spacing_in_points = transform.mark_spacing(axis, step);
 
             let line_alpha = remap_clamp(
                 spacing_in_points,
@@ -1208,7 +1954,7 @@ impl PreparedPlot {
             );
 
             if line_alpha > 0.0 {
-                let line_color = color_from_alpha(ui, line_alpha);
+                let line_color = axis::color_from_alpha(ui, line_alpha);
 
                 let mut p0 = pos_in_gui;
                 let mut p1 = pos_in_gui;
@@ -1216,40 +1962,6 @@ impl PreparedPlot {
                 p1[1 - axis] = transform.frame().max[1 - axis];
                 shapes.push(Shape::line_segment([p0, p1], Stroke::new(1.0, line_color)));
             }
-
-            let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
-
-            if text_alpha > 0.0 {
-                let color = color_from_alpha(ui, text_alpha);
-
-                let text: String = if let Some(formatter) = axis_formatters[axis].as_deref() {
-                    formatter(value_main, &axis_range)
-                } else {
-                    emath::round_to_decimals(value_main, 5).to_string() // hack
-                };
-
-                // Custom formatters can return empty string to signal "no label at this resolution"
-                if !text.is_empty() {
-                    let galley = ui.painter().layout_no_wrap(text, font_id.clone(), color);
-
-                    let mut text_pos = pos_in_gui + vec2(1.0, -galley.size().y);
-
-                    // Make sure we see the labels, even if the axis is off-screen:
-                    text_pos[1 - axis] = text_pos[1 - axis]
-                        .at_most(transform.frame().max[1 - axis] - galley.size()[1 - axis] - 2.0)
-                        .at_least(transform.frame().min[1 - axis] + 1.0);
-
-                    shapes.push(Shape::galley(text_pos, galley));
-                }
-            }
-        }
-
-        fn color_from_alpha(ui: &Ui, alpha: f32) -> Color32 {
-            if ui.visuals().dark_mode {
-                Rgba::from_white_alpha(alpha).into()
-            } else {
-                Rgba::from_black_alpha((4.0 * alpha).at_most(1.0)).into()
-            }
         }
     }
 
@@ -1269,27 +1981,34 @@ impl PreparedPlot {
 
         let interact_radius_sq: f32 = (16.0f32).powi(2);
 
-        let candidates = items.iter().filter_map(|item| {
+        let candidates = items.iter().filter_map(|(y_axis, item)| {
             let item = &**item;
-            let closest = item.find_closest(pointer, transform);
+            let closest = item.find_closest(pointer, self.y_axis_transform(*y_axis));
 
-            Some(item).zip(closest)
+            Some((*y_axis, item)).zip(closest)
         });
 
         let closest = candidates
             .min_by_key(|(_, elem)| elem.dist_sq.ord())
             .filter(|(_, elem)| elem.dist_sq <= interact_radius_sq);
 
-        let plot = items::PlotConfig {
-            ui,
-            transform,
-            show_x: *show_x,
-            show_y: *show_y,
-        };
-
-        if let Some((item, elem)) = closest {
+        if let Some(((y_axis, item), elem)) = closest {
+            let plot = items::PlotConfig {
+                ui,
+                transform: self.y_axis_transform(y_axis),
+                show_x: *show_x,
+                show_y: *show_y,
+                polar: self.polar,
+            };
             item.on_hover(elem, shapes, &plot, label_formatter);
         } else {
+            let plot = items::PlotConfig {
+                ui,
+                transform,
+                show_x: *show_x,
+                show_y: *show_y,
+                polar: self.polar,
+            };
             let value = transform.value_from_position(pointer);
             items::rulers_at_value(pointer, value, "", &plot, shapes, label_formatter);
         }
@@ -0,0 +1,130 @@
+//! The grid of polar plots, see [`Plot::polar`](super::Plot::polar).
+
+use std::f32::consts::TAU;
+
+use super::axis::color_from_alpha;
+use super::{
+    AxisScale, GridInput, GridSpacer, PlotPoint, ScreenTransform, MIN_LINE_SPACING_IN_POINTS,
+};
+use crate::*;
+
+/// The angular grid has a spoke every 30°.
+const NUM_SPOKES: usize = 12;
+
+/// Paint circles around the origin at the marks of `grid_spacer`, and spokes at every 30°, with labels.
+pub(super) fn paint_grid(
+    ui: &Ui,
+    transform: &ScreenTransform,
+    grid_spacer: &GridSpacer,
+    shapes: &mut Vec<Shape>,
+) {
+    let frame = *transform.frame();
+    let center = transform.position_from_point(&PlotPoint::new(0.0, 0.0));
+    let dpos_dvalue = transform.dpos_dvalue_x().abs();
+
+    // The range of radii that is visible in the frame:
+    let min_radius = frame.distance_to_pos(center);
+    let max_radius = [
+        frame.left_top(),
+        frame.right_top(),
+        frame.left_bottom(),
+        frame.right_bottom(),
+    ]
+    .iter()
+    .map(|corner| corner.distance(center))
+    .fold(0.0, f32::max);
+
+    let font_id = TextStyle::Body.resolve(ui.style());
+
+    let input = GridInput {
+        bounds: (
+            min_radius as f64 / dpos_dvalue,
+            max_radius as f64 / dpos_dvalue,
+        ),
+        base_step_size: MIN_LINE_SPACING_IN_POINTS / dpos_dvalue,
+    };
+    let range = input.bounds.0..=input.bounds.1;
+    for mark in grid_spacer(input) {
+        if mark.value <= 0.0 {
+            continue;
+        }
+        let radius = (mark.value * dpos_dvalue) as f32;
+        let spacing_in_points = (mark.step_size * dpos_dvalue) as f32;
+
+        let line_alpha = remap_clamp(
+            spacing_in_points,
+            (MIN_LINE_SPACING_IN_POINTS as f32)..=300.0,
+            0.0..=0.15,
+        );
+        if line_alpha > 0.0 {
+            let stroke = Stroke::new(1.0, color_from_alpha(ui, line_alpha));
+            shapes.push(Shape::circle_stroke(center, radius, stroke));
+        }
+
+        // The radii are labeled along the spoke at 0°:
+        let text_alpha = remap_clamp(spacing_in_points, 40.0..=150.0, 0.0..=0.4);
+        if text_alpha > 0.0 {
+            shapes.push(Shape::text(
+                &ui.fonts(),
+                center + vec2(radius + 2.0, -2.0),
+                Align2::LEFT_BOTTOM,
+                AxisScale::Linear.format_tick(mark.value, &range),
+                font_id.clone(),
+                color_from_alpha(ui, text_alpha),
+            ));
+        }
+    }
+
+    let spoke_color = color_from_alpha(ui, 0.1);
+    let text_color = color_from_alpha(ui, 0.4);
+    for spoke in 0..NUM_SPOKES {
+        let angle = spoke as f32 * TAU / NUM_SPOKES as f32;
+        // Screen coordinates go down, angles go counter-clockwise:
+        let direction = vec2(angle.cos(), -angle.sin());
+        shapes.push(Shape::line_segment(
+            [center, center + max_radius * direction],
+            Stroke::new(1.0, spoke_color),
+        ));
+
+        // The angles are labeled where the spokes leave the frame:
+        if frame.contains(center) {
+            let distance = distance_to_edge(frame, center, direction) - 14.0;
+            if distance > 40.0 {
+                shapes.push(Shape::text(
+                    &ui.fonts(),
+                    center + distance * direction,
+                    Align2::CENTER_CENTER,
+                    format!("{}°", spoke * 360 / NUM_SPOKES),
+                    font_id.clone(),
+                    text_color,
+                ));
+            }
+        }
+    }
+}
+
+/// How far a ray from `from`, which is inside `frame`, goes in `direction` before leaving it.
+fn distance_to_edge(frame: Rect, from: Pos2, direction: Vec2) -> f32 {
+    let distance = |from: f32, direction: f32, min: f32, max: f32| {
+        if direction > 0.0 {
+            (max - from) / direction
+        } else if direction < 0.0 {
+            (min - from) / direction
+        } else {
+            f32::INFINITY
+        }
+    };
+    let x = distance(from.x, direction.x, frame.left(), frame.right());
+    let y = distance(from.y, direction.y, frame.top(), frame.bottom());
+    x.min(y)
+}
+
+#[test]
+fn test_distance_to_edge() {
+    let frame = Rect::from_min_max(pos2(0.0, 0.0), pos2(100.0, 50.0));
+    let from = pos2(50.0, 25.0);
+    assert_eq!(distance_to_edge(frame, from, vec2(1.0, 0.0)), 50.0);
+    assert_eq!(distance_to_edge(frame, from, vec2(0.0, -1.0)), 25.0);
+    let diagonal = vec2(1.0, 1.0).normalized();
+    assert!((distance_to_edge(frame, from, diagonal) - 25.0 * 2_f32.sqrt()).abs() < 1e-4);
+}
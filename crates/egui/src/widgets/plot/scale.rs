@@ -0,0 +1,553 @@
+//! Axis scales: how the values on an axis are mapped to positions, where their grid marks go
+//! and how they are labeled.
+
+use std::ops::RangeInclusive;
+
+use super::{log_grid_spacer, GridInput, GridMark};
+use crate::*;
+
+const MINUTE: f64 = 60.0;
+const HOUR: f64 = 60.0 * MINUTE;
+const DAY: f64 = 24.0 * HOUR;
+const WEEK: f64 = 7.0 * DAY;
+
+/// Date axes fall back to plain numbers for times further than this from 1970, about three million years.
+const MAX_DATE_TIME: f64 = 1e14;
+
+/// How the values on an axis of a [`Plot`](super::Plot) are mapped to positions on the screen.
+///
+/// Items are always given in data values, and are transformed by the scale when they are painted.
+/// Zooming, panning, margins and [`Plot::data_aspect`](super::Plot::data_aspect) act on the
+/// transformed values, so e.g. dragging a [`Self::Log`] axis moves all decades by the same distance.
+///
+/// Other scales than [`Self::Linear`] place their own grid marks, and ignore
+/// [`Plot::x_grid_spacer`](super::Plot::x_grid_spacer) and [`Plot::y_grid_spacer`](super::Plot::y_grid_spacer).
+///
+/// ```
+/// # egui::__run_test_ui(|ui| {
+/// use egui::plot::{AxisScale, Line, Plot, PlotPoints};
+/// let growth = PlotPoints::from_explicit_callback(|x| 10.0_f64.powf(x), 0.0..=6.0, 100);
+/// Plot::new("growth")
+///     .y_axis_scale(AxisScale::Log)
+///     .show(ui, |plot_ui| plot_ui.line(Line::new(growth)));
+/// # });
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AxisScale {
+    /// Evenly spaced values.
+    Linear,
+
+    /// Evenly spaced powers of ten.
+    ///
+    /// Values at or below zero can't be shown. If the items reach them, the automatic bounds
+    /// show three decades below the largest value.
+    Log,
+
+    /// Linear around zero, and logarithmic further away from it than `linear_threshold` (which must be positive),
+    /// for data spanning many orders of magnitude with both signs.
+    SymLog { linear_threshold: f64 },
+
+    /// Linear, with values in seconds since 1970-01-01 00:00:00 UTC (Unix time).
+    ///
+    /// Grid marks are placed at whole seconds, minutes, hours, days, months or years,
+    /// and labeled with the date and time `utc_offset` seconds ahead of UTC, e.g. `3600` for UTC+01:00.
+    ///
+    /// The offset is fixed: it doesn't follow daylight saving time, nor any other change of a time zone.
+    /// So to label the local time of a zone observing daylight saving time, such as CET/CEST,
+    /// the labels of one part of the year are an hour off. To make this explicit,
+    /// the dates and times shown when hovering end with the offset, e.g. `UTC+01:00`.
+    DateTime { utc_offset: i32 },
+}
+
+impl Default for AxisScale {
+    fn default() -> Self {
+        Self::Linear
+    }
+}
+
+impl AxisScale {
+    /// Transform a value into the space where the axis is evenly spaced.
+    ///
+    /// Values at or below zero are moved far below the smallest positive values on a [`Self::Log`] axis.
+    pub(crate) fn forward(self, value: f64) -> f64 {
+        match self {
+            Self::Linear | Self::DateTime { .. } => value,
+            Self::Log => {
+                if value <= 0.0 {
+                    f64::MIN_POSITIVE.log10()
+                } else {
+                    value.log10()
+                }
+            }
+            Self::SymLog { linear_threshold } => {
+                value.signum() * (1.0 + value.abs() / linear_threshold).log10()
+            }
+        }
+    }
+
+    /// The inverse of [`Self::forward`].
+    pub(crate) fn inverse(self, value: f64) -> f64 {
+        match self {
+            Self::Linear | Self::DateTime { .. } => value,
+            Self::Log => 10.0_f64.powf(value),
+            Self::SymLog { linear_threshold } => {
+                value.signum() * linear_threshold * (10.0_f64.powf(value.abs()) - 1.0)
+            }
+        }
+    }
+
+    /// Transform the bounds of a range, making sure a [`Self::Log`] axis shows some of it.
+    pub(crate) fn forward_range(self, min: f64, max: f64) -> (f64, f64) {
+        match self {
+            Self::Log if min <= 0.0 && max > 0.0 => {
+                let max = max.log10();
+                (max - 3.0, max)
+            }
+            _ => (self.forward(min), self.forward(max)),
+        }
+    }
+
+    /// Grid marks within `input.bounds`, whose `base_step_size` is measured in transformed values.
+    pub(crate) fn grid_marks(self, input: GridInput) -> Vec<GridMark> {
+        match self {
+            Self::Linear => log_grid_spacer(10)(input),
+            Self::Log | Self::SymLog { .. } => self.decade_marks(input),
+            Self::DateTime { utc_offset } => date_time_marks(input, utc_offset as f64),
+        }
+    }
+
+    /// Marks at powers of ten, and at their multiples when there is room for them.
+    fn decade_marks(self, input: GridInput) -> Vec<GridMark> {
+        let (min, max) = input.bounds;
+        let (scaled_min, scaled_max) = self.forward_range(min, max);
+        if !scaled_min.is_finite() || !scaled_max.is_finite() || scaled_min >= scaled_max {
+            return vec![];
+        }
+
+        // Skip decades when even every decade would be too close to the next one:
+        let base_step_size = input.base_step_size.abs();
+        let decade_step = [1, 2, 5, 10, 20, 50]
+            .into_iter()
+            .find(|&step| step as f64 >= base_step_size)
+            .unwrap_or(100);
+        let decade_size = 10.0_f64.powi(decade_step) - 1.0;
+
+        let (first, last, signs): (f64, f64, &[f64]) = match self {
+            Self::SymLog { linear_threshold } => {
+                let largest = self.inverse(scaled_min.abs().max(scaled_max.abs()));
+                (linear_threshold.log10(), largest.log10(), &[-1.0, 1.0])
+            }
+            _ => (scaled_min, scaled_max, &[1.0]),
+        };
+        let first = (first / decade_step as f64).floor() as i32 * decade_step;
+        let last = last.ceil().clamp(-400.0, 400.0) as i32;
+
+        let mut marks = vec![];
+        if let Self::SymLog { linear_threshold } = self {
+            marks.push(GridMark {
+                value: 0.0,
+                step_size: linear_threshold,
+            });
+        }
+        for exponent in (first.max(-400)..=last).step_by(decade_step as usize) {
+            let decade = 10.0_f64.powi(exponent);
+            for sign in signs {
+                marks.push(GridMark {
+                    value: sign * decade,
+                    step_size: decade * decade_size,
+                });
+                if decade_step == 1 {
+                    marks.extend((2..10).map(|multiple| GridMark {
+                        value: sign * multiple as f64 * decade,
+                        step_size: decade,
+                    }));
+                }
+            }
+        }
+        marks.retain(|mark| min <= mark.value && mark.value <= max);
+        marks
+    }
+
+    /// The default tick label for `value`, where `range` is the visible range of the axis.
+    pub(crate) fn format_tick(self, value: f64, range: &RangeInclusive<f64>) -> String {
+        match self {
+            Self::Linear => emath::round_to_decimals(value, 5).to_string(), // hack
+            Self::Log | Self::SymLog { .. } => format_significant(value),
+            Self::DateTime { utc_offset } => {
+                format_date_tick(value, utc_offset, range.end() - range.start())
+            }
+        }
+    }
+
+    /// The value shown when hovering the plot, where `resolution` is how much the value changes per point.
+    pub(crate) fn format_hover(self, value: f64, resolution: f64) -> String {
+        let decimals = ((-resolution.abs().log10()).ceil().at_least(0.0) as usize).at_most(6);
+        match self {
+            Self::Log | Self::SymLog { .. }
+                if value != 0.0 && !(1e-4..1e6).contains(&value.abs()) =>
+            {
+                format!("{:.3e}", value)
+            }
+            Self::DateTime { utc_offset } => {
+                format_date_time(value, utc_offset, resolution.abs() < 1.0)
+            }
+            _ => format!("{:.*}", decimals, value),
+        }
+    }
+}
+
+/// Numbers with a few significant digits, switching to scientific notation for very large or small ones.
+fn format_significant(value: f64) -> String {
+    let magnitude = value.abs();
+    if magnitude == 0.0 || (1e-4..1e6).contains(&magnitude) {
+        emath::round_to_decimals(value, 6).to_string()
+    } else {
+        let exponent = magnitude.log10().floor();
+        let mantissa = emath::round_to_decimals(value / 10.0_f64.powf(exponent), 3);
+        format!("{}e{}", mantissa, exponent)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// Dates and times
+
+/// A local date and time, split from a Unix time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DateTime {
+    year: i64,
+    month: u32,
+    day: u32,
+    /// Milliseconds since midnight.
+    millis: i64,
+}
+
+impl DateTime {
+    fn new(value: f64, utc_offset: f64) -> Option<Self> {
+        let local = value + utc_offset;
+        if !local.is_finite() || local.abs() > MAX_DATE_TIME {
+            return None;
+        }
+        let millis = (local * 1000.0).round() as i64;
+        let (year, month, day) = civil_from_days(millis.div_euclid(86_400_000));
+        Some(Self {
+            year,
+            month,
+            day,
+            millis: millis.rem_euclid(86_400_000),
+        })
+    }
+
+    /// `HH:MM`, with seconds and milliseconds only if they are needed or `with_seconds` is set.
+    fn format_time(&self, with_seconds: bool) -> String {
+        let seconds = self.millis / 1000;
+        let (hours, minutes, seconds, millis) = (
+            seconds / 3600,
+            seconds / 60 % 60,
+            seconds % 60,
+            self.millis % 1000,
+        );
+        if millis != 0 {
+            format!("{:02}:{:02}:{:02}.{:03}", hours, minutes, seconds, millis)
+        } else if seconds != 0 || with_seconds {
+            format!("{:02}:{:02}:{:02}", hours, minutes, seconds)
+        } else {
+            format!("{:02}:{:02}", hours, minutes)
+        }
+    }
+
+    fn format_date(&self) -> String {
+        format!("{}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A tick label showing only as much of the date and time as is needed to tell the visible ticks apart.
+fn format_date_tick(value: f64, utc_offset: i32, width: f64) -> String {
+    let date_time = match DateTime::new(value, utc_offset as f64) {
+        Some(date_time) => date_time,
+        None => return format_significant(value),
+    };
+    if date_time.millis == 0 {
+        if date_time.month == 1 && date_time.day == 1 && width > 2.0 * 365.0 * DAY {
+            date_time.year.to_string()
+        } else if date_time.day == 1 && width > 60.0 * DAY {
+            format!("{}-{:02}", date_time.year, date_time.month)
+        } else {
+            date_time.format_date()
+        }
+    } else {
+        let time = date_time.format_time(width < 2.0 * MINUTE);
+        if width > 2.0 * DAY {
+            format!("{:02}-{:02} {}", date_time.month, date_time.day, time)
+        } else {
+            time
+        }
+    }
+}
+
+/// The full date and time, with the offset from UTC.
+fn format_date_time(value: f64, utc_offset: i32, with_millis: bool) -> String {
+    let date_time = match DateTime::new(value, utc_offset as f64) {
+        Some(date_time) => date_time,
+        None => return format_significant(value),
+    };
+    let time = if with_millis {
+        date_time.format_time(true)
+    } else {
+        DateTime {
+            millis: date_time.millis / 1000 * 1000,
+            ..date_time
+        }
+        .format_time(true)
+    };
+    let zone = if utc_offset == 0 {
+        "UTC".to_owned()
+    } else {
+        let sign = if utc_offset < 0 { '-' } else { '+' };
+        let minutes = utc_offset.abs() / 60;
+        format!("UTC{}{:02}:{:02}", sign, minutes / 60, minutes % 60)
+    };
+    format!("{} {} {}", date_time.format_date(), time, zone)
+}
+
+/// The distance between the grid marks of a date axis.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TimeStep {
+    Seconds(f64),
+    Months(i64),
+    Years(i64),
+}
+
+/// Steps of at least a second and less than a month.
+const SECOND_STEPS: [f64; 20] = [
+    1.0,
+    2.0,
+    5.0,
+    10.0,
+    15.0,
+    30.0,
+    MINUTE,
+    2.0 * MINUTE,
+    5.0 * MINUTE,
+    10.0 * MINUTE,
+    15.0 * MINUTE,
+    30.0 * MINUTE,
+    HOUR,
+    2.0 * HOUR,
+    3.0 * HOUR,
+    6.0 * HOUR,
+    12.0 * HOUR,
+    DAY,
+    2.0 * DAY,
+    WEEK,
+];
+
+const MONTH_STEPS: [i64; 4] = [1, 2, 3, 6];
+
+impl TimeStep {
+    /// The smallest step at least `min_seconds` long.
+    fn at_least(min_seconds: f64) -> Self {
+        let mut step = if min_seconds < 1.0 {
+            // 1, 2 or 5 times a power of ten:
+            Self::Seconds(10.0_f64.powf(min_seconds.log10().floor()))
+        } else {
+            Self::Seconds(1.0)
+        };
+        while step.approx_seconds() < min_seconds {
+            step = step.next();
+        }
+        step
+    }
+
+    /// The length in seconds, with months of 30 days and years of 365 days.
+    fn approx_seconds(self) -> f64 {
+        match self {
+            Self::Seconds(seconds) => seconds,
+            Self::Months(months) => months as f64 * 30.0 * DAY,
+            Self::Years(years) => years as f64 * 365.0 * DAY,
+        }
+    }
+
+    fn next(self) -> Self {
+        match self {
+            Self::Seconds(seconds) if seconds < 1.0 => {
+                let power = 10.0_f64.powf(seconds.log10().floor());
+                let multiple = (seconds / power).round();
+                Self::Seconds(next_1_2_5(multiple) as f64 * power)
+            }
+            Self::Seconds(seconds) => SECOND_STEPS
+                .into_iter()
+                .find(|&step| step > seconds)
+                .map_or(Self::Months(1), Self::Seconds),
+            Self::Months(months) => MONTH_STEPS
+                .into_iter()
+                .find(|&step| step > months)
+                .map_or(Self::Years(1), Self::Months),
+            Self::Years(years) => {
+                let power = 10_i64.pow((years as f64).log10().floor() as u32);
+                Self::Years(next_1_2_5((years / power) as f64) * power)
+            }
+        }
+    }
+
+    /// Add the marks within `min..=max` to `marks`.
+    fn marks(self, (min, max): (f64, f64), utc_offset: f64, marks: &mut Vec<GridMark>) {
+        let step_size = self.approx_seconds();
+        let months = match self {
+            Self::Seconds(seconds) => {
+                // Weeks start on Mondays, 1970-01-05 being the first one:
+                let origin = if seconds == WEEK { 4.0 * DAY } else { 0.0 } - utc_offset;
+                let first = ((min - origin) / seconds).ceil() as i64;
+                let last = ((max - origin) / seconds).floor() as i64;
+                marks.extend((first..=last).map(|i| GridMark {
+                    value: origin + i as f64 * seconds,
+                    step_size,
+                }));
+                return;
+            }
+            Self::Months(months) => months,
+            Self::Years(years) => 12 * years,
+        };
+
+        let start = match DateTime::new(min, utc_offset) {
+            Some(start) => start,
+            None => return,
+        };
+        // Months since the year 0, rounded up to a multiple of the step:
+        let month_index = start.year * 12 + start.month as i64 - 1;
+        let mut month_index = -(-month_index).div_euclid(months) * months;
+        loop {
+            let (year, month) = (month_index.div_euclid(12), month_index.rem_euclid(12) + 1);
+            let value = days_from_civil(year, month as u32, 1) as f64 * DAY - utc_offset;
+            if value > max {
+                break;
+            }
+            if value >= min {
+                marks.push(GridMark { value, step_size });
+            }
+            month_index += months;
+        }
+    }
+}
+
+/// The next of 1, 2, 5, 10.
+fn next_1_2_5(multiple: f64) -> i64 {
+    if multiple < 2.0 {
+        2
+    } else if multiple < 5.0 {
+        5
+    } else {
+        10
+    }
+}
+
+fn date_time_marks(input: GridInput, utc_offset: f64) -> Vec<GridMark> {
+    let (min, max) = input.bounds;
+    let base_step_size = input.base_step_size.abs();
+    let is_date = min.abs() <= MAX_DATE_TIME && max.abs() <= MAX_DATE_TIME;
+    let is_valid = is_date && base_step_size > 0.0 && min < max;
+    if !is_valid {
+        return log_grid_spacer(10)(input);
+    }
+
+    let mut marks = vec![];
+    let mut step = TimeStep::at_least(base_step_size);
+    for _ in 0..3 {
+        step.marks((min, max), utc_offset, &mut marks);
+        step = step.next();
+    }
+    marks
+}
+
+/// Year, month and day of the given number of days since 1970-01-01,
+/// in the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    // From Howard Hinnant's `chrono`-compatible date algorithms, with eras of 400 years starting on March 1st:
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year, month, day)
+}
+
+/// The inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let shifted_month = (month as i64 + 9) % 12;
+    let day_of_year = (153 * shifted_month + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[test]
+fn test_civil_days() {
+    assert_eq!(civil_from_days(0), (1970, 1, 1));
+    assert_eq!(civil_from_days(19_249), (2022, 9, 14));
+    assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    assert_eq!(civil_from_days(11_016), (2000, 2, 29));
+    for days in (-1_000_000..1_000_000).step_by(997) {
+        let (year, month, day) = civil_from_days(days);
+        assert_eq!(days_from_civil(year, month, day), days);
+    }
+}
+
+#[test]
+fn test_date_time_marks() {
+    let utc_offset = 2.0 * HOUR;
+    let start = days_from_civil(2022, 1, 15) as f64 * DAY;
+    let end = days_from_civil(2022, 12, 15) as f64 * DAY;
+    let mut marks = vec![];
+    TimeStep::Months(3).marks((start, end), utc_offset, &mut marks);
+    let dates: Vec<_> = marks
+        .iter()
+        .map(|mark| format_date_tick(mark.value, 2 * 3600, end - start))
+        .collect();
+    assert_eq!(dates, ["2022-04", "2022-07", "2022-10"]);
+
+    assert_eq!(TimeStep::at_least(0.03), TimeStep::Seconds(0.05));
+    assert_eq!(TimeStep::at_least(40.0), TimeStep::Seconds(MINUTE));
+    assert_eq!(TimeStep::at_least(20.0 * DAY), TimeStep::Months(1));
+    assert_eq!(TimeStep::at_least(400.0 * DAY), TimeStep::Years(2));
+
+    assert_eq!(
+        format_date_time(start + 3723.5, -5 * 3600, true),
+        "2022-01-14 20:02:03.500 UTC-05:00"
+    );
+    assert_eq!(format_date_tick(start + 3720.0, 0, HOUR), "01:02");
+}
+
+#[test]
+fn test_log_scale() {
+    let scale = AxisScale::Log;
+    assert_eq!(scale.forward(1000.0), 3.0);
+    assert!((scale.inverse(-2.0) - 0.01).abs() < 1e-12);
+    assert_eq!(scale.forward_range(-5.0, 100.0), (-1.0, 2.0));
+
+    let marks = scale.grid_marks(GridInput {
+        bounds: (1.0, 20.0),
+        base_step_size: 0.1,
+    });
+    let values: Vec<_> = marks.iter().map(|mark| mark.value).collect();
+    assert_eq!(
+        values,
+        [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 20.0]
+    );
+    assert_eq!(format_significant(2e-7), "2e-7");
+
+    let symlog = AxisScale::SymLog {
+        linear_threshold: 1.0,
+    };
+    assert!((symlog.inverse(symlog.forward(-250.0)) + 250.0).abs() < 1e-9);
+}
@@ -1,6 +1,6 @@
 use std::ops::RangeInclusive;
 
-use super::PlotPoint;
+use super::{AxisScale, GridMark, PlotPoint};
 use crate::*;
 
 /// 2D bounding box of f64 precision.
@@ -130,14 +130,41 @@ impl PlotBounds {
         self.translate_y(delta.y as f64);
     }
 
-    pub(crate) fn add_relative_margin_x(&mut self, margin_fraction: Vec2) {
-        let width = self.width().max(0.0);
-        self.expand_x(margin_fraction.x as f64 * width);
+    /// Add a margin relative to the size in the space of `scales`.
+    pub(crate) fn add_relative_margin_x(&mut self, margin_fraction: Vec2, scales: [AxisScale; 2]) {
+        let mut scaled = self.scaled(scales);
+        let width = scaled.width().max(0.0);
+        scaled.expand_x(margin_fraction.x as f64 * width);
+        self.set_x(&scaled.unscaled(scales));
     }
 
-    pub(crate) fn add_relative_margin_y(&mut self, margin_fraction: Vec2) {
-        let height = self.height().max(0.0);
-        self.expand_y(margin_fraction.y as f64 * height);
+    /// Add a margin relative to the size in the space of `scales`.
+    pub(crate) fn add_relative_margin_y(&mut self, margin_fraction: Vec2, scales: [AxisScale; 2]) {
+        let mut scaled = self.scaled(scales);
+        let height = scaled.height().max(0.0);
+        scaled.expand_y(margin_fraction.y as f64 * height);
+        self.set_y(&scaled.unscaled(scales));
+    }
+
+    /// The bounds in the space where the axes are evenly spaced, see [`AxisScale`].
+    pub(crate) fn scaled(self, scales: [AxisScale; 2]) -> Self {
+        let mut scaled = self;
+        for (axis, scale) in scales.into_iter().enumerate() {
+            let (min, max) = scale.forward_range(self.min[axis], self.max[axis]);
+            scaled.min[axis] = min;
+            scaled.max[axis] = max;
+        }
+        scaled
+    }
+
+    /// The inverse of [`Self::scaled`].
+    pub(crate) fn unscaled(self, scales: [AxisScale; 2]) -> Self {
+        let mut bounds = self;
+        for (axis, scale) in scales.into_iter().enumerate() {
+            bounds.min[axis] = scale.inverse(self.min[axis]);
+            bounds.max[axis] = scale.inverse(self.max[axis]);
+        }
+        bounds
     }
 
     pub(crate) fn range_x(&self) -> RangeInclusive<f64> {
@@ -162,6 +189,9 @@ impl PlotBounds {
 }
 
 /// Contains the screen rectangle and the plot bounds and provides methods to transform them.
+///
+/// The bounds are in data values, while zooming, panning and the aspect ratio act on
+/// the values transformed by the [`AxisScale`]s.
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[derive(Clone)]
 pub(crate) struct ScreenTransform {
@@ -171,6 +201,12 @@ pub(crate) struct ScreenTransform {
     /// The plot bounds.
     bounds: PlotBounds,
 
+    /// [`Self::bounds`] transformed by the [`AxisScale`]s, kept to not transform them for every point.
+    scaled_bounds: PlotBounds,
+
+    /// The scales of the X and Y axis.
+    scales: [AxisScale; 2],
+
     /// Whether to always center the x-range of the bounds.
     x_centered: bool,
 
@@ -179,23 +215,33 @@ pub(crate) struct ScreenTransform {
 }
 
 impl ScreenTransform {
-    pub fn new(frame: Rect, mut bounds: PlotBounds, x_centered: bool, y_centered: bool) -> Self {
+    pub fn new(
+        frame: Rect,
+        bounds: PlotBounds,
+        scales: [AxisScale; 2],
+        x_centered: bool,
+        y_centered: bool,
+    ) -> Self {
+        let mut scaled = bounds.scaled(scales);
+
         // Make sure they are not empty.
-        if !bounds.is_valid() {
-            bounds = PlotBounds::new_symmetrical(1.0);
+        if !scaled.is_valid() {
+            scaled = PlotBounds::new_symmetrical(1.0);
         }
 
         // Scale axes so that the origin is in the center.
         if x_centered {
-            bounds.make_x_symmetrical();
+            scaled.make_x_symmetrical();
         };
         if y_centered {
-            bounds.make_y_symmetrical();
+            scaled.make_y_symmetrical();
         };
 
         Self {
             frame,
-            bounds,
+            bounds: scaled.unscaled(scales),
+            scaled_bounds: scaled,
+            scales,
             x_centered,
             y_centered,
         }
@@ -211,6 +257,20 @@ impl ScreenTransform {
 
     pub fn set_bounds(&mut self, bounds: PlotBounds) {
         self.bounds = bounds;
+        self.scaled_bounds = bounds.scaled(self.scales);
+    }
+
+    fn set_scaled_bounds(&mut self, scaled_bounds: PlotBounds) {
+        self.bounds = scaled_bounds.unscaled(self.scales);
+        self.scaled_bounds = scaled_bounds;
+    }
+
+    pub fn scales(&self) -> [AxisScale; 2] {
+        self.scales
+    }
+
+    fn scaled_bounds(&self) -> PlotBounds {
+        self.scaled_bounds
     }
 
     pub fn translate_bounds(&mut self, mut delta_pos: Vec2) {
@@ -222,48 +282,61 @@ impl ScreenTransform {
         }
         delta_pos.x *= self.dvalue_dpos()[0] as f32;
         delta_pos.y *= self.dvalue_dpos()[1] as f32;
-        self.bounds.translate(delta_pos);
+        let mut scaled = self.scaled_bounds();
+        scaled.translate(delta_pos);
+        self.set_scaled_bounds(scaled);
     }
 
     /// Zoom by a relative factor with the given screen position as center.
     pub fn zoom(&mut self, zoom_factor: Vec2, center: Pos2) {
-        let center = self.value_from_position(center);
+        let center = self.scaled_value_from_position(center);
 
-        let mut new_bounds = self.bounds;
+        let mut new_bounds = self.scaled_bounds();
         new_bounds.min[0] = center.x + (new_bounds.min[0] - center.x) / (zoom_factor.x as f64);
         new_bounds.max[0] = center.x + (new_bounds.max[0] - center.x) / (zoom_factor.x as f64);
         new_bounds.min[1] = center.y + (new_bounds.min[1] - center.y) / (zoom_factor.y as f64);
         new_bounds.max[1] = center.y + (new_bounds.max[1] - center.y) / (zoom_factor.y as f64);
 
         if new_bounds.is_valid() {
-            self.bounds = new_bounds;
+            self.set_scaled_bounds(new_bounds);
         }
     }
 
     pub fn position_from_point(&self, value: &PlotPoint) -> Pos2 {
+        let bounds = self.scaled_bounds();
         let x = remap(
-            value.x,
-            self.bounds.min[0]..=self.bounds.max[0],
+            self.scales[0].forward(value.x),
+            bounds.min[0]..=bounds.max[0],
             (self.frame.left() as f64)..=(self.frame.right() as f64),
         );
         let y = remap(
-            value.y,
-            self.bounds.min[1]..=self.bounds.max[1],
+            self.scales[1].forward(value.y),
+            bounds.min[1]..=bounds.max[1],
             (self.frame.bottom() as f64)..=(self.frame.top() as f64), // negated y axis!
         );
         pos2(x as f32, y as f32)
     }
 
     pub fn value_from_position(&self, pos: Pos2) -> PlotPoint {
+        let scaled = self.scaled_value_from_position(pos);
+        PlotPoint::new(
+            self.scales[0].inverse(scaled.x),
+            self.scales[1].inverse(scaled.y),
+        )
+    }
+
+    /// The value at `pos`, transformed by the axis scales.
+    fn scaled_value_from_position(&self, pos: Pos2) -> PlotPoint {
+        let bounds = self.scaled_bounds();
         let x = remap(
             pos.x as f64,
             (self.frame.left() as f64)..=(self.frame.right() as f64),
-            self.bounds.min[0]..=self.bounds.max[0],
+            bounds.min[0]..=bounds.max[0],
         );
         let y = remap(
             pos.y as f64,
             (self.frame.bottom() as f64)..=(self.frame.top() as f64), // negated y axis!
-            self.bounds.min[1]..=self.bounds.max[1],
+            bounds.min[1]..=bounds.max[1],
         );
         PlotPoint::new(x, y)
     }
@@ -282,14 +355,14 @@ impl ScreenTransform {
         rect
     }
 
-    /// delta position / delta value
+    /// delta position / delta value, with the value transformed by the axis scale
     pub fn dpos_dvalue_x(&self) -> f64 {
-        self.frame.width() as f64 / self.bounds.width()
+        self.frame.width() as f64 / self.scaled_bounds().width()
     }
 
-    /// delta position / delta value
+    /// delta position / delta value, with the value transformed by the axis scale
     pub fn dpos_dvalue_y(&self) -> f64 {
-        -self.frame.height() as f64 / self.bounds.height() // negated y axis!
+        -self.frame.height() as f64 / self.scaled_bounds().height() // negated y axis!
     }
 
     /// delta position / delta value
@@ -302,10 +375,26 @@ impl ScreenTransform {
         [1.0 / self.dpos_dvalue_x(), 1.0 / self.dpos_dvalue_y()]
     }
 
+    /// How much the value changes per point on the given axis, close to `value`.
+    pub fn resolution(&self, axis: usize, value: f64) -> f64 {
+        let scale = self.scales[axis];
+        (scale.inverse(scale.forward(value) + self.dvalue_dpos()[axis]) - value).abs()
+    }
+
+    /// The distance in points between `mark` and the next mark of the same thickness.
+    pub fn mark_spacing(&self, axis: usize, mark: &GridMark) -> f32 {
+        // Symmetric scales are also spaced symmetrically, so measure away from zero:
+        let scale = self.scales[axis];
+        let value = mark.value.abs();
+        let distance = scale.forward(value + mark.step_size) - scale.forward(value);
+        (self.dpos_dvalue()[axis] * distance).abs() as f32
+    }
+
     fn aspect(&self) -> f64 {
+        let bounds = self.scaled_bounds();
         let rw = self.frame.width() as f64;
         let rh = self.frame.height() as f64;
-        (self.bounds.width() / rw) / (self.bounds.height() / rh)
+        (bounds.width() / rw) / (bounds.height() / rh)
     }
 
     /// Sets the aspect ratio by expanding the x- or y-axis.
@@ -320,13 +409,13 @@ impl ScreenTransform {
             return;
         }
 
+        let mut bounds = self.scaled_bounds();
         if current_aspect < aspect {
-            self.bounds
-                .expand_x((aspect / current_aspect - 1.0) * self.bounds.width() * 0.5);
+            bounds.expand_x((aspect / current_aspect - 1.0) * bounds.width() * 0.5);
         } else {
-            self.bounds
-                .expand_y((current_aspect / aspect - 1.0) * self.bounds.height() * 0.5);
+            bounds.expand_y((current_aspect / aspect - 1.0) * bounds.height() * 0.5);
         }
+        self.set_scaled_bounds(bounds);
     }
 
     /// Sets the aspect ratio by changing either the X or Y axis (callers choice).
@@ -339,12 +428,12 @@ impl ScreenTransform {
             return;
         }
 
+        let mut bounds = self.scaled_bounds();
         if change_x {
-            self.bounds
-                .expand_x((aspect / current_aspect - 1.0) * self.bounds.width() * 0.5);
+            bounds.expand_x((aspect / current_aspect - 1.0) * bounds.width() * 0.5);
         } else {
-            self.bounds
-                .expand_y((current_aspect / aspect - 1.0) * self.bounds.height() * 0.5);
+            bounds.expand_y((current_aspect / aspect - 1.0) * bounds.height() * 0.5);
         }
+        self.set_scaled_bounds(bounds);
     }
 }
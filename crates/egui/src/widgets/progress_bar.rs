@@ -14,6 +14,7 @@ pub struct ProgressBar {
     desired_width: Option<f32>,
     text: Option<ProgressBarText>,
     animate: bool,
+    indeterminate: bool,
 }
 
 impl ProgressBar {
@@ -24,6 +25,19 @@ impl ProgressBar {
             desired_width: None,
             text: None,
             animate: false,
+            indeterminate: false,
+        }
+    }
+
+    /// A progress bar for when the amount of progress is unknown.
+    ///
+    /// Shows a segment sweeping back and forth across the bar.
+    /// The animation is kept going with [`Context::request_repaint_after`],
+    /// so it will not make egui repaint faster than needed while it is visible.
+    pub fn indeterminate() -> Self {
+        Self {
+            indeterminate: true,
+            ..Self::new(0.0)
         }
     }
 
@@ -40,6 +54,8 @@ impl ProgressBar {
     }
 
     /// Show the progress in percent on the progress bar.
+    ///
+    /// Ignored for [`Self::indeterminate`] progress bars.
     pub fn show_percentage(mut self) -> Self {
         self.text = Some(ProgressBarText::Percentage);
         self
@@ -61,9 +77,15 @@ impl Widget for ProgressBar {
             desired_width,
             text,
             animate,
+            indeterminate,
         } = self;
 
-        let animate = animate && progress < 1.0;
+        let animate = animate && progress < 1.0 && !indeterminate;
+
+        let label = match &text {
+            Some(ProgressBarText::Custom(text)) => Some(text.text().to_owned()),
+            _ => None,
+        };
 
         let desired_width =
             desired_width.unwrap_or_else(|| ui.available_size_before_wrap().x.at_least(96.0));
@@ -84,55 +106,78 @@ impl Widget for ProgressBar {
                 visuals.extreme_bg_color,
                 Stroke::none(),
             );
-            let inner_rect = Rect::from_min_size(
-                outer_rect.min,
-                vec2(
-                    (outer_rect.width() * progress).at_least(outer_rect.height()),
-                    outer_rect.height(),
-                ),
-            );
 
-            let (dark, bright) = (0.7, 1.0);
-            let color_factor = if animate {
-                lerp(dark..=bright, ui.input().time.cos().abs())
+            if indeterminate {
+                // 30 fps is plenty for this, and much cheaper than repainting continuously:
+                ui.ctx()
+                    .request_repaint_after(std::time::Duration::from_secs_f32(1.0 / 30.0));
+
+                // A segment sweeping back and forth, slowing down at the ends:
+                let period = 2.0;
+                let t = (ui.input().time % period) / period;
+                let t = 0.5 - 0.5 * (t * std::f64::consts::TAU).cos();
+                let segment_width = (0.3 * outer_rect.width()).at_least(outer_rect.height());
+                let left = outer_rect.left() + t as f32 * (outer_rect.width() - segment_width);
+                let segment_rect =
+                    Rect::from_x_y_ranges(left..=left + segment_width, outer_rect.y_range());
+                ui.painter().rect(
+                    segment_rect,
+                    rounding,
+                    visuals.selection.bg_fill,
+                    Stroke::none(),
+                );
             } else {
-                bright
-            };
+                let inner_rect = Rect::from_min_size(
+                    outer_rect.min,
+                    vec2(
+                        (outer_rect.width() * progress).at_least(outer_rect.height()),
+                        outer_rect.height(),
+                    ),
+                );
 
-            ui.painter().rect(
-                inner_rect,
-                rounding,
-                Color32::from(Rgba::from(visuals.selection.bg_fill) * color_factor as f32),
-                Stroke::none(),
-            );
+                let (dark, bright) = (0.7, 1.0);
+                let color_factor = if animate {
+                    lerp(dark..=bright, ui.input().time.cos().abs())
+                } else {
+                    bright
+                };
 
-            if animate {
-                let n_points = 20;
-                let start_angle = ui.input().time * std::f64::consts::TAU;
-                let end_angle = start_angle + 240f64.to_radians() * ui.input().time.sin();
-                let circle_radius = rounding - 2.0;
-                let points: Vec<Pos2> = (0..n_points)
-                    .map(|i| {
-                        let angle = lerp(start_angle..=end_angle, i as f64 / n_points as f64);
-                        let (sin, cos) = angle.sin_cos();
-                        inner_rect.right_center()
-                            + circle_radius * vec2(cos as f32, sin as f32)
-                            + vec2(-rounding, 0.0)
-                    })
-                    .collect();
-                ui.painter().add(Shape::line(
-                    points,
-                    Stroke::new(2.0, visuals.faint_bg_color),
-                ));
+                ui.painter().rect(
+                    inner_rect,
+                    rounding,
+                    Color32::from(Rgba::from(visuals.selection.bg_fill) * color_factor as f32),
+                    Stroke::none(),
+                );
+
+                if animate {
+                    let n_points = 20;
+                    let start_angle = ui.input().time * std::f64::consts::TAU;
+                    let end_angle = start_angle + 240f64.to_radians() * ui.input().time.sin();
+                    let circle_radius = rounding - 2.0;
+                    let points: Vec<Pos2> = (0..n_points)
+                        .map(|i| {
+                            let angle = lerp(start_angle..=end_angle, i as f64 / n_points as f64);
+                            let (sin, cos) = angle.sin_cos();
+                            inner_rect.right_center()
+                                + circle_radius * vec2(cos as f32, sin as f32)
+                                + vec2(-rounding, 0.0)
+                        })
+                        .collect();
+                    ui.painter().add(Shape::line(
+                        points,
+                        Stroke::new(2.0, visuals.faint_bg_color),
+                    ));
+                }
             }
 
-            if let Some(text_kind) = text {
-                let text = match text_kind {
-                    ProgressBarText::Custom(text) => text,
-                    ProgressBarText::Percentage => {
-                        format!("{}%", (progress * 100.0) as usize).into()
-                    }
-                };
+            let text = match text {
+                Some(ProgressBarText::Custom(text)) => Some(text),
+                Some(ProgressBarText::Percentage) if !indeterminate => {
+                    Some(format!("{}%", (progress * 100.0) as usize).into())
+                }
+                _ => None,
+            };
+            if let Some(text) = text {
                 let galley = text.into_galley(ui, Some(false), f32::INFINITY, TextStyle::Button);
                 let text_pos = outer_rect.left_center() - Vec2::new(0.0, galley.size().y / 2.0)
                     + vec2(ui.spacing().item_spacing.x, 0.0);
@@ -147,6 +192,47 @@ impl Widget for ProgressBar {
             }
         }
 
+        response.widget_info(|| WidgetInfo {
+            label: label.clone(),
+            value: (!indeterminate).then(|| progress as f64),
+            min: Some(0.0),
+            max: Some(1.0),
+            ..WidgetInfo::new(WidgetType::ProgressBar)
+        });
         response
     }
 }
+
+#[test]
+fn test_indeterminate_widgets() {
+    let ctx = Context::default();
+    ctx.options().accessibility_tree = true;
+
+    let mut checked = false;
+    let mut run_frame = || {
+        ctx.run(Default::default(), |ctx| {
+            CentralPanel::default().show(ctx, |ui| {
+                ui.add(Checkbox::new(&mut checked, "Select all").indeterminate(true));
+                ui.add(ProgressBar::indeterminate());
+            });
+        })
+    };
+    run_frame();
+    let full_output = run_frame();
+
+    // The progress bar is animated, but without asking for continuous repaints:
+    assert!(!full_output.repaint_after.is_zero());
+    assert!(full_output.repaint_after <= std::time::Duration::from_secs_f32(1.0 / 30.0));
+
+    let tree = full_output.platform_output.accessibility_tree.unwrap();
+    let (_, checkbox) = tree.find_by_label("Select all").unwrap();
+    assert!(checkbox.info.mixed);
+    assert_eq!(checkbox.info.description(), "Select all: mixed checkbox");
+
+    let (_, progress_bar) = tree
+        .nodes
+        .iter()
+        .find(|(_, node)| node.info.typ == WidgetType::ProgressBar)
+        .unwrap();
+    assert_eq!(progress_bar.info.value, None);
+}
@@ -354,6 +354,11 @@ impl<'a> Slider<'a> {
             self.set_value(new_value);
         }
 
+        let accessibility_value = ui.input().accessibility_set_value(response.id);
+        if let Some(new_value) = accessibility_value {
+            self.set_value(new_value);
+        }
+
         if response.has_focus() {
             let (dec_key, inc_key) = match self.orientation {
                 SliderOrientation::Horizontal => (Key::ArrowLeft, Key::ArrowRight),
@@ -568,7 +573,13 @@ impl<'a> Widget for Slider<'a> {
         let mut response = inner_response.inner | inner_response.response;
         let value = self.get_value();
         response.changed = value != old_value;
-        response.widget_info(|| WidgetInfo::slider(value, &self.text));
+        let (min, max) = (*self.range.start(), *self.range.end());
+        response.widget_info(|| WidgetInfo {
+            min: Some(min.min(max)),
+            max: Some(min.max(max)),
+            step: self.step,
+            ..WidgetInfo::slider(value, &self.text)
+        });
         response
     }
 }
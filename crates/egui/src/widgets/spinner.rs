@@ -1,6 +1,6 @@
 use epaint::{emath::lerp, vec2, Pos2, Shape, Stroke};
 
-use crate::{Response, Sense, Ui, Widget};
+use crate::{Response, Sense, Ui, Widget, WidgetInfo, WidgetType};
 
 /// A spinner widget used to indicate loading.
 ///
@@ -53,6 +53,8 @@ impl Widget for Spinner {
             ));
         }
 
+        // A progress bar without a value, i.e. of unknown progress:
+        response.widget_info(|| WidgetInfo::new(WidgetType::ProgressBar));
         response
     }
 }
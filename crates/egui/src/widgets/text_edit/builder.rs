@@ -545,6 +545,27 @@ impl<'t> TextEdit<'t> {
             cursor_range = Some(new_cursor_range);
         }
 
+        if interactive && text.is_mutable() {
+            let new_text = ui
+                .input()
+                .accessibility_actions(id)
+                .filter_map(|action| match action {
+                    AccessibilityAction::SetText(new_text) => Some(new_text.clone()),
+                    _ => None,
+                })
+                .last();
+            if let Some(new_text) = new_text {
+                text.replace(&new_text);
+                galley = layouter(ui, text.as_str(), wrap_width);
+                let end = CursorRange::one(galley.end());
+                state.set_cursor_range(Some(end));
+                if cursor_range.is_some() {
+                    cursor_range = Some(end);
+                }
+                response.mark_changed();
+            }
+        }
+
         let mut text_draw_pos = response.rect.min;
 
         // Visual clipping for singleline text editor with text larger than width
@@ -646,7 +667,8 @@ impl<'t> TextEdit<'t> {
                 .ctx
                 .output()
                 .events
-                .push(OutputEvent::TextSelectionChanged(info));
+                .push(OutputEvent::TextSelectionChanged(info.clone()));
+            response.accessibility_node(|| info);
         } else {
             response.widget_info(|| {
                 WidgetInfo::text_edit(
@@ -1,4 +1,4 @@
-use egui::{color::*, widgets::color_picker::show_color, TextureFilter, *};
+use egui::{color::*, widgets::color_picker::show_color, TextureOptions, *};
 use std::collections::HashMap;
 
 const GRADIENT_SIZE: Vec2 = vec2(256.0, 24.0);
@@ -337,7 +337,7 @@ impl TextureManager {
                     size: [width, height],
                     pixels,
                 },
-                TextureFilter::Linear,
+                TextureOptions::LINEAR,
             )
         })
     }
@@ -3,8 +3,8 @@ use egui::*;
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 #[cfg_attr(feature = "serde", serde(default))]
 pub struct Painting {
-    /// in 0-1 normalized coordinates
-    lines: Vec<Vec<Pos2>>,
+    /// in 0-1 normalized coordinates, and how wide the line is there (pen pressure).
+    lines: Vec<Vec<(Pos2, f32)>>,
     stroke: Stroke,
 }
 
@@ -43,13 +43,39 @@ impl Painting {
             self.lines.push(vec![]);
         }
 
+        let pen = ui.input().pen();
+        if let Some(pen) = pen.filter(|pen| pen.eraser && pen.contact) {
+            let eraser_radius = 8.0;
+            let num_lines = self.lines.len();
+            self.lines.retain(|line| {
+                line.iter()
+                    .all(|(pos, _)| (to_screen * *pos).distance(pen.pos) > eraser_radius)
+            });
+            if self.lines.len() != num_lines {
+                self.lines.push(vec![]);
+                response.mark_changed();
+            }
+        }
+
         let current_line = self.lines.last_mut().unwrap();
 
         if let Some(pointer_pos) = response.interact_pointer_pos() {
-            let canvas_pos = from_screen * pointer_pos;
-            if current_line.last() != Some(&canvas_pos) {
-                current_line.push(canvas_pos);
-                response.mark_changed();
+            // With a pen, use every sample (not just the last one) and how hard it is pressed:
+            let mut points: Vec<(Pos2, f32)> = ui
+                .input()
+                .pen_samples()
+                .filter(|pen| pen.contact && !pen.eraser)
+                .map(|pen| (pen.pos, pen.pressure))
+                .collect();
+            if pen.is_none() {
+                points.push((pointer_pos, 1.0));
+            }
+            for (pos, pressure) in points {
+                let canvas_pos = from_screen * pos;
+                if current_line.last().map(|(pos, _)| pos) != Some(&canvas_pos) {
+                    current_line.push((canvas_pos, pressure));
+                    response.mark_changed();
+                }
             }
         } else if !current_line.is_empty() {
             self.lines.push(vec![]);
@@ -58,9 +84,22 @@ impl Painting {
 
         let mut shapes = vec![];
         for line in &self.lines {
-            if line.len() >= 2 {
-                let points: Vec<Pos2> = line.iter().map(|p| to_screen * *p).collect();
-                shapes.push(egui::Shape::line(points, self.stroke));
+            if line.iter().all(|(_, pressure)| *pressure == 1.0) {
+                if line.len() >= 2 {
+                    let points: Vec<Pos2> = line.iter().map(|(p, _)| to_screen * *p).collect();
+                    shapes.push(egui::Shape::line(points, self.stroke));
+                }
+            } else {
+                // Drawn with a pen, so the width varies:
+                for segment in line.windows(2) {
+                    let (a, pressure_a) = segment[0];
+                    let (b, pressure_b) = segment[1];
+                    let width = self.stroke.width * 0.5 * (pressure_a + pressure_b);
+                    shapes.push(egui::Shape::line_segment(
+                        [to_screen * a, to_screen * b],
+                        Stroke::new(width, self.stroke.color),
+                    ));
+                }
             }
         }
         painter.extend(shapes);
@@ -90,7 +129,7 @@ impl super::View for Painting {
             ui.add(crate::egui_github_link_file!());
         });
         self.ui_control(ui);
-        ui.label("Paint with your mouse/touch!");
+        ui.label("Paint with your mouse/touch/pen! Flip the pen around to erase.");
         Frame::canvas(ui.style()).show(ui, |ui| {
             self.ui_content(ui);
         });
@@ -1,12 +1,13 @@
 use std::f64::consts::TAU;
 use std::ops::RangeInclusive;
 
-use egui::plot::{GridInput, GridMark};
+use egui::plot::{AxisScale, GridInput, GridMark};
 use egui::*;
 use plot::{
-    Arrows, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, CoordinatesFormatter, Corner, HLine,
-    Legend, Line, LineStyle, MarkerShape, Plot, PlotImage, PlotPoint, PlotPoints, Points, Polygon,
-    Text, VLine,
+    Arrows, Band, Bar, BarChart, BoxElem, BoxPlot, BoxSpread, Callout, CoordinatesFormatter,
+    Corner, Cursor, DragPoint, ErrorBars, HLine, Legend, Line, LineStyle, MarkerShape, PieChart,
+    PieSlice, Plot, PlotImage, PlotPoint, PlotPoints, Points, Polygon, Span, StackedArea,
+    StreamBuffer, Text, VLine,
 };
 
 // ----------------------------------------------------------------------------
@@ -443,6 +444,8 @@ impl CustomAxisDemo {
             .data_aspect(2.0 * MINS_PER_DAY as f32)
             .x_axis_formatter(x_fmt)
             .y_axis_formatter(y_fmt)
+            .x_axis_label("Time")
+            .y_axis_label("Growth")
             .x_grid_spacer(CustomAxisDemo::x_grid)
             .label_formatter(label_fmt)
             .show(ui, |plot_ui| {
@@ -498,7 +501,7 @@ impl LinkedAxisDemo {
         ))
     }
 
-    fn configure_plot(plot_ui: &mut plot::PlotUi) {
+    fn configure_plot(plot_ui: &mut plot::PlotUi<'_>) {
         plot_ui.line(LinkedAxisDemo::line_with_slope(0.5));
         plot_ui.line(LinkedAxisDemo::line_with_slope(1.0));
         plot_ui.line(LinkedAxisDemo::line_with_slope(2.0));
@@ -581,7 +584,7 @@ impl ItemsDemo {
             ui.ctx().load_texture(
                 "plot_demo",
                 egui::ColorImage::example(),
-                egui::TextureFilter::Linear,
+                egui::TextureOptions::LINEAR,
             )
         });
         let image = PlotImage::new(
@@ -668,6 +671,103 @@ impl InteractionDemo {
 
 // ----------------------------------------------------------------------------
 
+#[derive(PartialEq)]
+struct AnnotationsDemo {
+    cursor: f64,
+    control_points: Vec<PlotPoint>,
+    selection: Option<RangeInclusive<f64>>,
+    callout_offset: Vec2,
+}
+
+impl Default for AnnotationsDemo {
+    fn default() -> Self {
+        Self {
+            cursor: 1.0,
+            control_points: vec![
+                PlotPoint::new(0.0, 0.0),
+                PlotPoint::new(1.0, 2.0),
+                PlotPoint::new(2.0, 1.0),
+                PlotPoint::new(3.0, 3.0),
+            ],
+            selection: Some(0.5..=1.5),
+            callout_offset: vec2(40.0, -40.0),
+        }
+    }
+}
+
+impl AnnotationsDemo {
+    /// The piecewise linear curve through the control points at `x`.
+    fn curve(&self, x: f64) -> f64 {
+        let points = &self.control_points;
+        let i = points
+            .partition_point(|point| point.x < x)
+            .clamp(1, points.len() - 1);
+        let (a, b) = (points[i - 1], points[i]);
+        if a.x == b.x {
+            a.y
+        } else {
+            a.y + (x - a.x) / (b.x - a.x) * (b.y - a.y)
+        }
+    }
+
+    fn ui(&mut self, ui: &mut Ui) -> Response {
+        ui.label("Drag the cursor, the points of the curve, the edges of the selection and the callout. Shift-drag to select a new range.");
+
+        let plot = Plot::new("annotations_demo")
+            .data_aspect(1.0)
+            .height(300.0)
+            .include_x(-0.5)
+            .include_x(3.5)
+            .include_y(-0.5)
+            .include_y(3.5);
+        let response = plot
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(PlotPoints::from_iter(
+                    self.control_points.iter().map(|point| [point.x, point.y]),
+                )));
+                let last = self.control_points.len() - 1;
+                for i in 0..=last {
+                    // Only the inner points can move sideways, and not past their neighbors,
+                    // to keep the curve a function of x.
+                    let is_end = i == 0 || i == last;
+                    let mut point = self.control_points[i];
+                    plot_ui.drag_point(DragPoint::new(i).lock_x(is_end), &mut point);
+                    if !is_end {
+                        let (min, max) =
+                            (self.control_points[i - 1].x, self.control_points[i + 1].x);
+                        point.x = point.x.clamp(min, max);
+                    }
+                    self.control_points[i] = point;
+                }
+
+                plot_ui.span(
+                    Span::new("selection").name("Selection"),
+                    &mut self.selection,
+                );
+                plot_ui.cursor(Cursor::vertical("cursor").name("Cursor"), &mut self.cursor);
+
+                let target = PlotPoint::new(self.cursor, self.curve(self.cursor));
+                let text = format!("y = {:.2}", target.y);
+                plot_ui.callout(
+                    Callout::new("callout", target, text),
+                    &mut self.callout_offset,
+                );
+            })
+            .response;
+
+        if let Some(selection) = &self.selection {
+            ui.label(format!(
+                "Selected: {:.2} to {:.2}",
+                selection.start(),
+                selection.end()
+            ));
+        }
+        response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(PartialEq, Eq)]
 enum Chart {
     GaussBars,
@@ -852,6 +952,284 @@ impl ChartsDemo {
 
 // ----------------------------------------------------------------------------
 
+#[derive(PartialEq)]
+struct ScalesDemo {
+    y_scale: AxisScale,
+    utc_offset_hours: i32,
+}
+
+impl Default for ScalesDemo {
+    fn default() -> Self {
+        Self {
+            y_scale: AxisScale::Log,
+            utc_offset_hours: 0,
+        }
+    }
+}
+
+impl ScalesDemo {
+    fn ui(&mut self, ui: &mut Ui) -> Response {
+        ui.horizontal(|ui| {
+            ui.label("Y axis:");
+            ui.selectable_value(&mut self.y_scale, AxisScale::Linear, "Linear");
+            ui.selectable_value(&mut self.y_scale, AxisScale::Log, "Log");
+            let symlog = AxisScale::SymLog {
+                linear_threshold: 1.0,
+            };
+            ui.selectable_value(&mut self.y_scale, symlog, "Symlog");
+        });
+        Plot::new("scales_demo")
+            .height(200.0)
+            .y_axis_scale(self.y_scale)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                let exponential = |x: f64| 10.0_f64.powf(x);
+                let cubic = |x: f64| x.powi(3);
+                plot_ui.line(
+                    Line::new(PlotPoints::from_explicit_callback(
+                        exponential,
+                        -2.0..=4.0,
+                        200,
+                    ))
+                    .name("10^x"),
+                );
+                plot_ui.line(
+                    Line::new(PlotPoints::from_explicit_callback(cubic, -4.0..=4.0, 200))
+                        .name("x³"),
+                );
+            });
+
+        ui.horizontal(|ui| {
+            ui.label("Time zone: UTC +");
+            ui.add(DragValue::new(&mut self.utc_offset_hours).clamp_range(-12..=14));
+        });
+        // A week of hourly temperatures, starting on 2022-09-12 at midnight UTC:
+        let start = 1_662_940_800.0;
+        let temperatures = PlotPoints::from_iter((0..7 * 24).map(|hour| {
+            let hours = hour as f64;
+            let daily = (TAU * (hours - 9.0) / 24.0).sin();
+            [start + hours * 3600.0, 15.0 + 5.0 * daily + 0.02 * hours]
+        }));
+        Plot::new("dates_demo")
+            .height(200.0)
+            .x_axis_scale(AxisScale::DateTime {
+                utc_offset: self.utc_offset_hours * 3600,
+            })
+            .y_axis_label("Temperature")
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(temperatures).name("Temperature"));
+            })
+            .response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(PartialEq, Default)]
+struct UncertaintyDemo {}
+
+impl UncertaintyDemo {
+    fn ui(&mut self, ui: &mut Ui) -> Response {
+        let measurements: Vec<[f64; 2]> = (0..10)
+            .map(|i| {
+                let x = i as f64 * 0.6;
+                [x, x.sin() + 0.15 * (7.0 * x).cos()]
+            })
+            .collect();
+        let spread = |x: f64| 0.25 + 0.1 * x.cos();
+        Plot::new("uncertainty_demo")
+            .height(200.0)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.band(
+                    Band::new(
+                        PlotPoints::from_explicit_callback(
+                            move |x| x.sin() - spread(x),
+                            0.0..=6.0,
+                            100,
+                        ),
+                        PlotPoints::from_explicit_callback(
+                            move |x| x.sin() + spread(x),
+                            0.0..=6.0,
+                            100,
+                        ),
+                    )
+                    .name("Model"),
+                );
+                plot_ui.line(
+                    Line::new(PlotPoints::from_explicit_callback(f64::sin, 0.0..=6.0, 100))
+                        .name("Model"),
+                );
+                plot_ui.error_bars(
+                    ErrorBars::new(measurements.clone())
+                        .y_errors_asymmetric(
+                            measurements.iter().map(|&[x, _]| [0.1, 0.1 + 0.05 * x]),
+                        )
+                        .x_errors([0.1; 10])
+                        .name("Measured"),
+                );
+                plot_ui.points(Points::new(measurements).radius(3.0).name("Measured"));
+            });
+
+        let hours: Vec<f64> = (0..24).map(|hour| hour as f64).collect();
+        let daylight = |hour: &f64| (TAU * (hour - 6.0) / 24.0).sin().max(0.0);
+        let solar: Vec<f64> = hours.iter().map(|hour| 3.0 * daylight(hour)).collect();
+        let wind: Vec<f64> = hours
+            .iter()
+            .map(|hour| 1.5 + 0.5 * (hour / 4.0).cos())
+            .collect();
+        Plot::new("stacked_area_demo")
+            .height(200.0)
+            .legend(Legend::default())
+            .y_axis_label("Power")
+            .show(ui, |plot_ui| {
+                plot_ui.stacked_area(
+                    StackedArea::new(hours)
+                        .layer("Base load", vec![2.0; 24])
+                        .layer("Wind", wind)
+                        .layer("Solar", solar),
+                );
+            })
+            .response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+#[derive(PartialEq)]
+struct PolarDemo {
+    donut: bool,
+    explode: bool,
+}
+
+impl Default for PolarDemo {
+    fn default() -> Self {
+        Self {
+            donut: true,
+            explode: false,
+        }
+    }
+}
+
+impl PolarDemo {
+    fn ui(&mut self, ui: &mut Ui) -> Response {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.donut, "Donut");
+            ui.checkbox(&mut self.explode, "Explode first slice");
+        });
+        let explode = if self.explode { 0.15 } else { 0.0 };
+        let chart = PieChart::new(vec![
+            PieSlice::new(38.0).name("Rust").explode(explode),
+            PieSlice::new(24.0).name("C++"),
+            PieSlice::new(17.0).name("Python"),
+            PieSlice::new(12.0).name("Go"),
+            PieSlice::new(9.0).name("Other"),
+        ])
+        .donut(if self.donut { 0.5 } else { 0.0 });
+        Plot::new("pie_demo")
+            .height(220.0)
+            .data_aspect(1.0)
+            .show_axes([false; 2])
+            .legend(Legend::default())
+            .show(ui, |plot_ui| plot_ui.pie_chart(chart));
+
+        // An antenna pattern, with a few measurements:
+        let pattern = |theta: f64| (4.0 * theta).cos().abs() * 0.8 + 0.2;
+        let measurements = PlotPoints::from_polar((0..24).map(|i| {
+            let theta = i as f64 * TAU / 24.0;
+            [pattern(theta) + 0.05 * (7.0 * theta).sin(), theta]
+        }));
+        Plot::new("polar_demo")
+            .height(220.0)
+            .polar(true)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.line(
+                    Line::new(PlotPoints::from_polar_callback(pattern, 0.0..=TAU, 361))
+                        .name("Pattern"),
+                );
+                plot_ui.points(Points::new(measurements).radius(3.0).name("Measured"));
+            })
+            .response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+struct StreamingDemo {
+    stream: StreamBuffer,
+    window: f64,
+    paused: bool,
+    last_sample_time: Option<f64>,
+}
+
+impl Default for StreamingDemo {
+    fn default() -> Self {
+        Self {
+            stream: StreamBuffer::new(2_000),
+            window: 5.0,
+            paused: false,
+            last_sample_time: None,
+        }
+    }
+}
+
+impl PartialEq for StreamingDemo {
+    fn eq(&self, other: &Self) -> bool {
+        self.window == other.window
+            && self.paused == other.paused
+            && self.stream.len() == other.stream.len()
+    }
+}
+
+impl StreamingDemo {
+    const SAMPLE_RATE: f64 = 100.0;
+
+    fn signal(t: f64) -> f64 {
+        t.sin() + 0.3 * (7.0 * t).sin() + 0.1 * (31.0 * t).cos()
+    }
+
+    fn ui(&mut self, ui: &mut Ui) -> Response {
+        // A real application would usually push samples from another thread:
+        let now = ui.input().time;
+        let mut t = self.last_sample_time.unwrap_or(now);
+        let mut samples = Vec::new();
+        while t + 1.0 / Self::SAMPLE_RATE <= now {
+            t += 1.0 / Self::SAMPLE_RATE;
+            samples.push([t, Self::signal(t)]);
+        }
+        self.last_sample_time = Some(t);
+        self.stream.extend(samples);
+        ui.ctx().request_repaint();
+
+        ui.horizontal(|ui| {
+            if ui.checkbox(&mut self.paused, "Pause").changed() {
+                self.stream.set_paused(self.paused);
+            }
+            ui.label("Window:");
+            ui.add(
+                DragValue::new(&mut self.window)
+                    .speed(0.1)
+                    .clamp_range(0.5..=20.0)
+                    .suffix(" s"),
+            );
+            ui.label(format!(
+                "{} / {} samples",
+                self.stream.len(),
+                self.stream.capacity()
+            ));
+        });
+        Plot::new("streaming_demo")
+            .follow_x(self.window)
+            .show(ui, |plot_ui| {
+                plot_ui.line(Line::new(&self.stream).name("Signal"));
+            })
+            .response
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(PartialEq, Eq)]
 enum Panel {
     Lines,
@@ -860,6 +1238,11 @@ enum Panel {
     Charts,
     Items,
     Interaction,
+    Annotations,
+    Scales,
+    Uncertainty,
+    Polar,
+    Streaming,
     CustomAxes,
     LinkedAxes,
 }
@@ -880,6 +1263,11 @@ pub struct PlotDemo {
     charts_demo: ChartsDemo,
     items_demo: ItemsDemo,
     interaction_demo: InteractionDemo,
+    annotations_demo: AnnotationsDemo,
+    scales_demo: ScalesDemo,
+    uncertainty_demo: UncertaintyDemo,
+    polar_demo: PolarDemo,
+    streaming_demo: StreamingDemo,
     custom_axes_demo: CustomAxisDemo,
     linked_axes_demo: LinkedAxisDemo,
     open_panel: Panel,
@@ -926,6 +1314,11 @@ impl super::View for PlotDemo {
             ui.selectable_value(&mut self.open_panel, Panel::Charts, "Charts");
             ui.selectable_value(&mut self.open_panel, Panel::Items, "Items");
             ui.selectable_value(&mut self.open_panel, Panel::Interaction, "Interaction");
+            ui.selectable_value(&mut self.open_panel, Panel::Annotations, "Annotations");
+            ui.selectable_value(&mut self.open_panel, Panel::Scales, "Scales");
+            ui.selectable_value(&mut self.open_panel, Panel::Uncertainty, "Uncertainty");
+            ui.selectable_value(&mut self.open_panel, Panel::Polar, "Polar");
+            ui.selectable_value(&mut self.open_panel, Panel::Streaming, "Streaming");
             ui.selectable_value(&mut self.open_panel, Panel::CustomAxes, "Custom Axes");
             ui.selectable_value(&mut self.open_panel, Panel::LinkedAxes, "Linked Axes");
         });
@@ -950,6 +1343,21 @@ impl super::View for PlotDemo {
             Panel::Interaction => {
                 self.interaction_demo.ui(ui);
             }
+            Panel::Annotations => {
+                self.annotations_demo.ui(ui);
+            }
+            Panel::Scales => {
+                self.scales_demo.ui(ui);
+            }
+            Panel::Uncertainty => {
+                self.uncertainty_demo.ui(ui);
+            }
+            Panel::Polar => {
+                self.polar_demo.ui(ui);
+            }
+            Panel::Streaming => {
+                self.streaming_demo.ui(ui);
+            }
             Panel::CustomAxes => {
                 self.custom_axes_demo.ui(ui);
             }
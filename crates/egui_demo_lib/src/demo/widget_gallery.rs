@@ -118,7 +118,7 @@ impl WidgetGallery {
             ui.ctx().load_texture(
                 "example",
                 egui::ColorImage::example(),
-                egui::TextureFilter::Linear,
+                egui::TextureOptions::LINEAR,
             )
         });
 
@@ -1,5 +1,5 @@
 use egui::mutex::Mutex;
-use egui::TextureFilter;
+use egui::{TextureFilter, TextureOptions};
 
 /// An image to be shown in egui.
 ///
@@ -13,7 +13,7 @@ pub struct RetainedImage {
     image: Mutex<egui::ColorImage>,
     /// Lazily loaded when we have an egui context.
     texture: Mutex<Option<egui::TextureHandle>>,
-    filter: TextureFilter,
+    options: TextureOptions,
 }
 
 impl RetainedImage {
@@ -23,7 +23,7 @@ impl RetainedImage {
             size: image.size,
             image: Mutex::new(image),
             texture: Default::default(),
-            filter: Default::default(),
+            options: Default::default(),
         }
     }
 
@@ -87,11 +87,39 @@ impl RetainedImage {
     /// let image = RetainedImage::from_color_image("my_image", color_image)
     ///     .with_texture_filter(TextureFilter::Nearest);
     /// ```
-    pub fn with_texture_filter(mut self, filter: TextureFilter) -> Self {
-        self.filter = filter;
+    pub fn with_texture_filter(self, filter: TextureFilter) -> Self {
+        let options = TextureOptions {
+            magnification: filter,
+            minification: filter,
+            ..self.options
+        };
+        self.with_options(options)
+    }
+
+    /// Set the texture sampling options to use for the image.
+    ///
+    /// **Note:** If the texture has already been uploaded to the GPU, this will require
+    /// re-uploading the texture with the updated options.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use egui_extras::RetainedImage;
+    /// # use egui::{Color32, epaint::{ColorImage, textures::TextureOptions}};
+    /// # let pixels = vec![Color32::BLACK];
+    /// # let color_image = ColorImage {
+    /// #   size: [1, 1],
+    /// #   pixels,
+    /// # };
+    /// #
+    /// // Show a large photo as a small thumbnail without aliasing
+    /// let image = RetainedImage::from_color_image("my_image", color_image)
+    ///     .with_options(TextureOptions::TRILINEAR);
+    /// ```
+    pub fn with_options(mut self, options: TextureOptions) -> Self {
+        self.options = options;
 
         // If the texture has already been uploaded, this will force it to be re-uploaded with the
-        // updated filter.
+        // updated options.
         *self.texture.lock() = None;
 
         self
@@ -130,7 +158,7 @@ impl RetainedImage {
             .get_or_insert_with(|| {
                 let image: &mut ColorImage = &mut self.image.lock();
                 let image = std::mem::take(image);
-                ctx.load_texture(&self.debug_name, image, self.filter)
+                ctx.load_texture(&self.debug_name, image, self.options)
             })
             .id()
     }
@@ -185,6 +213,30 @@ pub fn load_image_bytes(image_bytes: &[u8]) -> Result<egui::ColorImage, String>
     ))
 }
 
+/// Encode an image as PNG, e.g. to save a plot exported with [`egui::plot::Plot::export`].
+///
+/// Requires the "image" feature, with the "png" format of the `image` crate enabled
+/// like for [`load_image_bytes`].
+///
+/// # Errors
+/// If the "png" format is not enabled.
+#[cfg(feature = "image")]
+pub fn encode_png(image: &ColorImage) -> Result<Vec<u8>, String> {
+    let [width, height] = image.size;
+    let pixels = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    let image_buffer = image::RgbaImage::from_raw(width as _, height as _, pixels)
+        .ok_or_else(|| "Image size doesn't match the number of pixels".to_owned())?;
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image_buffer
+        .write_to(&mut bytes, image::ImageFormat::Png)
+        .map_err(|err| err.to_string())?;
+    Ok(bytes.into_inner())
+}
+
 /// Load an SVG and rasterize it into an egui image.
 ///
 /// Requires the "svg" feature.
@@ -9,7 +9,7 @@ use crate::{
     Size, StripLayout,
 };
 
-use egui::{Rect, Response, Ui, Vec2};
+use egui::{Id, Rect, Response, Sense, Ui, Vec2, WidgetInfo, WidgetType};
 
 /// Builder for a [`Table`] with (optional) fixed header and scrolling body.
 ///
@@ -165,8 +165,10 @@ impl<'a> TableBuilder<'a> {
         let widths = read_persisted_widths(ui, default_widths, resize_id);
 
         let table_top = ui.cursor().top();
+        let table_id = table_accessibility_node(ui);
 
-        {
+        let ctx = ui.ctx().clone();
+        ctx.with_accessibility_parent(table_id, || {
             let mut layout = StripLayout::new(ui, CellDirection::Horizontal, clip, cell_layout);
             header(TableRow {
                 layout: &mut layout,
@@ -176,10 +178,11 @@ impl<'a> TableBuilder<'a> {
                 height,
             });
             layout.allocate_rect();
-        }
+        });
 
         Table {
             ui,
+            table_id,
             table_top,
             resize_id,
             sizing,
@@ -217,9 +220,11 @@ impl<'a> TableBuilder<'a> {
         let widths = read_persisted_widths(ui, default_widths, resize_id);
 
         let table_top = ui.cursor().top();
+        let table_id = table_accessibility_node(ui);
 
         Table {
             ui,
+            table_id,
             table_top,
             resize_id,
             sizing,
@@ -235,6 +240,14 @@ impl<'a> TableBuilder<'a> {
     }
 }
 
+/// Add the table to the accessibility tree, returning its id.
+fn table_accessibility_node(ui: &mut Ui) -> Id {
+    let table_id = ui.next_auto_id().with("__table");
+    ui.interact(ui.available_rect_before_wrap(), table_id, Sense::hover())
+        .widget_info(|| WidgetInfo::new(WidgetType::Table));
+    table_id
+}
+
 fn read_persisted_widths(
     ui: &egui::Ui,
     default_widths: Vec<f32>,
@@ -259,6 +272,7 @@ fn read_persisted_widths(
 /// Is created by [`TableBuilder`] by either calling [`TableBuilder::body`] or after creating a header row with [`TableBuilder::header`].
 pub struct Table<'a> {
     ui: &'a mut Ui,
+    table_id: Id,
     table_top: f32,
     resize_id: Option<egui::Id>,
     sizing: Sizing,
@@ -279,6 +293,7 @@ impl<'a> Table<'a> {
     {
         let Table {
             ui,
+            table_id,
             table_top,
             resize_id,
             sizing,
@@ -299,17 +314,20 @@ impl<'a> Table<'a> {
             .auto_shrink([true; 2])
             .stick_to_bottom(stick_to_bottom);
 
-        scroll_area.show(ui, move |ui| {
-            let layout = StripLayout::new(ui, CellDirection::Horizontal, clip, cell_layout);
-
-            body(TableBody {
-                layout,
-                widths,
-                striped,
-                row_nr: 0,
-                start_y: avail_rect.top(),
-                end_y: avail_rect.bottom(),
-            });
+        let ctx = ui.ctx().clone();
+        ctx.with_accessibility_parent(table_id, || {
+            scroll_area.show(ui, move |ui| {
+                let layout = StripLayout::new(ui, CellDirection::Horizontal, clip, cell_layout);
+
+                body(TableBody {
+                    layout,
+                    widths,
+                    striped,
+                    row_nr: 0,
+                    start_y: avail_rect.top(),
+                    end_y: avail_rect.bottom(),
+                });
+            })
         });
 
         let bottom = ui.min_rect().bottom();
@@ -619,6 +637,15 @@ impl<'a, 'b> TableRow<'a, 'b> {
         let width = CellSize::Absolute(width);
         let height = CellSize::Absolute(self.height);
 
+        // The child `Ui` of the cell will use this id:
+        let cell_id = self.layout.ui.next_auto_id();
+        let add_contents = |ui: &mut Ui| {
+            ui.interact(ui.max_rect(), cell_id, Sense::hover())
+                .widget_info(|| WidgetInfo::new(WidgetType::Cell));
+            let ctx = ui.ctx().clone();
+            ctx.with_accessibility_parent(cell_id, || add_contents(ui));
+        };
+
         if self.striped {
             self.layout.add_striped(width, height, add_contents)
         } else {
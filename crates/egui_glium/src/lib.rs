@@ -73,6 +73,7 @@ impl EguiGlium {
             repaint_after,
             textures_delta,
             shapes,
+            damage: _, // we always repaint everything
         } = self.egui_ctx.run(raw_input, run_ui);
 
         self.egui_winit.handle_platform_output(
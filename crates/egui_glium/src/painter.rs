@@ -11,7 +11,7 @@ use {
         program,
         texture::{self, srgb_texture2d::SrgbTexture2d},
         uniform,
-        uniforms::{MagnifySamplerFilter, SamplerWrapFunction},
+        uniforms::{MagnifySamplerFilter, MinifySamplerFilter, SamplerWrapFunction},
     },
     std::rc::Rc,
 };
@@ -22,6 +22,9 @@ pub struct Painter {
 
     textures: ahash::HashMap<egui::TextureId, Rc<SrgbTexture2d>>,
 
+    /// How to sample each texture. Textures not in here use [`egui::TextureOptions::LINEAR`].
+    texture_options: ahash::HashMap<egui::TextureId, egui::TextureOptions>,
+
     /// [`egui::TextureId::User`] index
     next_native_tex_id: u64,
 }
@@ -56,6 +59,7 @@ impl Painter {
             max_texture_side,
             program,
             textures: Default::default(),
+            texture_options: Default::default(),
             next_native_tex_id: 0,
         }
     }
@@ -102,6 +106,11 @@ impl Painter {
                 Primitive::Mesh(mesh) => {
                     self.paint_mesh(target, display, pixels_per_point, clip_rect, mesh);
                 }
+                Primitive::Instanced(instanced) => {
+                    // We don't turn on `TessellationOptions::instancing`, but can still paint these:
+                    let mesh = instanced.to_mesh();
+                    self.paint_mesh(target, display, pixels_per_point, clip_rect, &mesh);
+                }
                 Primitive::Callback(_) => {
                     panic!("Custom rendering callbacks are not implemented in egui_glium");
                 }
@@ -145,13 +154,44 @@ impl Painter {
         let height_in_points = height_in_pixels as f32 / pixels_per_point;
 
         if let Some(texture) = self.texture(mesh.texture_id) {
-            // The texture coordinates for text are so that both nearest and linear should work with the egui font texture.
-            // For user textures linear sampling is more likely to be the right choice.
-            let filter = MagnifySamplerFilter::Linear;
+            let options = self
+                .texture_options
+                .get(&mesh.texture_id)
+                .copied()
+                .unwrap_or_default();
+            let magnify_filter = match options.magnification {
+                egui::TextureFilter::Nearest => MagnifySamplerFilter::Nearest,
+                egui::TextureFilter::Linear => MagnifySamplerFilter::Linear,
+            };
+            let minify_filter = match (options.minification, options.mipmap_mode) {
+                (egui::TextureFilter::Nearest, None) => MinifySamplerFilter::Nearest,
+                (egui::TextureFilter::Linear, None) => MinifySamplerFilter::Linear,
+                (egui::TextureFilter::Nearest, Some(egui::TextureFilter::Nearest)) => {
+                    MinifySamplerFilter::NearestMipmapNearest
+                }
+                (egui::TextureFilter::Linear, Some(egui::TextureFilter::Nearest)) => {
+                    MinifySamplerFilter::LinearMipmapNearest
+                }
+                (egui::TextureFilter::Nearest, Some(egui::TextureFilter::Linear)) => {
+                    MinifySamplerFilter::NearestMipmapLinear
+                }
+                (egui::TextureFilter::Linear, Some(egui::TextureFilter::Linear)) => {
+                    MinifySamplerFilter::LinearMipmapLinear
+                }
+            };
+            let wrap_function = match options.wrap_mode {
+                egui::TextureWrapMode::ClampToEdge => SamplerWrapFunction::Clamp,
+                egui::TextureWrapMode::Repeat => SamplerWrapFunction::Repeat,
+                egui::TextureWrapMode::MirroredRepeat => SamplerWrapFunction::Mirror,
+            };
 
             let uniforms = uniform! {
                 u_screen_size: [width_in_points, height_in_points],
-                u_sampler: texture.sampled().magnify_filter(filter).wrap_function(SamplerWrapFunction::Clamp),
+                u_sampler: texture
+                    .sampled()
+                    .magnify_filter(magnify_filter)
+                    .minify_filter(minify_filter)
+                    .wrap_function(wrap_function),
             };
 
             // egui outputs colors with premultiplied alpha:
@@ -241,6 +281,23 @@ impl Painter {
                     .map(|color| color.to_tuple())
                     .collect()
             }
+            egui::ImageData::Sdf(image) => {
+                // We have no shader path for signed distance fields,
+                // so approximate coverage from the distance. This looks soft, but readable.
+                let coverage = egui::epaint::FontImage {
+                    size: image.size,
+                    pixels: image
+                        .pixels
+                        .iter()
+                        .map(|&distance| egui::emath::remap_clamp(distance, 0.4..=0.6, 0.0..=1.0))
+                        .collect(),
+                };
+                let gamma = 1.0;
+                coverage
+                    .srgba_pixels(gamma)
+                    .map(|color| color.to_tuple())
+                    .collect()
+            }
         };
         let glium_image = glium::texture::RawImage2d {
             data: std::borrow::Cow::Owned(pixels),
@@ -249,7 +306,11 @@ impl Painter {
             format: glium::texture::ClientFormat::U8U8U8U8,
         };
         let format = texture::SrgbFormat::U8U8U8U8;
-        let mipmaps = texture::MipmapsOption::NoMipmap;
+        let mipmaps = if delta.options.mipmap_mode.is_some() {
+            texture::MipmapsOption::AutoGeneratedMipmaps
+        } else {
+            texture::MipmapsOption::NoMipmap
+        };
 
         if let Some(pos) = delta.pos {
             // update a sub-region
@@ -261,16 +322,24 @@ impl Painter {
                     height: glium_image.height,
                 };
                 gl_texture.main_level().write(rect, glium_image);
+
+                if gl_texture.get_mipmap_levels() > 1 {
+                    // The other levels are stale now.
+                    // SAFETY: the texture belongs to the context of `facade`, which is current.
+                    unsafe { gl_texture.generate_mipmaps() };
+                }
             }
         } else {
             let gl_texture =
                 SrgbTexture2d::with_format(facade, glium_image, format, mipmaps).unwrap();
             self.textures.insert(tex_id, gl_texture.into());
+            self.texture_options.insert(tex_id, delta.options);
         }
     }
 
     pub fn free_texture(&mut self, tex_id: egui::TextureId) {
         self.textures.remove(&tex_id);
+        self.texture_options.remove(&tex_id);
     }
 
     fn texture(&self, texture_id: egui::TextureId) -> Option<&SrgbTexture2d> {
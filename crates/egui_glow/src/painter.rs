@@ -1,10 +1,13 @@
 #![allow(unsafe_code)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 use egui::{
     emath::Rect,
-    epaint::{Color32, Mesh, PaintCallbackInfo, Primitive, Vertex},
+    epaint::{Color32, Instance, InstancedMesh, Mesh, PaintCallbackInfo, Primitive, Vertex},
 };
 use glow::HasContext as _;
 use memoffset::offset_of;
@@ -23,14 +26,32 @@ const FRAG_SRC: &str = include_str!("shader/fragment.glsl");
 pub type TextureFilter = egui::TextureFilter;
 
 trait TextureFilterExt {
-    fn glow_code(&self) -> u32;
+    fn glow_code(&self, mipmap: Option<TextureFilter>) -> u32;
 }
 
 impl TextureFilterExt for TextureFilter {
+    fn glow_code(&self, mipmap: Option<TextureFilter>) -> u32 {
+        match (self, mipmap) {
+            (TextureFilter::Linear, None) => glow::LINEAR,
+            (TextureFilter::Nearest, None) => glow::NEAREST,
+            (TextureFilter::Linear, Some(TextureFilter::Linear)) => glow::LINEAR_MIPMAP_LINEAR,
+            (TextureFilter::Linear, Some(TextureFilter::Nearest)) => glow::LINEAR_MIPMAP_NEAREST,
+            (TextureFilter::Nearest, Some(TextureFilter::Linear)) => glow::NEAREST_MIPMAP_LINEAR,
+            (TextureFilter::Nearest, Some(TextureFilter::Nearest)) => glow::NEAREST_MIPMAP_NEAREST,
+        }
+    }
+}
+
+trait TextureWrapModeExt {
+    fn glow_code(&self) -> u32;
+}
+
+impl TextureWrapModeExt for egui::TextureWrapMode {
     fn glow_code(&self) -> u32 {
         match self {
-            TextureFilter::Linear => glow::LINEAR,
-            TextureFilter::Nearest => glow::NEAREST,
+            egui::TextureWrapMode::ClampToEdge => glow::CLAMP_TO_EDGE,
+            egui::TextureWrapMode::Repeat => glow::REPEAT,
+            egui::TextureWrapMode::MirroredRepeat => glow::MIRRORED_REPEAT,
         }
     }
 }
@@ -50,6 +71,7 @@ pub struct Painter {
     program: glow::Program,
     u_screen_size: glow::UniformLocation,
     u_sampler: glow::UniformLocation,
+    u_sdf: glow::UniformLocation,
     is_webgl_1: bool,
     is_embedded: bool,
     vao: crate::vao::VertexArrayObject,
@@ -58,8 +80,21 @@ pub struct Painter {
     vbo: glow::Buffer,
     element_array_buffer: glow::Buffer,
 
+    /// For painting [`Primitive::Instanced`], if the context supports instancing.
+    instancing: Option<InstancingProgram>,
+
     textures: HashMap<egui::TextureId, glow::Texture>,
 
+    /// Textures of [`egui::ImageData::Sdf`], painted with the signed distance field shader path.
+    sdf_textures: HashSet<egui::TextureId>,
+
+    /// Textures whose sides aren't all powers of two, on WebGL1,
+    /// which can't be mipmapped or repeated: they would be sampled as black.
+    webgl_1_npot_textures: HashSet<egui::TextureId>,
+
+    /// Framebuffers for textures painted with [`Painter::paint_to_texture`].
+    offscreen_targets: HashMap<egui::TextureId, OffscreenTarget>,
+
     next_native_tex_id: u64,
 
     /// Stores outdated OpenGL textures that are yet to be deleted
@@ -89,6 +124,22 @@ impl CallbackFn {
     }
 }
 
+/// The shader program used to paint [`Primitive::Instanced`].
+struct InstancingProgram {
+    program: glow::Program,
+    u_screen_size: glow::UniformLocation,
+    u_sampler: glow::UniformLocation,
+    u_sdf: glow::UniformLocation,
+    vao: crate::vao::VertexArrayObject,
+    instance_vbo: glow::Buffer,
+}
+
+/// A texture we can render into, created by [`Painter::paint_to_texture`].
+struct OffscreenTarget {
+    fbo: glow::Framebuffer,
+    size_px: [u32; 2],
+}
+
 impl Painter {
     /// Create painter.
     ///
@@ -148,82 +199,44 @@ impl Painter {
         };
 
         unsafe {
-            let vert = compile_shader(
-                &gl,
-                glow::VERTEX_SHADER,
-                &format!(
-                    "{}\n{}\n{}\n{}",
-                    header,
-                    shader_prefix,
-                    if shader_version.is_new_shader_interface() {
-                        "#define NEW_SHADER_INTERFACE\n"
-                    } else {
-                        ""
-                    },
-                    VERT_SRC
-                ),
-            )?;
-            let frag = compile_shader(
+            let program = compile_program(
                 &gl,
-                glow::FRAGMENT_SHADER,
-                &format!(
-                    "{}\n{}\n{}\n{}\n{}",
-                    header,
-                    shader_prefix,
-                    srgb_support_define,
-                    if shader_version.is_new_shader_interface() {
-                        "#define NEW_SHADER_INTERFACE\n"
-                    } else {
-                        ""
-                    },
-                    FRAG_SRC
-                ),
+                header,
+                shader_prefix,
+                shader_version,
+                srgb_support_define,
+                "",
             )?;
-            let program = link_program(&gl, [vert, frag].iter())?;
-            gl.detach_shader(program, vert);
-            gl.detach_shader(program, frag);
-            gl.delete_shader(vert);
-            gl.delete_shader(frag);
             let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
             let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
+            let u_sdf = gl.get_uniform_location(program, "u_sdf").unwrap();
 
             let vbo = gl.create_buffer()?;
-
-            let a_pos_loc = gl.get_attrib_location(program, "a_pos").unwrap();
-            let a_tc_loc = gl.get_attrib_location(program, "a_tc").unwrap();
-            let a_srgba_loc = gl.get_attrib_location(program, "a_srgba").unwrap();
-
-            let stride = std::mem::size_of::<Vertex>() as i32;
-            let buffer_infos = vec![
-                vao::BufferInfo {
-                    location: a_pos_loc,
-                    vector_size: 2,
-                    data_type: glow::FLOAT,
-                    normalized: false,
-                    stride,
-                    offset: offset_of!(Vertex, pos) as i32,
-                },
-                vao::BufferInfo {
-                    location: a_tc_loc,
-                    vector_size: 2,
-                    data_type: glow::FLOAT,
-                    normalized: false,
-                    stride,
-                    offset: offset_of!(Vertex, uv) as i32,
-                },
-                vao::BufferInfo {
-                    location: a_srgba_loc,
-                    vector_size: 4,
-                    data_type: glow::UNSIGNED_BYTE,
-                    normalized: false,
-                    stride,
-                    offset: offset_of!(Vertex, color) as i32,
-                },
-            ];
-            let vao = crate::vao::VertexArrayObject::new(&gl, vbo, buffer_infos);
+            let vao =
+                crate::vao::VertexArrayObject::new(&gl, vbo, vertex_buffer_infos(&gl, program));
 
             let element_array_buffer = gl.create_buffer()?;
 
+            let instancing = if supports_instancing(&gl) {
+                match InstancingProgram::new(
+                    &gl,
+                    vbo,
+                    header,
+                    shader_prefix,
+                    shader_version,
+                    srgb_support_define,
+                ) {
+                    Ok(instancing) => Some(instancing),
+                    Err(err) => {
+                        tracing::warn!("Failed to set up instancing: {}", err);
+                        None
+                    }
+                }
+            } else {
+                tracing::debug!("Instancing not supported");
+                None
+            };
+
             crate::check_for_gl_error_even_in_release!(&gl, "after Painter::new");
 
             Ok(Painter {
@@ -232,6 +245,7 @@ impl Painter {
                 program,
                 u_screen_size,
                 u_sampler,
+                u_sdf,
                 is_webgl_1,
                 is_embedded: matches!(shader_version, ShaderVersion::Es100 | ShaderVersion::Es300),
                 vao,
@@ -239,7 +253,11 @@ impl Painter {
                 post_process,
                 vbo,
                 element_array_buffer,
+                instancing,
                 textures: Default::default(),
+                sdf_textures: Default::default(),
+                webgl_1_npot_textures: Default::default(),
+                offscreen_targets: Default::default(),
                 next_native_tex_id: 1 << 32,
                 textures_to_destroy: Vec::new(),
                 destroyed: false,
@@ -256,6 +274,16 @@ impl Painter {
         self.max_texture_side
     }
 
+    /// Can we paint [`Primitive::Instanced`] on the GPU?
+    ///
+    /// If so, you can turn on [`egui::epaint::TessellationOptions::instancing`].
+    /// Otherwise instanced meshes are still painted, but are first copied on the CPU.
+    ///
+    /// Instancing requires OpenGL 3.3, OpenGL ES 3.0 or WebGL 2.
+    pub fn supports_instancing(&self) -> bool {
+        self.instancing.is_some()
+    }
+
     /// The framebuffer we use as an intermediate render target,
     /// or `None` if we are painting to the screen framebuffer directly.
     ///
@@ -367,6 +395,52 @@ impl Painter {
         clipped_primitives: &[egui::ClippedPrimitive],
     ) {
         crate::profile_function!();
+        self.paint_primitives_within(screen_size_px, pixels_per_point, clipped_primitives, None);
+    }
+
+    /// Like [`Self::paint_primitives`], but only paints within the given rectangles (in points),
+    /// leaving the rest of the framebuffer untouched.
+    ///
+    /// Use this with [`egui::FullOutput::damage`] to only repaint what changed since last frame.
+    /// The framebuffer must still contain what was painted last frame,
+    /// and you should have cleared the damaged areas first, e.g. with [`clear_damage`].
+    ///
+    /// The rectangles must not overlap, or translucent shapes will be painted twice.
+    pub fn paint_damaged_primitives(
+        &mut self,
+        screen_size_px: [u32; 2],
+        pixels_per_point: f32,
+        clipped_primitives: &[egui::ClippedPrimitive],
+        damage: &[Rect],
+    ) {
+        crate::profile_function!();
+        if self.post_process.is_some() {
+            // The post-processing framebuffer is cleared and copied to the screen in full.
+            self.paint_primitives_within(
+                screen_size_px,
+                pixels_per_point,
+                clipped_primitives,
+                None,
+            );
+            return;
+        }
+        for &damage_rect in damage {
+            self.paint_primitives_within(
+                screen_size_px,
+                pixels_per_point,
+                clipped_primitives,
+                Some(damage_rect),
+            );
+        }
+    }
+
+    fn paint_primitives_within(
+        &mut self,
+        screen_size_px: [u32; 2],
+        pixels_per_point: f32,
+        clipped_primitives: &[egui::ClippedPrimitive],
+        damage_rect: Option<Rect>,
+    ) {
         self.assert_not_destroyed();
 
         if let Some(ref mut post_process) = self.post_process {
@@ -387,12 +461,19 @@ impl Painter {
             primitive,
         } in clipped_primitives
         {
+            let clip_rect = &damage_rect.map_or(*clip_rect, |damage| clip_rect.intersect(damage));
+            if !clip_rect.is_positive() {
+                continue;
+            }
             set_clip_rect(&self.gl, size_in_pixels, pixels_per_point, *clip_rect);
 
             match primitive {
                 Primitive::Mesh(mesh) => {
                     self.paint_mesh(mesh);
                 }
+                Primitive::Instanced(instanced) => {
+                    self.paint_instanced(instanced, size_in_pixels, pixels_per_point);
+                }
                 Primitive::Callback(callback) => {
                     if callback.rect.is_positive() {
                         crate::profile_scope!("callback");
@@ -478,6 +559,10 @@ impl Painter {
                 );
 
                 self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+                self.gl.uniform_1_i32(
+                    Some(&self.u_sdf),
+                    self.sdf_textures.contains(&mesh.texture_id) as i32,
+                );
             }
 
             unsafe {
@@ -495,6 +580,308 @@ impl Painter {
         }
     }
 
+    #[inline(never)] // Easier profiling
+    fn paint_instanced(
+        &mut self,
+        instanced: &InstancedMesh,
+        (width_in_pixels, height_in_pixels): (u32, u32),
+        pixels_per_point: f32,
+    ) {
+        let instancing = if let Some(instancing) = &self.instancing {
+            instancing
+        } else {
+            // The tessellator shouldn't have produced this, but we can still paint it:
+            self.paint_mesh(&instanced.to_mesh());
+            return;
+        };
+
+        let mesh = &instanced.mesh;
+        debug_assert!(mesh.is_valid());
+        let texture = if let Some(texture) = self.texture(mesh.texture_id) {
+            texture
+        } else {
+            tracing::warn!("Failed to find texture {:?}", mesh.texture_id);
+            return;
+        };
+
+        unsafe {
+            self.gl.use_program(Some(instancing.program));
+            self.gl.uniform_2_f32(
+                Some(&instancing.u_screen_size),
+                width_in_pixels as f32 / pixels_per_point,
+                height_in_pixels as f32 / pixels_per_point,
+            );
+            self.gl.uniform_1_i32(Some(&instancing.u_sampler), 0);
+            self.gl.uniform_1_i32(
+                Some(&instancing.u_sdf),
+                self.sdf_textures.contains(&mesh.texture_id) as i32,
+            );
+            instancing.vao.bind(&self.gl);
+
+            self.gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
+            self.gl.buffer_data_u8_slice(
+                glow::ARRAY_BUFFER,
+                bytemuck::cast_slice(&mesh.vertices),
+                glow::STREAM_DRAW,
+            );
+            self.gl
+                .bind_buffer(glow::ARRAY_BUFFER, Some(instancing.instance_vbo));
+            self.gl.buffer_data_u8_slice(
+                glow::ARRAY_BUFFER,
+                bytemuck::cast_slice(&instanced.instances[..]),
+                glow::STREAM_DRAW,
+            );
+            self.gl
+                .bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.element_array_buffer));
+            self.gl.buffer_data_u8_slice(
+                glow::ELEMENT_ARRAY_BUFFER,
+                bytemuck::cast_slice(&mesh.indices),
+                glow::STREAM_DRAW,
+            );
+
+            self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+            self.gl.draw_elements_instanced(
+                glow::TRIANGLES,
+                mesh.indices.len() as i32,
+                glow::UNSIGNED_INT,
+                0,
+                instanced.instances.len() as i32,
+            );
+
+            // Back to the program for normal meshes:
+            instancing.vao.unbind(&self.gl);
+            self.gl.use_program(Some(self.program));
+            self.vao.bind(&self.gl);
+            self.gl
+                .bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(self.element_array_buffer));
+        }
+
+        check_for_gl_error!(&self.gl, "paint_instanced");
+    }
+
+    // ------------------------------------------------------------------------
+
+    /// Paint the given primitives into a new texture instead of to the screen.
+    ///
+    /// This is useful for caching expensive, mostly static UI, or for showing egui
+    /// on surfaces in a 3D scene.
+    /// The returned [`egui::TextureId`] can be shown with [`egui::Image`] like any other texture,
+    /// and freed with [`Self::free_texture`].
+    ///
+    /// `size_px` is the size of the texture in physical pixels, and the primitives
+    /// are expected to be positioned within `[0, size_px / pixels_per_point]` points.
+    /// The texture is cleared to `clear_color` before painting.
+    ///
+    /// [`egui::Shape::Callback`]s are not supported when painting to a texture, and will be ignored.
+    pub fn paint_to_texture(
+        &mut self,
+        size_px: [u32; 2],
+        pixels_per_point: f32,
+        clear_color: egui::Rgba,
+        clipped_primitives: &[egui::ClippedPrimitive],
+    ) -> egui::TextureId {
+        crate::profile_function!();
+        self.assert_not_destroyed();
+
+        let id = egui::TextureId::User(self.next_native_tex_id);
+        self.next_native_tex_id += 1;
+        self.paint_offscreen(
+            id,
+            size_px,
+            pixels_per_point,
+            clear_color,
+            clipped_primitives,
+        );
+        id
+    }
+
+    /// Paint new content into a texture previously created with [`Self::paint_to_texture`].
+    ///
+    /// The texture is resized if `size_px` has changed, but keeps its [`egui::TextureId`].
+    ///
+    /// Panics if `id` wasn't returned by [`Self::paint_to_texture`], or was freed since.
+    pub fn repaint_texture(
+        &mut self,
+        id: egui::TextureId,
+        size_px: [u32; 2],
+        pixels_per_point: f32,
+        clear_color: egui::Rgba,
+        clipped_primitives: &[egui::ClippedPrimitive],
+    ) {
+        crate::profile_function!();
+        self.assert_not_destroyed();
+        assert!(
+            self.offscreen_targets.contains_key(&id),
+            "Tried to repaint {:?}, which is not a texture created by paint_to_texture",
+            id
+        );
+
+        self.paint_offscreen(
+            id,
+            size_px,
+            pixels_per_point,
+            clear_color,
+            clipped_primitives,
+        );
+    }
+
+    fn paint_offscreen(
+        &mut self,
+        id: egui::TextureId,
+        size_px: [u32; 2],
+        pixels_per_point: f32,
+        clear_color: egui::Rgba,
+        clipped_primitives: &[egui::ClippedPrimitive],
+    ) {
+        let [w, h] = size_px;
+        assert!(
+            w >= 1 && h >= 1,
+            "Tried to paint to a texture of size {}x{}. A texture must at least be one texel wide.",
+            w,
+            h
+        );
+        assert!(
+            w as usize <= self.max_texture_side && h as usize <= self.max_texture_side,
+            "Tried to paint to a texture of size {}x{}, but the maximum supported texture side is only {}",
+            w,
+            h,
+            self.max_texture_side
+        );
+
+        unsafe {
+            let fbo = self.offscreen_fbo(id, size_px);
+            self.gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+
+            clear(&self.gl, size_px, clear_color);
+            let size_in_pixels = self.prepare_painting(size_px, pixels_per_point);
+
+            // OpenGL textures have their origin in the lower left corner, while egui
+            // places `uv = (0, 0)` in the upper left, so we paint everything upside down:
+            let height_in_points = h as f32 / pixels_per_point;
+            let flip_y = |y: f32| height_in_points - y;
+
+            for egui::ClippedPrimitive {
+                clip_rect,
+                primitive,
+            } in clipped_primitives
+            {
+                match primitive {
+                    Primitive::Mesh(mesh) => {
+                        let clip_rect = Rect::from_x_y_ranges(
+                            clip_rect.x_range(),
+                            flip_y(clip_rect.max.y)..=flip_y(clip_rect.min.y),
+                        );
+                        set_clip_rect(&self.gl, size_in_pixels, pixels_per_point, clip_rect);
+
+                        let mut mesh = (**mesh).clone();
+                        for vertex in &mut mesh.vertices {
+                            vertex.pos.y = flip_y(vertex.pos.y);
+                        }
+                        self.paint_mesh(&mesh);
+                    }
+                    Primitive::Instanced(instanced) => {
+                        let clip_rect = Rect::from_x_y_ranges(
+                            clip_rect.x_range(),
+                            flip_y(clip_rect.max.y)..=flip_y(clip_rect.min.y),
+                        );
+                        set_clip_rect(&self.gl, size_in_pixels, pixels_per_point, clip_rect);
+
+                        let mut mesh = instanced.to_mesh();
+                        for vertex in &mut mesh.vertices {
+                            vertex.pos.y = flip_y(vertex.pos.y);
+                        }
+                        self.paint_mesh(&mesh);
+                    }
+                    Primitive::Callback(_) => {
+                        tracing::warn!(
+                            "Paint callbacks are not supported when painting to a texture"
+                        );
+                    }
+                }
+            }
+
+            self.vao.unbind(&self.gl);
+            self.gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, None);
+            self.gl.disable(glow::SCISSOR_TEST);
+            self.gl
+                .bind_framebuffer(glow::FRAMEBUFFER, self.intermediate_fbo());
+
+            check_for_gl_error!(&self.gl, "paint_to_texture");
+        }
+    }
+
+    /// Get (or create) the framebuffer for an offscreen texture, making sure it has the right size.
+    unsafe fn offscreen_fbo(
+        &mut self,
+        id: egui::TextureId,
+        size_px: [u32; 2],
+    ) -> glow::Framebuffer {
+        if let Some(target) = self.offscreen_targets.get(&id) {
+            if target.size_px == size_px {
+                return target.fbo;
+            }
+        }
+
+        let texture = self.gl.create_texture().unwrap();
+        self.gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        for (param, value) in [
+            (glow::TEXTURE_MAG_FILTER, glow::LINEAR),
+            (glow::TEXTURE_MIN_FILTER, glow::LINEAR),
+            (glow::TEXTURE_WRAP_S, glow::CLAMP_TO_EDGE),
+            (glow::TEXTURE_WRAP_T, glow::CLAMP_TO_EDGE),
+        ] {
+            self.gl
+                .tex_parameter_i32(glow::TEXTURE_2D, param, value as i32);
+        }
+
+        let (internal_format, src_format) = if self.is_webgl_1 {
+            let format = if self.srgb_support {
+                glow::SRGB_ALPHA
+            } else {
+                glow::RGBA
+            };
+            (format, format)
+        } else {
+            (glow::SRGB8_ALPHA8, glow::RGBA)
+        };
+        self.gl.tex_image_2d(
+            glow::TEXTURE_2D,
+            0,
+            internal_format as _,
+            size_px[0] as _,
+            size_px[1] as _,
+            0,
+            src_format,
+            glow::UNSIGNED_BYTE,
+            None,
+        );
+        check_for_gl_error!(&self.gl, "offscreen texture");
+
+        let fbo = if let Some(target) = self.offscreen_targets.get_mut(&id) {
+            target.size_px = size_px;
+            target.fbo
+        } else {
+            let fbo = self.gl.create_framebuffer().unwrap();
+            self.offscreen_targets
+                .insert(id, OffscreenTarget { fbo, size_px });
+            fbo
+        };
+        self.gl.bind_framebuffer(glow::FRAMEBUFFER, Some(fbo));
+        self.gl.framebuffer_texture_2d(
+            glow::FRAMEBUFFER,
+            glow::COLOR_ATTACHMENT0,
+            glow::TEXTURE_2D,
+            Some(texture),
+            0,
+        );
+        self.gl.bind_texture(glow::TEXTURE_2D, None);
+
+        if let Some(old_tex) = self.textures.insert(id, texture) {
+            self.gl.delete_texture(old_tex);
+        }
+        fbo
+    }
+
     // ------------------------------------------------------------------------
 
     pub fn set_texture(&mut self, tex_id: egui::TextureId, delta: &egui::epaint::ImageDelta) {
@@ -510,6 +897,26 @@ impl Painter {
             self.gl.bind_texture(glow::TEXTURE_2D, Some(glow_texture));
         }
 
+        if matches!(delta.image, egui::ImageData::Sdf(_)) {
+            self.sdf_textures.insert(tex_id);
+        } else {
+            self.sdf_textures.remove(&tex_id);
+        }
+
+        if delta.pos.is_none() {
+            let [w, h] = delta.image.size();
+            if self.is_webgl_1 && !(w.is_power_of_two() && h.is_power_of_two()) {
+                self.webgl_1_npot_textures.insert(tex_id);
+            } else {
+                self.webgl_1_npot_textures.remove(&tex_id);
+            }
+        }
+        let mut options = delta.options;
+        if self.webgl_1_npot_textures.contains(&tex_id) {
+            options.mipmap_mode = None;
+            options.wrap_mode = egui::TextureWrapMode::ClampToEdge;
+        }
+
         match &delta.image {
             egui::ImageData::Color(image) => {
                 assert_eq!(
@@ -520,7 +927,7 @@ impl Painter {
 
                 let data: &[u8] = bytemuck::cast_slice(image.pixels.as_ref());
 
-                self.upload_texture_srgb(delta.pos, image.size, delta.filter, data);
+                self.upload_texture_srgb(delta.pos, image.size, options, data);
             }
             egui::ImageData::Font(image) => {
                 assert_eq!(
@@ -539,7 +946,21 @@ impl Painter {
                     .flat_map(|a| a.to_array())
                     .collect();
 
-                self.upload_texture_srgb(delta.pos, image.size, delta.filter, &data);
+                self.upload_texture_srgb(delta.pos, image.size, options, &data);
+            }
+            egui::ImageData::Sdf(image) => {
+                assert_eq!(
+                    image.width() * image.height(),
+                    image.pixels.len(),
+                    "Mismatch between texture size and texel count"
+                );
+
+                let data: Vec<u8> = image
+                    .sdf_srgba_pixels()
+                    .flat_map(|a| a.to_array())
+                    .collect();
+
+                self.upload_texture_srgb(delta.pos, image.size, options, &data);
             }
         };
     }
@@ -548,7 +969,7 @@ impl Painter {
         &mut self,
         pos: Option<[usize; 2]>,
         [w, h]: [usize; 2],
-        texture_filter: TextureFilter,
+        options: egui::TextureOptions,
         data: &[u8],
     ) {
         assert_eq!(data.len(), w * h * 4);
@@ -570,23 +991,23 @@ impl Painter {
             self.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_MAG_FILTER,
-                texture_filter.glow_code() as i32,
+                options.magnification.glow_code(None) as i32,
             );
             self.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_MIN_FILTER,
-                texture_filter.glow_code() as i32,
+                options.minification.glow_code(options.mipmap_mode) as i32,
             );
 
             self.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_WRAP_S,
-                glow::CLAMP_TO_EDGE as i32,
+                options.wrap_mode.glow_code() as i32,
             );
             self.gl.tex_parameter_i32(
                 glow::TEXTURE_2D,
                 glow::TEXTURE_WRAP_T,
-                glow::CLAMP_TO_EDGE as i32,
+                options.wrap_mode.glow_code() as i32,
             );
             check_for_gl_error!(&self.gl, "tex_parameter");
 
@@ -632,6 +1053,11 @@ impl Painter {
                 );
                 check_for_gl_error!(&self.gl, "tex_image_2d");
             }
+
+            if options.mipmap_mode.is_some() {
+                self.gl.generate_mipmap(glow::TEXTURE_2D);
+                check_for_gl_error!(&self.gl, "generate_mipmap");
+            }
         }
     }
 
@@ -639,6 +1065,11 @@ impl Painter {
         if let Some(old_tex) = self.textures.remove(&tex_id) {
             unsafe { self.gl.delete_texture(old_tex) };
         }
+        self.sdf_textures.remove(&tex_id);
+        self.webgl_1_npot_textures.remove(&tex_id);
+        if let Some(target) = self.offscreen_targets.remove(&tex_id) {
+            unsafe { self.gl.delete_framebuffer(target.fbo) };
+        }
     }
 
     /// Get the [`glow::Texture`] bound to a [`egui::TextureId`].
@@ -672,8 +1103,15 @@ impl Painter {
         for tex in self.textures.values() {
             self.gl.delete_texture(*tex);
         }
+        for target in self.offscreen_targets.values() {
+            self.gl.delete_framebuffer(target.fbo);
+        }
         self.gl.delete_buffer(self.vbo);
         self.gl.delete_buffer(self.element_array_buffer);
+        if let Some(instancing) = &self.instancing {
+            self.gl.delete_program(instancing.program);
+            self.gl.delete_buffer(instancing.instance_vbo);
+        }
         for t in &self.textures_to_destroy {
             self.gl.delete_texture(*t);
         }
@@ -698,6 +1136,167 @@ impl Painter {
     }
 }
 
+impl InstancingProgram {
+    /// `vbo` is the vertex buffer of the [`Painter`], which we share.
+    unsafe fn new(
+        gl: &glow::Context,
+        vbo: glow::Buffer,
+        header: &str,
+        shader_prefix: &str,
+        shader_version: ShaderVersion,
+        srgb_support_define: &str,
+    ) -> Result<Self, String> {
+        let program = compile_program(
+            gl,
+            header,
+            shader_prefix,
+            shader_version,
+            srgb_support_define,
+            "#define INSTANCING\n",
+        )?;
+        let u_screen_size = gl.get_uniform_location(program, "u_screen_size").unwrap();
+        let u_sampler = gl.get_uniform_location(program, "u_sampler").unwrap();
+        let u_sdf = gl.get_uniform_location(program, "u_sdf").unwrap();
+
+        let instance_vbo = gl.create_buffer()?;
+
+        let a_instance_offset_loc = gl
+            .get_attrib_location(program, "a_instance_offset")
+            .unwrap();
+        let a_instance_srgba_loc = gl.get_attrib_location(program, "a_instance_srgba").unwrap();
+
+        let stride = std::mem::size_of::<Instance>() as i32;
+        let instance_infos = vec![
+            vao::BufferInfo {
+                location: a_instance_offset_loc,
+                vector_size: 2,
+                data_type: glow::FLOAT,
+                normalized: false,
+                stride,
+                offset: offset_of!(Instance, offset) as i32,
+            },
+            vao::BufferInfo {
+                location: a_instance_srgba_loc,
+                vector_size: 4,
+                data_type: glow::UNSIGNED_BYTE,
+                normalized: false,
+                stride,
+                offset: offset_of!(Instance, color) as i32,
+            },
+        ];
+        let vao = crate::vao::VertexArrayObject::new_instanced(
+            gl,
+            vbo,
+            vertex_buffer_infos(gl, program),
+            instance_vbo,
+            instance_infos,
+        );
+
+        Ok(Self {
+            program,
+            u_screen_size,
+            u_sampler,
+            u_sdf,
+            vao,
+            instance_vbo,
+        })
+    }
+}
+
+/// Compile the egui shaders, with some extra `#define`s for the vertex shader.
+unsafe fn compile_program(
+    gl: &glow::Context,
+    header: &str,
+    shader_prefix: &str,
+    shader_version: ShaderVersion,
+    srgb_support_define: &str,
+    vertex_defines: &str,
+) -> Result<glow::Program, String> {
+    let vert = compile_shader(
+        gl,
+        glow::VERTEX_SHADER,
+        &format!(
+            "{}\n{}\n{}\n{}\n{}",
+            header,
+            shader_prefix,
+            if shader_version.is_new_shader_interface() {
+                "#define NEW_SHADER_INTERFACE\n"
+            } else {
+                ""
+            },
+            vertex_defines,
+            VERT_SRC
+        ),
+    )?;
+    let frag = compile_shader(
+        gl,
+        glow::FRAGMENT_SHADER,
+        &format!(
+            "{}\n{}\n{}\n{}\n{}",
+            header,
+            shader_prefix,
+            srgb_support_define,
+            if shader_version.is_new_shader_interface() {
+                "#define NEW_SHADER_INTERFACE\n"
+            } else {
+                ""
+            },
+            FRAG_SRC
+        ),
+    )?;
+    let program = link_program(gl, [vert, frag].iter())?;
+    gl.detach_shader(program, vert);
+    gl.detach_shader(program, frag);
+    gl.delete_shader(vert);
+    gl.delete_shader(frag);
+    Ok(program)
+}
+
+/// The attributes of [`Vertex`] in the given program.
+unsafe fn vertex_buffer_infos(gl: &glow::Context, program: glow::Program) -> Vec<vao::BufferInfo> {
+    let a_pos_loc = gl.get_attrib_location(program, "a_pos").unwrap();
+    let a_tc_loc = gl.get_attrib_location(program, "a_tc").unwrap();
+    let a_srgba_loc = gl.get_attrib_location(program, "a_srgba").unwrap();
+
+    let stride = std::mem::size_of::<Vertex>() as i32;
+    vec![
+        vao::BufferInfo {
+            location: a_pos_loc,
+            vector_size: 2,
+            data_type: glow::FLOAT,
+            normalized: false,
+            stride,
+            offset: offset_of!(Vertex, pos) as i32,
+        },
+        vao::BufferInfo {
+            location: a_tc_loc,
+            vector_size: 2,
+            data_type: glow::FLOAT,
+            normalized: false,
+            stride,
+            offset: offset_of!(Vertex, uv) as i32,
+        },
+        vao::BufferInfo {
+            location: a_srgba_loc,
+            vector_size: 4,
+            data_type: glow::UNSIGNED_BYTE,
+            normalized: false,
+            stride,
+            offset: offset_of!(Vertex, color) as i32,
+        },
+    ]
+}
+
+/// Instanced drawing is core since OpenGL 3.3 and OpenGL ES 3.0 (and so WebGL 2).
+fn supports_instancing(gl: &glow::Context) -> bool {
+    let version = gl.version();
+    if version.is_embedded {
+        version.major >= 3
+    } else {
+        (version.major, version.minor) >= (3, 3)
+    }
+}
+
 pub fn clear(gl: &glow::Context, screen_size_in_pixels: [u32; 2], clear_color: egui::Rgba) {
     crate::profile_function!();
     unsafe {
@@ -731,6 +1330,35 @@ pub fn clear(gl: &glow::Context, screen_size_in_pixels: [u32; 2], clear_color: e
     }
 }
 
+/// Clear only the given rectangles (in points), e.g. [`egui::FullOutput::damage`].
+///
+/// See [`Painter::paint_damaged_primitives`].
+pub fn clear_damage(
+    gl: &glow::Context,
+    screen_size_in_pixels: [u32; 2],
+    pixels_per_point: f32,
+    damage: &[Rect],
+    clear_color: egui::Rgba,
+) {
+    crate::profile_function!();
+    let size_in_pixels = (screen_size_in_pixels[0], screen_size_in_pixels[1]);
+    unsafe {
+        gl.viewport(0, 0, size_in_pixels.0 as i32, size_in_pixels.1 as i32);
+        gl.clear_color(
+            clear_color[0],
+            clear_color[1],
+            clear_color[2],
+            clear_color[3],
+        );
+        gl.enable(glow::SCISSOR_TEST);
+        for &rect in damage {
+            set_clip_rect(gl, size_in_pixels, pixels_per_point, rect);
+            gl.clear(glow::COLOR_BUFFER_BIT);
+        }
+        gl.disable(glow::SCISSOR_TEST);
+    }
+}
+
 impl Drop for Painter {
     fn drop(&mut self) {
         if !self.destroyed {
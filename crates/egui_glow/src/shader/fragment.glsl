@@ -1,8 +1,14 @@
+#if defined(GL_ES) && __VERSION__ < 300
+    // Needed for anti-aliasing of signed distance field glyphs:
+    #extension GL_OES_standard_derivatives : enable
+#endif
+
 #ifdef GL_ES
     precision mediump float;
 #endif
 
 uniform sampler2D u_sampler;
+uniform bool u_sdf; // is the texture a signed distance field (in alpha)?
 
 #ifdef NEW_SHADER_INTERFACE
     in vec4 v_rgba;
@@ -16,11 +22,26 @@ uniform sampler2D u_sampler;
     varying vec2 v_tc;
 #endif
 
+// Coverage from a signed distance field, where the outline is at 0.5.
+float sdf_coverage(float distance) {
+#if defined(GL_ES) && __VERSION__ < 300 && !defined(GL_OES_standard_derivatives)
+    float width = 0.1; // no derivatives - use a fixed smoothing width
+#else
+    // Smooth over roughly one physical pixel (but never zero, since smoothstep needs a range):
+    float width = max(0.7 * length(vec2(dFdx(distance), dFdy(distance))), 0.001);
+#endif
+    return smoothstep(0.5 - width, 0.5 + width, distance);
+}
+
 #ifdef SRGB_SUPPORTED
     void main() {
         // The texture sampler is sRGB aware, and OpenGL already expects linear rgba output
         // so no need for any sRGB conversions here:
-        gl_FragColor = v_rgba * texture2D(u_sampler, v_tc);
+        vec4 texture_rgba = texture2D(u_sampler, v_tc);
+        if (u_sdf) {
+            texture_rgba = vec4(sdf_coverage(texture_rgba.a));
+        }
+        gl_FragColor = v_rgba * texture_rgba;
     }
 #else
     // 0-255 sRGB  from  0-1 linear
@@ -50,6 +71,9 @@ uniform sampler2D u_sampler;
     void main() {
         // We must decode the colors, since WebGL1 doesn't come with sRGBA textures:
         vec4 texture_rgba = linear_from_srgba(texture2D(u_sampler, v_tc) * 255.0);
+        if (u_sdf) {
+            texture_rgba = vec4(sdf_coverage(texture_rgba.a)); // alpha is always linear
+        }
         /// Multiply vertex color with texture color (in linear space).
         gl_FragColor = v_rgba * texture_rgba;
 
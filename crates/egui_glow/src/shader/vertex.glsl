@@ -16,6 +16,10 @@ uniform vec2 u_screen_size;
 I vec2 a_pos;
 I vec4 a_srgba; // 0-255 sRGB
 I vec2 a_tc;
+#ifdef INSTANCING
+    I vec2 a_instance_offset;
+    I vec4 a_instance_srgba; // 0-255 sRGB
+#endif
 O vec4 v_rgba;
 O vec2 v_tc;
 
@@ -32,12 +36,20 @@ vec4 linear_from_srgba(vec4 srgba) {
 }
 
 void main() {
+#ifdef INSTANCING
+    vec2 pos = a_pos + a_instance_offset;
+#else
+    vec2 pos = a_pos;
+#endif
     gl_Position = vec4(
-                      2.0 * a_pos.x / u_screen_size.x - 1.0,
-                      1.0 - 2.0 * a_pos.y / u_screen_size.y,
+                      2.0 * pos.x / u_screen_size.x - 1.0,
+                      1.0 - 2.0 * pos.y / u_screen_size.y,
                       0.0,
                       1.0);
     // egui encodes vertex colors in gamma space, so we must decode the colors here:
     v_rgba = linear_from_srgba(a_srgba);
+#ifdef INSTANCING
+    v_rgba *= linear_from_srgba(a_instance_srgba);
+#endif
     v_tc = a_tc;
 }
@@ -40,21 +40,7 @@ impl VertexArrayObject {
             // Store state in the VAO:
             gl.bind_vertex_array(Some(vao));
             gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
-
-            for attribute in &buffer_infos {
-                gl.vertex_attrib_pointer_f32(
-                    attribute.location,
-                    attribute.vector_size,
-                    attribute.data_type,
-                    attribute.normalized,
-                    attribute.stride,
-                    attribute.offset,
-                );
-                check_for_gl_error!(gl, "vertex_attrib_pointer_f32");
-                gl.enable_vertex_attrib_array(attribute.location);
-                check_for_gl_error!(gl, "enable_vertex_attrib_array");
-            }
-
+            enable_attributes(gl, &buffer_infos);
             gl.bind_vertex_array(None);
 
             Some(vao)
@@ -70,6 +56,38 @@ impl VertexArrayObject {
         }
     }
 
+    /// Like [`Self::new`], but also reads one set of `instance_infos` per instance from `instance_vbo`.
+    ///
+    /// Only call this if the context supports instancing, which means it also supports VAO:s.
+    #[allow(clippy::needless_pass_by_value)] // false positive
+    pub(crate) unsafe fn new_instanced(
+        gl: &glow::Context,
+        vbo: glow::Buffer,
+        buffer_infos: Vec<BufferInfo>,
+        instance_vbo: glow::Buffer,
+        instance_infos: Vec<BufferInfo>,
+    ) -> Self {
+        let vao = gl.create_vertex_array().unwrap();
+        check_for_gl_error!(gl, "create_vertex_array");
+
+        gl.bind_vertex_array(Some(vao));
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(vbo));
+        enable_attributes(gl, &buffer_infos);
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(instance_vbo));
+        enable_attributes(gl, &instance_infos);
+        for attribute in &instance_infos {
+            gl.vertex_attrib_divisor(attribute.location, 1);
+            check_for_gl_error!(gl, "vertex_attrib_divisor");
+        }
+        gl.bind_vertex_array(None);
+
+        Self {
+            vao: Some(vao),
+            vbo,
+            buffer_infos,
+        }
+    }
+
     pub(crate) unsafe fn bind(&self, gl: &glow::Context) {
         if let Some(vao) = self.vao {
             gl.bind_vertex_array(Some(vao));
@@ -77,20 +95,7 @@ impl VertexArrayObject {
         } else {
             gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vbo));
             check_for_gl_error!(gl, "bind_buffer");
-
-            for attribute in &self.buffer_infos {
-                gl.vertex_attrib_pointer_f32(
-                    attribute.location,
-                    attribute.vector_size,
-                    attribute.data_type,
-                    attribute.normalized,
-                    attribute.stride,
-                    attribute.offset,
-                );
-                check_for_gl_error!(gl, "vertex_attrib_pointer_f32");
-                gl.enable_vertex_attrib_array(attribute.location);
-                check_for_gl_error!(gl, "enable_vertex_attrib_array");
-            }
+            enable_attributes(gl, &self.buffer_infos);
         }
     }
 
@@ -106,6 +111,23 @@ impl VertexArrayObject {
     }
 }
 
+/// Point the attributes at the currently bound `ARRAY_BUFFER`, and enable them.
+unsafe fn enable_attributes(gl: &glow::Context, buffer_infos: &[BufferInfo]) {
+    for attribute in buffer_infos {
+        gl.vertex_attrib_pointer_f32(
+            attribute.location,
+            attribute.vector_size,
+            attribute.data_type,
+            attribute.normalized,
+            attribute.stride,
+            attribute.offset,
+        );
+        check_for_gl_error!(gl, "vertex_attrib_pointer_f32");
+        gl.enable_vertex_attrib_array(attribute.location);
+        check_for_gl_error!(gl, "enable_vertex_attrib_array");
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 fn supports_vao(gl: &glow::Context) -> bool {
@@ -22,8 +22,11 @@ impl EguiGlow {
             })
             .unwrap();
 
+        let egui_ctx = egui::Context::default();
+        egui_ctx.tessellation_options().instancing = painter.supports_instancing();
+
         Self {
-            egui_ctx: Default::default(),
+            egui_ctx,
             egui_winit: egui_winit::State::new(event_loop),
             painter,
             shapes: Default::default(),
@@ -55,6 +58,7 @@ impl EguiGlow {
             repaint_after,
             textures_delta,
             shapes,
+            damage: _, // we always repaint everything
         } = self.egui_ctx.run(raw_input, run_ui);
 
         self.egui_winit
@@ -72,10 +72,56 @@ fn tessellate_circles(c: &mut Criterion) {
     });
 }
 
+/// A big plot: many line segments and markers, in a few clip rectangles.
+///
+/// Compare with `cargo bench -p epaint --features rayon` to see the gain of parallel tessellation.
+fn tessellate_plot(c: &mut Criterion) {
+    c.bench_function("tessellate_plot_100k", move |b| {
+        let mut clipped_shapes = vec![];
+        for plot in 0..4 {
+            let clip_rect =
+                Rect::from_min_size(pos2(0.0, 256.0 * plot as f32), vec2(1024.0, 256.0));
+            let mut prev = clip_rect.left_center();
+            for i in 0..25_000 {
+                let x = i as f32 * 1024.0 / 25_000.0;
+                let pos = pos2(x, clip_rect.center().y + 100.0 * (x / 20.0).sin());
+                let stroke = Stroke::new(1.5, Color32::LIGHT_BLUE);
+                let shape = if i % 10 == 0 {
+                    Shape::circle_stroke(pos, 3.0, stroke)
+                } else {
+                    Shape::line_segment([prev, pos], stroke)
+                };
+                clipped_shapes.push(ClippedShape(clip_rect, shape));
+                prev = pos;
+            }
+        }
+        assert_eq!(clipped_shapes.len(), 100_000);
+
+        let pixels_per_point = 2.0;
+        let options = TessellationOptions::default();
+
+        let atlas = TextureAtlas::new([4096, 256]);
+        let font_tex_size = atlas.size();
+        let prepared_discs = atlas.prepared_discs();
+
+        b.iter(|| {
+            let clipped_primitive = tessellate_shapes(
+                pixels_per_point,
+                options,
+                font_tex_size,
+                prepared_discs.clone(),
+                clipped_shapes.clone(),
+            );
+            black_box(clipped_primitive);
+        });
+    });
+}
+
 criterion_group!(
     benches,
     single_dashed_lines,
     many_dashed_lines,
-    tessellate_circles
+    tessellate_circles,
+    tessellate_plot
 );
 criterion_main!(benches);
@@ -0,0 +1,368 @@
+//! Mapping numbers to colors, e.g. for heatmaps.
+
+use std::ops::RangeInclusive;
+
+use crate::{Color32, Rgba};
+
+/// Maps values in `0..=1` to colors.
+///
+/// A colormap is a table of [`Self::TABLE_SIZE`] evenly spaced colors,
+/// interpolated in linear space from the color stops it was created with.
+///
+/// The default is [`Self::viridis`].
+///
+/// ```
+/// use epaint::{Color32, Colormap};
+/// let colormap = Colormap::new(vec![Color32::BLACK, Color32::WHITE]);
+/// assert_eq!(colormap.colors().len(), Colormap::TABLE_SIZE);
+/// assert_eq!(colormap.sample(0.0), Color32::BLACK);
+/// assert_eq!(colormap.sample(1.0), Color32::WHITE);
+/// assert_eq!(colormap.sample_range(15.0, 10.0..=20.0), colormap.sample(0.5));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Colormap {
+    /// [`Self::TABLE_SIZE`] colors, the first for `0.0` and the last for `1.0`.
+    colors: Vec<Color32>,
+}
+
+impl Default for Colormap {
+    fn default() -> Self {
+        Self::viridis()
+    }
+}
+
+impl Colormap {
+    /// The number of colors in the table of a colormap.
+    pub const TABLE_SIZE: usize = 256;
+
+    /// A gradient through evenly spaced colors, the first one for `0.0` and the last one for `1.0`.
+    ///
+    /// Panics if `colors` is empty.
+    pub fn new(colors: impl Into<Vec<Color32>>) -> Self {
+        let colors = colors.into();
+        assert!(!colors.is_empty(), "A colormap needs at least one color");
+        let last = (colors.len() - 1).max(1) as f32;
+        let stops: Vec<_> = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, color)| (i as f32 / last, color))
+            .collect();
+        Self::from_sorted_stops(&stops)
+    }
+
+    /// A gradient through colors at the given positions in `0..=1`.
+    ///
+    /// The stops are sorted by position. Below the first and above the last stop the color is constant.
+    ///
+    /// Panics if `stops` is empty or a position is `NaN`.
+    ///
+    /// ```
+    /// use epaint::{Color32, Colormap};
+    /// let colormap = Colormap::from_stops(vec![
+    ///     (0.0, Color32::BLUE),
+    ///     (0.9, Color32::WHITE),
+    ///     (1.0, Color32::RED),
+    /// ]);
+    /// assert_eq!(colormap.sample(0.0), Color32::BLUE);
+    /// assert_eq!(colormap.sample(1.0), Color32::RED);
+    /// ```
+    pub fn from_stops(stops: impl Into<Vec<(f32, Color32)>>) -> Self {
+        let mut stops = stops.into();
+        assert!(!stops.is_empty(), "A colormap needs at least one color");
+        assert!(
+            stops.iter().all(|(t, _)| !t.is_nan()),
+            "Colormap stops must not be NaN"
+        );
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        Self::from_sorted_stops(&stops)
+    }
+
+    fn from_sorted_stops(stops: &[(f32, Color32)]) -> Self {
+        let last = (Self::TABLE_SIZE - 1) as f32;
+        let colors = (0..Self::TABLE_SIZE)
+            .map(|i| {
+                let t = i as f32 / last;
+                let index = stops.partition_point(|(position, _)| *position <= t);
+                if index == 0 {
+                    return stops[0].1;
+                }
+                if index == stops.len() {
+                    return stops[index - 1].1;
+                }
+
+                // Interpolate in linear space, so that the gradient doesn't darken between stops:
+                let ((t0, a), (t1, b)) = (stops[index - 1], stops[index]);
+                let t = (t - t0) / (t1 - t0);
+                let (a, b) = (Rgba::from(a), Rgba::from(b));
+                Color32::from(a * (1.0 - t) + b * t)
+            })
+            .collect();
+        Self { colors }
+    }
+
+    /// Perceptually uniform, dark blue through green to yellow, and readable with color blindness.
+    pub fn viridis() -> Self {
+        Self::from_hex(&[
+            0x440154, 0x472c7a, 0x3b518b, 0x2c718e, 0x21908d, 0x27ad81, 0x5cc863, 0xaadc32,
+            0xfde725,
+        ])
+    }
+
+    /// Perceptually uniform, black through purple and orange to light yellow.
+    pub fn magma() -> Self {
+        Self::from_hex(&[
+            0x000004, 0x1c1044, 0x4f127b, 0x812581, 0xb5367a, 0xe55064, 0xfb8761, 0xfec287,
+            0xfcfdbf,
+        ])
+    }
+
+    /// Perceptually uniform, black through red and orange to bright yellow.
+    pub fn inferno() -> Self {
+        Self::from_hex(&[
+            0x000004, 0x1f0c48, 0x550f6d, 0x88226a, 0xba3655, 0xe35933, 0xf98c0a, 0xf9c932,
+            0xfcffa4,
+        ])
+    }
+
+    /// Perceptually uniform, dark blue through purple and orange to yellow.
+    pub fn plasma() -> Self {
+        Self::from_hex(&[
+            0x0d0887, 0x4c02a1, 0x7e03a8, 0xa92395, 0xcc4778, 0xe56b5d, 0xf89441, 0xfdc328,
+            0xf0f921,
+        ])
+    }
+
+    /// Perceptually uniform, dark blue through gray to yellow,
+    /// and almost the same for viewers with red-green color blindness.
+    pub fn cividis() -> Self {
+        Self::from_hex(&[
+            0x00224e, 0x123570, 0x3b496c, 0x575d6d, 0x707173, 0x8a8678, 0xa59c74, 0xc3b369,
+            0xfee838,
+        ])
+    }
+
+    /// A rainbow from dark blue through green to dark red, with more contrast than [`Self::viridis`].
+    ///
+    /// Not perceptually uniform, so small differences may look larger than they are.
+    pub fn turbo() -> Self {
+        Self::from_hex(&[
+            0x30123b, 0x4662d7, 0x36aaf9, 0x1ae4b6, 0x72fe5e, 0xc7ef34, 0xfaba39, 0xf66b19,
+            0x7a0403,
+        ])
+    }
+
+    /// Diverging, blue through light gray to red, for values around a meaningful center.
+    pub fn coolwarm() -> Self {
+        Self::from_hex(&[
+            0x3b4cc0, 0x5f7fe8, 0x8caffe, 0xb8d0f9, 0xdddcdc, 0xf4c4ad, 0xf49a7b, 0xde6049,
+            0xb40426,
+        ])
+    }
+
+    /// Diverging, dark red through white to dark blue, for values around a meaningful center.
+    pub fn red_blue() -> Self {
+        Self::from_hex(&[
+            0x67001f, 0xb2182b, 0xd6604d, 0xf4a582, 0xfddbc7, 0xf7f7f7, 0xd1e5f0, 0x92c5de,
+            0x4393c3, 0x2166ac, 0x053061,
+        ])
+    }
+
+    /// Diverging, brown through white to teal, for values around a meaningful center.
+    pub fn brown_teal() -> Self {
+        Self::from_hex(&[
+            0x543005, 0x8c510a, 0xbf812d, 0xdfc27d, 0xf6e8c3, 0xf5f5f5, 0xc7eae5, 0x80cdc1,
+            0x35978f, 0x01665e, 0x003c30,
+        ])
+    }
+
+    /// Black to white.
+    pub fn grayscale() -> Self {
+        Self::new(vec![Color32::BLACK, Color32::WHITE])
+    }
+
+    fn from_hex(colors: &[u32]) -> Self {
+        Self::new(
+            colors
+                .iter()
+                .map(|&rgb| color_from_hex(rgb))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// The same colors in the opposite direction.
+    pub fn reversed(mut self) -> Self {
+        self.colors.reverse();
+        self
+    }
+
+    /// The [`Self::TABLE_SIZE`] colors of the table, evenly spaced from `0.0` to `1.0`.
+    pub fn colors(&self) -> &[Color32] {
+        &self.colors
+    }
+
+    /// The color for `t`, which is clamped to `0..=1`.
+    ///
+    /// `NaN` gives [`Color32::TRANSPARENT`].
+    pub fn sample(&self, t: f32) -> Color32 {
+        if t.is_nan() {
+            return Color32::TRANSPARENT;
+        }
+        let last = self.colors.len() - 1;
+        let position = t.clamp(0.0, 1.0) * last as f32;
+        let index = (position as usize).min(last - 1);
+
+        // Neighboring colors of the table are so close that interpolating them in gamma space is fine:
+        let (a, b) = (self.colors[index], self.colors[index + 1]);
+        let t = position - index as f32;
+        let lerp = |a: u8, b: u8| (a as f32 + t * (b as f32 - a as f32)).round() as u8;
+        Color32::from_rgba_premultiplied(
+            lerp(a.r(), b.r()),
+            lerp(a.g(), b.g()),
+            lerp(a.b(), b.b()),
+            lerp(a.a(), b.a()),
+        )
+    }
+
+    /// The color for `value`, where the start of `range` maps to `0.0` and the end to `1.0`.
+    ///
+    /// An empty range maps everything to the middle of the colormap.
+    pub fn sample_range(&self, value: f64, range: RangeInclusive<f64>) -> Color32 {
+        let (min, max) = (*range.start(), *range.end());
+        let t = if max == min {
+            0.5
+        } else {
+            (value - min) / (max - min)
+        };
+        self.sample(t as f32)
+    }
+
+    /// `n` evenly spaced colors from the start to the end of the colormap.
+    pub fn samples(&self, n: usize) -> Vec<Color32> {
+        match n {
+            0 => vec![],
+            1 => vec![self.sample(0.5)],
+            n => (0..n)
+                .map(|i| self.sample(i as f32 / (n - 1) as f32))
+                .collect(),
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A list of distinct colors for categories, e.g. one per line in a plot.
+///
+/// Unlike a [`Colormap`], the colors are not interpolated, and the list repeats.
+///
+/// ```
+/// use epaint::{colormap::Palette, Color32};
+/// let palette = Palette::new(vec![Color32::RED, Color32::BLUE]);
+/// assert_eq!(palette.color(3), Color32::BLUE);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Palette {
+    colors: Vec<Color32>,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::tab10()
+    }
+}
+
+impl Palette {
+    /// Panics if `colors` is empty.
+    pub fn new(colors: impl Into<Vec<Color32>>) -> Self {
+        let colors = colors.into();
+        assert!(!colors.is_empty(), "A palette needs at least one color");
+        Self { colors }
+    }
+
+    /// The ten default category colors of Matplotlib and D3.
+    pub fn tab10() -> Self {
+        Self::from_hex(&[
+            0x1f77b4, 0xff7f0e, 0x2ca02c, 0xd62728, 0x9467bd, 0x8c564b, 0xe377c2, 0x7f7f7f,
+            0xbcbd22, 0x17becf,
+        ])
+    }
+
+    /// Eight soft colors, from ColorBrewer.
+    pub fn set2() -> Self {
+        Self::from_hex(&[
+            0x66c2a5, 0xfc8d62, 0x8da0cb, 0xe78ac3, 0xa6d854, 0xffd92f, 0xe5c494, 0xb3b3b3,
+        ])
+    }
+
+    /// Eight colors that stay distinct with all common kinds of color blindness, by Okabe and Ito.
+    pub fn okabe_ito() -> Self {
+        Self::from_hex(&[
+            0xe69f00, 0x56b4e9, 0x009e73, 0xf0e442, 0x0072b2, 0xd55e00, 0xcc79a7, 0x000000,
+        ])
+    }
+
+    fn from_hex(colors: &[u32]) -> Self {
+        Self::new(
+            colors
+                .iter()
+                .map(|&rgb| color_from_hex(rgb))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    /// All colors of the palette.
+    pub fn colors(&self) -> &[Color32] {
+        &self.colors
+    }
+
+    /// The color for category `index`, starting over after the last color.
+    pub fn color(&self, index: usize) -> Color32 {
+        self.colors[index % self.colors.len()]
+    }
+}
+
+fn color_from_hex(rgb: u32) -> Color32 {
+    let [_, r, g, b] = rgb.to_be_bytes();
+    Color32::from_rgb(r, g, b)
+}
+
+#[test]
+fn test_colormap_sample() {
+    let colormap = Colormap::viridis();
+    assert_eq!(colormap.sample(0.0), Color32::from_rgb(0x44, 0x01, 0x54));
+    assert_eq!(colormap.sample(1.0), Color32::from_rgb(0xfd, 0xe7, 0x25));
+    assert_eq!(colormap.sample(-1.0), colormap.sample(0.0));
+    assert_eq!(colormap.sample(2.0), colormap.sample(1.0));
+    // Between the two colors of the table closest to the middle stop (0x21908d):
+    assert_eq!(colormap.sample(0.5), Color32::from_rgb(0x21, 0x91, 0x8d));
+    assert_eq!(colormap.colors().len(), Colormap::TABLE_SIZE);
+    assert_eq!(colormap.sample(f32::NAN), Color32::TRANSPARENT);
+
+    let single = Colormap::new(vec![Color32::RED]);
+    assert_eq!(single.sample(0.3), Color32::RED);
+}
+
+#[test]
+fn test_colormap_stops() {
+    // Halfway between black and white in linear space:
+    let middle_gray = Color32::from_gray(188);
+
+    let colormap = Colormap::from_stops(vec![(0.75, Color32::WHITE), (0.25, Color32::BLACK)]);
+    assert_eq!(colormap.sample(0.0), Color32::BLACK);
+    assert_eq!(colormap.sample(0.5), middle_gray);
+    assert_eq!(colormap.sample(1.0), Color32::WHITE);
+
+    let reversed = colormap.reversed();
+    assert_eq!(reversed.sample(0.0), Color32::WHITE);
+    assert_eq!(reversed.sample(0.2), Color32::WHITE);
+    assert_eq!(reversed.sample(1.0), Color32::BLACK);
+
+    let grayscale = Colormap::grayscale();
+    assert_eq!(grayscale.sample_range(5.0, 0.0..=10.0), middle_gray);
+    assert_eq!(grayscale.sample_range(5.0, 5.0..=5.0), middle_gray);
+    assert_eq!(
+        grayscale.samples(3),
+        vec![Color32::BLACK, middle_gray, Color32::WHITE]
+    );
+}
@@ -1,4 +1,4 @@
-use crate::{textures::TextureFilter, Color32};
+use crate::{textures::TextureOptions, Color32};
 
 /// An image stored in RAM.
 ///
@@ -15,13 +15,21 @@ pub enum ImageData {
 
     /// Used for the font texture.
     Font(FontImage),
+
+    /// Used for the texture of glyphs rendered as signed distance fields,
+    /// see [`crate::text::GlyphRendering::Sdf`].
+    ///
+    /// Each value is `0.5` on the outline of a glyph, going towards `1.0` inside of it
+    /// and towards `0.0` outside of it.
+    /// Backends must sample this without any gamma, and turn it into coverage in the fragment shader.
+    Sdf(FontImage),
 }
 
 impl ImageData {
     pub fn size(&self) -> [usize; 2] {
         match self {
             Self::Color(image) => image.size,
-            Self::Font(image) => image.size,
+            Self::Font(image) | Self::Sdf(image) => image.size,
         }
     }
 
@@ -35,7 +43,7 @@ impl ImageData {
 
     pub fn bytes_per_pixel(&self) -> usize {
         match self {
-            Self::Color(_) | Self::Font(_) => 4,
+            Self::Color(_) | Self::Font(_) | Self::Sdf(_) => 4,
         }
     }
 }
@@ -127,6 +135,23 @@ impl ColorImage {
     pub fn height(&self) -> usize {
         self.size[1]
     }
+
+    /// Clone a sub-region as a new image.
+    pub fn region(&self, [x, y]: [usize; 2], [w, h]: [usize; 2]) -> ColorImage {
+        assert!(x + w <= self.width());
+        assert!(y + h <= self.height());
+
+        let mut pixels = Vec::with_capacity(w * h);
+        for y in y..y + h {
+            let offset = y * self.width() + x;
+            pixels.extend(&self.pixels[offset..(offset + w)]);
+        }
+        assert_eq!(pixels.len(), w * h);
+        ColorImage {
+            size: [w, h],
+            pixels,
+        }
+    }
 }
 
 impl std::ops::Index<(usize, usize)> for ColorImage {
@@ -209,6 +234,17 @@ impl FontImage {
         })
     }
 
+    /// Returns a signed distance field as `sRGBA` premultiplied pixels, row by row, top to bottom.
+    ///
+    /// The distance is stored as-is in all four channels.
+    /// Since the alpha channel of an `sRGBA` texture is linear, read the distance from it.
+    pub fn sdf_srgba_pixels(&'_ self) -> impl ExactSizeIterator<Item = Color32> + '_ {
+        self.pixels.iter().map(|distance| {
+            let a = fast_round(distance * 255.0);
+            Color32::from_rgba_premultiplied(a, a, a, a)
+        })
+    }
+
     /// Clone a sub-region as a new image.
     pub fn region(&self, [x, y]: [usize; 2], [w, h]: [usize; 2]) -> FontImage {
         assert!(x + w <= self.width());
@@ -274,7 +310,8 @@ pub struct ImageDelta {
     /// If [`Self::pos`] is `Some`, this describes a patch of the whole image starting at [`Self::pos`].
     pub image: ImageData,
 
-    pub filter: TextureFilter,
+    /// How the texture should be sampled.
+    pub options: TextureOptions,
 
     /// If `None`, set the whole texture to [`Self::image`].
     ///
@@ -284,19 +321,19 @@ pub struct ImageDelta {
 
 impl ImageDelta {
     /// Update the whole texture.
-    pub fn full(image: impl Into<ImageData>, filter: TextureFilter) -> Self {
+    pub fn full(image: impl Into<ImageData>, options: TextureOptions) -> Self {
         Self {
             image: image.into(),
-            filter,
+            options,
             pos: None,
         }
     }
 
     /// Update a sub-region of an existing texture.
-    pub fn partial(pos: [usize; 2], image: impl Into<ImageData>, filter: TextureFilter) -> Self {
+    pub fn partial(pos: [usize; 2], image: impl Into<ImageData>, options: TextureOptions) -> Self {
         Self {
             image: image.into(),
-            filter,
+            options,
             pos: Some(pos),
         }
     }
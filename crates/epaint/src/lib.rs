@@ -14,14 +14,17 @@
 
 mod bezier;
 pub mod color;
+pub mod colormap;
 pub mod image;
 mod mesh;
 pub mod mutex;
+pub mod raster;
 mod shadow;
 mod shape;
 pub mod shape_transform;
 pub mod stats;
 mod stroke;
+pub mod svg;
 pub mod tessellator;
 pub mod text;
 mod texture_atlas;
@@ -32,18 +35,19 @@ pub mod util;
 pub use {
     bezier::{CubicBezierShape, QuadraticBezierShape},
     color::{Color32, Rgba},
+    colormap::Colormap,
     image::{ColorImage, FontImage, ImageData, ImageDelta},
-    mesh::{Mesh, Mesh16, Vertex},
+    mesh::{Instance, InstancedMesh, Mesh, Mesh16, Vertex},
     shadow::Shadow,
     shape::{
-        CircleShape, PaintCallback, PaintCallbackInfo, PathShape, RectShape, Rounding, Shape,
-        TextShape,
+        CircleShape, InstancedShape, PaintCallback, PaintCallbackInfo, PathShape, RectShape,
+        Rounding, Shape, TextShape,
     },
     stats::PaintStats,
     stroke::Stroke,
     tessellator::{tessellate_shapes, TessellationOptions, Tessellator},
     text::{FontFamily, FontId, Fonts, Galley},
-    texture_atlas::TextureAtlas,
+    texture_atlas::{PreparedDisc, TextureAtlas},
     texture_handle::TextureHandle,
     textures::TextureManager,
 };
@@ -110,10 +114,16 @@ pub struct ClippedPrimitive {
     pub primitive: Primitive,
 }
 
-/// A rendering primitive - either a [`Mesh`] or a [`PaintCallback`].
+/// A rendering primitive - either a [`Mesh`], an [`InstancedMesh`] or a [`PaintCallback`].
+///
+/// Cloning a primitive is cheap, since the meshes are shared.
 #[derive(Clone, Debug)]
 pub enum Primitive {
-    Mesh(Mesh),
+    Mesh(std::sync::Arc<Mesh>),
+
+    /// Only produced if [`TessellationOptions::instancing`] is on.
+    Instanced(InstancedMesh),
+
     Callback(PaintCallback),
 }
 
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use crate::*;
 use emath::*;
 
@@ -22,6 +24,31 @@ pub struct Vertex {
     pub color: Color32, // 32 bit
 }
 
+/// One copy of an [`InstancedMesh`].
+///
+/// Should be friendly to send to GPU as is.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct Instance {
+    /// Added to the position of each vertex (in points).
+    pub offset: Vec2, // 64 bit
+
+    /// sRGBA with premultiplied alpha.
+    ///
+    /// Multiplied with the color of each vertex (in linear space),
+    /// so use white vertices to paint each copy in exactly this color.
+    pub color: Color32, // 32 bit
+}
+
+impl Instance {
+    #[inline]
+    pub fn new(offset: Vec2, color: Color32) -> Self {
+        Self { offset, color }
+    }
+}
+
 /// Textured triangles in two dimensions.
 #[derive(Clone, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
@@ -267,6 +294,75 @@ impl Mesh {
 
 // ----------------------------------------------------------------------------
 
+/// A [`Mesh`] that should be painted once per [`Instance`].
+///
+/// This is produced by the [`crate::Tessellator`] for [`crate::Shape::Instanced`]
+/// when [`crate::TessellationOptions::instancing`] is on.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstancedMesh {
+    /// The mesh to copy, in the coordinates of the shape it was tessellated from.
+    pub mesh: Arc<Mesh>,
+
+    /// Where to put each copy, and how to tint it.
+    pub instances: Arc<[Instance]>,
+}
+
+impl InstancedMesh {
+    pub fn is_empty(&self) -> bool {
+        self.mesh.is_empty() || self.instances.is_empty()
+    }
+
+    /// Combine all the copies into one [`Mesh`].
+    ///
+    /// This is what to paint instead, if your backend doesn't support instancing.
+    pub fn to_mesh(&self) -> Mesh {
+        let mut mesh = Mesh::with_texture(self.mesh.texture_id);
+        self.append_copies(Rect::EVERYTHING, &mut mesh);
+        mesh
+    }
+
+    /// Append a copy of the mesh for each instance that may be visible within `clip_rect`.
+    pub(crate) fn append_copies(&self, clip_rect: Rect, out: &mut Mesh) {
+        crate::epaint_assert!(self.mesh.is_valid());
+
+        if out.is_empty() {
+            out.texture_id = self.mesh.texture_id;
+        } else {
+            crate::epaint_assert!(out.texture_id == self.mesh.texture_id);
+        }
+
+        let bounds = self.mesh.calc_bounds();
+        for instance in self.instances.iter() {
+            if !clip_rect.intersects(bounds.translate(instance.offset)) {
+                continue;
+            }
+
+            let index_offset = out.vertices.len() as u32;
+            out.indices
+                .extend(self.mesh.indices.iter().map(|index| index + index_offset));
+            out.vertices
+                .extend(self.mesh.vertices.iter().map(|vertex| Vertex {
+                    pos: vertex.pos + instance.offset,
+                    uv: vertex.uv,
+                    color: tint(vertex.color, instance.color),
+                }));
+        }
+    }
+}
+
+/// Multiply two colors in linear space, like the GPU does when painting an [`InstancedMesh`].
+fn tint(color: Color32, tint: Color32) -> Color32 {
+    if color == Color32::WHITE {
+        tint
+    } else if tint == Color32::WHITE {
+        color
+    } else {
+        (Rgba::from(color) * Rgba::from(tint)).into()
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// A version of [`Mesh`] that uses 16-bit indices.
 ///
 /// This is produced by [`Mesh::split_to_u16`] and is meant to be used for legacy render backends.
@@ -0,0 +1,294 @@
+//! Painting tessellated meshes into an image on the CPU, e.g. to save a plot as a PNG
+//! without a GPU.
+
+use emath::Vec2;
+
+use crate::{
+    ahash::HashMap, ClippedPrimitive, Color32, ColorImage, FontImage, ImageData, Mesh, Primitive,
+    TextureId, Vertex,
+};
+
+/// Paint `primitives` into an image of `size` pixels, on top of `background`.
+///
+/// Positions are in points, and scaled by `pixels_per_point`.
+/// Meshes are sampled from the textures in `textures`, or painted with only their vertex colors
+/// when their texture is missing.
+/// [`ImageData::Sdf`] textures are turned into coverage like the `egui_glow` and `egui-wgpu` shaders do,
+/// smoothing the outline over about one pixel.
+///
+/// Colors are blended in gamma space, and there is no anti-aliasing
+/// besides the feathering of the tessellator. [`Primitive::Callback`]s are skipped.
+pub fn rasterize(
+    primitives: &[ClippedPrimitive],
+    textures: &HashMap<TextureId, ImageData>,
+    size: [usize; 2],
+    pixels_per_point: f32,
+    background: Color32,
+) -> ColorImage {
+    let mut image = ColorImage::new(size, background);
+    for ClippedPrimitive {
+        clip_rect,
+        primitive,
+    } in primitives
+    {
+        let clip_rect = [
+            (clip_rect.min.x * pixels_per_point).round().max(0.0) as usize,
+            (clip_rect.min.y * pixels_per_point).round().max(0.0) as usize,
+            ((clip_rect.max.x * pixels_per_point).round().max(0.0) as usize).min(size[0]),
+            ((clip_rect.max.y * pixels_per_point).round().max(0.0) as usize).min(size[1]),
+        ];
+        match primitive {
+            Primitive::Mesh(mesh) => {
+                paint_mesh(&mut image, mesh, textures, clip_rect, pixels_per_point);
+            }
+            Primitive::Instanced(instanced) => {
+                for instance in instanced.instances.iter() {
+                    let mut mesh = (*instanced.mesh).clone();
+                    mesh.translate(instance.offset);
+                    for vertex in &mut mesh.vertices {
+                        vertex.color = multiply(vertex.color, instance.color);
+                    }
+                    paint_mesh(&mut image, &mesh, textures, clip_rect, pixels_per_point);
+                }
+            }
+            Primitive::Callback(_) => {}
+        }
+    }
+    image
+}
+
+/// `clip_rect` is `[min_x, min_y, max_x, max_y]` in pixels.
+fn paint_mesh(
+    image: &mut ColorImage,
+    mesh: &Mesh,
+    textures: &HashMap<TextureId, ImageData>,
+    clip_rect: [usize; 4],
+    pixels_per_point: f32,
+) {
+    let texture = textures.get(&mesh.texture_id);
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [triangle[0], triangle[1], triangle[2]].map(|i| &mesh.vertices[i as usize]);
+        paint_triangle(image, [a, b, c], texture, clip_rect, pixels_per_point);
+    }
+}
+
+fn paint_triangle(
+    image: &mut ColorImage,
+    vertices: [&Vertex; 3],
+    texture: Option<&ImageData>,
+    clip_rect: [usize; 4],
+    pixels_per_point: f32,
+) {
+    let [a, b, c] = vertices.map(|vertex| vertex.pos.to_vec2() * pixels_per_point);
+    let area = (b - a).x * (c - a).y - (b - a).y * (c - a).x;
+    if area == 0.0 || !area.is_finite() {
+        return;
+    }
+
+    let min_x = a.x.min(b.x).min(c.x).floor().max(clip_rect[0] as f32) as usize;
+    let min_y = a.y.min(b.y).min(c.y).floor().max(clip_rect[1] as f32) as usize;
+    let max_x = (a.x.max(b.x).max(c.x).ceil().max(0.0) as usize).min(clip_rect[2]);
+    let max_y = (a.y.max(b.y).max(c.y).ceil().max(0.0) as usize).min(clip_rect[3]);
+
+    // The texture coordinates are affine over the triangle, so they change by the same amount from pixel to pixel:
+    let uv_at = |p: Vec2| {
+        let wa = ((b - p).x * (c - p).y - (b - p).y * (c - p).x) / area;
+        let wb = ((c - p).x * (a - p).y - (c - p).y * (a - p).x) / area;
+        let wc = 1.0 - wa - wb;
+        vertices[0].uv.to_vec2() * wa
+            + vertices[1].uv.to_vec2() * wb
+            + vertices[2].uv.to_vec2() * wc
+    };
+    let uv_per_pixel = [
+        uv_at(crate::vec2(1.0, 0.0)) - uv_at(Vec2::ZERO),
+        uv_at(crate::vec2(0.0, 1.0)) - uv_at(Vec2::ZERO),
+    ];
+
+    let colors = vertices.map(|vertex| vertex.color);
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = crate::vec2(x as f32 + 0.5, y as f32 + 0.5);
+            // Barycentric coordinates, which are all positive inside the triangle:
+            let wa = ((b - p).x * (c - p).y - (b - p).y * (c - p).x) / area;
+            let wb = ((c - p).x * (a - p).y - (c - p).y * (a - p).x) / area;
+            let wc = 1.0 - wa - wb;
+            if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                continue;
+            }
+
+            let weights = [wa, wb, wc];
+            let channel = |i: usize| -> f32 {
+                (0..3)
+                    .map(|v| weights[v] * colors[v].to_array()[i] as f32)
+                    .sum()
+            };
+            let mut color = Color32::from_rgba_premultiplied(
+                channel(0).round() as u8,
+                channel(1).round() as u8,
+                channel(2).round() as u8,
+                channel(3).round() as u8,
+            );
+            if let Some(texture) = texture {
+                let uv = vertices[0].uv.to_vec2() * wa
+                    + vertices[1].uv.to_vec2() * wb
+                    + vertices[2].uv.to_vec2() * wc;
+                color = multiply(color, sample(texture, uv, uv_per_pixel));
+            }
+
+            let pixel = &mut image.pixels[y * image.size[0] + x];
+            *pixel = blend(*pixel, color);
+        }
+    }
+}
+
+/// The color of `texture` at the normalized texture coordinates `uv`,
+/// which change by `uv_per_pixel` from one pixel to the next along X and Y.
+fn sample(texture: &ImageData, uv: Vec2, uv_per_pixel: [Vec2; 2]) -> Color32 {
+    let [width, height] = texture.size();
+    if width == 0 || height == 0 {
+        return Color32::WHITE;
+    }
+    let texel = |uv: Vec2| {
+        let x = ((uv.x * width as f32).max(0.0) as usize).min(width - 1);
+        let y = ((uv.y * height as f32).max(0.0) as usize).min(height - 1);
+        y * width + x
+    };
+    match texture {
+        ImageData::Color(image) => image.pixels[texel(uv)],
+        ImageData::Font(image) => {
+            // Like `FontImage::srgba_pixels` with a gamma of 1:
+            let coverage = image.pixels[texel(uv)];
+            let a = (coverage.powf(1.0 / 2.2) * 255.0).round() as u8;
+            Color32::from_rgba_premultiplied(a, a, a, a)
+        }
+        ImageData::Sdf(image) => {
+            // Like the `sdf_coverage` of the shaders, where the outline is at 0.5:
+            let distance = sample_bilinear(image, uv);
+            let dx = sample_bilinear(image, uv + uv_per_pixel[0]) - distance;
+            let dy = sample_bilinear(image, uv + uv_per_pixel[1]) - distance;
+            let width = (0.7 * crate::vec2(dx, dy).length()).max(0.001);
+            let coverage = smoothstep(0.5 - width, 0.5 + width, distance);
+            let a = (coverage * 255.0).round() as u8;
+            Color32::from_rgba_premultiplied(a, a, a, a)
+        }
+    }
+}
+
+/// The value of `image` at `uv`, interpolated between the four closest texels.
+fn sample_bilinear(image: &FontImage, uv: Vec2) -> f32 {
+    let [width, height] = image.size;
+    let x = (uv.x * width as f32 - 0.5).clamp(0.0, (width - 1) as f32);
+    let y = (uv.y * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (x as usize, y as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (tx, ty) = (x - x0 as f32, y - y0 as f32);
+    let texel = |x: usize, y: usize| image.pixels[y * width + x];
+    let top = emath::lerp(texel(x0, y0)..=texel(x1, y0), tx);
+    let bottom = emath::lerp(texel(x0, y1)..=texel(x1, y1), tx);
+    emath::lerp(top..=bottom, ty)
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Multiply premultiplied colors.
+fn multiply(a: Color32, b: Color32) -> Color32 {
+    let mul = |a: u8, b: u8| ((a as u16 * b as u16 + 127) / 255) as u8;
+    Color32::from_rgba_premultiplied(
+        mul(a.r(), b.r()),
+        mul(a.g(), b.g()),
+        mul(a.b(), b.b()),
+        mul(a.a(), b.a()),
+    )
+}
+
+/// Paint the premultiplied color `src` over `dst`.
+fn blend(dst: Color32, src: Color32) -> Color32 {
+    let inverse_alpha = 255 - src.a() as u16;
+    let over =
+        |dst: u8, src: u8| (src as u16 + (dst as u16 * inverse_alpha + 127) / 255).min(255) as u8;
+    Color32::from_rgba_premultiplied(
+        over(dst.r(), src.r()),
+        over(dst.g(), src.g()),
+        over(dst.b(), src.b()),
+        over(dst.a(), src.a()),
+    )
+}
+
+#[test]
+fn test_rasterize() {
+    use crate::{pos2, vec2, Rect};
+
+    let mut mesh = Mesh::default();
+    let rect = Rect::from_min_size(pos2(1.0, 1.0), vec2(2.0, 2.0));
+    mesh.add_colored_rect(rect, Color32::RED);
+    let primitives = [ClippedPrimitive {
+        clip_rect: Rect::EVERYTHING,
+        primitive: Primitive::Mesh(mesh.into()),
+    }];
+
+    let image = rasterize(
+        &primitives,
+        &HashMap::default(),
+        [8, 8],
+        2.0,
+        Color32::WHITE,
+    );
+    let pixel = |x: usize, y: usize| image.pixels[y * 8 + x];
+    assert_eq!(pixel(0, 0), Color32::WHITE);
+    assert_eq!(pixel(1, 1), Color32::WHITE);
+    assert_eq!(pixel(2, 2), Color32::RED);
+    assert_eq!(pixel(5, 5), Color32::RED);
+    assert_eq!(pixel(6, 6), Color32::WHITE);
+
+    assert_eq!(
+        blend(Color32::WHITE, Color32::from_black_alpha(128)),
+        Color32::from_gray(127)
+    );
+}
+
+#[test]
+fn test_rasterize_sdf() {
+    use crate::{pos2, vec2, Rect};
+
+    // The distance goes from 0 to 1 from left to right, so the outline is in the middle:
+    let texture_id = TextureId::Managed(1);
+    let mut textures = HashMap::default();
+    textures.insert(
+        texture_id,
+        ImageData::Sdf(FontImage {
+            size: [8, 1],
+            pixels: (0..8).map(|x| x as f32 / 7.0).collect(),
+        }),
+    );
+
+    // However much the glyph is magnified, the edge is antialiased over a pixel or two:
+    for width in [16, 64] {
+        let mut mesh = Mesh::with_texture(texture_id);
+        mesh.add_rect_with_uv(
+            Rect::from_min_size(pos2(0.0, 0.0), vec2(width as f32, 1.0)),
+            Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+            Color32::WHITE,
+        );
+        let primitives = [ClippedPrimitive {
+            clip_rect: Rect::EVERYTHING,
+            primitive: Primitive::Mesh(mesh.into()),
+        }];
+        let image = rasterize(
+            &primitives,
+            &textures,
+            [width, 1],
+            1.0,
+            Color32::TRANSPARENT,
+        );
+
+        let alpha: Vec<u8> = image.pixels.iter().map(|color| color.a()).collect();
+        assert_eq!(alpha[0], 0);
+        assert_eq!(alpha[width - 1], 255);
+        assert!(alpha.windows(2).all(|pair| pair[0] <= pair[1]));
+        let num_partial = alpha.iter().filter(|&&a| 0 < a && a < 255).count();
+        assert!(1 <= num_partial && num_partial <= 3, "{:?}", alpha);
+    }
+}
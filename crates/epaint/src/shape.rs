@@ -5,7 +5,7 @@ use std::{any::Any, sync::Arc};
 
 use crate::{
     text::{FontId, Fonts, Galley},
-    Color32, Mesh, Stroke, TextureId,
+    Color32, Instance, Mesh, Stroke, TextureId,
 };
 use emath::*;
 
@@ -57,6 +57,9 @@ pub enum Shape {
     /// A cubic [Bézier Curve](https://en.wikipedia.org/wiki/B%C3%A9zier_curve).
     CubicBezier(CubicBezierShape),
 
+    /// Many copies of the same shape, e.g. the markers of a scatter plot.
+    Instanced(InstancedShape),
+
     /// Backend-specific painting.
     Callback(PaintCallback),
 }
@@ -239,6 +242,12 @@ impl Shape {
         Shape::mesh(mesh)
     }
 
+    /// Copies of `shape`, one per instance. See [`InstancedShape`].
+    #[inline]
+    pub fn instanced(shape: impl Into<Shape>, instances: impl Into<Arc<[Instance]>>) -> Self {
+        Self::Instanced(InstancedShape::new(shape, instances))
+    }
+
     /// The visual bounding rectangle (includes stroke widths)
     pub fn visual_bounding_rect(&self) -> Rect {
         match self {
@@ -264,6 +273,7 @@ impl Shape {
             Self::Mesh(mesh) => mesh.calc_bounds(),
             Self::QuadraticBezier(bezier) => bezier.visual_bounding_rect(),
             Self::CubicBezier(bezier) => bezier.visual_bounding_rect(),
+            Self::Instanced(instanced) => instanced.visual_bounding_rect(),
             Self::Callback(custom) => custom.rect,
         }
     }
@@ -273,10 +283,10 @@ impl Shape {
 impl Shape {
     #[inline(always)]
     pub fn texture_id(&self) -> super::TextureId {
-        if let Shape::Mesh(mesh) = self {
-            mesh.texture_id
-        } else {
-            super::TextureId::default()
+        match self {
+            Shape::Mesh(mesh) => mesh.texture_id,
+            Shape::Instanced(instanced) => instanced.shape.texture_id(),
+            _ => super::TextureId::default(),
         }
     }
 
@@ -321,6 +331,9 @@ impl Shape {
                     *p += delta;
                 }
             }
+            Shape::Instanced(instanced) => {
+                Arc::make_mut(&mut instanced.shape).translate(delta);
+            }
             Shape::Callback(shape) => {
                 shape.rect = shape.rect.translate(delta);
             }
@@ -664,6 +677,57 @@ impl From<TextShape> for Shape {
 
 // ----------------------------------------------------------------------------
 
+/// Many copies of the same shape, each moved by its own offset and tinted with its own color.
+///
+/// The shape is only tessellated once. If the backend supports it
+/// (see [`crate::TessellationOptions::instancing`]) the copies are then made by the GPU,
+/// which is a lot faster than painting thousands of separate [`Shape`]s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InstancedShape {
+    /// The shape to copy, usually centered on the origin and painted in white.
+    ///
+    /// Must not contain any [`Shape::Text`] or [`Shape::Callback`].
+    pub shape: Arc<Shape>,
+
+    /// Where to put each copy, and what color to tint it with.
+    pub instances: Arc<[Instance]>,
+}
+
+impl InstancedShape {
+    #[inline]
+    pub fn new(shape: impl Into<Shape>, instances: impl Into<Arc<[Instance]>>) -> Self {
+        Self {
+            shape: Arc::new(shape.into()),
+            instances: instances.into(),
+        }
+    }
+
+    /// The visual bounding rectangle of all the copies.
+    pub fn visual_bounding_rect(&self) -> Rect {
+        let bounds = self.shape.visual_bounding_rect();
+        if !bounds.is_positive() || self.instances.is_empty() {
+            return Rect::NOTHING;
+        }
+        let mut offsets = Rect::NOTHING;
+        for instance in self.instances.iter() {
+            offsets.extend_with(instance.offset.to_pos2());
+        }
+        Rect::from_min_max(
+            bounds.min + offsets.min.to_vec2(),
+            bounds.max + offsets.max.to_vec2(),
+        )
+    }
+}
+
+impl From<InstancedShape> for Shape {
+    #[inline(always)]
+    fn from(shape: InstancedShape) -> Self {
+        Self::Instanced(shape)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 /// Creates equally spaced filled circles from a line.
 fn points_from_line(
     path: &[Pos2],
@@ -35,6 +35,11 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
                     for vertex in &mut row.visuals.mesh.vertices {
                         adjust_color(&mut vertex.color);
                     }
+                    for (_, mesh) in &mut row.visuals.texture_meshes {
+                        for vertex in &mut mesh.vertices {
+                            adjust_color(&mut vertex.color);
+                        }
+                    }
                 }
             }
         }
@@ -51,6 +56,18 @@ pub fn adjust_colors(shape: &mut Shape, adjust_color: &impl Fn(&mut Color32)) {
             adjust_color(&mut bezier.fill);
             adjust_color(&mut bezier.stroke.color);
         }
+        Shape::Instanced(instanced) => {
+            // The colors are multiplied, so only adjust one of them:
+            instanced.instances = instanced
+                .instances
+                .iter()
+                .map(|instance| {
+                    let mut instance = *instance;
+                    adjust_color(&mut instance.color);
+                    instance
+                })
+                .collect();
+        }
         Shape::Callback(_) => {
             // Can't tint user callback code
         }
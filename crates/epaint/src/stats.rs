@@ -92,7 +92,14 @@ impl AllocInfo {
     }
 
     fn from_galley_row(row: &crate::text::Row) -> Self {
-        Self::from_mesh(&row.visuals.mesh) + Self::from_slice(&row.glyphs)
+        Self::from_mesh(&row.visuals.mesh)
+            + row
+                .visuals
+                .texture_meshes
+                .iter()
+                .map(|(_, mesh)| Self::from_mesh(mesh))
+                .sum()
+            + Self::from_slice(&row.glyphs)
     }
 
     pub fn from_mesh(mesh: &Mesh) -> Self {
@@ -174,6 +181,12 @@ pub struct PaintStats {
     pub clipped_primitives: AllocInfo,
     pub vertices: AllocInfo,
     pub indices: AllocInfo,
+
+    /// Number of layers whose tessellation was reused from the previous frame.
+    pub cached_layers: usize,
+
+    /// Number of layers that had to be tessellated, because they changed.
+    pub tessellated_layers: usize,
 }
 
 impl PaintStats {
@@ -204,7 +217,8 @@ impl PaintStats {
             | Shape::LineSegment { .. }
             | Shape::Rect { .. }
             | Shape::CubicBezier(_)
-            | Shape::QuadraticBezier(_) => {}
+            | Shape::QuadraticBezier(_)
+            | Shape::Instanced(_) => {}
             Shape::Path(path_shape) => {
                 self.shape_path += AllocInfo::from_slice(&path_shape.points);
             }
@@ -214,6 +228,10 @@ impl PaintStats {
                 for row in &text_shape.galley.rows {
                     self.text_shape_indices += AllocInfo::from_slice(&row.visuals.mesh.indices);
                     self.text_shape_vertices += AllocInfo::from_slice(&row.visuals.mesh.vertices);
+                    for (_, mesh) in &row.visuals.texture_meshes {
+                        self.text_shape_indices += AllocInfo::from_slice(&mesh.indices);
+                        self.text_shape_vertices += AllocInfo::from_slice(&mesh.vertices);
+                    }
                 }
             }
             Shape::Mesh(mesh) => {
@@ -231,9 +249,12 @@ impl PaintStats {
     ) -> Self {
         self.clipped_primitives += AllocInfo::from_slice(clipped_primitives);
         for clipped_primitive in clipped_primitives {
-            if let Primitive::Mesh(mesh) = &clipped_primitive.primitive {
-                self.vertices += AllocInfo::from_slice(&mesh.vertices);
-                self.indices += AllocInfo::from_slice(&mesh.indices);
+            match &clipped_primitive.primitive {
+                Primitive::Mesh(mesh) | Primitive::Instanced(crate::InstancedMesh { mesh, .. }) => {
+                    self.vertices += AllocInfo::from_slice(&mesh.vertices);
+                    self.indices += AllocInfo::from_slice(&mesh.indices);
+                }
+                Primitive::Callback(_) => {}
             }
         }
         self
@@ -0,0 +1,547 @@
+//! Writing shapes as SVG, e.g. to save a plot in a vector format.
+//!
+//! ```
+//! use epaint::{pos2, svg, vec2, ClippedShape, Color32, Rect, Shape};
+//! let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0));
+//! let shapes = [ClippedShape(rect, Shape::circle_filled(rect.center(), 10.0, Color32::RED))];
+//! let svg = svg::shapes_to_svg(&shapes, rect, 2.0, &Default::default());
+//! assert!(svg.contains("<circle"));
+//! ```
+
+use std::fmt::Write as _;
+
+use crate::{
+    ahash::HashMap, text::FontFamily, CircleShape, ClippedPrimitive, ClippedShape, Color32,
+    ColorImage, ImageData, Mesh, PathShape, Pos2, Primitive, Rect, RectShape, Rounding, Shape,
+    Stroke, TextShape, TextureId,
+};
+
+/// Write `shapes` as an SVG document showing `rect`.
+///
+/// Coordinates are in points, and the document is `pixels_per_point` times larger in pixels,
+/// so that e.g. a `pixels_per_point` of `2.0` gives a document of twice the size.
+///
+/// Text is written as text in the generic `sans-serif` and `monospace` font families,
+/// so it can be selected and searched, but may look slightly different than in egui.
+/// Meshes with one of the `textures` (other than the font texture) are painted with
+/// [`crate::raster::rasterize`] and embedded as PNG images, with `pixels_per_point` pixels per point.
+/// Other meshes are written as triangles with their vertex colors, ignoring their texture.
+/// [`Shape::Callback`]s are skipped.
+pub fn shapes_to_svg(
+    shapes: &[ClippedShape],
+    rect: Rect,
+    pixels_per_point: f32,
+    textures: &HashMap<TextureId, ImageData>,
+) -> String {
+    let mut svg = SvgWriter {
+        out: String::new(),
+        num_clip_paths: 0,
+        textures,
+        pixels_per_point,
+    };
+    let size = rect.size() * pixels_per_point;
+    writeln!(
+        svg.out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="{} {} {} {}">"#,
+        num(size.x),
+        num(size.y),
+        num(rect.left()),
+        num(rect.top()),
+        num(rect.width()),
+        num(rect.height()),
+    )
+    .ok();
+
+    // Shapes with the same clip rectangle in a row share a group.
+    let mut clip_rect = None;
+    for ClippedShape(shape_clip_rect, shape) in shapes {
+        if clip_rect != Some(*shape_clip_rect) {
+            if clip_rect.is_some() {
+                svg.out.push_str("</g>\n");
+            }
+            svg.begin_clip_group(*shape_clip_rect);
+            clip_rect = Some(*shape_clip_rect);
+        }
+        svg.shape(shape);
+    }
+    if clip_rect.is_some() {
+        svg.out.push_str("</g>\n");
+    }
+
+    svg.out.push_str("</svg>\n");
+    svg.out
+}
+
+struct SvgWriter<'a> {
+    out: String,
+    num_clip_paths: usize,
+    textures: &'a HashMap<TextureId, ImageData>,
+    pixels_per_point: f32,
+}
+
+impl<'a> SvgWriter<'a> {
+    fn begin_clip_group(&mut self, rect: Rect) {
+        let id = self.num_clip_paths;
+        self.num_clip_paths += 1;
+        writeln!(
+            self.out,
+            r#"<clipPath id="clip{}"><rect x="{}" y="{}" width="{}" height="{}"/></clipPath>"#,
+            id,
+            num(rect.left()),
+            num(rect.top()),
+            num(rect.width().max(0.0)),
+            num(rect.height().max(0.0)),
+        )
+        .ok();
+        writeln!(self.out, r#"<g clip-path="url(#clip{})">"#, id).ok();
+    }
+
+    fn shape(&mut self, shape: &Shape) {
+        match shape {
+            Shape::Noop | Shape::Callback(_) => {}
+            Shape::Vec(shapes) => {
+                for shape in shapes {
+                    self.shape(shape);
+                }
+            }
+            Shape::Circle(CircleShape {
+                center,
+                radius,
+                fill,
+                stroke,
+            }) => {
+                writeln!(
+                    self.out,
+                    r#"<circle cx="{}" cy="{}" r="{}"{}{}/>"#,
+                    num(center.x),
+                    num(center.y),
+                    num(*radius),
+                    fill_attributes(*fill),
+                    stroke_attributes(*stroke),
+                )
+                .ok();
+            }
+            Shape::LineSegment { points, stroke } => {
+                self.path(
+                    &format!("M{}L{}", point(points[0]), point(points[1])),
+                    Color32::TRANSPARENT,
+                    *stroke,
+                );
+            }
+            Shape::Path(PathShape {
+                points,
+                closed,
+                fill,
+                stroke,
+            }) => {
+                if points.is_empty() {
+                    return;
+                }
+                let points: Vec<String> = points.iter().map(|p| point(*p)).collect();
+                let tag = if *closed { "polygon" } else { "polyline" };
+                let fill = if *closed { *fill } else { Color32::TRANSPARENT };
+                writeln!(
+                    self.out,
+                    r#"<{} points="{}"{}{} stroke-linejoin="round"/>"#,
+                    tag,
+                    points.join(" "),
+                    fill_attributes(fill),
+                    stroke_attributes(*stroke),
+                )
+                .ok();
+            }
+            Shape::Rect(RectShape {
+                rect,
+                rounding,
+                fill,
+                stroke,
+            }) => {
+                self.path(&rounded_rect_path(*rect, *rounding), *fill, *stroke);
+            }
+            Shape::Text(text_shape) => self.text(text_shape),
+            Shape::Mesh(mesh) => self.mesh(mesh),
+            Shape::QuadraticBezier(bezier) => {
+                let [a, b, c] = bezier.points;
+                let mut d = format!("M{}Q{} {}", point(a), point(b), point(c));
+                if bezier.closed {
+                    d.push('Z');
+                }
+                let fill = if bezier.closed {
+                    bezier.fill
+                } else {
+                    Color32::TRANSPARENT
+                };
+                self.path(&d, fill, bezier.stroke);
+            }
+            Shape::CubicBezier(bezier) => {
+                let [a, b, c, e] = bezier.points;
+                let mut d = format!("M{}C{} {} {}", point(a), point(b), point(c), point(e));
+                if bezier.closed {
+                    d.push('Z');
+                }
+                let fill = if bezier.closed {
+                    bezier.fill
+                } else {
+                    Color32::TRANSPARENT
+                };
+                self.path(&d, fill, bezier.stroke);
+            }
+            Shape::Instanced(instanced) => {
+                for instance in instanced.instances.iter() {
+                    let mut shape = (*instanced.shape).clone();
+                    crate::shape_transform::adjust_colors(&mut shape, &|color| {
+                        *color = tint(*color, instance.color);
+                    });
+                    writeln!(
+                        self.out,
+                        r#"<g transform="translate({} {})">"#,
+                        num(instance.offset.x),
+                        num(instance.offset.y)
+                    )
+                    .ok();
+                    self.shape(&shape);
+                    self.out.push_str("</g>\n");
+                }
+            }
+        }
+    }
+
+    fn path(&mut self, d: &str, fill: Color32, stroke: Stroke) {
+        writeln!(
+            self.out,
+            r#"<path d="{}"{}{}/>"#,
+            d,
+            fill_attributes(fill),
+            stroke_attributes(stroke),
+        )
+        .ok();
+    }
+
+    fn text(&mut self, text_shape: &TextShape) {
+        let TextShape {
+            pos,
+            galley,
+            override_text_color,
+            angle,
+            ..
+        } = text_shape;
+
+        if *angle != 0.0 {
+            writeln!(
+                self.out,
+                r#"<g transform="rotate({} {} {})">"#,
+                num(angle.to_degrees()),
+                num(pos.x),
+                num(pos.y),
+            )
+            .ok();
+        }
+
+        for row in &galley.rows {
+            // One text element for each run of glyphs with the same format:
+            let mut glyphs = row.glyphs.iter().peekable();
+            while let Some(first) = glyphs.next() {
+                let mut run = vec![first];
+                while let Some(glyph) = glyphs.next_if(|g| g.section_index == first.section_index) {
+                    run.push(glyph);
+                }
+                if run.iter().all(|glyph| glyph.chr.is_whitespace()) {
+                    continue;
+                }
+
+                let format = &galley.job.sections[first.section_index as usize].format;
+                let color = override_text_color.unwrap_or(format.color);
+                let family = match &format.font_id.family {
+                    FontFamily::Proportional => "sans-serif".to_owned(),
+                    FontFamily::Monospace => "monospace".to_owned(),
+                    FontFamily::Name(name) => escape(name),
+                };
+                let xs: Vec<String> = run.iter().map(|glyph| num(pos.x + glyph.pos.x)).collect();
+                let text: String = run.iter().map(|glyph| glyph.chr).collect();
+                writeln!(
+                    self.out,
+                    r#"<text x="{}" y="{}" font-family="{}" font-size="{}" dominant-baseline="central" xml:space="preserve"{}>{}</text>"#,
+                    xs.join(" "),
+                    num(pos.y + first.pos.y + 0.5 * first.size.y),
+                    family,
+                    num(format.font_id.size),
+                    fill_attributes(color),
+                    escape(&text),
+                )
+                .ok();
+            }
+        }
+
+        if *angle != 0.0 {
+            self.out.push_str("</g>\n");
+        }
+    }
+
+    fn mesh(&mut self, mesh: &Mesh) {
+        if mesh.texture_id != TextureId::default() && self.textures.contains_key(&mesh.texture_id) {
+            self.textured_mesh(mesh);
+            return;
+        }
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices =
+                [triangle[0], triangle[1], triangle[2]].map(|i| &mesh.vertices[i as usize]);
+            let color = average_color(vertices.map(|vertex| vertex.color));
+            if color == Color32::TRANSPARENT {
+                continue;
+            }
+            writeln!(
+                self.out,
+                r#"<polygon points="{} {} {}"{}/>"#,
+                point(vertices[0].pos),
+                point(vertices[1].pos),
+                point(vertices[2].pos),
+                fill_attributes(color),
+            )
+            .ok();
+        }
+    }
+
+    /// Paint a mesh with a texture into an image, and embed it.
+    fn textured_mesh(&mut self, mesh: &Mesh) {
+        let rect = mesh.calc_bounds();
+        let size = rect.size() * self.pixels_per_point;
+        let size = [size.x.ceil() as usize, size.y.ceil() as usize];
+        if size[0] == 0 || size[1] == 0 || !rect.is_finite() {
+            return;
+        }
+
+        let mut mesh = mesh.clone();
+        mesh.translate(-rect.min.to_vec2());
+        let primitive = ClippedPrimitive {
+            clip_rect: Rect::EVERYTHING,
+            primitive: Primitive::Mesh(std::sync::Arc::new(mesh)),
+        };
+        let image = crate::raster::rasterize(
+            &[primitive],
+            self.textures,
+            size,
+            self.pixels_per_point,
+            Color32::TRANSPARENT,
+        );
+        let png = match encode_png(&image) {
+            Ok(png) => png,
+            Err(_) => return,
+        };
+        writeln!(
+            self.out,
+            r#"<image x="{}" y="{}" width="{}" height="{}" preserveAspectRatio="none" href="data:image/png;base64,{}"/>"#,
+            num(rect.left()),
+            num(rect.top()),
+            num(size[0] as f32 / self.pixels_per_point),
+            num(size[1] as f32 / self.pixels_per_point),
+            base64(&png),
+        )
+        .ok();
+    }
+}
+
+fn encode_png(image: &ColorImage) -> Result<Vec<u8>, png::EncodingError> {
+    let mut png = vec![];
+    let mut encoder = png::Encoder::new(&mut png, image.size[0] as u32, image.size[1] as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let rgba: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|color| color.to_srgba_unmultiplied())
+        .collect();
+    encoder.write_header()?.write_image_data(&rgba)?;
+    Ok(png)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// A number with at most two decimals, and without trailing zeros.
+fn num(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_owned()
+    } else {
+        text.to_owned()
+    }
+}
+
+fn point(pos: Pos2) -> String {
+    format!("{},{}", num(pos.x), num(pos.y))
+}
+
+fn rgb(color: Color32) -> (String, f32) {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    (format!("#{:02x}{:02x}{:02x}", r, g, b), a as f32 / 255.0)
+}
+
+fn fill_attributes(fill: Color32) -> String {
+    if fill == Color32::TRANSPARENT {
+        return r#" fill="none""#.to_owned();
+    }
+    match rgb(fill) {
+        (color, opacity) if opacity < 1.0 => {
+            format!(r#" fill="{}" fill-opacity="{}""#, color, num(opacity))
+        }
+        (color, _) => format!(r#" fill="{}""#, color),
+    }
+}
+
+fn stroke_attributes(stroke: Stroke) -> String {
+    if stroke.is_empty() {
+        return String::new();
+    }
+    let (color, opacity) = rgb(stroke.color);
+    let mut attributes = format!(
+        r#" stroke="{}" stroke-width="{}" stroke-linecap="round""#,
+        color,
+        num(stroke.width)
+    );
+    if opacity < 1.0 {
+        write!(attributes, r#" stroke-opacity="{}""#, num(opacity)).ok();
+    }
+    attributes
+}
+
+fn rounded_rect_path(rect: Rect, rounding: Rounding) -> String {
+    let max_radius = 0.5 * rect.width().min(rect.height());
+    let [nw, ne, sw, se] =
+        [rounding.nw, rounding.ne, rounding.sw, rounding.se].map(|r| r.clamp(0.0, max_radius));
+    let (left, right, top, bottom) = (rect.left(), rect.right(), rect.top(), rect.bottom());
+    let arc = |r: f32, x: f32, y: f32| {
+        if r > 0.0 {
+            format!("A{} {} 0 0 1 {} {}", num(r), num(r), num(x), num(y))
+        } else {
+            String::new()
+        }
+    };
+    format!(
+        "M{} {}H{}{}V{}{}H{}{}V{}{}Z",
+        num(left + nw),
+        num(top),
+        num(right - ne),
+        arc(ne, right, top + ne),
+        num(bottom - se),
+        arc(se, right - se, bottom),
+        num(left + sw),
+        arc(sw, left, bottom - sw),
+        num(top + nw),
+        arc(nw, left + nw, top),
+    )
+}
+
+/// Multiply premultiplied colors.
+fn tint(color: Color32, tint: Color32) -> Color32 {
+    let mul = |a: u8, b: u8| ((a as u16 * b as u16 + 127) / 255) as u8;
+    Color32::from_rgba_premultiplied(
+        mul(color.r(), tint.r()),
+        mul(color.g(), tint.g()),
+        mul(color.b(), tint.b()),
+        mul(color.a(), tint.a()),
+    )
+}
+
+fn average_color(colors: [Color32; 3]) -> Color32 {
+    let average = |channel: fn(&Color32) -> u8| {
+        (colors.iter().map(|c| channel(c) as u16).sum::<u16>() / 3) as u8
+    };
+    Color32::from_rgba_premultiplied(
+        average(Color32::r),
+        average(Color32::g),
+        average(Color32::b),
+        average(Color32::a),
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[test]
+fn test_shapes_to_svg() {
+    use crate::{pos2, vec2};
+
+    let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0));
+    let shapes = [
+        ClippedShape(
+            rect,
+            Shape::rect_filled(rect, 0.0, Color32::from_rgb(0x12, 0x34, 0x56)),
+        ),
+        ClippedShape(
+            rect,
+            Shape::line_segment(
+                [pos2(0.0, 0.0), pos2(10.5, 20.25)],
+                Stroke::new(1.0, Color32::from_black_alpha(128)),
+            ),
+        ),
+    ];
+    let svg = shapes_to_svg(&shapes, rect, 3.0, &HashMap::default());
+    assert!(svg.starts_with(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="300" height="150" viewBox="0 0 100 50">"#
+    ));
+    assert!(svg.contains(r##"<path d="M0 0H100V50H0V0Z" fill="#123456"/>"##));
+    assert!(svg.contains(r##"d="M0,0L10.5,20.25" fill="none" stroke="#000000" stroke-width="1""##));
+    assert!(svg.contains(r#"stroke-opacity="0.5""#));
+    assert_eq!(svg.matches("<clipPath").count(), 1);
+    assert!(svg.ends_with("</g>\n</svg>\n"));
+
+    assert_eq!(num(1.0), "1");
+    assert_eq!(num(-0.001), "0");
+    assert_eq!(escape("a < b & \"c\""), "a &lt; b &amp; &quot;c&quot;");
+    assert_eq!(base64(b"egui"), "ZWd1aQ==");
+    assert_eq!(base64(b"plot"), "cGxvdA==");
+    assert_eq!(base64(b"svg"), "c3Zn");
+}
+
+#[test]
+fn test_textured_mesh_to_svg() {
+    use crate::{pos2, vec2};
+
+    let rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 50.0));
+    let texture_id = TextureId::Managed(7);
+    let mut mesh = Mesh::with_texture(texture_id);
+    mesh.add_rect_with_uv(
+        Rect::from_min_size(pos2(10.0, 20.0), vec2(4.0, 2.0)),
+        Rect::from_min_max(pos2(0.0, 0.0), pos2(1.0, 1.0)),
+        Color32::WHITE,
+    );
+    let shapes = [ClippedShape(rect, Shape::mesh(mesh))];
+
+    // Without the texture, the mesh is written with its vertex colors:
+    let svg = shapes_to_svg(&shapes, rect, 1.0, &HashMap::default());
+    assert_eq!(svg.matches("<polygon").count(), 2);
+
+    let mut textures = HashMap::default();
+    textures.insert(
+        texture_id,
+        ImageData::Color(ColorImage::new([2, 2], Color32::RED)),
+    );
+    let svg = shapes_to_svg(&shapes, rect, 1.0, &textures);
+    assert!(!svg.contains("<polygon"));
+    assert!(svg.contains(r#"<image x="10" y="20" width="4" height="2""#));
+    assert!(svg.contains("data:image/png;base64,iVBORw0KGgo"));
+}
@@ -5,6 +5,9 @@
 
 #![allow(clippy::identity_op)]
 
+use std::sync::Arc;
+
+use crate::text::FontTexture;
 use crate::texture_atlas::PreparedDisc;
 use crate::*;
 use emath::*;
@@ -641,6 +644,17 @@ pub struct TessellationOptions {
 
     /// The default value will be 1.0e-5, it will be used during float compare.
     pub epsilon: f32,
+
+    /// If `true`, each [`Shape::Instanced`] is tessellated into a [`Primitive::Instanced`],
+    /// leaving it to the GPU to paint all the copies.
+    /// If `false`, the tessellator adds each copy to the [`Mesh`] instead.
+    ///
+    /// Only turn this on if your backend can paint [`Primitive::Instanced`].
+    /// `eframe` does this for you when supported.
+    ///
+    /// Default: `false`.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub instancing: bool,
 }
 
 impl Default for TessellationOptions {
@@ -656,6 +670,7 @@ impl Default for TessellationOptions {
             debug_ignore_clip_rects: false,
             bezier_tolerance: 0.1,
             epsilon: 1.0e-5,
+            instancing: false,
         }
     }
 }
@@ -965,15 +980,23 @@ fn mul_color(color: Color32, factor: f32) -> Color32 {
 
 // ----------------------------------------------------------------------------
 
+/// With the `rayon` feature, lists of shapes at least twice this long are tessellated in parallel,
+/// in chunks of at least this many shapes.
+#[cfg(feature = "rayon")]
+const PARALLEL_CHUNK_SIZE: usize = 1024;
+
 /// Converts [`Shape`]s into triangles ([`Mesh`]).
 ///
 /// For performance reasons it is smart to reuse the same [`Tessellator`].
 ///
 /// Se also [`tessellate_shapes`], a convenient wrapper around [`Tessellator`].
+#[derive(Clone)]
 pub struct Tessellator {
     pixels_per_point: f32,
     options: TessellationOptions,
     font_tex_size: [usize; 2],
+    /// Font textures other than the main one. See [`Self::set_font_texture`].
+    font_textures: Vec<(FontTexture, TextureId, [usize; 2])>,
     /// See [`TextureAtlas::prepared_discs`].
     prepared_discs: Vec<PreparedDisc>,
     /// size of feathering in points. normally the size of a physical pixel. 0.0 if disabled
@@ -1005,6 +1028,7 @@ impl Tessellator {
             pixels_per_point,
             options,
             font_tex_size,
+            font_textures: Default::default(),
             prepared_discs,
             feathering,
             clip_rect: Rect::EVERYTHING,
@@ -1013,6 +1037,23 @@ impl Tessellator {
         }
     }
 
+    /// Tell the tessellator about a font texture other than the main one,
+    /// e.g. an extra page of the font atlas, or the one of [`FontTexture::Sdf`] glyphs.
+    ///
+    /// Glyphs in font textures the tessellator doesn't know about are not painted.
+    ///
+    /// * `texture_id`: where the backend has the texture, i.e. the one you uploaded its [`Fonts::font_texture_deltas`] to.
+    /// * `size`: current size of the texture, see [`Fonts::font_textures`].
+    pub fn set_font_texture(
+        &mut self,
+        texture: FontTexture,
+        texture_id: TextureId,
+        size: [usize; 2],
+    ) {
+        self.font_textures.retain(|(t, _, _)| *t != texture);
+        self.font_textures.push((texture, texture_id, size));
+    }
+
     /// Set the [`Rect`] to use for culling.
     pub fn set_clip_rect(&mut self, clip_rect: Rect) {
         self.clip_rect = clip_rect;
@@ -1027,6 +1068,116 @@ impl Tessellator {
         }
     }
 
+    /// Turns [`Shape`]:s into sets of triangles.
+    ///
+    /// The given shapes will tessellated in the same order as they are given.
+    /// They will be batched together by clip rectangle and texture.
+    ///
+    /// See also [`tessellate_shapes`].
+    pub fn tessellate_shapes(&mut self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        let options = self.options;
+
+        #[cfg(feature = "rayon")]
+        let mut clipped_primitives = if shapes.len() >= 2 * PARALLEL_CHUNK_SIZE {
+            self.tessellate_in_parallel(shapes)
+        } else {
+            self.tessellate_in_order(shapes)
+        };
+
+        #[cfg(not(feature = "rayon"))]
+        let mut clipped_primitives = self.tessellate_in_order(shapes);
+
+        if options.debug_paint_clip_rects {
+            clipped_primitives = add_clip_rects(self, clipped_primitives);
+        }
+
+        if options.debug_ignore_clip_rects {
+            for clipped_primitive in &mut clipped_primitives {
+                clipped_primitive.clip_rect = Rect::EVERYTHING;
+            }
+        }
+
+        clipped_primitives.retain(|p| {
+            p.clip_rect.is_positive()
+                && match &p.primitive {
+                    Primitive::Mesh(mesh) => !mesh.is_empty(),
+                    Primitive::Instanced(instanced) => !instanced.is_empty(),
+                    Primitive::Callback(_) => true,
+                }
+        });
+
+        for clipped_primitive in &clipped_primitives {
+            match &clipped_primitive.primitive {
+                Primitive::Mesh(mesh) | Primitive::Instanced(InstancedMesh { mesh, .. }) => {
+                    crate::epaint_assert!(mesh.is_valid(), "Tessellator generated invalid Mesh");
+                }
+                Primitive::Callback(_) => {}
+            }
+        }
+
+        clipped_primitives
+    }
+
+    fn tessellate_in_order(&mut self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        let mut clipped_primitives: Vec<ClippedPrimitive> = Vec::default();
+        for clipped_shape in shapes {
+            self.tessellate_clipped_shape(clipped_shape, &mut clipped_primitives);
+        }
+        clipped_primitives
+    }
+
+    /// Split the shapes into chunks, tessellate each chunk on its own thread, and stitch them together.
+    ///
+    /// The result is exactly the same as that of [`Self::tessellate_in_order`].
+    #[cfg(feature = "rayon")]
+    fn tessellate_in_parallel(&mut self, shapes: Vec<ClippedShape>) -> Vec<ClippedPrimitive> {
+        use rayon::prelude::*;
+
+        let num_chunks = (shapes.len() / PARALLEL_CHUNK_SIZE)
+            .min(4 * rayon::current_num_threads())
+            .max(1);
+        let chunk_size = (shapes.len() + num_chunks - 1) / num_chunks;
+
+        let mut shapes = shapes.into_iter();
+        let chunks: Vec<Vec<ClippedShape>> = (0..num_chunks)
+            .map(|_| shapes.by_ref().take(chunk_size).collect())
+            .collect();
+
+        let this = &*self;
+        let tessellated_chunks: Vec<Vec<ClippedPrimitive>> = chunks
+            .into_par_iter()
+            .map(|chunk| this.clone().tessellate_in_order(chunk))
+            .collect();
+
+        let mut clipped_primitives: Vec<ClippedPrimitive> = Vec::default();
+        for chunk in tessellated_chunks {
+            let mut chunk = chunk.into_iter();
+            if let Some(first) = chunk.next() {
+                // Tessellating in order would have added the first shapes of this chunk
+                // to the last mesh of the previous chunk, if they have the same clip rect and texture:
+                match (clipped_primitives.last_mut(), first) {
+                    (
+                        Some(ClippedPrimitive {
+                            clip_rect,
+                            primitive: Primitive::Mesh(last_mesh),
+                        }),
+                        ClippedPrimitive {
+                            clip_rect: first_clip_rect,
+                            primitive: Primitive::Mesh(first_mesh),
+                        },
+                    ) if *clip_rect == first_clip_rect
+                        && last_mesh.texture_id == first_mesh.texture_id =>
+                    {
+                        Arc::make_mut(last_mesh).append_ref(&first_mesh);
+                    }
+                    (_, first) => clipped_primitives.push(first),
+                }
+            }
+            clipped_primitives.extend(chunk);
+        }
+        clipped_primitives
+    }
+
     /// Tessellate a clipped shape into a list of primitives.
     pub fn tessellate_clipped_shape(
         &mut self,
@@ -1054,6 +1205,16 @@ impl Tessellator {
             return;
         }
 
+        if self.options.instancing {
+            if let Shape::Instanced(instanced) = &new_shape {
+                out_primitives.push(ClippedPrimitive {
+                    clip_rect: new_clip_rect,
+                    primitive: Primitive::Instanced(self.tessellate_instanced(instanced)),
+                });
+                return;
+            }
+        }
+
         let start_new_mesh = match out_primitives.last() {
             None => true,
             Some(output_clipped_primitive) => {
@@ -1062,7 +1223,7 @@ impl Tessellator {
                         Primitive::Mesh(output_mesh) => {
                             output_mesh.texture_id != new_shape.texture_id()
                         }
-                        Primitive::Callback(_) => true,
+                        Primitive::Instanced(_) | Primitive::Callback(_) => true,
                     }
             }
         };
@@ -1070,17 +1231,24 @@ impl Tessellator {
         if start_new_mesh {
             out_primitives.push(ClippedPrimitive {
                 clip_rect: new_clip_rect,
-                primitive: Primitive::Mesh(Mesh::default()),
+                primitive: Primitive::Mesh(Default::default()),
             });
         }
 
-        let out = out_primitives.last_mut().unwrap();
+        let out_mesh = match &mut out_primitives.last_mut().unwrap().primitive {
+            Primitive::Mesh(out_mesh) => Arc::make_mut(out_mesh),
+            Primitive::Instanced(_) | Primitive::Callback(_) => unreachable!(),
+        };
+        self.clip_rect = new_clip_rect;
 
-        if let Primitive::Mesh(out_mesh) = &mut out.primitive {
-            self.clip_rect = new_clip_rect;
-            self.tessellate_shape(new_shape, out_mesh);
+        if let Shape::Text(text_shape) = &new_shape {
+            self.tessellate_text_shape(text_shape, out_mesh);
+            if !self.font_textures.is_empty() {
+                // Glyphs outside the main font texture need meshes of their own:
+                self.tessellate_text_texture_meshes(new_clip_rect, text_shape, out_primitives);
+            }
         } else {
-            unreachable!();
+            self.tessellate_shape(new_shape, out_mesh);
         }
     }
 
@@ -1122,25 +1290,42 @@ impl Tessellator {
                 self.tessellate_rect(&rect_shape, out);
             }
             Shape::Text(text_shape) => {
-                if self.options.debug_paint_text_rects {
-                    let rect = text_shape.galley.rect.translate(text_shape.pos.to_vec2());
-                    self.tessellate_rect(
-                        &RectShape::stroke(rect.expand(0.5), 2.0, (0.5, Color32::GREEN)),
-                        out,
-                    );
-                }
-                self.tessellate_text(&text_shape, out);
+                self.tessellate_text_shape(&text_shape, out);
             }
             Shape::QuadraticBezier(quadratic_shape) => {
                 self.tessellate_quadratic_bezier(quadratic_shape, out);
             }
             Shape::CubicBezier(cubic_shape) => self.tessellate_cubic_bezier(cubic_shape, out),
+            Shape::Instanced(instanced_shape) => {
+                let clip_rect = if self.options.coarse_tessellation_culling {
+                    self.clip_rect
+                } else {
+                    Rect::EVERYTHING
+                };
+                self.tessellate_instanced(&instanced_shape)
+                    .append_copies(clip_rect, out);
+            }
             Shape::Callback(_) => {
                 panic!("Shape::Callback passed to Tessellator");
             }
         }
     }
 
+    /// Tessellate the shape of an [`InstancedShape`] once, into an [`InstancedMesh`].
+    pub fn tessellate_instanced(&mut self, shape: &InstancedShape) -> InstancedMesh {
+        // The shape is usually centered on the origin, far from the clip rectangle,
+        // so we must not cull it:
+        let clip_rect = std::mem::replace(&mut self.clip_rect, Rect::EVERYTHING);
+        let mut mesh = Mesh::default();
+        self.tessellate_shape((*shape.shape).clone(), &mut mesh);
+        self.clip_rect = clip_rect;
+
+        InstancedMesh {
+            mesh: Arc::new(mesh),
+            instances: shape.instances.clone(),
+        }
+    }
+
     /// Tessellate a single [`CircleShape`] into a [`Mesh`].
     ///
     /// * `shape`: the circle to tessellate.
@@ -1319,9 +1504,42 @@ impl Tessellator {
     }
 
     /// Tessellate a single [`TextShape`] into a [`Mesh`].
+    ///
+    /// Only the glyphs in the main font texture end up in `out`.
+    /// Glyphs in other font textures (e.g. [`FontTexture::Sdf`]) are only painted by
+    /// [`Self::tessellate_clipped_shape`], since they need a [`Mesh`] of their own.
+    ///
     /// * `text_shape`: the text to tessellate.
     /// * `out`: triangles are appended to this.
     pub fn tessellate_text(&mut self, text_shape: &TextShape, out: &mut Mesh) {
+        let uv_normalizer = vec2(
+            1.0 / self.font_tex_size[0] as f32,
+            1.0 / self.font_tex_size[1] as f32,
+        );
+        self.tessellate_text_in_texture(text_shape, FontTexture::Coverage(0), uv_normalizer, out);
+    }
+
+    /// Like [`Self::tessellate_text`], plus the debug rectangle of
+    /// [`TessellationOptions::debug_paint_text_rects`].
+    fn tessellate_text_shape(&mut self, text_shape: &TextShape, out: &mut Mesh) {
+        if self.options.debug_paint_text_rects {
+            let rect = text_shape.galley.rect.translate(text_shape.pos.to_vec2());
+            self.tessellate_rect(
+                &RectShape::stroke(rect.expand(0.5), 2.0, (0.5, Color32::GREEN)),
+                out,
+            );
+        }
+        self.tessellate_text(text_shape, out);
+    }
+
+    /// Tessellate the glyphs of a [`TextShape`] that are in the given font texture.
+    fn tessellate_text_in_texture(
+        &mut self,
+        text_shape: &TextShape,
+        texture: FontTexture,
+        uv_normalizer: Vec2,
+        out: &mut Mesh,
+    ) {
         let TextShape {
             pos: galley_pos,
             galley,
@@ -1349,15 +1567,23 @@ impl Tessellator {
             self.round_to_pixel(galley_pos.y),
         );
 
-        let uv_normalizer = vec2(
-            1.0 / self.font_tex_size[0] as f32,
-            1.0 / self.font_tex_size[1] as f32,
-        );
-
         let rotator = Rot2::from_angle(*angle);
 
         for row in &galley.rows {
-            if row.visuals.mesh.is_empty() {
+            let (row_mesh, glyph_vertex_range) = if texture == FontTexture::Coverage(0) {
+                (&row.visuals.mesh, row.visuals.glyph_vertex_range.clone())
+            } else if let Some((_, mesh)) = row
+                .visuals
+                .texture_meshes
+                .iter()
+                .find(|(mesh_texture, _)| *mesh_texture == texture)
+            {
+                (mesh, 0..mesh.vertices.len()) // only glyphs
+            } else {
+                continue;
+            };
+
+            if row_mesh.is_empty() {
                 continue;
             }
 
@@ -1375,44 +1601,34 @@ impl Tessellator {
 
             let index_offset = out.vertices.len() as u32;
 
-            out.indices.extend(
-                row.visuals
-                    .mesh
-                    .indices
-                    .iter()
-                    .map(|index| index + index_offset),
-            );
+            out.indices
+                .extend(row_mesh.indices.iter().map(|index| index + index_offset));
 
-            out.vertices.extend(
-                row.visuals
-                    .mesh
-                    .vertices
-                    .iter()
-                    .enumerate()
-                    .map(|(i, vertex)| {
-                        let Vertex { pos, uv, mut color } = *vertex;
-
-                        if let Some(override_text_color) = override_text_color {
-                            if row.visuals.glyph_vertex_range.contains(&i) {
-                                color = *override_text_color;
-                            }
+            out.vertices
+                .extend(row_mesh.vertices.iter().enumerate().map(|(i, vertex)| {
+                    let Vertex { pos, uv, mut color } = *vertex;
+
+                    if let Some(override_text_color) = override_text_color {
+                        // Colour glyphs keep their colors:
+                        if texture != FontTexture::Color && glyph_vertex_range.contains(&i) {
+                            color = *override_text_color;
                         }
+                    }
 
-                        let offset = if *angle == 0.0 {
-                            pos.to_vec2()
-                        } else {
-                            rotator * pos.to_vec2()
-                        };
+                    let offset = if *angle == 0.0 {
+                        pos.to_vec2()
+                    } else {
+                        rotator * pos.to_vec2()
+                    };
 
-                        Vertex {
-                            pos: galley_pos + offset,
-                            uv: (uv.to_vec2() * uv_normalizer).to_pos2(),
-                            color,
-                        }
-                    }),
-            );
+                    Vertex {
+                        pos: galley_pos + offset,
+                        uv: (uv.to_vec2() * uv_normalizer).to_pos2(),
+                        color,
+                    }
+                }));
 
-            if *underline != Stroke::none() {
+            if texture == FontTexture::Coverage(0) && *underline != Stroke::none() {
                 self.scratchpad_path.clear();
                 self.scratchpad_path
                     .add_line_segment([row_rect.left_bottom(), row_rect.right_bottom()]);
@@ -1422,6 +1638,53 @@ impl Tessellator {
         }
     }
 
+    /// Add the glyphs of `text_shape` that are not in the main font texture,
+    /// each font texture with a [`Mesh`] of its own.
+    fn tessellate_text_texture_meshes(
+        &mut self,
+        clip_rect: Rect,
+        text_shape: &TextShape,
+        out_primitives: &mut Vec<ClippedPrimitive>,
+    ) {
+        for i in 0..self.font_textures.len() {
+            let (texture, texture_id, [width, height]) = self.font_textures[i];
+
+            let uses_texture = text_shape.galley.rows.iter().any(|row| {
+                row.visuals
+                    .texture_meshes
+                    .iter()
+                    .any(|(mesh_texture, _)| *mesh_texture == texture)
+            });
+            if !uses_texture {
+                continue;
+            }
+
+            let append_to_last = match out_primitives.last() {
+                Some(ClippedPrimitive {
+                    clip_rect: last_clip_rect,
+                    primitive: Primitive::Mesh(last_mesh),
+                }) => *last_clip_rect == clip_rect && last_mesh.texture_id == texture_id,
+                _ => false,
+            };
+            if !append_to_last {
+                out_primitives.push(ClippedPrimitive {
+                    clip_rect,
+                    primitive: Primitive::Mesh(Arc::new(Mesh::with_texture(texture_id))),
+                });
+            }
+
+            if let Some(ClippedPrimitive {
+                primitive: Primitive::Mesh(out_mesh),
+                ..
+            }) = out_primitives.last_mut()
+            {
+                let uv_normalizer = vec2(1.0 / width as f32, 1.0 / height as f32);
+                let out_mesh = Arc::make_mut(out_mesh);
+                self.tessellate_text_in_texture(text_shape, texture, uv_normalizer, out_mesh);
+            }
+        }
+    }
+
     /// Tessellate a single [`QuadraticBezierShape`] into a [`Mesh`].
     ///
     /// * `quadratic_shape`: the shape to tessellate.
@@ -1533,38 +1796,7 @@ pub fn tessellate_shapes(
 ) -> Vec<ClippedPrimitive> {
     let mut tessellator =
         Tessellator::new(pixels_per_point, options, font_tex_size, prepared_discs);
-
-    let mut clipped_primitives: Vec<ClippedPrimitive> = Vec::default();
-
-    for clipped_shape in shapes {
-        tessellator.tessellate_clipped_shape(clipped_shape, &mut clipped_primitives);
-    }
-
-    if options.debug_paint_clip_rects {
-        clipped_primitives = add_clip_rects(&mut tessellator, clipped_primitives);
-    }
-
-    if options.debug_ignore_clip_rects {
-        for clipped_primitive in &mut clipped_primitives {
-            clipped_primitive.clip_rect = Rect::EVERYTHING;
-        }
-    }
-
-    clipped_primitives.retain(|p| {
-        p.clip_rect.is_positive()
-            && match &p.primitive {
-                Primitive::Mesh(mesh) => !mesh.is_empty(),
-                Primitive::Callback(_) => true,
-            }
-    });
-
-    for clipped_primitive in &clipped_primitives {
-        if let Primitive::Mesh(mesh) = &clipped_primitive.primitive {
-            crate::epaint_assert!(mesh.is_valid(), "Tessellator generated invalid Mesh");
-        }
-    }
-
-    clipped_primitives
+    tessellator.tessellate_shapes(shapes)
 }
 
 fn add_clip_rects(
@@ -1587,7 +1819,7 @@ fn add_clip_rects(
                 clipped_primitive,
                 ClippedPrimitive {
                     clip_rect: Rect::EVERYTHING, // whatever
-                    primitive: Primitive::Mesh(clip_rect_mesh),
+                    primitive: Primitive::Mesh(Arc::new(clip_rect_mesh)),
                 },
             ]
         })
@@ -1626,3 +1858,109 @@ fn test_tessellator() {
     );
     assert_eq!(primitives.len(), 2);
 }
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_parallel_tessellation() {
+    use crate::*;
+
+    let clip_rects = [
+        Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0)),
+        Rect::from_min_size(pos2(50.0, 50.0), vec2(100.0, 100.0)),
+    ];
+
+    let mut clipped_shapes = vec![];
+    for i in 0..10_000 {
+        let clip_rect = clip_rects[(i / 700) % 2];
+        let pos = pos2((i % 100) as f32, (i / 100) as f32);
+        let shape = match i % 3 {
+            0 => Shape::circle_filled(pos, 2.0, Color32::RED),
+            1 => Shape::line_segment([pos, pos + vec2(5.0, 3.0)], (1.0, Color32::BLUE)),
+            _ => {
+                // Sometimes the same texture as the other shapes, sometimes not:
+                let mut mesh = Mesh::with_texture(TextureId::Managed((i / 500 % 2) as u64));
+                mesh.add_colored_rect(Rect::from_min_size(pos, vec2(2.0, 2.0)), Color32::GREEN);
+                Shape::mesh(mesh)
+            }
+        };
+        clipped_shapes.push(ClippedShape(clip_rect, shape));
+    }
+
+    let tessellator = Tessellator::new(2.0, Default::default(), [1024, 1024], vec![]);
+    let in_order = tessellator
+        .clone()
+        .tessellate_in_order(clipped_shapes.clone());
+    let in_parallel = tessellator.clone().tessellate_in_parallel(clipped_shapes);
+
+    assert_eq!(in_order.len(), in_parallel.len());
+    for (a, b) in in_order.iter().zip(&in_parallel) {
+        assert_eq!(a.clip_rect, b.clip_rect);
+        match (&a.primitive, &b.primitive) {
+            (Primitive::Mesh(a), Primitive::Mesh(b)) => assert_eq!(a, b),
+            _ => panic!("Expected only meshes"),
+        }
+    }
+}
+
+#[test]
+fn test_instanced_tessellation() {
+    use crate::*;
+
+    let clip_rect = Rect::from_min_size(pos2(0.0, 0.0), vec2(100.0, 100.0));
+    let marker = Shape::rect_filled(
+        Rect::from_center_size(Pos2::ZERO, vec2(2.0, 2.0)),
+        0.0,
+        Color32::WHITE,
+    );
+    let instances = vec![
+        Instance::new(vec2(10.0, 10.0), Color32::RED),
+        Instance::new(vec2(50.0, 20.0), Color32::GREEN),
+        Instance::new(vec2(500.0, 20.0), Color32::BLUE), // outside the clip rect
+    ];
+    let clipped_shapes = vec![ClippedShape(
+        clip_rect,
+        Shape::instanced(marker.clone(), instances),
+    )];
+
+    let tessellate = |instancing| {
+        let options = TessellationOptions {
+            instancing,
+            ..Default::default()
+        };
+        tessellate_shapes(1.0, options, [1024, 1024], vec![], clipped_shapes.clone())
+    };
+
+    let mut marker_mesh = Mesh::default();
+    Tessellator::new(1.0, Default::default(), [1024, 1024], vec![])
+        .tessellate_shape(marker, &mut marker_mesh);
+
+    let instanced = tessellate(true);
+    assert_eq!(instanced.len(), 1);
+    let instanced = match &instanced[0].primitive {
+        Primitive::Instanced(instanced) => instanced,
+        _ => panic!("Expected an instanced mesh"),
+    };
+    assert_eq!(*instanced.mesh, marker_mesh);
+    assert_eq!(instanced.instances.len(), 3);
+
+    // Without instancing, we get the visible copies in one mesh:
+    let copied = tessellate(false);
+    assert_eq!(copied.len(), 1);
+    let copied = match &copied[0].primitive {
+        Primitive::Mesh(mesh) => mesh,
+        _ => panic!("Expected a mesh"),
+    };
+    assert_eq!(copied.vertices.len(), 2 * marker_mesh.vertices.len());
+    for (copy, original) in copied.vertices.iter().zip(&marker_mesh.vertices) {
+        assert_eq!(copy.pos, original.pos + vec2(10.0, 10.0));
+        if original.color == Color32::WHITE {
+            assert_eq!(copy.color, Color32::RED);
+        } else {
+            assert_eq!(copy.color, Color32::TRANSPARENT); // feathering
+        }
+    }
+    assert_eq!(
+        instanced.to_mesh().vertices.len(),
+        3 * marker_mesh.vertices.len()
+    );
+}
@@ -0,0 +1,1207 @@
+//! Colour glyphs (e.g. colour emoji), rasterized into an RGBA font texture ([`FontTexture::Color`]).
+//!
+//! Supported are the `COLR`/`CPAL` tables, both version 0 (layers of outlines, each with its own colour)
+//! and version 1 (with transforms, clips, gradients and composite modes),
+//! and the `CBDT` and `sbix` tables (PNG or BGRA bitmaps).
+//! With the `svg_glyphs` feature, glyphs in the `SVG` table are rendered using [`resvg`](https://docs.rs/resvg).
+//! Without it they are rasterized from their outline, like any other glyph.
+//! Variable fonts are always painted at their default instance.
+
+use std::borrow::Cow;
+
+use ab_glyph_rasterizer::{point, Point};
+use emath::{vec2, Vec2};
+use owned_ttf_parser::{
+    self as ttf_parser,
+    colr::{CompositeMode, GradientExtend, Paint},
+    GlyphId, RgbaColor, Transform,
+};
+
+use super::{
+    font::{FontTexture, GlyphInfo, UvRect},
+    FontData,
+};
+use crate::{textures::TextureOptions, Color32, ColorImage, ImageDelta, TextureAtlas};
+
+/// A font with colour glyphs.
+///
+/// Parsed once, when the font is loaded, and then used for rasterizing all of its colour glyphs.
+///
+/// `ab_glyph` doesn't give access to the face it parsed, so we parse the font a second time,
+/// though with the same `ttf-parser` (which is why the version of `ab_glyph` is pinned).
+/// Parsing only reads the table directory and a few headers, so this is cheap.
+pub(crate) struct ColorFont(ColorFace);
+
+enum ColorFace {
+    Static(Box<ttf_parser::Face<'static>>),
+    Owned(owned_ttf_parser::OwnedFace),
+}
+
+impl ColorFont {
+    /// Returns `None` if the font has no colour glyphs we can rasterize.
+    pub fn new(font_data: &FontData) -> Option<Self> {
+        let font_index = font_data.index;
+        let face = match &font_data.font {
+            Cow::Borrowed(data) => {
+                ColorFace::Static(Box::new(ttf_parser::Face::parse(data, font_index).ok()?))
+            }
+            Cow::Owned(data) => {
+                // Check before we copy the font data:
+                let face = ttf_parser::Face::parse(data, font_index).ok()?;
+                if !has_color_tables(&face) {
+                    return None;
+                }
+                let face = owned_ttf_parser::OwnedFace::from_vec(data.clone(), font_index).ok()?;
+                ColorFace::Owned(face)
+            }
+        };
+        let font = Self(face);
+        has_color_tables(font.face()).then(|| font)
+    }
+
+    fn face(&self) -> &ttf_parser::Face<'_> {
+        match &self.0 {
+            ColorFace::Static(face) => face,
+            ColorFace::Owned(face) => owned_ttf_parser::AsFaceRef::as_face_ref(face),
+        }
+    }
+}
+
+fn has_color_tables(face: &ttf_parser::Face<'_>) -> bool {
+    let tables = face.tables();
+    tables.colr.is_some()
+        || tables.cbdt.is_some()
+        || tables.sbix.is_some()
+        || (cfg!(feature = "svg_glyphs") && tables.svg.is_some())
+}
+
+// ----------------------------------------------------------------------------
+
+/// The RGBA texture atlas for colour glyphs, see [`FontTexture::Color`].
+pub(crate) struct ColorAtlas {
+    /// Decides where the glyphs go, and keeps track of the part of the texture that needs updating.
+    ///
+    /// Its own (coverage) image is never written to.
+    packer: TextureAtlas,
+
+    /// Always the same size as [`Self::packer`].
+    image: ColorImage,
+
+    num_glyphs: usize,
+}
+
+impl ColorAtlas {
+    pub fn new(size: [usize; 2]) -> Self {
+        Self {
+            packer: TextureAtlas::new_glyph_page(size),
+            image: ColorImage::new(size, Color32::TRANSPARENT),
+            num_glyphs: 0,
+        }
+    }
+
+    /// Has any glyph been added?
+    ///
+    /// If not, no texture needs to be created for this atlas.
+    pub fn is_used(&self) -> bool {
+        self.num_glyphs > 0
+    }
+
+    pub fn size(&self) -> [usize; 2] {
+        self.image.size
+    }
+
+    /// When this get high, it might be time to clear and start over!
+    pub fn fill_ratio(&self) -> f32 {
+        self.packer.fill_ratio()
+    }
+
+    /// The change to the atlas since last call.
+    ///
+    /// Always `None` until the first glyph has been added, see [`Self::is_used`].
+    pub fn take_delta(&mut self) -> Option<ImageDelta> {
+        if !self.is_used() {
+            return None;
+        }
+
+        match self.packer.take_dirty()? {
+            None => Some(ImageDelta::full(self.image.clone(), TextureOptions::LINEAR)),
+            Some((pos, size)) => {
+                let region = self.image.region(pos, size);
+                Some(ImageDelta::partial(pos, region, TextureOptions::LINEAR))
+            }
+        }
+    }
+
+    /// Copy the image into the atlas, and return where it ended up.
+    fn add(&mut self, image: &ColorImage) -> [usize; 2] {
+        let [w, h] = image.size;
+        let ((x, y), _) = self.packer.allocate((w, h));
+
+        let size = self.packer.size();
+        if self.image.size != size {
+            // The atlas grew (always downwards):
+            self.image
+                .pixels
+                .resize(size[0] * size[1], Color32::TRANSPARENT);
+            self.image.size = size;
+        }
+
+        for row in 0..h {
+            let dst = (y + row) * size[0] + x;
+            self.image.pixels[dst..dst + w].copy_from_slice(&image.pixels[row * w..(row + 1) * w]);
+        }
+        self.num_glyphs += 1;
+
+        [x, y]
+    }
+
+    /// Rasterize the glyph in colour, if the font has colour tables for it.
+    ///
+    /// Returns `None` for glyphs that should be rasterized from their outline instead.
+    pub fn glyph_info(
+        &mut self,
+        color_font: &ColorFont,
+        font: &ab_glyph::FontArc,
+        glyph_id: ab_glyph::GlyphId,
+        scale_in_pixels: f32,
+        y_offset: f32,
+        pixels_per_point: f32,
+    ) -> Option<GlyphInfo> {
+        use ab_glyph::{Font as _, ScaleFont};
+
+        let scaled_font = font.as_scaled(scale_in_pixels);
+        let glyph = rasterize_color_glyph(
+            color_font.face(),
+            GlyphId(glyph_id.0),
+            scaled_font.h_scale_factor(),
+        )?;
+        let [w, h] = glyph.image.size;
+        if w > self.size()[0] || h > self.size()[0] {
+            return None; // Would never fit. Use the outline instead.
+        }
+        let pos = self.add(&glyph.image);
+
+        let offset_in_pixels = glyph.left_top + scale_in_pixels * Vec2::Y;
+        let uv_rect = UvRect {
+            offset: offset_in_pixels / pixels_per_point + y_offset * Vec2::Y,
+            size: vec2(w as f32, h as f32) / pixels_per_point,
+            min: [pos[0] as u16, pos[1] as u16],
+            max: [(pos[0] + w) as u16, (pos[1] + h) as u16],
+            texture: FontTexture::Color,
+        };
+
+        let advance_width_in_points = scaled_font.h_advance(glyph_id) / pixels_per_point;
+
+        Some(GlyphInfo {
+            id: glyph_id,
+            advance_width: advance_width_in_points,
+            uv_rect,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// A glyph rasterized in colour.
+struct ColorGlyphImage {
+    image: ColorImage,
+
+    /// Top left corner of the image, relative to the glyph origin on the baseline. In pixels.
+    left_top: Vec2,
+}
+
+fn rasterize_color_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: GlyphId,
+    pixels_per_unit: f32,
+) -> Option<ColorGlyphImage> {
+    let glyph = if face.is_color_glyph(glyph_id) {
+        rasterize_colr_glyph(face, glyph_id, pixels_per_unit)
+    } else {
+        rasterize_bitmap_glyph(face, glyph_id, pixels_per_unit)
+            .or_else(|| rasterize_svg_glyph(face, glyph_id, pixels_per_unit))
+    }?;
+
+    if glyph.image.width() == 0 || glyph.image.height() == 0 {
+        None
+    } else {
+        Some(glyph)
+    }
+}
+
+// ----------------------------------------------------------------------------
+// COLR
+
+fn rasterize_colr_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: GlyphId,
+    pixels_per_unit: f32,
+) -> Option<ColorGlyphImage> {
+    // Layers in the "foreground" colour are painted white,
+    // since the glyph is shared by all text colors.
+    let foreground = RgbaColor::new(255, 255, 255, 255);
+
+    // Font units are y-up, pixels are y-down:
+    let to_pixels = Transform::new(pixels_per_unit, 0.0, 0.0, -pixels_per_unit, 0.0, 0.0);
+
+    // First find out how large the image needs to be…
+    let mut painter = ColrPainter::new(face, to_pixels, Pass::Bounds(None));
+    face.paint_color_glyph(glyph_id, 0, foreground, &mut painter)?;
+    let (min, max) = match painter.pass {
+        Pass::Bounds(bounds) => bounds?,
+        Pass::Paint(_) => unreachable!(),
+    };
+    let (left, top) = (min.x.floor(), min.y.floor());
+    let w = (max.x.ceil() - left) as usize;
+    let h = (max.y.ceil() - top) as usize;
+
+    // …then paint it:
+    let to_image = Transform::combine(Transform::new_translate(-left, -top), to_pixels);
+    let mut painter = ColrPainter::new(face, to_image, Pass::Paint(ColrCanvas::new([w, h])));
+    face.paint_color_glyph(glyph_id, 0, foreground, &mut painter)?;
+    let canvas = match painter.pass {
+        Pass::Paint(canvas) => canvas,
+        Pass::Bounds(_) => unreachable!(),
+    };
+
+    let pixels = canvas.layers[0]
+        .0
+        .iter()
+        .map(|rgba| {
+            let [r, g, b, a] = rgba.map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+            Color32::from_rgba_premultiplied(r, g, b, a)
+        })
+        .collect();
+
+    Some(ColorGlyphImage {
+        image: ColorImage {
+            size: [w, h],
+            pixels,
+        },
+        left_top: vec2(left, top),
+    })
+}
+
+/// What [`ColrPainter`] does with the glyph.
+enum Pass {
+    /// Find the pixel bounds of all outlines (min, max).
+    Bounds(Option<(Point, Point)>),
+
+    Paint(ColrCanvas),
+}
+
+/// Paints a `COLR` glyph, see [`ttf_parser::Face::paint_color_glyph`].
+struct ColrPainter<'f, 'a> {
+    face: &'f ttf_parser::Face<'a>,
+
+    /// From font units to pixels.
+    transform: Transform,
+
+    /// The transforms under [`Self::transform`].
+    transform_stack: Vec<Transform>,
+
+    pass: Pass,
+}
+
+impl<'f, 'a> ColrPainter<'f, 'a> {
+    fn new(face: &'f ttf_parser::Face<'a>, transform: Transform, pass: Pass) -> Self {
+        Self {
+            face,
+            transform,
+            transform_stack: vec![],
+            pass,
+        }
+    }
+}
+
+impl<'f, 'a> ttf_parser::colr::Painter<'a> for ColrPainter<'f, 'a> {
+    fn outline_glyph(&mut self, glyph_id: GlyphId) {
+        let mut outline = Outline::new(self.transform);
+        self.face.outline_glyph(glyph_id, &mut outline);
+        outline.close_contour();
+
+        match &mut self.pass {
+            Pass::Bounds(bounds) => {
+                for p in outline.points() {
+                    let (min, max) = bounds.get_or_insert((p, p));
+                    *min = point(min.x.min(p.x), min.y.min(p.y));
+                    *max = point(max.x.max(p.x), max.y.max(p.y));
+                }
+            }
+            Pass::Paint(canvas) => canvas.outline = Some(outline.rasterize(canvas.size)),
+        }
+    }
+
+    fn paint(&mut self, paint: Paint<'a>) {
+        if let Pass::Paint(canvas) = &mut self.pass {
+            if let Some(shader) = Shader::new(paint, self.transform) {
+                canvas.paint(&shader);
+            }
+        }
+    }
+
+    fn push_clip(&mut self) {
+        if let Pass::Paint(canvas) = &mut self.pass {
+            let outline = canvas.outline.take();
+            let size = canvas.size[0] * canvas.size[1];
+            canvas.push_clip(outline.unwrap_or_else(|| vec![0.0; size]));
+        }
+    }
+
+    fn push_clip_box(&mut self, clip_box: ttf_parser::colr::ClipBox) {
+        if let Pass::Paint(canvas) = &mut self.pass {
+            let mut outline = Outline::new(self.transform);
+            let ttf_parser::RectF {
+                x_min,
+                y_min,
+                x_max,
+                y_max,
+            } = clip_box;
+            ttf_parser::OutlineBuilder::move_to(&mut outline, x_min, y_min);
+            ttf_parser::OutlineBuilder::line_to(&mut outline, x_max, y_min);
+            ttf_parser::OutlineBuilder::line_to(&mut outline, x_max, y_max);
+            ttf_parser::OutlineBuilder::line_to(&mut outline, x_min, y_max);
+            outline.close_contour();
+            canvas.push_clip(outline.rasterize(canvas.size));
+        }
+    }
+
+    fn pop_clip(&mut self) {
+        if let Pass::Paint(canvas) = &mut self.pass {
+            canvas.clips.pop();
+        }
+    }
+
+    fn push_layer(&mut self, mode: CompositeMode) {
+        if let Pass::Paint(canvas) = &mut self.pass {
+            let size = canvas.size[0] * canvas.size[1];
+            canvas.layers.push((vec![[0.0; 4]; size], mode));
+        }
+    }
+
+    fn pop_layer(&mut self) {
+        if let Pass::Paint(canvas) = &mut self.pass {
+            canvas.pop_layer();
+        }
+    }
+
+    fn push_translate(&mut self, tx: f32, ty: f32) {
+        self.push_transform(Transform::new_translate(tx, ty));
+    }
+
+    fn push_scale(&mut self, sx: f32, sy: f32) {
+        self.push_transform(Transform::new(sx, 0.0, 0.0, sy, 0.0, 0.0));
+    }
+
+    fn push_rotate(&mut self, angle: f32) {
+        // Counter-clockwise, in half turns:
+        let (sin, cos) = (angle * std::f32::consts::PI).sin_cos();
+        self.push_transform(Transform::new(cos, sin, -sin, cos, 0.0, 0.0));
+    }
+
+    fn push_skew(&mut self, skew_x: f32, skew_y: f32) {
+        // In half turns, with positive `skew_x` skewing to the left:
+        let x = (-skew_x * std::f32::consts::PI).tan();
+        let y = (skew_y * std::f32::consts::PI).tan();
+        self.push_transform(Transform::new(1.0, y, x, 1.0, 0.0, 0.0));
+    }
+
+    fn push_transform(&mut self, transform: Transform) {
+        self.transform_stack.push(self.transform);
+        self.transform = Transform::combine(self.transform, transform);
+    }
+
+    fn pop_transform(&mut self) {
+        if let Some(transform) = self.transform_stack.pop() {
+            self.transform = transform;
+        }
+    }
+}
+
+/// The pixels of a `COLR` glyph being painted.
+struct ColrCanvas {
+    size: [usize; 2],
+
+    /// Coverage of the last outline, until it is used as a clip.
+    ///
+    /// `COLR` v0 glyphs paint their outlines directly, without clipping to them first.
+    outline: Option<Vec<f32>>,
+
+    /// Coverage of each clip, intersected with all the clips below it.
+    clips: Vec<Vec<f32>>,
+
+    /// Premultiplied RGBA of each layer and how it is composited onto the one below it, bottom to top.
+    ///
+    /// The bottom layer is the glyph.
+    layers: Vec<(Vec<[f32; 4]>, CompositeMode)>,
+}
+
+impl ColrCanvas {
+    fn new(size: [usize; 2]) -> Self {
+        Self {
+            size,
+            outline: None,
+            clips: vec![],
+            layers: vec![(vec![[0.0; 4]; size[0] * size[1]], CompositeMode::SourceOver)],
+        }
+    }
+
+    fn push_clip(&mut self, mut coverage: Vec<f32>) {
+        if let Some(clip) = self.clips.last() {
+            for (coverage, clip) in coverage.iter_mut().zip(clip) {
+                *coverage *= clip;
+            }
+        }
+        self.clips.push(coverage);
+    }
+
+    /// Paint everything within the current clip (and outline, if any) onto the top layer.
+    fn paint(&mut self, shader: &Shader) {
+        let [w, h] = self.size;
+        let layer = &mut self
+            .layers
+            .last_mut()
+            .expect("the glyph layer is never popped")
+            .0;
+        for y in 0..h {
+            for x in 0..w {
+                let i = y * w + x;
+                let clip = self.clips.last().map_or(1.0, |clip| clip[i]);
+                let outline = self.outline.as_ref().map_or(1.0, |outline| outline[i]);
+                let coverage = clip * outline;
+                if coverage <= 0.0 {
+                    continue;
+                }
+                if let Some(color) = shader.color(vec2(x as f32 + 0.5, y as f32 + 0.5)) {
+                    let src = color.map(|c| c * coverage);
+                    layer[i] = composite(CompositeMode::SourceOver, src, layer[i]);
+                }
+            }
+        }
+    }
+
+    /// Composite the top layer onto the one below it, within the current clip.
+    fn pop_layer(&mut self) {
+        if self.layers.len() < 2 {
+            return;
+        }
+        let (src, mode) = self.layers.pop().unwrap();
+        let dst = &mut self.layers.last_mut().unwrap().0;
+        for (i, (dst, src)) in dst.iter_mut().zip(src).enumerate() {
+            let clip = self.clips.last().map_or(1.0, |clip| clip[i]);
+            let result = composite(mode, src, *dst);
+            for c in 0..4 {
+                dst[c] += clip * (result[c] - dst[c]);
+            }
+        }
+    }
+}
+
+/// An outline in pixels, see [`ttf_parser::OutlineBuilder`].
+struct Outline {
+    /// From font units to pixels.
+    transform: Transform,
+
+    contour_start: Point,
+    last: Point,
+    curves: Vec<Curve>,
+}
+
+enum Curve {
+    Line(Point, Point),
+    Quad(Point, Point, Point),
+    Cubic(Point, Point, Point, Point),
+}
+
+impl Outline {
+    fn new(transform: Transform) -> Self {
+        Self {
+            transform,
+            contour_start: point(0.0, 0.0),
+            last: point(0.0, 0.0),
+            curves: vec![],
+        }
+    }
+
+    fn to_pixels(&self, x: f32, y: f32) -> Point {
+        let t = &self.transform;
+        point(t.a * x + t.c * y + t.e, t.b * x + t.d * y + t.f)
+    }
+
+    fn close_contour(&mut self) {
+        if self.last != self.contour_start {
+            self.curves.push(Curve::Line(self.last, self.contour_start));
+            self.last = self.contour_start;
+        }
+    }
+
+    fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.curves.iter().flat_map(|curve| match *curve {
+            Curve::Line(p0, p1) => vec![p0, p1],
+            Curve::Quad(p0, p1, p2) => vec![p0, p1, p2],
+            Curve::Cubic(p0, p1, p2, p3) => vec![p0, p1, p2, p3],
+        })
+    }
+
+    /// Coverage of each pixel.
+    fn rasterize(&self, [w, h]: [usize; 2]) -> Vec<f32> {
+        // The image fits all outlines, so this is only about rounding errors:
+        let clamp = |p: Point| point(p.x.clamp(0.0, w as f32), p.y.clamp(0.0, h as f32));
+
+        let mut rasterizer = ab_glyph_rasterizer::Rasterizer::new(w, h);
+        for curve in &self.curves {
+            match *curve {
+                Curve::Line(p0, p1) => rasterizer.draw_line(clamp(p0), clamp(p1)),
+                Curve::Quad(p0, p1, p2) => rasterizer.draw_quad(clamp(p0), clamp(p1), clamp(p2)),
+                Curve::Cubic(p0, p1, p2, p3) => {
+                    rasterizer.draw_cubic(clamp(p0), clamp(p1), clamp(p2), clamp(p3));
+                }
+            }
+        }
+
+        let mut coverage = vec![0.0; w * h];
+        rasterizer.for_each_pixel(|i, c| coverage[i] = c.min(1.0));
+        coverage
+    }
+}
+
+impl ttf_parser::OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close_contour();
+        self.contour_start = self.to_pixels(x, y);
+        self.last = self.contour_start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p1 = self.to_pixels(x, y);
+        self.curves.push(Curve::Line(self.last, p1));
+        self.last = p1;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p1, p2) = (self.to_pixels(x1, y1), self.to_pixels(x, y));
+        self.curves.push(Curve::Quad(self.last, p1, p2));
+        self.last = p2;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p1, p2, p3) = (
+            self.to_pixels(x1, y1),
+            self.to_pixels(x2, y2),
+            self.to_pixels(x, y),
+        );
+        self.curves.push(Curve::Cubic(self.last, p1, p2, p3));
+        self.last = p3;
+    }
+
+    fn close(&mut self) {
+        self.close_contour();
+    }
+}
+
+/// The colour of each pixel painted by a [`Paint`].
+enum Shader {
+    /// Premultiplied RGBA.
+    Solid([f32; 4]),
+
+    Gradient {
+        /// From pixels back to font units.
+        to_font_units: Transform,
+
+        kind: GradientKind,
+
+        /// Offset and premultiplied RGBA, in order of offset.
+        stops: Vec<(f32, [f32; 4])>,
+
+        extend: GradientExtend,
+    },
+}
+
+/// The geometry of a gradient, in font units.
+enum GradientKind {
+    /// Offset 0 at `start`, and 1 at `start + step`.
+    Linear { start: Vec2, step: Vec2 },
+
+    /// Circles from the start circle (offset 0) to the end circle (offset 1), and beyond.
+    Radial { center: [Vec2; 2], radius: [f32; 2] },
+
+    /// Counter-clockwise around the center, angles in degrees.
+    Sweep {
+        center: Vec2,
+        start_angle: f32,
+        end_angle: f32,
+    },
+}
+
+impl Shader {
+    /// `None` for gradients that paint nothing.
+    fn new(paint: Paint<'_>, transform: Transform) -> Option<Self> {
+        let (kind, stops, extend): (_, Vec<ttf_parser::colr::ColorStop>, _) = match paint {
+            Paint::Solid(color) => return Some(Self::Solid(premultiplied(color))),
+            Paint::LinearGradient(gradient) => {
+                let p0 = vec2(gradient.x0, gradient.y0);
+                let p1 = vec2(gradient.x1, gradient.y1);
+                let p2 = vec2(gradient.x2, gradient.y2);
+
+                // The gradient runs from p0 to p1, but along the normal of the line from p0 to p2:
+                let normal = (p2 - p0).rot90();
+                let step = if normal.length_sq() > 0.0 {
+                    normal * ((p1 - p0).dot(normal) / normal.length_sq())
+                } else {
+                    p1 - p0
+                };
+                if step.length_sq() <= 0.0 {
+                    return None;
+                }
+                let kind = GradientKind::Linear { start: p0, step };
+                (kind, gradient.stops(0, &[]).collect(), gradient.extend)
+            }
+            Paint::RadialGradient(gradient) => {
+                let kind = GradientKind::Radial {
+                    center: [
+                        vec2(gradient.x0, gradient.y0),
+                        vec2(gradient.x1, gradient.y1),
+                    ],
+                    radius: [gradient.r0, gradient.r1],
+                };
+                (kind, gradient.stops(0, &[]).collect(), gradient.extend)
+            }
+            Paint::SweepGradient(gradient) => {
+                // The angles are stored with a bias of 1.0, i.e. -1.0 is 0°.
+                let kind = GradientKind::Sweep {
+                    center: vec2(gradient.center_x, gradient.center_y),
+                    start_angle: (gradient.start_angle + 1.0) * 180.0,
+                    end_angle: (gradient.end_angle + 1.0) * 180.0,
+                };
+                (kind, gradient.stops(0, &[]).collect(), gradient.extend)
+            }
+        };
+
+        let mut stops: Vec<(f32, [f32; 4])> = stops
+            .into_iter()
+            .map(|stop| (stop.stop_offset, premultiplied(stop.color)))
+            .collect();
+        stops.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        if stops.is_empty() {
+            return None;
+        }
+
+        Some(Self::Gradient {
+            to_font_units: invert(&transform)?,
+            kind,
+            stops,
+            extend,
+        })
+    }
+
+    /// Premultiplied RGBA at the given pixel, or `None` if nothing is painted there.
+    fn color(&self, pos: Vec2) -> Option<[f32; 4]> {
+        match self {
+            Self::Solid(color) => Some(*color),
+            Self::Gradient {
+                to_font_units,
+                kind,
+                stops,
+                extend,
+            } => {
+                let t = to_font_units;
+                let pos = vec2(
+                    t.a * pos.x + t.c * pos.y + t.e,
+                    t.b * pos.x + t.d * pos.y + t.f,
+                );
+                let offset = kind.offset(pos)?;
+                Some(color_line(stops, *extend, offset))
+            }
+        }
+    }
+}
+
+impl GradientKind {
+    /// Offset along the color line at the given position, if any.
+    fn offset(&self, pos: Vec2) -> Option<f32> {
+        match self {
+            Self::Linear { start, step } => Some((pos - *start).dot(*step) / step.length_sq()),
+            Self::Radial { center, radius } => {
+                // Find the largest `t` for which `pos` is on the circle interpolated between the two circles,
+                // and which has a positive radius.
+                let dc = center[1] - center[0];
+                let dr = radius[1] - radius[0];
+                let dp = pos - center[0];
+                let a = dc.length_sq() - dr * dr;
+                let b = dp.dot(dc) + radius[0] * dr;
+                let c = dp.length_sq() - radius[0] * radius[0];
+                let has_radius = |t: f32| radius[0] + t * dr >= 0.0;
+
+                if a.abs() < 1e-6 {
+                    // Linear equation:
+                    let t = c / (2.0 * b);
+                    (b != 0.0 && has_radius(t)).then(|| t)
+                } else {
+                    let discriminant = b * b - a * c;
+                    if discriminant < 0.0 {
+                        return None;
+                    }
+                    let root = discriminant.sqrt();
+                    let (t0, t1) = ((b + root) / a, (b - root) / a);
+                    let (t0, t1) = if t0 > t1 { (t0, t1) } else { (t1, t0) };
+                    if has_radius(t0) {
+                        Some(t0)
+                    } else {
+                        has_radius(t1).then(|| t1)
+                    }
+                }
+            }
+            Self::Sweep {
+                center,
+                start_angle,
+                end_angle,
+            } => {
+                if start_angle == end_angle {
+                    return None;
+                }
+                let d = pos - *center;
+                let angle = d.y.atan2(d.x).to_degrees().rem_euclid(360.0);
+                Some((angle - start_angle) / (end_angle - start_angle))
+            }
+        }
+    }
+}
+
+/// The color at the given offset.
+///
+/// Outside of the range of the stops the color line is extended according to `extend`.
+fn color_line(stops: &[(f32, [f32; 4])], extend: GradientExtend, offset: f32) -> [f32; 4] {
+    let (first, last) = (stops[0].0, stops[stops.len() - 1].0);
+    let offset = if last > first {
+        let t = (offset - first) / (last - first);
+        let t = match extend {
+            GradientExtend::Pad => t.clamp(0.0, 1.0),
+            GradientExtend::Repeat => t - t.floor(),
+            GradientExtend::Reflect => {
+                let t = t.rem_euclid(2.0);
+                if t > 1.0 {
+                    2.0 - t
+                } else {
+                    t
+                }
+            }
+        };
+        first + t * (last - first)
+    } else {
+        offset
+    };
+
+    if offset <= first {
+        return stops[0].1;
+    }
+    for pair in stops.windows(2) {
+        let ((o0, c0), (o1, c1)) = (pair[0], pair[1]);
+        if offset <= o1 {
+            let t = if o1 > o0 {
+                (offset - o0) / (o1 - o0)
+            } else {
+                1.0
+            };
+            return [0, 1, 2, 3].map(|i| c0[i] + t * (c1[i] - c0[i]));
+        }
+    }
+    stops[stops.len() - 1].1
+}
+
+fn premultiplied(color: RgbaColor) -> [f32; 4] {
+    let alpha = color.alpha as f32 / 255.0;
+    let [r, g, b] = [color.red, color.green, color.blue].map(|c| c as f32 / 255.0 * alpha);
+    [r, g, b, alpha]
+}
+
+fn invert(t: &Transform) -> Option<Transform> {
+    let det = t.a * t.d - t.b * t.c;
+    if det.abs() < f32::EPSILON * f32::EPSILON {
+        return None;
+    }
+    let (a, b, c, d) = (t.d / det, -t.b / det, -t.c / det, t.a / det);
+    Some(Transform::new(
+        a,
+        b,
+        c,
+        d,
+        -(a * t.e + c * t.f),
+        -(b * t.e + d * t.f),
+    ))
+}
+
+/// Composite the premultiplied `src` onto `dst`.
+fn composite(mode: CompositeMode, src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    use CompositeMode::*;
+
+    let (sa, da) = (src[3], dst[3]);
+    let porter_duff = |fa: f32, fb: f32| [0, 1, 2, 3].map(|i| src[i] * fa + dst[i] * fb);
+    match mode {
+        Clear => [0.0; 4],
+        Source => src,
+        Destination => dst,
+        SourceOver => porter_duff(1.0, 1.0 - sa),
+        DestinationOver => porter_duff(1.0 - da, 1.0),
+        SourceIn => porter_duff(da, 0.0),
+        DestinationIn => porter_duff(0.0, sa),
+        SourceOut => porter_duff(1.0 - da, 0.0),
+        DestinationOut => porter_duff(0.0, 1.0 - sa),
+        SourceAtop => porter_duff(da, 1.0 - sa),
+        DestinationAtop => porter_duff(1.0 - da, sa),
+        Xor => porter_duff(1.0 - da, 1.0 - sa),
+        Plus => porter_duff(1.0, 1.0).map(|c| c.min(1.0)),
+        _ => {
+            let unmultiply = |c: [f32; 4]| {
+                let a = c[3];
+                [0, 1, 2].map(|i| if a > 0.0 { c[i] / a } else { 0.0 })
+            };
+            let blended = blend(mode, unmultiply(src), unmultiply(dst));
+            let [r, g, b] =
+                [0, 1, 2].map(|i| (1.0 - da) * src[i] + (1.0 - sa) * dst[i] + sa * da * blended[i]);
+            [r, g, b, sa + da - sa * da]
+        }
+    }
+}
+
+/// The blend modes of <https://www.w3.org/TR/compositing-1/#blending>, on unmultiplied RGB.
+fn blend(mode: CompositeMode, src: [f32; 3], dst: [f32; 3]) -> [f32; 3] {
+    use CompositeMode::*;
+
+    let separable = |f: fn(f32, f32) -> f32| [0, 1, 2].map(|i| f(src[i], dst[i]));
+    match mode {
+        Multiply => separable(|s, d| s * d),
+        Screen => separable(screen),
+        Overlay => separable(|s, d| hard_light(d, s)),
+        Darken => separable(f32::min),
+        Lighten => separable(f32::max),
+        ColorDodge => separable(|s, d| {
+            if d <= 0.0 {
+                0.0
+            } else if s >= 1.0 {
+                1.0
+            } else {
+                (d / (1.0 - s)).min(1.0)
+            }
+        }),
+        ColorBurn => separable(|s, d| {
+            if d >= 1.0 {
+                1.0
+            } else if s <= 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - d) / s).min(1.0)
+            }
+        }),
+        HardLight => separable(hard_light),
+        SoftLight => separable(|s, d| {
+            if s <= 0.5 {
+                d - (1.0 - 2.0 * s) * d * (1.0 - d)
+            } else {
+                let dd = if d <= 0.25 {
+                    ((16.0 * d - 12.0) * d + 4.0) * d
+                } else {
+                    d.sqrt()
+                };
+                d + (2.0 * s - 1.0) * (dd - d)
+            }
+        }),
+        Difference => separable(|s, d| (s - d).abs()),
+        Exclusion => separable(|s, d| s + d - 2.0 * s * d),
+        Hue => set_lum(set_sat(src, sat(dst)), lum(dst)),
+        Saturation => set_lum(set_sat(dst, sat(src)), lum(dst)),
+        Color => set_lum(src, lum(dst)),
+        Luminosity => set_lum(dst, lum(src)),
+        _ => src, // Porter-Duff modes are handled by `composite`
+    }
+}
+
+fn screen(s: f32, d: f32) -> f32 {
+    s + d - s * d
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        d * 2.0 * s
+    } else {
+        screen(d, 2.0 * s - 1.0)
+    }
+}
+
+fn lum([r, g, b]: [f32; 3]) -> f32 {
+    0.3 * r + 0.59 * g + 0.11 * b
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    let c = c.map(|c| c + d);
+
+    // Clip to the valid range, keeping the luminosity:
+    let l = lum(c);
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+    let mut c = c;
+    if min < 0.0 {
+        c = c.map(|c| l + (c - l) * l / (l - min));
+    }
+    if max > 1.0 {
+        c = c.map(|c| l + (c - l) * (1.0 - l) / (max - l));
+    }
+    c
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+    if max > min {
+        c.map(|c| (c - min) * s / (max - min))
+    } else {
+        [0.0; 3]
+    }
+}
+
+// ----------------------------------------------------------------------------
+// CBDT and sbix
+
+fn rasterize_bitmap_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: GlyphId,
+    pixels_per_unit: f32,
+) -> Option<ColorGlyphImage> {
+    let pixels_per_em = pixels_per_unit * face.units_per_em() as f32;
+
+    let raster = face.glyph_raster_image(glyph_id, pixels_per_em.round() as u16)?;
+    let image = match raster.format {
+        ttf_parser::RasterImageFormat::PNG => decode_png(raster.data)?,
+        ttf_parser::RasterImageFormat::BitmapPremulBgra32 => {
+            let size = [raster.width as usize, raster.height as usize];
+            if raster.data.len() != size[0] * size[1] * 4 {
+                return None;
+            }
+            let pixels = raster
+                .data
+                .chunks_exact(4)
+                .map(|p| Color32::from_rgba_premultiplied(p[2], p[1], p[0], p[3]))
+                .collect();
+            ColorImage { size, pixels }
+        }
+        _ => return None, // monochrome bitmaps: use the outline
+    };
+
+    let scale = pixels_per_em / raster.pixels_per_em as f32;
+    let left_top = vec2(raster.x as f32, -(raster.y as f32 + image.height() as f32)) * scale;
+
+    Some(ColorGlyphImage {
+        image: resize(&image, scale),
+        left_top,
+    })
+}
+
+fn decode_png(data: &[u8]) -> Option<ColorImage> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).ok()?;
+    let bytes = &buf[..info.buffer_size()];
+    let size = [info.width as usize, info.height as usize];
+
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => bytes
+            .chunks_exact(4)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[1], p[2], p[3]))
+            .collect(),
+        png::ColorType::Rgb => bytes
+            .chunks_exact(3)
+            .map(|p| Color32::from_rgb(p[0], p[1], p[2]))
+            .collect(),
+        png::ColorType::GrayscaleAlpha => bytes
+            .chunks_exact(2)
+            .map(|p| Color32::from_rgba_unmultiplied(p[0], p[0], p[0], p[1]))
+            .collect(),
+        png::ColorType::Grayscale => bytes.iter().map(|&l| Color32::from_gray(l)).collect(),
+        png::ColorType::Indexed => return None, // expanded by the decoder
+    };
+
+    Some(ColorImage { size, pixels })
+}
+
+/// Resize an image by the given factor, averaging all the texels covered by each new texel.
+fn resize(image: &ColorImage, scale: f32) -> ColorImage {
+    let [src_w, src_h] = image.size;
+    let w = ((src_w as f32 * scale).round() as usize).max(1);
+    let h = ((src_h as f32 * scale).round() as usize).max(1);
+    if [w, h] == image.size {
+        return image.clone();
+    }
+
+    let sx = src_w as f32 / w as f32;
+    let sy = src_h as f32 / h as f32;
+
+    let mut pixels = Vec::with_capacity(w * h);
+    for y in 0..h {
+        let y0 = (y as f32 * sy) as usize;
+        let y1 = (((y + 1) as f32 * sy).ceil() as usize).clamp(y0 + 1, src_h);
+        for x in 0..w {
+            let x0 = (x as f32 * sx) as usize;
+            let x1 = (((x + 1) as f32 * sx).ceil() as usize).clamp(x0 + 1, src_w);
+
+            // Colors are premultiplied, so we can just average them:
+            let mut sum = [0_u32; 4];
+            for src_y in y0..y1 {
+                for src_x in x0..x1 {
+                    let c = image[(src_x, src_y)];
+                    for (sum, c) in sum.iter_mut().zip(c.to_array()) {
+                        *sum += c as u32;
+                    }
+                }
+            }
+            let n = ((x1 - x0) * (y1 - y0)) as u32;
+            let [r, g, b, a] = sum.map(|sum| ((sum + n / 2) / n) as u8);
+            pixels.push(Color32::from_rgba_premultiplied(r, g, b, a));
+        }
+    }
+
+    ColorImage {
+        size: [w, h],
+        pixels,
+    }
+}
+
+// ----------------------------------------------------------------------------
+// SVG
+
+#[cfg(feature = "svg_glyphs")]
+fn rasterize_svg_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: GlyphId,
+    pixels_per_unit: f32,
+) -> Option<ColorGlyphImage> {
+    use usvg::NodeExt as _;
+
+    // The document may contain many glyphs, but parsing it again for each one is fine,
+    // since each glyph is only rasterized once per size.
+    let document = face.glyph_svg_image(glyph_id)?;
+    let tree = usvg::Tree::from_data(document.data, &usvg::Options::default().to_ref()).ok()?;
+    let node = tree.node_by_id(&format!("glyph{}", glyph_id.0))?;
+
+    // In font units, but y-down, with the glyph origin at (0, 0):
+    let bbox = node.calculate_bbox()?.to_rect()?;
+
+    // One pixel of margin, so nothing is cut off at the edges:
+    let fit_to = usvg::FitTo::Zoom(pixels_per_unit);
+    let size = fit_to.fit_to(bbox.size().to_screen_size())?;
+    let mut pixmap = tiny_skia::Pixmap::new(size.width() + 2, size.height() + 2)?;
+    resvg::render_node(
+        &tree,
+        &node,
+        fit_to,
+        tiny_skia::Transform::from_translate(1.0, 1.0),
+        pixmap.as_mut(),
+    )?;
+
+    let pixels = pixmap
+        .data()
+        .chunks_exact(4)
+        .map(|p| Color32::from_rgba_premultiplied(p[0], p[1], p[2], p[3]))
+        .collect();
+    let left_top = vec2(bbox.x() as f32, bbox.y() as f32) * pixels_per_unit - Vec2::splat(1.0);
+
+    Some(ColorGlyphImage {
+        image: ColorImage {
+            size: [pixmap.width() as usize, pixmap.height() as usize],
+            pixels,
+        },
+        left_top,
+    })
+}
+
+#[cfg(not(feature = "svg_glyphs"))]
+fn rasterize_svg_glyph(
+    _face: &ttf_parser::Face<'_>,
+    _glyph_id: GlyphId,
+    _pixels_per_unit: f32,
+) -> Option<ColorGlyphImage> {
+    None
+}
+
+// ----------------------------------------------------------------------------
+
+#[test]
+fn test_resize_color_image() {
+    let mut image = ColorImage::new([4, 2], Color32::TRANSPARENT);
+    image[(0, 0)] = Color32::WHITE;
+    image[(1, 1)] = Color32::WHITE;
+
+    let half = resize(&image, 0.5);
+    assert_eq!(half.size, [2, 1]);
+    assert_eq!(
+        half[(0, 0)],
+        Color32::from_rgba_premultiplied(128, 128, 128, 128)
+    );
+    assert_eq!(half[(1, 0)], Color32::TRANSPARENT);
+}
+
+#[test]
+fn test_colr_gradients() {
+    let stops = [(0.25, [0.0, 0.0, 0.0, 1.0]), (0.75, [1.0, 1.0, 1.0, 1.0])];
+    assert_eq!(color_line(&stops, GradientExtend::Pad, 0.0), stops[0].1);
+    assert_eq!(color_line(&stops, GradientExtend::Pad, 0.5)[0], 0.5);
+    assert_eq!(color_line(&stops, GradientExtend::Repeat, 1.0)[0], 0.5);
+    assert_eq!(color_line(&stops, GradientExtend::Reflect, 1.0)[0], 0.5);
+    assert_eq!(color_line(&stops, GradientExtend::Reflect, 0.0)[0], 0.5);
+
+    // Two concentric circles of radius 1 and 3:
+    let radial = GradientKind::Radial {
+        center: [Vec2::ZERO; 2],
+        radius: [1.0, 3.0],
+    };
+    assert_eq!(radial.offset(vec2(2.0, 0.0)), Some(0.5));
+    assert_eq!(radial.offset(vec2(0.0, -5.0)), Some(2.0));
+
+    // Starting at 0° (-1.0 with the bias) and going to 90° (-0.5):
+    let shader = Shader::Gradient {
+        to_font_units: Transform::new(1.0, 0.0, 0.0, -1.0, 0.0, 0.0),
+        kind: GradientKind::Sweep {
+            center: Vec2::ZERO,
+            start_angle: 0.0,
+            end_angle: 90.0,
+        },
+        stops: vec![(0.0, [0.0; 4]), (1.0, [1.0; 4])],
+        extend: GradientExtend::Pad,
+    };
+    // Up on screen is counter-clockwise from the x axis in font units:
+    let up_right = shader.color(vec2(1.0, -1.0)).unwrap();
+    assert!((up_right[0] - 0.5).abs() < 1e-5);
+}
+
+#[test]
+fn test_composite_modes() {
+    let red = [1.0, 0.0, 0.0, 1.0];
+    let half_blue = [0.0, 0.0, 0.5, 0.5];
+    assert_eq!(
+        composite(CompositeMode::SourceOver, half_blue, red),
+        [0.5, 0.0, 0.5, 1.0]
+    );
+    assert_eq!(
+        composite(CompositeMode::DestinationOut, red, half_blue),
+        [0.0; 4]
+    );
+    assert_eq!(
+        composite(CompositeMode::SourceIn, half_blue, red),
+        half_blue
+    );
+    assert_eq!(composite(CompositeMode::Multiply, red, red), red);
+
+    // Luminosity of white onto red gives white:
+    let white = [1.0; 4];
+    let result = composite(CompositeMode::Luminosity, white, red);
+    assert!(
+        result.iter().all(|c| (c - 1.0).abs() < 1e-5),
+        "{:?}",
+        result
+    );
+}
@@ -1,6 +1,9 @@
-use crate::{
-    mutex::{Mutex, RwLock},
-    TextureAtlas,
+use crate::mutex::{Mutex, RwLock};
+
+use super::{
+    color_glyph::{ColorAtlas, ColorFont},
+    font_atlas::{FontAtlas, GlyphRemap},
+    sdf::SdfAtlas,
 };
 use emath::{vec2, Vec2};
 use std::collections::BTreeSet;
@@ -8,6 +11,35 @@ use std::sync::Arc;
 
 // ----------------------------------------------------------------------------
 
+/// Which font texture a glyph is in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum FontTexture {
+    /// A page of glyphs rasterized as coverage at the size they are used.
+    ///
+    /// Page 0 is the main font texture ([`crate::TextureId::default`]).
+    /// More pages are added when that fills up.
+    Coverage(u32),
+
+    /// The texture of glyphs rendered as signed distance fields,
+    /// see [`super::GlyphRendering::Sdf`].
+    Sdf,
+
+    /// The RGBA texture of colour glyphs (e.g. colour emoji).
+    ///
+    /// These are painted in their own colors (only the alpha of the text color is used),
+    /// and are unaffected by [`crate::TextShape::override_text_color`].
+    Color,
+}
+
+impl Default for FontTexture {
+    fn default() -> Self {
+        Self::Coverage(0)
+    }
+}
+
+// ----------------------------------------------------------------------------
+
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct UvRect {
@@ -23,6 +55,9 @@ pub struct UvRect {
 
     /// Bottom right corner (exclusive).
     pub max: [u16; 2],
+
+    /// The texture `min` and `max` refer to.
+    pub texture: FontTexture,
 }
 
 impl UvRect {
@@ -54,6 +89,27 @@ impl Default for GlyphInfo {
 
 // ----------------------------------------------------------------------------
 
+/// Where a [`FontImpl`] puts its glyphs.
+#[derive(Clone)]
+pub(crate) enum GlyphAtlas {
+    /// Rasterize each glyph for each size, see [`super::GlyphRendering::Coverage`].
+    Coverage(Arc<Mutex<FontAtlas>>),
+
+    /// Like [`Self::Coverage`], but rasterize the glyphs that the font has in colour into the [`ColorAtlas`].
+    Color {
+        atlas: Arc<Mutex<FontAtlas>>,
+        color_atlas: Arc<Mutex<ColorAtlas>>,
+
+        /// The colour tables of the font.
+        color_font: Arc<ColorFont>,
+    },
+
+    /// Share one distance field per glyph between all sizes, see [`super::GlyphRendering::Sdf`].
+    Sdf(Arc<Mutex<SdfAtlas>>),
+}
+
+// ----------------------------------------------------------------------------
+
 /// A specific font with a size.
 /// The interface uses points as the unit for everything.
 pub struct FontImpl {
@@ -66,12 +122,12 @@ pub struct FontImpl {
     y_offset: f32,
     pixels_per_point: f32,
     glyph_info_cache: RwLock<ahash::HashMap<char, GlyphInfo>>, // TODO(emilk): standard Mutex
-    atlas: Arc<Mutex<TextureAtlas>>,
+    atlas: GlyphAtlas,
 }
 
 impl FontImpl {
-    pub fn new(
-        atlas: Arc<Mutex<TextureAtlas>>,
+    pub(crate) fn new(
+        atlas: GlyphAtlas,
         pixels_per_point: f32,
         name: String,
         ab_glyph_font: ab_glyph::FontArc,
@@ -105,6 +161,30 @@ impl FontImpl {
         }
     }
 
+    /// The coverage atlas has been repacked: update where our glyphs are,
+    /// and forget the ones that were evicted.
+    pub(crate) fn remap_glyphs(&self, remap: &GlyphRemap) {
+        self.glyph_info_cache.write().retain(|_, glyph_info| {
+            let uv_rect = &mut glyph_info.uv_rect;
+            match uv_rect.texture {
+                FontTexture::Coverage(page) if !uv_rect.is_nothing() => {
+                    if let Some(&(new_page, new_min)) = remap.get(&(page, uv_rect.min)) {
+                        uv_rect.max = [
+                            new_min[0] + (uv_rect.max[0] - uv_rect.min[0]),
+                            new_min[1] + (uv_rect.max[1] - uv_rect.min[1]),
+                        ];
+                        uv_rect.min = new_min;
+                        uv_rect.texture = FontTexture::Coverage(new_page);
+                        true
+                    } else {
+                        false // evicted
+                    }
+                }
+                _ => true,
+            }
+        });
+    }
+
     fn ignore_character(&self, chr: char) -> bool {
         if self.name == "emoji-icon-font" {
             // HACK: https://github.com/emilk/egui/issues/1284 https://github.com/jslegers/emoji-icon-font/issues/18
@@ -170,14 +250,48 @@ impl FontImpl {
                 None // unsupported character
             }
         } else {
-            let glyph_info = allocate_glyph(
-                &mut self.atlas.lock(),
-                &self.ab_glyph_font,
-                glyph_id,
-                self.scale_in_pixels as f32,
-                self.y_offset,
-                self.pixels_per_point,
-            );
+            let glyph_info = match &self.atlas {
+                GlyphAtlas::Coverage(atlas) => allocate_glyph(
+                    &mut atlas.lock(),
+                    &self.ab_glyph_font,
+                    glyph_id,
+                    self.scale_in_pixels as f32,
+                    self.y_offset,
+                    self.pixels_per_point,
+                ),
+                GlyphAtlas::Color {
+                    atlas,
+                    color_atlas,
+                    color_font,
+                } => color_atlas
+                    .lock()
+                    .glyph_info(
+                        color_font,
+                        &self.ab_glyph_font,
+                        glyph_id,
+                        self.scale_in_pixels as f32,
+                        self.y_offset,
+                        self.pixels_per_point,
+                    )
+                    .unwrap_or_else(|| {
+                        allocate_glyph(
+                            &mut atlas.lock(),
+                            &self.ab_glyph_font,
+                            glyph_id,
+                            self.scale_in_pixels as f32,
+                            self.y_offset,
+                            self.pixels_per_point,
+                        )
+                    }),
+                GlyphAtlas::Sdf(sdf_atlas) => sdf_atlas.lock().glyph_info(
+                    &self.name,
+                    &self.ab_glyph_font,
+                    glyph_id,
+                    self.scale_in_pixels as f32,
+                    self.y_offset,
+                    self.pixels_per_point,
+                ),
+            };
 
             self.glyph_info_cache.write().insert(c, glyph_info);
             Some(glyph_info)
@@ -352,7 +466,7 @@ fn invisible_char(c: char) -> bool {
 }
 
 fn allocate_glyph(
-    atlas: &mut TextureAtlas,
+    atlas: &mut FontAtlas,
     font: &ab_glyph::FontArc,
     glyph_id: ab_glyph::GlyphId,
     scale_in_pixels: f32,
@@ -372,7 +486,7 @@ fn allocate_glyph(
         if glyph_width == 0 || glyph_height == 0 {
             UvRect::default()
         } else {
-            let (glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
+            let (page, glyph_pos, image) = atlas.allocate((glyph_width, glyph_height));
             glyph.draw(|x, y, v| {
                 if v > 0.0 {
                     let px = glyph_pos.0 + x as usize;
@@ -391,6 +505,7 @@ fn allocate_glyph(
                     (glyph_pos.0 + glyph_width) as u16,
                     (glyph_pos.1 + glyph_height) as u16,
                 ],
+                texture: FontTexture::Coverage(page),
             }
         }
     });
@@ -0,0 +1,403 @@
+//! The pages of glyphs rasterized as coverage, see [`super::GlyphRendering::Coverage`].
+
+use crate::{FontImage, ImageDelta, TextureAtlas};
+
+use super::{FontTexture, Galley};
+
+/// We never use more than this many pages.
+/// If they are all full of glyphs that are in use, the last page overflows.
+const MAX_PAGES: usize = 8;
+
+/// Glyphs not used for this many frames may be evicted when the atlas is getting full.
+const MIN_UNUSED_FRAMES: u32 = 2;
+
+/// Once the atlas is getting full, look for glyphs to evict this often (in frames),
+/// and whenever a page is added.
+const EVICTION_INTERVAL: u32 = 30;
+
+/// Pages start out this tall, and grow as needed.
+const INITIAL_PAGE_HEIGHT: usize = 64;
+
+/// Where a glyph is: page and top left texel.
+type SlotKey = (u32, [u16; 2]);
+
+/// Where glyphs moved when the atlas was repacked. Evicted glyphs are missing.
+pub(crate) type GlyphRemap = ahash::HashMap<SlotKey, SlotKey>;
+
+#[derive(Clone, Copy, Debug)]
+struct GlyphSlot {
+    /// Width and height in texels.
+    size: [u16; 2],
+
+    /// [`FontAtlas::generation`] when the glyph was last used.
+    last_used: u32,
+}
+
+impl GlyphSlot {
+    fn num_texels(&self) -> usize {
+        self.size[0] as usize * self.size[1] as usize
+    }
+}
+
+/// Statistics about the font atlas, see [`super::Fonts::font_atlas_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FontAtlasStats {
+    /// Number of font textures with glyphs rasterized as coverage ([`FontTexture::Coverage`]).
+    pub num_pages: usize,
+
+    /// Number of glyphs currently in the atlas.
+    pub num_glyphs: usize,
+
+    /// Number of texels covered by the glyphs currently in the atlas.
+    pub glyph_texels: usize,
+
+    /// Number of texels of all the pages together.
+    pub page_texels: usize,
+
+    /// Total number of glyphs that have been evicted for not being used.
+    pub num_evicted: usize,
+
+    /// Number of times the atlas has been repacked after evicting glyphs.
+    pub num_repacks: usize,
+}
+
+/// Glyphs rasterized as coverage, in one or more pages ([`TextureAtlas`]).
+///
+/// Page 0 is the main font texture, which also has the white texel and the pre-rasterized discs.
+/// When a page is full, a new one is started.
+/// When the atlas is getting full, glyphs that haven't been used for a while are evicted
+/// (least recently used first), and the remaining glyphs are repacked into as few pages as possible.
+pub(crate) struct FontAtlas {
+    pages: Vec<TextureAtlas>,
+
+    /// All glyphs in the atlas.
+    slots: ahash::HashMap<SlotKey, GlyphSlot>,
+
+    /// Frame counter, used to find the least recently used glyphs.
+    generation: u32,
+
+    /// Has a page been added since we last looked for glyphs to evict?
+    page_added: bool,
+
+    num_evicted: usize,
+    num_repacks: usize,
+}
+
+impl FontAtlas {
+    pub fn new(size: [usize; 2]) -> Self {
+        Self {
+            pages: vec![TextureAtlas::new(size)],
+            slots: Default::default(),
+            generation: 0,
+            page_added: false,
+            num_evicted: 0,
+            num_repacks: 0,
+        }
+    }
+
+    /// The main font texture.
+    pub fn main_page(&self) -> &TextureAtlas {
+        &self.pages[0]
+    }
+
+    /// Size of each page.
+    pub fn page_sizes(&self) -> impl Iterator<Item = (FontTexture, [usize; 2])> + '_ {
+        self.pages
+            .iter()
+            .enumerate()
+            .map(|(i, page)| (FontTexture::Coverage(i as u32), page.size()))
+    }
+
+    /// Have we run out of space, and started overwriting old glyphs?
+    pub fn overflowed(&self) -> bool {
+        self.pages.last().map_or(false, |page| page.overflowed())
+    }
+
+    /// How much of the maximum number of pages is in use.
+    pub fn fill_ratio(&self) -> f32 {
+        let full_pages = self.pages.len() - 1;
+        let last_page = self.pages.last().map_or(0.0, |page| page.fill_ratio());
+        (full_pages as f32 + last_page) / MAX_PAGES as f32
+    }
+
+    pub fn stats(&self) -> FontAtlasStats {
+        FontAtlasStats {
+            num_pages: self.pages.len(),
+            num_glyphs: self.slots.len(),
+            glyph_texels: self.slots.values().map(GlyphSlot::num_texels).sum(),
+            page_texels: self
+                .pages
+                .iter()
+                .map(|page| page.size()[0] * page.size()[1])
+                .sum(),
+            num_evicted: self.num_evicted,
+            num_repacks: self.num_repacks,
+        }
+    }
+
+    /// Allocate space for a new glyph, starting a new page if needed.
+    ///
+    /// Returns the page, where in it the glyph goes, and the image of that page.
+    pub fn allocate(&mut self, (w, h): (usize, usize)) -> (u32, (usize, usize), &mut FontImage) {
+        let last_page = self.pages.len() - 1;
+        let (page, pos) = if let Some((pos, _)) = self.pages[last_page].try_allocate((w, h)) {
+            (last_page, pos)
+        } else if self.pages.len() == MAX_PAGES {
+            // Nothing to do but to start overwriting glyphs. Fonts will be recreated next frame.
+            (last_page, self.pages[last_page].allocate((w, h)).0)
+        } else {
+            let [width, _] = self.pages[0].size();
+            let mut new_page = TextureAtlas::new_glyph_page([width, INITIAL_PAGE_HEIGHT]);
+            let (pos, _) = new_page.allocate((w, h));
+            self.pages.push(new_page);
+            self.page_added = true;
+            (last_page + 1, pos)
+        };
+
+        self.slots.insert(
+            (page as u32, [pos.0 as u16, pos.1 as u16]),
+            GlyphSlot {
+                size: [w as u16, h as u16],
+                last_used: self.generation,
+            },
+        );
+        (page as u32, pos, self.pages[page].image_mut())
+    }
+
+    /// Mark all glyphs of the galley as used this frame.
+    pub fn touch(&mut self, galley: &Galley) {
+        for row in &galley.rows {
+            for glyph in &row.glyphs {
+                let uv_rect = &glyph.uv_rect;
+                if let FontTexture::Coverage(page) = uv_rect.texture {
+                    if let Some(slot) = self.slots.get_mut(&(page, uv_rect.min)) {
+                        slot.last_used = self.generation;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The changes to page 0 since last call.
+    pub fn take_main_delta(&mut self) -> Option<ImageDelta> {
+        self.pages[0].take_delta()
+    }
+
+    /// The changes to all pages but page 0 since last call.
+    pub fn take_page_deltas(&mut self) -> Vec<(FontTexture, ImageDelta)> {
+        self.pages
+            .iter_mut()
+            .enumerate()
+            .skip(1)
+            .filter_map(|(i, page)| Some((FontTexture::Coverage(i as u32), page.take_delta()?)))
+            .collect()
+    }
+
+    /// Call once per frame.
+    ///
+    /// If the atlas is getting full, evict the least recently used glyphs and repack the rest.
+    /// Returns where the remaining glyphs ended up, if we did.
+    ///
+    /// Looking for glyphs to evict means going through all of them,
+    /// so we only do it every [`EVICTION_INTERVAL`] frames, or when a page was added.
+    pub fn begin_frame(&mut self) -> Option<GlyphRemap> {
+        self.generation = self.generation.wrapping_add(1);
+
+        let getting_full = self.pages.len() > 1 || self.pages[0].fill_ratio() > 0.8;
+        if !getting_full {
+            return None;
+        }
+        if !std::mem::take(&mut self.page_added) && self.generation % EVICTION_INTERVAL != 0 {
+            return None;
+        }
+
+        let page_capacity = self.pages[0].capacity();
+
+        let used_texels: usize = self
+            .pages
+            .iter()
+            .map(|page| (page.fill_ratio().min(1.0) * page_capacity as f32) as usize)
+            .sum();
+        let mut live_texels: usize = self.slots.values().map(GlyphSlot::num_texels).sum();
+
+        // Least recently used first:
+        let generation = self.generation;
+        let age = |slot: &GlyphSlot| generation.wrapping_sub(slot.last_used);
+        let mut candidates: Vec<(SlotKey, GlyphSlot)> = self
+            .slots
+            .iter()
+            .filter(|(_, slot)| age(slot) > MIN_UNUSED_FRAMES)
+            .map(|(key, slot)| (*key, *slot))
+            .collect();
+        candidates.sort_by_key(|(_, slot)| std::cmp::Reverse(age(slot)));
+
+        let target_texels = page_capacity / 2;
+        let mut evicted = vec![];
+        for (key, slot) in candidates {
+            if live_texels <= target_texels {
+                break;
+            }
+            live_texels -= slot.num_texels();
+            evicted.push(key);
+        }
+
+        // Only worth it if we free up a good chunk of the atlas:
+        if evicted.is_empty() || live_texels > used_texels / 2 {
+            return None;
+        }
+
+        for key in &evicted {
+            self.slots.remove(key);
+        }
+        self.num_evicted += evicted.len();
+        self.num_repacks += 1;
+
+        let remap = self.repack();
+        self.page_added = false; // by repacking
+        Some(remap)
+    }
+
+    /// Move all glyphs into new pages, tallest first.
+    fn repack(&mut self) -> GlyphRemap {
+        let [width, _] = self.pages[0].size();
+        let old_pages = std::mem::replace(
+            &mut self.pages,
+            vec![TextureAtlas::new([width, INITIAL_PAGE_HEIGHT])],
+        );
+        let old_slots = std::mem::take(&mut self.slots);
+
+        let mut slots: Vec<(SlotKey, GlyphSlot)> = old_slots.into_iter().collect();
+        slots.sort_by_key(|((page, min), slot)| (std::cmp::Reverse(slot.size[1]), *page, *min));
+
+        let mut remap = GlyphRemap::default();
+        for ((old_page, old_min), slot) in slots {
+            let [w, h] = [slot.size[0] as usize, slot.size[1] as usize];
+            let (new_page, new_pos, image) = self.allocate((w, h));
+
+            let old_image = old_pages[old_page as usize].image();
+            for y in 0..h {
+                for x in 0..w {
+                    image[(new_pos.0 + x, new_pos.1 + y)] =
+                        old_image[(old_min[0] as usize + x, old_min[1] as usize + y)];
+                }
+            }
+
+            let new_key = (new_page, [new_pos.0 as u16, new_pos.1 as u16]);
+            self.slots.insert(new_key, slot); // keep `last_used`
+            remap.insert((old_page, old_min), new_key);
+        }
+        remap
+    }
+}
+
+#[test]
+fn test_font_atlas_eviction() {
+    let mut atlas = FontAtlas::new([1024, 64]);
+
+    // Fill more than one page, marking each glyph with its own value:
+    let glyphs: Vec<(SlotKey, f32)> = (0..150)
+        .map(|i| {
+            let value = i as f32 / 150.0;
+            let (page, pos, image) = atlas.allocate((100, 100));
+            image[pos] = value;
+            ((page, [pos.0 as u16, pos.1 as u16]), value)
+        })
+        .collect();
+    assert_eq!(atlas.stats().num_pages, 2);
+
+    // Keep using a few of the glyphs:
+    let used = &glyphs[100..110];
+    let mut remap = None;
+    for _ in 0..=EVICTION_INTERVAL {
+        for (key, _) in used {
+            let key = remap.as_ref().map_or(*key, |remap: &GlyphRemap| remap[key]);
+            atlas.slots.get_mut(&key).unwrap().last_used = atlas.generation;
+        }
+        if let Some(new_remap) = atlas.begin_frame() {
+            remap = Some(new_remap);
+        }
+    }
+
+    let remap = remap.expect("the atlas should have been repacked");
+    let stats = atlas.stats();
+    assert_eq!(stats.num_pages, 1);
+    assert_eq!(stats.num_repacks, 1);
+    assert!(stats.num_glyphs < glyphs.len());
+    assert_eq!(stats.num_glyphs + stats.num_evicted, glyphs.len());
+
+    for (key, value) in used {
+        let (page, min) = remap[key];
+        let image = atlas.pages[page as usize].image();
+        assert_eq!(image[(min[0] as usize, min[1] as usize)], *value);
+    }
+}
+
+#[test]
+fn test_galley_across_repack() {
+    use crate::{
+        text::{FontDefinitions, FontId},
+        ClippedShape, Color32, Fonts, Pos2, Rect, Shape,
+    };
+
+    let fonts = Fonts::new(1.0, 1024, FontDefinitions::default());
+    let galley =
+        |text: &str| fonts.layout_no_wrap(text.into(), FontId::proportional(14.0), Color32::WHITE);
+    // The coverage of each glyph of the galley:
+    let glyph_texels = |galley: &Galley| -> Vec<Vec<f32>> {
+        let fonts = fonts.lock();
+        let atlas = fonts.fonts.atlas.lock();
+        let mut texels = vec![];
+        for glyph in galley.rows.iter().flat_map(|row| &row.glyphs) {
+            let uv = &glyph.uv_rect;
+            if let FontTexture::Coverage(page) = uv.texture {
+                let image = atlas.pages[page as usize].image();
+                texels.push(
+                    (uv.min[1]..uv.max[1])
+                        .flat_map(|y| (uv.min[0]..uv.max[0]).map(move |x| (x, y)))
+                        .map(|(x, y)| image[(x as usize, y as usize)])
+                        .collect(),
+                );
+            }
+        }
+        texels
+    };
+    let shape = |galley| {
+        vec![ClippedShape(
+            Rect::EVERYTHING,
+            Shape::galley(Pos2::ZERO, galley),
+        )]
+    };
+
+    fonts.begin_frame(1.0, 1024);
+    let mut shown = shape(galley("Shown each frame"));
+    let mut hidden = shape(galley("Kept, but not shown"));
+    let galley_of = |shapes: &[ClippedShape]| match &shapes[0].1 {
+        Shape::Text(text_shape) => text_shape.galley.clone(),
+        _ => unreachable!(),
+    };
+    let shown_texels = glyph_texels(&galley_of(&shown));
+    let hidden_texels = glyph_texels(&galley_of(&hidden));
+    let epoch = galley_of(&shown).font_atlas_epoch;
+
+    // Fill the atlas with glyphs that are used only once, until it is repacked:
+    let mut size = 8.0;
+    while fonts.font_atlas_stats().num_repacks == 0 {
+        fonts.begin_frame(1.0, 1024);
+        fonts.layout_no_wrap(
+            "abcdefghijklmnopqrstuvwxyz".into(),
+            FontId::proportional(size),
+            Color32::WHITE,
+        );
+        fonts.refresh_galleys(&mut shown);
+        size += 1.0;
+        assert!(size < 200.0, "the atlas should have been repacked");
+    }
+    assert!(fonts.font_atlas_stats().num_evicted > 0);
+
+    // Both galleys now point at where their glyphs are:
+    fonts.refresh_galleys(&mut hidden);
+    assert_ne!(galley_of(&shown).font_atlas_epoch, epoch);
+    assert_ne!(galley_of(&hidden).font_atlas_epoch, epoch);
+    assert_eq!(glyph_texels(&galley_of(&shown)), shown_texels);
+    assert_eq!(glyph_texels(&galley_of(&hidden)), hidden_texels);
+}
@@ -4,10 +4,14 @@ use std::sync::Arc;
 use crate::{
     mutex::{Mutex, MutexGuard},
     text::{
-        font::{Font, FontImpl},
+        color_glyph::{ColorAtlas, ColorFont},
+        font::{Font, FontImpl, FontTexture, GlyphAtlas},
+        font_atlas::{FontAtlas, FontAtlasStats, GlyphRemap},
+        sdf::SdfAtlas,
         Galley, LayoutJob,
     },
-    TextureAtlas,
+    texture_atlas::PreparedDisc,
+    ClippedShape, Shape,
 };
 use emath::NumExt as _;
 
@@ -124,6 +128,10 @@ pub struct FontData {
 
     /// Extra scale and vertical tweak to apply to all text of this font.
     pub tweak: FontTweak,
+
+    /// How to rasterize the glyphs of this font.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rendering: GlyphRendering,
 }
 
 impl FontData {
@@ -132,6 +140,7 @@ impl FontData {
             font: std::borrow::Cow::Borrowed(font),
             index: 0,
             tweak: Default::default(),
+            rendering: Default::default(),
         }
     }
 
@@ -140,12 +149,47 @@ impl FontData {
             font: std::borrow::Cow::Owned(font),
             index: 0,
             tweak: Default::default(),
+            rendering: Default::default(),
         }
     }
 
     pub fn tweak(self, tweak: FontTweak) -> Self {
         Self { tweak, ..self }
     }
+
+    pub fn rendering(self, rendering: GlyphRendering) -> Self {
+        Self { rendering, ..self }
+    }
+}
+
+// ----------------------------------------------------------------------------
+
+/// How the glyphs of a font are rasterized into a font texture.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum GlyphRendering {
+    /// Rasterize each glyph at each size it is used at.
+    ///
+    /// This gives the crispest result for small text, but every new size fills up more of the font atlas.
+    ///
+    /// Glyphs the font has in colour (e.g. colour emoji in `COLR`, `CBDT` or `sbix` tables)
+    /// are rasterized in colour, into a separate RGBA texture ([`FontTexture::Color`]).
+    Coverage,
+
+    /// Rasterize each glyph once as a signed distance field, and share it between all sizes.
+    ///
+    /// Use this for text that is zoomed, rotated or animated in size.
+    /// Small text looks slightly softer than with [`Self::Coverage`].
+    ///
+    /// The glyphs end up in a separate texture of [`crate::ImageData::Sdf`],
+    /// which needs support from the rendering backend (`egui_glow` and `egui-wgpu` have it).
+    Sdf,
+}
+
+impl Default for GlyphRendering {
+    fn default() -> Self {
+        Self::Coverage
+    }
 }
 
 // ----------------------------------------------------------------------------
@@ -335,7 +379,9 @@ impl FontDefinitions {
 ///
 /// If you are using `egui`, use `egui::Context::set_fonts` and `egui::Context::fonts`.
 ///
-/// You need to call [`Self::begin_frame`] and [`Self::font_image_delta`] once every frame.
+/// You need to call [`Self::begin_frame`] and [`Self::font_image_delta`] once every frame
+/// (and [`Self::font_texture_deltas`], for when the font atlas grows past the main font texture,
+/// or if you use [`GlyphRendering::Sdf`]).
 pub struct Fonts(Arc<Mutex<FontsAndCache>>);
 
 impl Fonts {
@@ -369,25 +415,72 @@ impl Fonts {
         let pixels_per_point_changed =
             (fonts_and_cache.fonts.pixels_per_point - pixels_per_point).abs() > 1e-3;
         let max_texture_side_changed = fonts_and_cache.fonts.max_texture_side != max_texture_side;
-        let font_atlas_almost_full = fonts_and_cache.fonts.atlas.lock().fill_ratio() > 0.8;
-        let needs_recreate =
-            pixels_per_point_changed || max_texture_side_changed || font_atlas_almost_full;
+        let font_atlas_overflowed = fonts_and_cache.fonts.atlas.lock().overflowed();
+        let sdf_atlas_almost_full =
+            fonts_and_cache.fonts.sdf_atlas.lock().atlas().fill_ratio() > 0.8;
+        let color_atlas_almost_full = fonts_and_cache.fonts.color_atlas.lock().fill_ratio() > 0.8;
+        let needs_recreate = pixels_per_point_changed
+            || max_texture_side_changed
+            || font_atlas_overflowed
+            || sdf_atlas_almost_full
+            || color_atlas_almost_full;
 
         if needs_recreate {
             let definitions = fonts_and_cache.fonts.definitions.clone();
 
+            // The distance fields don't depend on `pixels_per_point`, so keep them if we can:
+            let sdf_atlas = if max_texture_side_changed || sdf_atlas_almost_full {
+                None
+            } else {
+                Some(fonts_and_cache.fonts.sdf_atlas.clone())
+            };
+
             *fonts_and_cache = FontsAndCache {
-                fonts: FontsImpl::new(pixels_per_point, max_texture_side, definitions),
+                fonts: FontsImpl::with_sdf_atlas(
+                    pixels_per_point,
+                    max_texture_side,
+                    definitions,
+                    sdf_atlas,
+                ),
                 galley_cache: Default::default(),
             };
+        } else {
+            let remap = fonts_and_cache.fonts.atlas.lock().begin_frame();
+            if let Some(remap) = remap {
+                // Glyphs have moved, so all cached glyph positions are stale:
+                fonts_and_cache.fonts.remap_glyphs(&remap);
+                fonts_and_cache.galley_cache.clear();
+            }
         }
 
         fonts_and_cache.galley_cache.flush_cache();
     }
 
-    /// Call at the end of each frame (before painting) to get the change to the font texture since last call.
+    /// Call at the end of each frame (before painting) to get the change to the main font texture since last call.
+    ///
+    /// This is [`FontTexture::Coverage(0)`](FontTexture::Coverage),
+    /// see also [`Self::font_texture_deltas`] for the other font textures.
     pub fn font_image_delta(&self) -> Option<crate::ImageDelta> {
-        self.lock().fonts.atlas.lock().take_delta()
+        self.lock().fonts.atlas.lock().take_main_delta()
+    }
+
+    /// Call at the end of each frame (before painting) to get the changes to the font textures
+    /// other than the main one since last call.
+    ///
+    /// These are the extra pages of glyphs added when the main font texture fills up,
+    /// and the [`crate::ImageData::Sdf`] texture of [`GlyphRendering::Sdf`] glyphs.
+    /// Each should be uploaded to its own texture, and the [`crate::Tessellator`]
+    /// told about it with [`crate::Tessellator::set_font_texture`].
+    pub fn font_texture_deltas(&self) -> Vec<(FontTexture, crate::ImageDelta)> {
+        let fonts = &self.lock().fonts;
+        let mut deltas = fonts.atlas.lock().take_page_deltas();
+        if let Some(delta) = fonts.sdf_atlas.lock().take_delta() {
+            deltas.push((FontTexture::Sdf, delta));
+        }
+        if let Some(delta) = fonts.color_atlas.lock().take_delta() {
+            deltas.push((FontTexture::Color, delta));
+        }
+        deltas
     }
 
     /// Access the underlying [`FontsAndCache`].
@@ -407,16 +500,34 @@ impl Fonts {
         self.lock().fonts.max_texture_side
     }
 
-    /// The font atlas.
+    /// The pre-rasterized discs in the main font texture.
     /// Pass this to [`crate::Tessellator`].
-    pub fn texture_atlas(&self) -> Arc<Mutex<TextureAtlas>> {
-        self.lock().fonts.atlas.clone()
+    pub fn prepared_discs(&self) -> Vec<PreparedDisc> {
+        self.lock().fonts.atlas.lock().main_page().prepared_discs()
     }
 
-    /// Current size of the font image.
+    /// Current size of the main font image.
     /// Pass this to [`crate::Tessellator`].
     pub fn font_image_size(&self) -> [usize; 2] {
-        self.lock().fonts.atlas.lock().size()
+        self.lock().fonts.atlas.lock().main_page().size()
+    }
+
+    /// All font textures other than the main one, with their current sizes.
+    ///
+    /// Pass these to [`crate::Tessellator::set_font_texture`].
+    /// Textures no longer in this list (e.g. after the font atlas was repacked) can be freed.
+    pub fn font_textures(&self) -> Vec<(FontTexture, [usize; 2])> {
+        let fonts = &self.lock().fonts;
+        let mut textures: Vec<_> = fonts.atlas.lock().page_sizes().skip(1).collect();
+        let sdf_atlas = fonts.sdf_atlas.lock();
+        if sdf_atlas.is_used() {
+            textures.push((FontTexture::Sdf, sdf_atlas.atlas().size()));
+        }
+        let color_atlas = fonts.color_atlas.lock();
+        if color_atlas.is_used() {
+            textures.push((FontTexture::Color, color_atlas.size()));
+        }
+        textures
     }
 
     /// Width of this character in points.
@@ -458,14 +569,39 @@ impl Fonts {
         self.lock().galley_cache.num_galleys_in_cache()
     }
 
+    /// Get the text in `shapes` ready for painting with the current font textures.
+    ///
+    /// Galleys laid out before the font atlas was repacked or recreated (or by other [`Fonts`])
+    /// refer to glyphs that have since moved, so they are laid out again.
+    /// The glyphs of the other galleys are marked as used, so they are not evicted while they are shown.
+    ///
+    /// Call this each frame before tessellating, e.g. with [`crate::tessellate_shapes`].
+    /// `egui::Context::tessellate` does it for you.
+    pub fn refresh_galleys(&self, shapes: &mut [ClippedShape]) {
+        let mut fonts_and_cache = self.lock();
+        let FontsAndCache {
+            fonts,
+            galley_cache,
+        } = &mut *fonts_and_cache;
+        for ClippedShape(_, shape) in shapes {
+            refresh_galleys(fonts, galley_cache, shape);
+        }
+    }
+
     /// How full is the font atlas?
     ///
     /// This increases as new fonts and/or glyphs are used,
-    /// but can also decrease in a call to [`Self::begin_frame`].
+    /// but can also decrease in a call to [`Self::begin_frame`],
+    /// when glyphs that haven't been used in a while are evicted.
     pub fn font_atlas_fill_ratio(&self) -> f32 {
         self.lock().fonts.atlas.lock().fill_ratio()
     }
 
+    /// Statistics about the pages of the font atlas, and how many glyphs have been evicted from it.
+    pub fn font_atlas_stats(&self) -> FontAtlasStats {
+        self.lock().fonts.atlas.lock().stats()
+    }
+
     /// Will wrap text at the given width and line break at `\n`.
     ///
     /// The implementation uses memoization so repeated calls are cheap.
@@ -511,6 +647,24 @@ impl Fonts {
     }
 }
 
+fn refresh_galleys(fonts: &mut FontsImpl, galley_cache: &mut GalleyCache, shape: &mut Shape) {
+    match shape {
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                refresh_galleys(fonts, galley_cache, shape);
+            }
+        }
+        Shape::Text(text_shape) => {
+            if text_shape.galley.font_atlas_epoch == fonts.font_atlas_epoch {
+                fonts.atlas.lock().touch(&text_shape.galley);
+            } else {
+                text_shape.galley = galley_cache.layout(fonts, text_shape.galley.job.clone());
+            }
+        }
+        _ => {}
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 pub struct FontsAndCache {
@@ -520,7 +674,7 @@ pub struct FontsAndCache {
 
 impl FontsAndCache {
     fn layout_job(&mut self, job: LayoutJob) -> Arc<Galley> {
-        self.galley_cache.layout(&mut self.fonts, job)
+        self.galley_cache.layout(&mut self.fonts, job.into())
     }
 }
 
@@ -533,9 +687,21 @@ pub struct FontsImpl {
     pixels_per_point: f32,
     max_texture_side: usize,
     definitions: FontDefinitions,
-    atlas: Arc<Mutex<TextureAtlas>>,
+    pub(crate) atlas: Arc<Mutex<FontAtlas>>,
+    sdf_atlas: Arc<Mutex<SdfAtlas>>,
+    color_atlas: Arc<Mutex<ColorAtlas>>,
     font_impl_cache: FontImplCache,
     sized_family: ahash::HashMap<(u32, FontFamily), Font>,
+
+    /// Changes whenever glyphs move in the font atlas, see [`Galley::font_atlas_epoch`].
+    font_atlas_epoch: u64,
+}
+
+/// A new value for [`FontsImpl::font_atlas_epoch`], unique among all fonts.
+fn next_font_atlas_epoch() -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static NEXT_EPOCH: AtomicU64 = AtomicU64::new(1);
+    NEXT_EPOCH.fetch_add(1, Ordering::Relaxed)
 }
 
 impl FontsImpl {
@@ -545,6 +711,17 @@ impl FontsImpl {
         pixels_per_point: f32,
         max_texture_side: usize,
         definitions: FontDefinitions,
+    ) -> Self {
+        Self::with_sdf_atlas(pixels_per_point, max_texture_side, definitions, None)
+    }
+
+    /// Reuse the glyphs of a previous [`SdfAtlas`], if given.
+    /// It must come from a [`FontsImpl`] with the same `max_texture_side` and `definitions`.
+    fn with_sdf_atlas(
+        pixels_per_point: f32,
+        max_texture_side: usize,
+        definitions: FontDefinitions,
+        sdf_atlas: Option<Arc<Mutex<SdfAtlas>>>,
     ) -> Self {
         assert!(
             0.0 < pixels_per_point && pixels_per_point < 100.0,
@@ -554,20 +731,34 @@ impl FontsImpl {
 
         let texture_width = max_texture_side.at_most(8 * 1024);
         let initial_height = 64;
-        let atlas = TextureAtlas::new([texture_width, initial_height]);
+        let atlas = FontAtlas::new([texture_width, initial_height]);
 
         let atlas = Arc::new(Mutex::new(atlas));
 
-        let font_impl_cache =
-            FontImplCache::new(atlas.clone(), pixels_per_point, &definitions.font_data);
+        let sdf_atlas = sdf_atlas.unwrap_or_else(|| {
+            Arc::new(Mutex::new(SdfAtlas::new([texture_width, initial_height])))
+        });
+
+        let color_atlas = Arc::new(Mutex::new(ColorAtlas::new([texture_width, initial_height])));
+
+        let font_impl_cache = FontImplCache::new(
+            atlas.clone(),
+            sdf_atlas.clone(),
+            color_atlas.clone(),
+            pixels_per_point,
+            &definitions.font_data,
+        );
 
         Self {
             pixels_per_point,
             max_texture_side,
             definitions,
             atlas,
+            sdf_atlas,
+            color_atlas,
             font_impl_cache,
             sized_family: Default::default(),
+            font_atlas_epoch: next_font_atlas_epoch(),
         }
     }
 
@@ -576,6 +767,11 @@ impl FontsImpl {
         self.pixels_per_point
     }
 
+    /// Galleys laid out with another epoch must be laid out again, see [`Fonts::refresh_galleys`].
+    pub(crate) fn font_atlas_epoch(&self) -> u64 {
+        self.font_atlas_epoch
+    }
+
     #[inline]
     pub fn definitions(&self) -> &FontDefinitions {
         &self.definitions
@@ -603,6 +799,13 @@ impl FontsImpl {
             })
     }
 
+    /// The font atlas has been repacked, see [`FontAtlas::begin_frame`].
+    fn remap_glyphs(&mut self, remap: &GlyphRemap) {
+        self.font_impl_cache.remap_glyphs(remap);
+        self.sized_family.clear(); // `Font` caches glyph infos too
+        self.font_atlas_epoch = next_font_atlas_epoch();
+    }
+
     /// Width of this character in points.
     fn glyph_width(&mut self, font_id: &FontId, c: char) -> f32 {
         self.font(font_id).glyph_width(c)
@@ -630,17 +833,21 @@ struct GalleyCache {
 }
 
 impl GalleyCache {
-    fn layout(&mut self, fonts: &mut FontsImpl, job: LayoutJob) -> Arc<Galley> {
+    fn layout(&mut self, fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Arc<Galley> {
         let hash = crate::util::hash(&job); // TODO(emilk): even faster hasher?
 
         match self.cache.entry(hash) {
             std::collections::hash_map::Entry::Occupied(entry) => {
                 let cached = entry.into_mut();
-                cached.last_used = self.generation;
+                if cached.last_used != self.generation {
+                    fonts.atlas.lock().touch(&cached.galley);
+                    cached.last_used = self.generation;
+                }
                 cached.galley.clone()
             }
             std::collections::hash_map::Entry::Vacant(entry) => {
-                let galley = super::layout(fonts, job.into());
+                let galley = super::layout(fonts, job);
+                fonts.atlas.lock().touch(&galley);
                 let galley = Arc::new(galley);
                 entry.insert(CachedGalley {
                     last_used: self.generation,
@@ -655,6 +862,11 @@ impl GalleyCache {
         self.cache.len()
     }
 
+    /// Forget all galleys, e.g. because the glyphs they refer to have moved.
+    fn clear(&mut self) {
+        self.cache.clear();
+    }
+
     /// Must be called once per frame to clear the [`Galley`] cache.
     pub fn flush_cache(&mut self) {
         let current_generation = self.generation;
@@ -668,9 +880,22 @@ impl GalleyCache {
 // ----------------------------------------------------------------------------
 
 struct FontImplCache {
-    atlas: Arc<Mutex<TextureAtlas>>,
+    atlas: Arc<Mutex<FontAtlas>>,
+    sdf_atlas: Arc<Mutex<SdfAtlas>>,
+    color_atlas: Arc<Mutex<ColorAtlas>>,
     pixels_per_point: f32,
-    ab_glyph_fonts: BTreeMap<String, (FontTweak, ab_glyph::FontArc)>,
+
+    /// The [`ColorFont`] is there if the font has colour glyphs.
+    #[allow(clippy::type_complexity)]
+    ab_glyph_fonts: BTreeMap<
+        String,
+        (
+            FontTweak,
+            GlyphRendering,
+            Option<Arc<ColorFont>>,
+            ab_glyph::FontArc,
+        ),
+    >,
 
     /// Map font pixel sizes and names to the cached [`FontImpl`].
     cache: ahash::HashMap<(u32, String), Arc<FontImpl>>,
@@ -678,7 +903,9 @@ struct FontImplCache {
 
 impl FontImplCache {
     pub fn new(
-        atlas: Arc<Mutex<TextureAtlas>>,
+        atlas: Arc<Mutex<FontAtlas>>,
+        sdf_atlas: Arc<Mutex<SdfAtlas>>,
+        color_atlas: Arc<Mutex<ColorAtlas>>,
         pixels_per_point: f32,
         font_data: &BTreeMap<String, FontData>,
     ) -> Self {
@@ -686,19 +913,35 @@ impl FontImplCache {
             .iter()
             .map(|(name, font_data)| {
                 let tweak = font_data.tweak;
+                // Distance fields have no colour, so only look for colour glyphs when we'd use them:
+                let color_font = match font_data.rendering {
+                    GlyphRendering::Coverage => ColorFont::new(font_data).map(Arc::new),
+                    GlyphRendering::Sdf => None,
+                };
                 let ab_glyph = ab_glyph_font_from_font_data(name, font_data);
-                (name.clone(), (tweak, ab_glyph))
+                (
+                    name.clone(),
+                    (tweak, font_data.rendering, color_font, ab_glyph),
+                )
             })
             .collect();
 
         Self {
             atlas,
+            sdf_atlas,
+            color_atlas,
             pixels_per_point,
             ab_glyph_fonts,
             cache: Default::default(),
         }
     }
 
+    fn remap_glyphs(&mut self, remap: &GlyphRemap) {
+        for font_impl in self.cache.values() {
+            font_impl.remap_glyphs(remap);
+        }
+    }
+
     #[inline]
     pub fn scale_as_pixels(&self, scale_in_points: f32) -> u32 {
         let scale_in_pixels = self.pixels_per_point * scale_in_points;
@@ -709,7 +952,7 @@ impl FontImplCache {
     }
 
     pub fn font_impl(&mut self, scale_in_pixels: u32, font_name: &str) -> Arc<FontImpl> {
-        let (tweak, ab_glyph_font) = self
+        let (tweak, rendering, color_font, ab_glyph_font) = self
             .ab_glyph_fonts
             .get(font_name)
             .unwrap_or_else(|| panic!("No font data found for {:?}", font_name))
@@ -725,8 +968,17 @@ impl FontImplCache {
         self.cache
             .entry((scale_in_pixels, font_name.to_owned()))
             .or_insert_with(|| {
+                let atlas = match (rendering, color_font) {
+                    (GlyphRendering::Coverage, None) => GlyphAtlas::Coverage(self.atlas.clone()),
+                    (GlyphRendering::Coverage, Some(color_font)) => GlyphAtlas::Color {
+                        atlas: self.atlas.clone(),
+                        color_atlas: self.color_atlas.clone(),
+                        color_font,
+                    },
+                    (GlyphRendering::Sdf, _) => GlyphAtlas::Sdf(self.sdf_atlas.clone()),
+                };
                 Arc::new(FontImpl::new(
-                    self.atlas.clone(),
+                    atlas,
                     self.pixels_per_point,
                     font_name.to_owned(),
                     ab_glyph_font,
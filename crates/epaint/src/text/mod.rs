@@ -1,8 +1,11 @@
 //! Everything related to text, fonts, text layout, cursors etc.
 
+mod color_glyph;
 pub mod cursor;
 mod font;
+mod font_atlas;
 mod fonts;
+mod sdf;
 mod text_layout;
 mod text_layout_types;
 
@@ -10,7 +13,11 @@ mod text_layout_types;
 pub const TAB_SIZE: usize = 4;
 
 pub use {
-    fonts::{FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl},
+    font::FontTexture,
+    font_atlas::FontAtlasStats,
+    fonts::{
+        FontData, FontDefinitions, FontFamily, FontId, FontTweak, Fonts, FontsImpl, GlyphRendering,
+    },
     text_layout::layout,
     text_layout_types::*,
 };
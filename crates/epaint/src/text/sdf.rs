@@ -0,0 +1,291 @@
+//! Glyphs rendered as signed distance fields, see [`super::GlyphRendering::Sdf`].
+//!
+//! Each glyph is rasterized once, at [`SDF_SCALE_IN_PIXELS`], and then shared by all sizes of the font.
+//! The distance field is computed with the exact euclidean distance transform of Felzenszwalb & Huttenlocher,
+//! with the sub-pixel trick from Mapbox's `TinySDF`.
+
+use emath::{vec2, Vec2};
+
+use super::font::{FontTexture, GlyphInfo, UvRect};
+use crate::TextureAtlas;
+
+/// The size glyphs are rasterized at before being turned into a distance field.
+const SDF_SCALE_IN_PIXELS: f32 = 48.0;
+
+/// How far out from the outline (in texels at [`SDF_SCALE_IN_PIXELS`]) the distance field reaches.
+const SDF_SPREAD: usize = 6;
+
+/// Where a glyph ended up in the SDF atlas.
+#[derive(Clone, Copy, Debug)]
+struct SdfGlyph {
+    /// Top left corner of the padded glyph, relative to the glyph origin, at [`SDF_SCALE_IN_PIXELS`].
+    offset: Vec2,
+
+    /// Top left corner in the atlas, in texels.
+    min: [u16; 2],
+
+    /// Bottom right corner in the atlas (exclusive), in texels.
+    max: [u16; 2],
+}
+
+/// The texture atlas for all glyphs of fonts using [`super::GlyphRendering::Sdf`].
+///
+/// Shared by all sizes of all such fonts, and kept when `pixels_per_point` changes.
+pub(crate) struct SdfAtlas {
+    atlas: TextureAtlas,
+
+    /// `None` for glyphs without an outline (e.g. space).
+    glyphs: ahash::HashMap<(String, ab_glyph::GlyphId), Option<SdfGlyph>>,
+}
+
+impl SdfAtlas {
+    pub fn new(size: [usize; 2]) -> Self {
+        Self {
+            atlas: TextureAtlas::new_sdf(size),
+            glyphs: Default::default(),
+        }
+    }
+
+    pub fn atlas(&self) -> &TextureAtlas {
+        &self.atlas
+    }
+
+    /// Has any glyph been added?
+    ///
+    /// If not, no texture needs to be created for this atlas.
+    pub fn is_used(&self) -> bool {
+        !self.glyphs.is_empty()
+    }
+
+    /// The change to the atlas since last call.
+    ///
+    /// Always `None` until the first glyph has been added, see [`Self::is_used`].
+    pub fn take_delta(&mut self) -> Option<crate::ImageDelta> {
+        if self.is_used() {
+            self.atlas.take_delta()
+        } else {
+            None
+        }
+    }
+
+    /// Look up (or rasterize) the given glyph, and place it for the given font size.
+    pub fn glyph_info(
+        &mut self,
+        font_name: &str,
+        font: &ab_glyph::FontArc,
+        glyph_id: ab_glyph::GlyphId,
+        scale_in_pixels: f32,
+        y_offset: f32,
+        pixels_per_point: f32,
+    ) -> GlyphInfo {
+        use ab_glyph::{Font as _, ScaleFont};
+
+        let atlas = &mut self.atlas;
+        let sdf_glyph = *self
+            .glyphs
+            .entry((font_name.to_owned(), glyph_id))
+            .or_insert_with(|| allocate_sdf_glyph(atlas, font, glyph_id));
+
+        let uv_rect = sdf_glyph.map_or_else(UvRect::default, |sdf_glyph| {
+            let scale = scale_in_pixels / SDF_SCALE_IN_PIXELS;
+            let size_in_texels = vec2(
+                (sdf_glyph.max[0] - sdf_glyph.min[0]) as f32,
+                (sdf_glyph.max[1] - sdf_glyph.min[1]) as f32,
+            );
+            let offset_in_pixels = sdf_glyph.offset * scale + scale_in_pixels * Vec2::Y;
+            UvRect {
+                offset: offset_in_pixels / pixels_per_point + y_offset * Vec2::Y,
+                size: size_in_texels * scale / pixels_per_point,
+                min: sdf_glyph.min,
+                max: sdf_glyph.max,
+                texture: FontTexture::Sdf,
+            }
+        });
+
+        let advance_width_in_points =
+            font.as_scaled(scale_in_pixels).h_advance(glyph_id) / pixels_per_point;
+
+        GlyphInfo {
+            id: glyph_id,
+            advance_width: advance_width_in_points,
+            uv_rect,
+        }
+    }
+}
+
+fn allocate_sdf_glyph(
+    atlas: &mut TextureAtlas,
+    font: &ab_glyph::FontArc,
+    glyph_id: ab_glyph::GlyphId,
+) -> Option<SdfGlyph> {
+    use ab_glyph::Font as _;
+
+    let glyph =
+        glyph_id.with_scale_and_position(SDF_SCALE_IN_PIXELS, ab_glyph::Point { x: 0.0, y: 0.0 });
+    let glyph = font.outline_glyph(glyph)?;
+
+    let bb = glyph.px_bounds();
+    let glyph_width = bb.width() as usize;
+    let glyph_height = bb.height() as usize;
+    if glyph_width == 0 || glyph_height == 0 {
+        return None;
+    }
+
+    let w = glyph_width + 2 * SDF_SPREAD;
+    let h = glyph_height + 2 * SDF_SPREAD;
+    let mut coverage = vec![0.0; w * h];
+    glyph.draw(|x, y, v| {
+        let px = SDF_SPREAD + x as usize;
+        let py = SDF_SPREAD + y as usize;
+        coverage[py * w + px] = v.min(1.0);
+    });
+    let distances = signed_distance_field(&coverage, [w, h], SDF_SPREAD as f32);
+
+    let (pos, image) = atlas.allocate((w, h));
+    for y in 0..h {
+        for x in 0..w {
+            image[(pos.0 + x, pos.1 + y)] = distances[y * w + x];
+        }
+    }
+
+    Some(SdfGlyph {
+        offset: vec2(bb.min.x, bb.min.y) - Vec2::splat(SDF_SPREAD as f32),
+        min: [pos.0 as u16, pos.1 as u16],
+        max: [(pos.0 + w) as u16, (pos.1 + h) as u16],
+    })
+}
+
+// ----------------------------------------------------------------------------
+
+const INF: f64 = 1e20;
+
+/// Turn a coverage image into a signed distance field.
+///
+/// The result is `0.5` on the outline, and reaches `1.0` (inside) and `0.0` (outside)
+/// at a distance of `spread` texels from it.
+fn signed_distance_field(coverage: &[f32], [w, h]: [usize; 2], spread: f32) -> Vec<f32> {
+    crate::epaint_assert!(coverage.len() == w * h);
+
+    // Squared distances to the outline, using the coverage of edge texels
+    // as an estimate of how far into the texel the outline is.
+    let mut outer: Vec<f64> = coverage
+        .iter()
+        .map(|&a| {
+            if a >= 1.0 {
+                0.0
+            } else if a <= 0.0 {
+                INF
+            } else {
+                (0.5 - a as f64).max(0.0).powi(2)
+            }
+        })
+        .collect();
+    let mut inner: Vec<f64> = coverage
+        .iter()
+        .map(|&a| {
+            if a >= 1.0 {
+                INF
+            } else if a <= 0.0 {
+                0.0
+            } else {
+                (a as f64 - 0.5).max(0.0).powi(2)
+            }
+        })
+        .collect();
+
+    edt(&mut outer, w, h);
+    edt(&mut inner, w, h);
+
+    outer
+        .iter()
+        .zip(&inner)
+        .map(|(outer, inner)| {
+            let distance = (outer.sqrt() - inner.sqrt()) as f32;
+            (0.5 - distance / (2.0 * spread)).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// 2D squared euclidean distance transform, in-place.
+fn edt(grid: &mut [f64], width: usize, height: usize) {
+    let n = width.max(height);
+    let mut f = vec![0.0; n];
+    let mut v = vec![0; n];
+    let mut z = vec![0.0; n + 1];
+
+    for x in 0..width {
+        edt_1d(grid, x, width, height, &mut f, &mut v, &mut z);
+    }
+    for y in 0..height {
+        edt_1d(grid, y * width, 1, width, &mut f, &mut v, &mut z);
+    }
+}
+
+/// 1D squared distance transform of the lower envelope of parabolas.
+fn edt_1d(
+    grid: &mut [f64],
+    offset: usize,
+    stride: usize,
+    length: usize,
+    f: &mut [f64],
+    v: &mut [usize],
+    z: &mut [f64],
+) {
+    v[0] = 0;
+    z[0] = -INF;
+    z[1] = INF;
+    f[0] = grid[offset];
+
+    let mut k = 0;
+    for q in 1..length {
+        f[q] = grid[offset + q * stride];
+        let q2 = (q * q) as f64;
+        let mut s;
+        loop {
+            let r = v[k];
+            s = (f[q] - f[r] + q2 - (r * r) as f64) / (q - r) as f64 / 2.0;
+            if s <= z[k] && k > 0 {
+                k -= 1;
+            } else {
+                break;
+            }
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = INF;
+    }
+
+    let mut k = 0;
+    for q in 0..length {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let r = v[k];
+        let qr = q as f64 - r as f64;
+        grid[offset + q * stride] = f[r] + qr * qr;
+    }
+}
+
+#[test]
+fn test_signed_distance_field() {
+    let [w, h] = [20, 20];
+    let mut coverage = vec![0.0; w * h];
+    for y in 5..15 {
+        for x in 5..15 {
+            coverage[y * w + x] = 1.0;
+        }
+    }
+
+    let sdf = signed_distance_field(&coverage, [w, h], 6.0);
+
+    assert!(sdf[10 * w + 10] > 0.8, "deep inside");
+    assert_eq!(sdf[0], 0.0, "far outside");
+
+    // Monotonic across the outline:
+    let row = &sdf[10 * w..11 * w];
+    for x in 0..10 {
+        assert!(row[x] <= row[x + 1]);
+    }
+    assert!(row[4] < 0.5 && 0.5 < row[5]);
+}
@@ -1,7 +1,7 @@
 use std::ops::RangeInclusive;
 use std::sync::Arc;
 
-use super::{FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
+use super::{FontTexture, FontsImpl, Galley, Glyph, LayoutJob, LayoutSection, Row, RowVisuals};
 use crate::{Color32, Mesh, Stroke, Vertex};
 use emath::*;
 
@@ -80,7 +80,9 @@ pub fn layout(fonts: &mut FontsImpl, job: Arc<LayoutJob>) -> Galley {
         }
     }
 
-    galley_from_rows(point_scale, job, rows)
+    let mut galley = galley_from_rows(point_scale, job, rows);
+    galley.font_atlas_epoch = fonts.font_atlas_epoch();
+    galley
 }
 
 fn layout_section(
@@ -467,6 +469,10 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
         mesh_bounds = mesh_bounds.union(row.visuals.mesh_bounds);
         num_vertices += row.visuals.mesh.vertices.len();
         num_indices += row.visuals.mesh.indices.len();
+        for (_, mesh) in &row.visuals.texture_meshes {
+            num_vertices += mesh.vertices.len();
+            num_indices += mesh.indices.len();
+        }
     }
 
     let rect = Rect::from_min_max(pos2(min_x, 0.0), pos2(max_x, cursor_y));
@@ -479,6 +485,7 @@ fn galley_from_rows(point_scale: PointScale, job: Arc<LayoutJob>, mut rows: Vec<
         num_vertices,
         num_indices,
         pixels_per_point: point_scale.pixels_per_point,
+        font_atlas_epoch: 0,
     }
 }
 
@@ -519,9 +526,22 @@ fn tessellate_row(
     }
 
     let glyph_vertex_start = mesh.vertices.len();
-    tessellate_glyphs(point_scale, job, row, &mut mesh);
+    tessellate_glyphs(point_scale, job, row, FontTexture::Coverage(0), &mut mesh);
     let glyph_vertex_end = mesh.vertices.len();
 
+    // Glyphs in other font textures (extra atlas pages, SDF) get a mesh per texture:
+    let mut texture_meshes: Vec<(FontTexture, Mesh)> = vec![];
+    for glyph in &row.glyphs {
+        let texture = glyph.uv_rect.texture;
+        if texture != FontTexture::Coverage(0) && !texture_meshes.iter().any(|(t, _)| *t == texture)
+        {
+            texture_meshes.push((texture, Mesh::default()));
+        }
+    }
+    for (texture, texture_mesh) in &mut texture_meshes {
+        tessellate_glyphs(point_scale, job, row, *texture, texture_mesh);
+    }
+
     if format_summary.any_underline {
         add_row_hline(point_scale, row, &mut mesh, |glyph| {
             let format = &job.sections[glyph.section_index as usize].format;
@@ -540,12 +560,16 @@ fn tessellate_row(
         });
     }
 
-    let mesh_bounds = mesh.calc_bounds();
+    let mut mesh_bounds = mesh.calc_bounds();
+    for (_, texture_mesh) in &texture_meshes {
+        mesh_bounds = mesh_bounds.union(texture_mesh.calc_bounds());
+    }
 
     RowVisuals {
         mesh,
         mesh_bounds,
         glyph_vertex_range: glyph_vertex_start..glyph_vertex_end,
+        texture_meshes,
     }
 }
 
@@ -594,10 +618,17 @@ fn add_row_backgrounds(job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
     end_run(run_start.take(), last_rect.right());
 }
 
-fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh: &mut Mesh) {
+/// Add the glyphs that are in the given font texture.
+fn tessellate_glyphs(
+    point_scale: PointScale,
+    job: &LayoutJob,
+    row: &Row,
+    texture: FontTexture,
+    mesh: &mut Mesh,
+) {
     for glyph in &row.glyphs {
         let uv_rect = glyph.uv_rect;
-        if !uv_rect.is_nothing() {
+        if !uv_rect.is_nothing() && uv_rect.texture == texture {
             let mut left_top = glyph.pos + uv_rect.offset;
             left_top.x = point_scale.round_to_pixel(left_top.x);
             left_top.y = point_scale.round_to_pixel(left_top.y);
@@ -610,7 +641,11 @@ fn tessellate_glyphs(point_scale: PointScale, job: &LayoutJob, row: &Row, mesh:
 
             let format = &job.sections[glyph.section_index as usize].format;
 
-            let color = format.color;
+            let color = if texture == FontTexture::Color {
+                Color32::from_white_alpha(format.color.a())
+            } else {
+                format.color
+            };
 
             if format.italics {
                 let idx = mesh.vertices.len() as u32;
@@ -3,7 +3,10 @@
 use std::ops::Range;
 use std::sync::Arc;
 
-use super::{cursor::*, font::UvRect};
+use super::{
+    cursor::*,
+    font::{FontTexture, UvRect},
+};
 use crate::{Color32, FontId, Mesh, Stroke};
 use emath::*;
 
@@ -311,6 +314,10 @@ impl Default for TextWrapping {
 /// You can create a [`Galley`] using [`crate::Fonts::layout_job`];
 ///
 /// This needs to be recreated if `pixels_per_point` (dpi scale) changes.
+///
+/// Holding on to a galley across frames is fine even though the font atlas may be repacked in the meantime,
+/// which moves the glyphs around in the font textures:
+/// [`crate::Fonts::refresh_galleys`] lays out such galleys again before they are painted.
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub struct Galley {
@@ -349,6 +356,11 @@ pub struct Galley {
     /// so that we can warn if this has changed once we get to
     /// tessellation.
     pub pixels_per_point: f32,
+
+    /// Which font atlas the glyphs are in, and how it was packed when this galley was laid out.
+    /// See [`crate::Fonts::refresh_galleys`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub font_atlas_epoch: u64,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -388,6 +400,12 @@ pub struct RowVisuals {
     /// The range of vertices in the mesh the contain glyphs.
     /// Before comes backgrounds (if any), and after any underlines and strikethrough.
     pub glyph_vertex_range: Range<usize>,
+
+    /// Glyphs that are not in the main font texture (e.g. [`FontTexture::Sdf`]),
+    /// one mesh per texture, painted after [`Self::mesh`].
+    ///
+    /// These only contain glyphs, and also use non-normalized (texel) UV coordinates.
+    pub texture_meshes: Vec<(FontTexture, Mesh)>,
 }
 
 impl Default for RowVisuals {
@@ -396,6 +414,7 @@ impl Default for RowVisuals {
             mesh: Default::default(),
             mesh_bounds: Rect::NOTHING,
             glyph_vertex_range: 0..0,
+            texture_meshes: vec![],
         }
     }
 }
@@ -1,6 +1,11 @@
 use emath::{remap_clamp, Rect};
 
-use crate::{textures::TextureFilter, FontImage, ImageDelta};
+use crate::{textures::TextureOptions, FontImage, ImageData, ImageDelta};
+
+/// On some low-precision GPUs (my old iPad) characters get muddled up
+/// if we don't add some empty pixels between the characters.
+/// On modern high-precision GPUs this is not needed.
+const PADDING: usize = 1;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct Rectu {
@@ -39,7 +44,7 @@ struct PrerasterizedDisc {
 }
 
 /// A pre-rasterized disc (filled circle), somewhere in the texture atlas.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct PreparedDisc {
     /// The radius of this disc in texels.
     pub r: f32,
@@ -72,19 +77,14 @@ pub struct TextureAtlas {
 
     /// pre-rasterized discs of radii `2^i`, where `i` is the index.
     discs: Vec<PrerasterizedDisc>,
+
+    /// Does this atlas contain signed distance fields rather than coverage?
+    sdf: bool,
 }
 
 impl TextureAtlas {
     pub fn new(size: [usize; 2]) -> Self {
-        assert!(size[0] >= 1024, "Tiny texture atlas");
-        let mut atlas = Self {
-            image: FontImage::new(size),
-            dirty: Rectu::EVERYTHING,
-            cursor: (0, 0),
-            row_height: 0,
-            overflowed: false,
-            discs: vec![], // will be filled in below
-        };
+        let mut atlas = Self::new_empty(size, false);
 
         // Make the top left pixel fully white for `WHITE_UV`, i.e. painting something with solid color:
         let (pos, image) = atlas.allocate((1, 1));
@@ -129,6 +129,39 @@ impl TextureAtlas {
         atlas
     }
 
+    /// An atlas for glyphs rendered as signed distance fields.
+    ///
+    /// Unlike [`Self::new`] this has no white texel and no [`Self::prepared_discs`],
+    /// and [`Self::take_delta`] will produce [`ImageData::Sdf`].
+    pub fn new_sdf(size: [usize; 2]) -> Self {
+        Self::new_empty(size, true)
+    }
+
+    /// An extra page of glyphs.
+    ///
+    /// Unlike [`Self::new`] this has no white texel and no [`Self::prepared_discs`].
+    pub(crate) fn new_glyph_page(size: [usize; 2]) -> Self {
+        Self::new_empty(size, false)
+    }
+
+    fn new_empty(size: [usize; 2], sdf: bool) -> Self {
+        assert!(size[0] >= 1024, "Tiny texture atlas");
+        Self {
+            image: FontImage::new(size),
+            dirty: Rectu::EVERYTHING,
+            cursor: (0, 0),
+            row_height: 0,
+            overflowed: false,
+            discs: vec![],
+            sdf,
+        }
+    }
+
+    /// Does this atlas contain signed distance fields? See [`Self::new_sdf`].
+    pub fn is_sdf(&self) -> bool {
+        self.sdf
+    }
+
     pub fn size(&self) -> [usize; 2] {
         self.image.size
     }
@@ -174,27 +207,91 @@ impl TextureAtlas {
 
     /// Call to get the change to the image since last call.
     pub fn take_delta(&mut self) -> Option<ImageDelta> {
+        match self.take_dirty()? {
+            None => Some(ImageDelta::full(
+                self.image_data(self.image.clone()),
+                TextureOptions::LINEAR,
+            )),
+            Some((pos, size)) => {
+                let region = self.image.region(pos, size);
+                Some(ImageDelta::partial(
+                    pos,
+                    self.image_data(region),
+                    TextureOptions::LINEAR,
+                ))
+            }
+        }
+    }
+
+    /// The part of the image that has changed since last call, as position and size.
+    ///
+    /// `None` if nothing changed, and `Some(None)` if the whole image changed (e.g. because it grew).
+    #[allow(clippy::option_option)]
+    pub(crate) fn take_dirty(&mut self) -> Option<Option<([usize; 2], [usize; 2])>> {
         let dirty = std::mem::replace(&mut self.dirty, Rectu::NOTHING);
         if dirty == Rectu::NOTHING {
             None
         } else if dirty == Rectu::EVERYTHING {
-            Some(ImageDelta::full(self.image.clone(), TextureFilter::Linear))
+            Some(None)
         } else {
             let pos = [dirty.min_x, dirty.min_y];
             let size = [dirty.max_x - dirty.min_x, dirty.max_y - dirty.min_y];
-            let region = self.image.region(pos, size);
-            Some(ImageDelta::partial(pos, region, TextureFilter::Linear))
+            Some(Some((pos, size)))
+        }
+    }
+
+    fn image_data(&self, image: FontImage) -> ImageData {
+        if self.sdf {
+            ImageData::Sdf(image)
+        } else {
+            ImageData::Font(image)
+        }
+    }
+
+    /// Has someone requested more space than was available?
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// The number of texels this atlas can hold before it overflows.
+    pub(crate) fn capacity(&self) -> usize {
+        self.image.width() * self.max_height()
+    }
+
+    pub(crate) fn image(&self) -> &FontImage {
+        &self.image
+    }
+
+    /// Write access to the whole image, without invalidating anything.
+    ///
+    /// Only for writing to a region just returned by [`Self::allocate`].
+    pub(crate) fn image_mut(&mut self) -> &mut FontImage {
+        &mut self.image
+    }
+
+    /// Like [`Self::allocate`], but returns `None` if the rect doesn't fit,
+    /// instead of starting to overwrite old contents.
+    pub fn try_allocate(
+        &mut self,
+        (w, h): (usize, usize),
+    ) -> Option<((usize, usize), &mut FontImage)> {
+        let (mut cursor_y, mut row_height) = (self.cursor.1, self.row_height);
+        if self.cursor.0 + w > self.image.width() {
+            cursor_y += row_height + PADDING;
+            row_height = 0;
+        }
+        row_height = row_height.max(h);
+
+        if w <= self.image.width() && cursor_y + row_height <= self.max_height() {
+            Some(self.allocate((w, h)))
+        } else {
+            None
         }
     }
 
     /// Returns the coordinates of where the rect ended up,
     /// and invalidates the region.
     pub fn allocate(&mut self, (w, h): (usize, usize)) -> ((usize, usize), &mut FontImage) {
-        /// On some low-precision GPUs (my old iPad) characters get muddled up
-        /// if we don't add some empty pixels between the characters.
-        /// On modern high-precision GPUs this is not needed.
-        const PADDING: usize = 1;
-
         assert!(
             w <= self.image.width(),
             "Tried to allocate a {} wide glyph in a {} wide texture atlas",
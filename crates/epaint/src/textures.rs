@@ -26,7 +26,7 @@ impl TextureManager {
     /// MUST have a white pixel at (0,0) ([`crate::WHITE_UV`]).
     ///
     /// The texture is given a retain-count of `1`, requiring one call to [`Self::free`] to free it.
-    pub fn alloc(&mut self, name: String, image: ImageData, filter: TextureFilter) -> TextureId {
+    pub fn alloc(&mut self, name: String, image: ImageData, options: TextureOptions) -> TextureId {
         let id = TextureId::Managed(self.next_id);
         self.next_id += 1;
 
@@ -35,10 +35,10 @@ impl TextureManager {
             size: image.size(),
             bytes_per_pixel: image.bytes_per_pixel(),
             retain_count: 1,
-            filter,
+            options,
         });
 
-        self.delta.set.push((id, ImageDelta::full(image, filter)));
+        self.delta.set.push((id, ImageDelta::full(image, options)));
         id
     }
 
@@ -56,6 +56,7 @@ impl TextureManager {
                 // whole update
                 meta.size = delta.image.size();
                 meta.bytes_per_pixel = delta.image.bytes_per_pixel();
+                meta.options = delta.options;
                 // since we update the whole image, we can discard all old enqueued deltas
                 self.delta.set.retain(|(x, _)| x != &id);
             }
@@ -114,6 +115,13 @@ impl TextureManager {
     pub fn num_allocated(&self) -> usize {
         self.metas.len()
     }
+
+    /// Make sure the textures allocated from now on don't reuse any id ever allocated by `other`.
+    ///
+    /// Useful when the output of two managers ends up in the same place.
+    pub fn continue_ids_of(&mut self, other: &Self) {
+        self.next_id = self.next_id.max(other.next_id);
+    }
 }
 
 /// Meta-data about an allocated texture.
@@ -131,12 +139,141 @@ pub struct TextureMeta {
     /// Free when this reaches zero.
     pub retain_count: usize,
 
-    /// The texture filtering mode to use when rendering
-    pub filter: TextureFilter,
+    /// How the texture should be sampled when rendering.
+    pub options: TextureOptions,
+}
+
+/// How the texture texels are sampled.
+///
+/// The default is [`Self::LINEAR`].
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct TextureOptions {
+    /// How to filter when magnifying (when texels are larger than pixels).
+    pub magnification: TextureFilter,
+
+    /// How to filter when minifying (when texels are smaller than pixels).
+    pub minification: TextureFilter,
+
+    /// How to handle texture coordinates outside of the `[0, 1]` range.
+    pub wrap_mode: TextureWrapMode,
+
+    /// If set, mipmaps are generated for the texture, and this is how to blend between them.
+    ///
+    /// Use `Some(TextureFilter::Linear)` together with linear minification and magnification
+    /// for trilinear filtering, which avoids aliasing when showing large images at small sizes.
+    ///
+    /// Mipmaps cost 33% more texture memory, and are regenerated on each texture update.
+    ///
+    /// WebGL1 only supports mipmaps and repeating for textures whose sides are powers of two,
+    /// so `egui_glow` ignores both for other textures there.
+    pub mipmap_mode: Option<TextureFilter>,
+}
+
+impl TextureOptions {
+    /// Linear magnification and minification.
+    pub const LINEAR: Self = Self {
+        magnification: TextureFilter::Linear,
+        minification: TextureFilter::Linear,
+        wrap_mode: TextureWrapMode::ClampToEdge,
+        mipmap_mode: None,
+    };
+
+    /// Nearest magnification and minification.
+    pub const NEAREST: Self = Self {
+        magnification: TextureFilter::Nearest,
+        minification: TextureFilter::Nearest,
+        wrap_mode: TextureWrapMode::ClampToEdge,
+        mipmap_mode: None,
+    };
+
+    /// Linear magnification and minification, with linear blending between generated mipmaps.
+    ///
+    /// Good for showing large images at small sizes, e.g. thumbnails of photos.
+    pub const TRILINEAR: Self = Self {
+        magnification: TextureFilter::Linear,
+        minification: TextureFilter::Linear,
+        wrap_mode: TextureWrapMode::ClampToEdge,
+        mipmap_mode: Some(TextureFilter::Linear),
+    };
+
+    /// Linear magnification and minification, repeating the texture outside of `[0, 1]`.
+    pub const LINEAR_REPEAT: Self = Self {
+        magnification: TextureFilter::Linear,
+        minification: TextureFilter::Linear,
+        wrap_mode: TextureWrapMode::Repeat,
+        mipmap_mode: None,
+    };
+
+    /// Linear magnification and minification, mirroring the texture outside of `[0, 1]`.
+    pub const LINEAR_MIRRORED_REPEAT: Self = Self {
+        magnification: TextureFilter::Linear,
+        minification: TextureFilter::Linear,
+        wrap_mode: TextureWrapMode::MirroredRepeat,
+        mipmap_mode: None,
+    };
+
+    /// Nearest magnification and minification, repeating the texture outside of `[0, 1]`.
+    pub const NEAREST_REPEAT: Self = Self {
+        magnification: TextureFilter::Nearest,
+        minification: TextureFilter::Nearest,
+        wrap_mode: TextureWrapMode::Repeat,
+        mipmap_mode: None,
+    };
+
+    /// Nearest magnification and minification, mirroring the texture outside of `[0, 1]`.
+    pub const NEAREST_MIRRORED_REPEAT: Self = Self {
+        magnification: TextureFilter::Nearest,
+        minification: TextureFilter::Nearest,
+        wrap_mode: TextureWrapMode::MirroredRepeat,
+        mipmap_mode: None,
+    };
+
+    /// Use the given wrap mode.
+    #[inline]
+    pub fn with_wrap_mode(self, wrap_mode: TextureWrapMode) -> Self {
+        Self { wrap_mode, ..self }
+    }
+
+    /// Generate mipmaps and blend between them with the given filter.
+    #[inline]
+    pub fn with_mipmap_mode(self, mipmap_mode: Option<TextureFilter>) -> Self {
+        Self {
+            mipmap_mode,
+            ..self
+        }
+    }
+
+    /// Number of mip levels needed for a texture of the given size,
+    /// or `1` if [`Self::mipmap_mode`] is `None`.
+    pub fn mip_level_count(&self, [w, h]: [usize; 2]) -> u32 {
+        if self.mipmap_mode.is_some() {
+            usize::BITS - w.max(h).max(1).leading_zeros()
+        } else {
+            1
+        }
+    }
+}
+
+impl Default for TextureOptions {
+    fn default() -> Self {
+        Self::LINEAR
+    }
+}
+
+impl From<TextureFilter> for TextureOptions {
+    #[inline]
+    fn from(filter: TextureFilter) -> Self {
+        Self {
+            magnification: filter,
+            minification: filter,
+            ..Self::LINEAR
+        }
+    }
 }
 
 /// How the texture texels are filtered.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
 pub enum TextureFilter {
     /// Show the nearest pixel value.
@@ -157,11 +294,38 @@ impl Default for TextureFilter {
     }
 }
 
+/// How to sample a texture outside of the `[0, 1]` texture coordinate range.
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum TextureWrapMode {
+    /// Stretch the edge texels out to infinity.
+    ///
+    /// This is the default.
+    ClampToEdge,
+
+    /// Tile the texture.
+    Repeat,
+
+    /// Tile the texture, mirroring every other repetition.
+    MirroredRepeat,
+}
+
+impl Default for TextureWrapMode {
+    fn default() -> Self {
+        Self::ClampToEdge
+    }
+}
+
 impl TextureMeta {
     /// Size in bytes.
-    /// width x height x [`Self::bytes_per_pixel`].
+    /// width x height x [`Self::bytes_per_pixel`], plus the mip levels if [`TextureOptions::mipmap_mode`] is set
+    /// (about a third more).
     pub fn bytes_used(&self) -> usize {
-        self.size[0] * self.size[1] * self.bytes_per_pixel
+        let [w, h] = self.size;
+        let num_texels: usize = (0..self.options.mip_level_count(self.size))
+            .map(|level| (w >> level).max(1) * (h >> level).max(1))
+            .sum();
+        num_texels * self.bytes_per_pixel
     }
 }
 
@@ -229,3 +393,21 @@ impl std::fmt::Debug for TexturesDelta {
         debug_struct.finish()
     }
 }
+
+#[test]
+fn test_bytes_used() {
+    let meta = |options| TextureMeta {
+        name: String::new(),
+        size: [256, 64],
+        bytes_per_pixel: 4,
+        retain_count: 1,
+        options,
+    };
+    assert_eq!(meta(TextureOptions::LINEAR).bytes_used(), 256 * 64 * 4);
+    let mipmapped = TextureOptions::LINEAR.with_mipmap_mode(Some(TextureFilter::Linear));
+    // 256x64, 128x32, 64x16, 32x8, 16x4, 8x2, 4x1, 2x1, 1x1:
+    assert_eq!(
+        meta(mipmapped).bytes_used(),
+        (16384 + 4096 + 1024 + 256 + 64 + 16 + 4 + 2 + 1) * 4
+    );
+}